@@ -11,7 +11,8 @@ use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::Unchecked;
 use meilisearch_types::tasks::network::{DbTaskNetwork, NetworkTopologyChange};
 use meilisearch_types::tasks::{
-    Details, DsrUpdate, ExportIndexSettings, IndexSwap, KindWithContent, Status, Task, TaskId,
+    Details, DsrUpdate, ExportIndexSettings, IndexSwap, KindWithContent, Priority, Status, Task,
+    TaskId,
 };
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
@@ -100,6 +101,18 @@ pub struct TaskDump {
     pub network: Option<DbTaskNetwork>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_metadata: Option<String>,
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub run_at: Option<OffsetDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_uid: Option<TaskId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 // A `Kind` specific version made for the dump. If modified you may break the dump.
@@ -186,6 +199,10 @@ impl From<Task> for TaskDump {
             finished_at: task.finished_at,
             network: task.network,
             custom_metadata: task.custom_metadata,
+            priority: task.priority,
+            run_at: task.run_at,
+            transaction_uid: task.transaction_uid,
+            idempotency_key: task.idempotency_key,
         }
     }
 }
@@ -411,6 +428,10 @@ pub(crate) mod test {
                     finished_at: Some(datetime!(2022-11-21 0:00 UTC)),
                     network: None,
                     custom_metadata: None,
+                    priority: Priority::Normal,
+                    run_at: None,
+                    transaction_uid: None,
+                    idempotency_key: None,
                 },
                 None,
             ),
@@ -437,6 +458,10 @@ pub(crate) mod test {
                     finished_at: None,
                     network: None,
                     custom_metadata: None,
+                    priority: Priority::Normal,
+                    run_at: None,
+                    transaction_uid: None,
+                    idempotency_key: None,
                 },
                 Some(vec![
                     json!({ "id": 4, "race": "leonberg" }).as_object().unwrap().clone(),
@@ -458,6 +483,10 @@ pub(crate) mod test {
                     finished_at: None,
                     network: None,
                     custom_metadata: None,
+                    priority: Priority::Normal,
+                    run_at: None,
+                    transaction_uid: None,
+                    idempotency_key: None,
                 },
                 None,
             ),
@@ -165,6 +165,10 @@ impl CompatV5ToV6 {
                     finished_at: task_view.finished_at,
                     network: None,
                     custom_metadata: None,
+                    priority: Default::default(),
+                    run_at: None,
+                    transaction_uid: None,
+                    idempotency_key: None,
                 };
 
                 (task, content_file)
@@ -211,6 +215,10 @@ impl CompatV5ToV6 {
         None
     }
 
+    pub fn task_retention_policy(&self) -> Option<v6::TaskRetentionPolicy> {
+        None
+    }
+
     pub fn dynamic_search_rules(
         &self,
     ) -> Result<Box<dyn Iterator<Item = Result<(String, v6::DynamicSearchRule)>> + '_>> {
@@ -145,6 +145,13 @@ impl DumpReader {
             DumpReader::Compat(compat) => compat.webhooks(),
         }
     }
+
+    pub fn task_retention_policy(&self) -> Option<v6::TaskRetentionPolicy> {
+        match self {
+            DumpReader::Current(current) => current.task_retention_policy(),
+            DumpReader::Compat(compat) => compat.task_retention_policy(),
+        }
+    }
 }
 
 impl From<V6Reader> for DumpReader {
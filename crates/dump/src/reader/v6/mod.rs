@@ -27,6 +27,7 @@ pub type ChatCompletionSettings = meilisearch_types::features::ChatCompletionSet
 pub type RuntimeTogglableFeatures = meilisearch_types::features::RuntimeTogglableFeatures;
 pub type Network = meilisearch_types::network::Network;
 pub type Webhooks = meilisearch_types::webhooks::WebhooksDumpView;
+pub type TaskRetentionPolicy = meilisearch_types::task_retention::TaskRetentionPolicy;
 pub type DynamicSearchRule = meilisearch_types::dynamic_search_rules::DynamicSearchRule;
 
 // ===== Other types to clarify the code of the compat module
@@ -66,6 +67,7 @@ pub struct V6Reader {
     features: Option<RuntimeTogglableFeatures>,
     network: Option<Network>,
     webhooks: Option<Webhooks>,
+    task_retention_policy: Option<TaskRetentionPolicy>,
 }
 
 impl V6Reader {
@@ -134,6 +136,17 @@ impl V6Reader {
             },
         };
 
+        let task_retention_policy = match fs::read(dump.path().join("task-retention-policy.json")) {
+            Ok(policy_file) => Some(serde_json::from_reader(&*policy_file)?),
+            Err(error) => match error.kind() {
+                ErrorKind::NotFound => {
+                    debug!("`task-retention-policy.json` not found in dump");
+                    None
+                }
+                _ => return Err(error.into()),
+            },
+        };
+
         Ok(V6Reader {
             metadata: serde_json::from_reader(&*meta_file)?,
             instance_uid,
@@ -145,6 +158,7 @@ impl V6Reader {
             network,
             dump,
             webhooks,
+            task_retention_policy,
         })
     }
 
@@ -295,6 +309,10 @@ impl V6Reader {
     pub fn webhooks(&self) -> Option<&Webhooks> {
         self.webhooks.as_ref()
     }
+
+    pub fn task_retention_policy(&self) -> Option<TaskRetentionPolicy> {
+        self.task_retention_policy
+    }
 }
 pub struct UpdateFile {
     reader: BufReader<File>,
@@ -9,6 +9,7 @@ use meilisearch_types::features::{ChatCompletionSettings, RuntimeTogglableFeatur
 use meilisearch_types::keys::Key;
 use meilisearch_types::network::Network;
 use meilisearch_types::settings::{Checked, Settings};
+use meilisearch_types::task_retention::TaskRetentionPolicy;
 use meilisearch_types::webhooks::WebhooksDumpView;
 use serde_json::{Map, Value};
 use tempfile::TempDir;
@@ -83,6 +84,13 @@ impl DumpWriter {
         )?)
     }
 
+    pub fn create_task_retention_policy(&self, policy: TaskRetentionPolicy) -> Result<()> {
+        Ok(std::fs::write(
+            self.dir.path().join("task-retention-policy.json"),
+            serde_json::to_string(&policy)?,
+        )?)
+    }
+
     pub fn persist_to(self, mut writer: impl Write) -> Result<()> {
         let gz_encoder = GzEncoder::new(&mut writer, Compression::default());
         let mut tar_encoder = tar::Builder::new(gz_encoder);
@@ -4,7 +4,7 @@ use std::ops::ControlFlow;
 
 use itertools::Itertools as _;
 
-use crate::{Condition, IndexFilterCondition, Token, VectorFilter};
+use crate::{Condition, GeoShapeRelation, IndexFilterCondition, Token, VectorFilter};
 
 pub type FilterConstraintSet = Vec<BTreeMap<ConstraintTarget, Vec<ConstraintCondition>>>;
 
@@ -143,6 +143,18 @@ impl FilterConstraints {
                 these_constraints.insert(ConstraintTarget::Geo, vec![constraint]);
                 constraints.push(these_constraints);
             }
+            IndexFilterCondition::GeoShape { relation, shape } => {
+                let constraint = ConstraintCondition {
+                    kind: ConstraintConditionKind::GeoShape {
+                        relation: *relation,
+                        shape: shape.clone(),
+                    },
+                    polarity,
+                };
+                let mut these_constraints = BTreeMap::new();
+                these_constraints.insert(ConstraintTarget::Geo, vec![constraint]);
+                constraints.push(these_constraints);
+            }
         }
         fuel.restore_depth_fuel();
     }
@@ -197,6 +209,7 @@ pub enum ConstraintConditionKind {
     GeoLowerThan { point: [Token; 2], radius: Token, resolution: Option<Token> },
     GeoBoundingBox { top_right_point: [Token; 2], bottom_left_point: [Token; 2] },
     GeoPolygon { points: Vec<[Token; 2]> },
+    GeoShape { relation: GeoShapeRelation, shape: Token },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -80,6 +80,7 @@ pub enum ErrorKind {
     GeoBoundingBox,
     GeoPolygon,
     GeoPolygonNotEnoughPoints(usize),
+    GeoShape,
     GeoCoordinatesNotPair(usize),
     MisusedGeoRadius,
     MisusedGeoBoundingBox,
@@ -221,6 +222,9 @@ impl Display for Error {
             ErrorKind::GeoPolygonNotEnoughPoints(n) => {
                 writeln!(f, "The `_geoPolygon` filter expects at least 3 points but only {n} were specified")?;
             }
+            ErrorKind::GeoShape => {
+                writeln!(f, "The `_geoShape` filter doesn't match the expected format: `_geoShape(relation, 'geojson')` where the relation is one of `intersects`, `within`, `contains` or `disjoint`.")?
+            }
             ErrorKind::GeoCoordinatesNotPair(number) => {
                 writeln!(f, "Was expecting 2 coordinates but instead found {number}.")?
             }
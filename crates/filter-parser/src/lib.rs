@@ -20,6 +20,7 @@
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
 //! geoPolygon     = "_geoPolygon([[" WS* float WS* "," WS* float WS* "],+])"
+//! geoShape       = "_geoShape(" WS* ("intersects" | "within" | "contains" | "disjoint") WS* "," value ")"
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -281,6 +282,30 @@ impl<T: Into<OwnedSpan>> From<T> for Token {
     }
 }
 
+/// The spatial relation a `_geoShape` filter checks between the shape of a document and the shape of the filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoShapeRelation {
+    /// The shapes share at least one point.
+    Intersects,
+    /// The shape of the document is entirely inside the shape of the filter.
+    Within,
+    /// The shape of the document entirely covers the shape of the filter.
+    Contains,
+    /// The shapes do not share any point.
+    Disjoint,
+}
+
+impl std::fmt::Display for GeoShapeRelation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeoShapeRelation::Intersects => f.write_str("intersects"),
+            GeoShapeRelation::Within => f.write_str("within"),
+            GeoShapeRelation::Contains => f.write_str("contains"),
+            GeoShapeRelation::Disjoint => f.write_str("disjoint"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VectorFilter {
     Fragment(Token),
@@ -313,12 +338,77 @@ pub enum IndexFilterCondition {
     GeoLowerThan { point: [Token; 2], radius: Token, resolution: Option<Token> },
     GeoBoundingBox { top_right_point: [Token; 2], bottom_left_point: [Token; 2] },
     GeoPolygon { points: Vec<[Token; 2]> },
+    GeoShape { relation: GeoShapeRelation, shape: Token },
 }
 
 impl IndexFilterCondition {
     pub fn fids(&self, depth: usize) -> impl Iterator<Item = &Token> {
         FidIter { stack: vec![(depth, self)] }
     }
+
+    /// Returns the condition without the clauses that only filter on `field`, `None` if nothing remains.
+    ///
+    /// Only the conjunctions are relaxed: a disjunction or a negation is removed as a whole
+    /// when all of its clauses are on `field`, and is kept untouched otherwise.
+    pub fn without_field(&self, field: &str) -> Option<Self> {
+        match self {
+            IndexFilterCondition::And(subfilters) => {
+                let mut subfilters: Vec<_> =
+                    subfilters.iter().filter_map(|filter| filter.without_field(field)).collect();
+                match subfilters.len() {
+                    0 => None,
+                    1 => subfilters.pop(),
+                    _ => Some(IndexFilterCondition::And(subfilters)),
+                }
+            }
+            _ if self.only_filters_on(field) => None,
+            _ => Some(self.clone()),
+        }
+    }
+
+    /// Returns the value `field` must be equal to for the condition to match, if any.
+    ///
+    /// Only the conjunctions are looked into, the longest value is returned when several
+    /// clauses of a conjunction select a value for `field`.
+    pub fn equality_value(&self, field: &str) -> Option<&str> {
+        match self {
+            IndexFilterCondition::Condition { fid, op: Condition::Equal(value) }
+                if fid.fragment() == field =>
+            {
+                Some(value.fragment())
+            }
+            IndexFilterCondition::In { fid, els } if fid.fragment() == field => {
+                match els.as_slice() {
+                    [value] => Some(value.fragment()),
+                    _ => None,
+                }
+            }
+            IndexFilterCondition::And(subfilters) => subfilters
+                .iter()
+                .filter_map(|filter| filter.equality_value(field))
+                .max_by_key(|value| value.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether all the clauses of this condition filter on `field`.
+    fn only_filters_on(&self, field: &str) -> bool {
+        match self {
+            IndexFilterCondition::Condition { fid, .. } | IndexFilterCondition::In { fid, .. } => {
+                fid.fragment() == field
+            }
+            IndexFilterCondition::Not(filter) => filter.only_filters_on(field),
+            IndexFilterCondition::Or(subfilters) | IndexFilterCondition::And(subfilters) => {
+                !subfilters.is_empty()
+                    && subfilters.iter().all(|filter| filter.only_filters_on(field))
+            }
+            IndexFilterCondition::VectorExists { .. }
+            | IndexFilterCondition::GeoLowerThan { .. }
+            | IndexFilterCondition::GeoBoundingBox { .. }
+            | IndexFilterCondition::GeoPolygon { .. }
+            | IndexFilterCondition::GeoShape { .. } => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -332,6 +422,7 @@ pub enum FilterCondition {
     GeoLowerThan { point: [Token; 2], radius: Token, resolution: Option<Token> },
     GeoBoundingBox { top_right_point: [Token; 2], bottom_left_point: [Token; 2] },
     GeoPolygon { points: Vec<[Token; 2]> },
+    GeoShape { relation: GeoShapeRelation, shape: Token },
     Foreign { fid: Token, op: Box<Self> },
 }
 
@@ -365,6 +456,7 @@ impl FilterCondition {
             | FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
             | FilterCondition::GeoPolygon { .. }
+            | FilterCondition::GeoShape { .. }
             | FilterCondition::In { .. } => None,
             FilterCondition::Foreign { fid: _, op } => op.use_contains_operator(),
         }
@@ -380,6 +472,7 @@ impl FilterCondition {
             FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
             | FilterCondition::GeoPolygon { .. }
+            | FilterCondition::GeoShape { .. }
             | FilterCondition::In { .. } => None,
             FilterCondition::VectorExists { fid, .. } => Some(fid),
             FilterCondition::Foreign { fid: _, op } => op.use_vector_filter(),
@@ -398,6 +491,7 @@ impl FilterCondition {
             FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
             | FilterCondition::GeoPolygon { .. }
+            | FilterCondition::GeoShape { .. }
             | FilterCondition::VectorExists { .. } => None,
             FilterCondition::Foreign { fid, op } => {
                 (fid.fragment() == field).then_some(fid).or_else(|| op.use_field(field))
@@ -819,6 +913,34 @@ fn parse_geo_polygon(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// geoShape       = "_geoShape(" WS* ("intersects" | "within" | "contains" | "disjoint") WS* "," value ")"
+/// If we parse `_geoShape` we MUST parse the rest of the expression.
+fn parse_geo_shape(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoShape but not after
+
+    let (input, _) = tuple((multispace0, word_exact("_geoShape")))(input)?;
+
+    // if we were able to parse `_geoShape` and can't parse the rest of the input we return a failure
+
+    let (input, (relation, shape)) = delimited(
+        char('('),
+        separated_pair(ws(parse_geo_shape_relation), tag(","), parse_value),
+        char(')'),
+    )(input)
+    .map_cut(ErrorKind::GeoShape)?;
+
+    Ok((input, FilterCondition::GeoShape { relation, shape }))
+}
+
+fn parse_geo_shape_relation(input: Span) -> IResult<GeoShapeRelation> {
+    alt((
+        map(word_exact("intersects"), |_| GeoShapeRelation::Intersects),
+        map(word_exact("within"), |_| GeoShapeRelation::Within),
+        map(word_exact("contains"), |_| GeoShapeRelation::Contains),
+        map(word_exact("disjoint"), |_| GeoShapeRelation::Disjoint),
+    ))(input)
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -878,7 +1000,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoShape | condition | exists | not_exists | to
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(
@@ -896,7 +1018,7 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
             }),
         ),
         // Made a random block of functions because we reached the maximum number of elements per alt
-        alt((parse_geo_radius, parse_geo_bounding_box, parse_geo_polygon)),
+        alt((parse_geo_radius, parse_geo_bounding_box, parse_geo_polygon, parse_geo_shape)),
         parse_in,
         parse_not_in,
         parse_condition,
@@ -1003,6 +1125,9 @@ impl std::fmt::Display for IndexFilterCondition {
                 }
                 write!(f, "])")
             }
+            IndexFilterCondition::GeoShape { relation, shape } => {
+                write!(f, "_geoShape({relation}, {shape})")
+            }
         }
     }
 }
@@ -1079,6 +1204,9 @@ impl std::fmt::Display for FilterCondition {
                 }
                 write!(f, "])")
             }
+            FilterCondition::GeoShape { relation, shape } => {
+                write!(f, "_geoShape({relation}, {shape})")
+            }
             FilterCondition::Foreign { fid, op } => {
                 write!(f, "_foreign({fid}, {op})")
             }
@@ -1262,6 +1390,11 @@ pub mod tests {
         insta::assert_snapshot!(p("_geoPolygon([12, 13], [14, 15], [16, 17])"), @"_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], ])");
         insta::assert_snapshot!(p("_geoPolygon([12, 13], [14, 15], [-1.2,2939.2], [1,1])"), @"_geoPolygon([[{12}, {13}], [{14}, {15}], [{-1.2}, {2939.2}], [{1}, {1}], ])");
 
+        // Test geo shape
+        insta::assert_snapshot!(p(r#"_geoShape(intersects, '{"type":"Point","coordinates":[1,2]}')"#), @r#"_geoShape(intersects, {{"type":"Point","coordinates":[1,2]}})"#);
+        insta::assert_snapshot!(p(r#"_geoShape( within ,'{"type":"Point","coordinates":[1,2]}' )"#), @r#"_geoShape(within, {{"type":"Point","coordinates":[1,2]}})"#);
+        insta::assert_snapshot!(p(r#"NOT _geoShape(disjoint, "{}")"#), @"NOT (_geoShape(disjoint, {{}}))");
+
         // Test OR + AND
         insta::assert_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -1398,6 +1531,11 @@ pub mod tests {
         17:18 _geoPolygon([1,2)
         ");
 
+        insta::assert_snapshot!(p("_geoShape(overlaps, '{}')"), @r"
+        The `_geoShape` filter doesn't match the expected format: `_geoShape(relation, 'geojson')` where the relation is one of `intersects`, `within`, `contains` or `disjoint`.
+        19:26 _geoShape(overlaps, '{}')
+        ");
+
         insta::assert_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
         `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
@@ -152,6 +152,10 @@ impl<'a> Dump<'a> {
             status: task.status,
             network: task.network,
             custom_metadata: task.custom_metadata,
+            priority: task.priority,
+            run_at: task.run_at,
+            transaction_uid: task.transaction_uid,
+            idempotency_key: task.idempotency_key,
             kind: match task.kind {
                 KindDump::DocumentImport {
                     primary_key,
@@ -249,6 +253,25 @@ impl<'a> Dump<'a> {
         };
 
         self.index_scheduler.queue.tasks.all_tasks.put(&mut self.wtxn, &task.uid, &task)?;
+        if let Some(transaction_uid) = task.transaction_uid {
+            self.index_scheduler.queue.tasks.update_transaction(
+                &mut self.wtxn,
+                transaction_uid,
+                |bitmap| {
+                    bitmap.insert(task.uid);
+                },
+            )?;
+        }
+        if !task.priority.is_normal() {
+            self.index_scheduler.queue.tasks.update_priority(
+                &mut self.wtxn,
+                task.priority,
+                |bitmap| {
+                    bitmap.insert(task.uid);
+                },
+            )?;
+        }
+        self.index_scheduler.queue.tasks.insert_idempotency_key(&mut self.wtxn, &task, None)?;
         if let Some(batch_id) = task.batch_uid {
             self.batch_to_task_mapping.entry(batch_id).or_default().insert(task.uid);
         }
@@ -272,6 +295,14 @@ impl<'a> Dump<'a> {
             task.enqueued_at,
             task.uid,
         )?;
+        if let Some(run_at) = task.run_at {
+            utils::insert_task_datetime(
+                &mut self.wtxn,
+                self.index_scheduler.queue.tasks.run_at,
+                run_at,
+                task.uid,
+            )?;
+        }
 
         // we can't override the started_at & finished_at, so we must only set it if the tasks is finished and won't change
         if matches!(task.status, Status::Succeeded | Status::Failed | Status::Canceled) {
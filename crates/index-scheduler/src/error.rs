@@ -133,6 +133,29 @@ pub enum Error {
     TaskCancelationWithEmptyQuery,
     #[error("Aborted task")]
     AbortedTask,
+    #[error("Transaction `{transaction_uid}` was rolled back because its task `{task_uid}` failed: {message}")]
+    TransactionAborted { transaction_uid: TaskId, task_uid: TaskId, message: String },
+    #[error("Transaction `{0}` was rolled back because some of its tasks were canceled.")]
+    TransactionIncomplete(TaskId),
+    #[error("Transaction `{transaction_uid}` was rolled back because its operations on the index `{index}` cannot be applied together.")]
+    TransactionNotBatchable { transaction_uid: TaskId, index: String },
+    #[error("Transaction `{transaction_uid}` was partially committed: the changes of the indexes {committed:?} were committed but the commit of the index `{index}` failed and will be retried: {error}")]
+    TransactionPartiallyCommitted {
+        transaction_uid: TaskId,
+        committed: Vec<String>,
+        index: String,
+        error: Box<Error>,
+    },
+    #[error("Transaction `{transaction_uid}` was partially committed: the changes of the indexes {committed:?} were committed but the commit of the index `{index}` failed {attempts} times and won't be retried: {error}")]
+    TransactionCommitFailed {
+        transaction_uid: TaskId,
+        committed: Vec<String>,
+        index: String,
+        attempts: u32,
+        error: Box<Error>,
+    },
+    #[error("The idempotency key `{idempotency_key}` was already used to enqueue the task `{task_uid}` with a different request.")]
+    IdempotencyKeyMismatch { idempotency_key: String, task_uid: TaskId },
 
     #[error("S3 error: status: {status}, body: {body}")]
     S3Error { status: StatusCode, body: String },
@@ -256,6 +279,11 @@ impl Error {
             | Error::TaskCancelationWithEmptyQuery
             | Error::FromRemoteWhenExporting { .. }
             | Error::AbortedTask
+            | Error::TransactionAborted { .. }
+            | Error::TransactionIncomplete(_)
+            | Error::TransactionNotBatchable { .. }
+            | Error::TransactionCommitFailed { .. }
+            | Error::IdempotencyKeyMismatch { .. }
             | Error::S3Error { .. }
             | Error::S3HttpError(_)
             | Error::S3XmlError(_)
@@ -284,6 +312,7 @@ impl Error {
             | Error::RollbackFailed { .. }
             | Error::ReceiveImportFinishedUnknownRemote(_)
             | Error::InvalidRemoteUrl { .. }
+            | Error::TransactionPartiallyCommitted { .. }
             | Error::HeedTransaction(_) => false,
             #[cfg(test)]
             Error::PlannedFailure => false,
@@ -333,6 +362,12 @@ impl ErrorCode for Error {
             Error::BatchNotFound(_) => Code::BatchNotFound,
             Error::TaskDeletionWithEmptyQuery => Code::MissingTaskFilters,
             Error::TaskCancelationWithEmptyQuery => Code::MissingTaskFilters,
+            Error::TransactionAborted { .. }
+            | Error::TransactionIncomplete(_)
+            | Error::TransactionNotBatchable { .. }
+            | Error::TransactionPartiallyCommitted { .. }
+            | Error::TransactionCommitFailed { .. } => Code::TransactionAborted,
+            Error::IdempotencyKeyMismatch { .. } => Code::IdempotencyKeyMismatch,
             Error::NoSpaceLeftInTaskQueue => Code::NoSpaceLeftOnDevice,
             Error::ImportTaskWithoutNetworkTask => Code::ImportTaskWithoutNetworkTask,
             Error::NetworkVersionMismatch { .. } => Code::NetworkVersionMismatch,
@@ -295,6 +295,9 @@ where
             Ok(IndexFilterCondition::GeoBoundingBox { top_right_point, bottom_left_point })
         }
         FilterCondition::GeoPolygon { points } => Ok(IndexFilterCondition::GeoPolygon { points }),
+        FilterCondition::GeoShape { relation, shape } => {
+            Ok(IndexFilterCondition::GeoShape { relation, shape })
+        }
         FilterCondition::Foreign { .. } => foreign_filter(filter),
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
@@ -81,7 +82,9 @@ pub struct IndexMapper {
 
     /// A few types of long running batches of tasks that act on a single index set this field
     /// so that a handle to the index is available from other threads (search) in an optimized manner.
-    currently_updating_index: Arc<RwLock<Option<(String, Index)>>>,
+    ///
+    /// There is one entry per batch being processed concurrently.
+    currently_updating_index: Arc<RwLock<HashMap<String, Index>>>,
 }
 
 /// Whether the index is available for use or is forbidden to be inserted back in the index map
@@ -355,6 +358,9 @@ impl IndexMapper {
             .get(rtxn, name)?
             .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
 
+        // We drop the handle of the resized index only, the other indexes may still be updating.
+        self.unset_currently_updating_index(name);
+
         // We remove the index from the in-memory index map.
         self.index_map.write().unwrap().close_for_resize(
             &uuid,
@@ -394,12 +400,8 @@ impl IndexMapper {
     /// Return an index, may open it if it wasn't already opened.
     pub fn index<'a>(&self, rtxn: &RoTxn, name: impl IndexUid<'a>) -> Result<Index> {
         let name = name.uid();
-        if let Some((current_name, current_index)) =
-            self.currently_updating_index.read().unwrap().as_ref()
-        {
-            if current_name == name {
-                return Ok(current_index.clone());
-            }
+        if let Some(current_index) = self.currently_updating_index.read().unwrap().get(name) {
+            return Ok(current_index.clone());
         }
 
         let uuid = self
@@ -507,8 +509,8 @@ impl IndexMapper {
         to: (u32, u32, u32),
     ) -> Result<RollbackOutcome> {
         let name = name.uid();
-        // remove any currently updating index to make sure that we aren't keeping a reference to the index somewhere
-        drop(self.currently_updating_index.write().unwrap().take());
+        // remove the index from the currently updating ones to make sure that we aren't keeping a reference to it somewhere
+        self.unset_currently_updating_index(name);
 
         let uuid = self
             .index_mapping
@@ -665,12 +667,30 @@ impl IndexMapper {
         &self.indexer_config
     }
 
+    /// Returns a mapper on the same indexes whose indexer uses at most `max_memory`.
+    pub fn with_max_memory(&self, max_memory: Option<usize>) -> IndexMapper {
+        let indexer_config = IndexerConfig { max_memory, ..(*self.indexer_config).clone() };
+        IndexMapper { indexer_config: Arc::new(indexer_config), ..self.clone() }
+    }
+
     pub fn index_base_map_size(&self) -> usize {
         self.index_base_map_size
     }
 
+    /// Registers the index as being updated, or forget all the updating indexes when `None`.
     pub fn set_currently_updating_index(&self, index: Option<(String, Index)>) {
-        *self.currently_updating_index.write().unwrap() = index;
+        let mut currently_updating = self.currently_updating_index.write().unwrap();
+        match index {
+            Some((name, index)) => {
+                currently_updating.insert(name, index);
+            }
+            None => currently_updating.clear(),
+        }
+    }
+
+    /// Forget the given index as being updated, leaving the other updating indexes untouched.
+    pub fn unset_currently_updating_index(&self, name: &str) {
+        drop(self.currently_updating_index.write().unwrap().remove(name));
     }
 }
 
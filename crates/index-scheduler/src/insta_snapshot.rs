@@ -30,6 +30,7 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         index_mapper,
         features: _,
         webhooks: _,
+        webhook_deliveries: _,
         test_breakpoint_sdr: _,
         planned_failures: _,
         run_loop_iteration: _,
@@ -38,6 +39,7 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         runtime: _,
         web_client: _,
         dsr_fuel: _,
+        search_cursor_secret: _,
     } = scheduler;
 
     let rtxn = env.read_txn().unwrap();
@@ -214,6 +216,10 @@ pub fn snapshot_persisted_db(rtxn: &RoTxn, db: &Database<Str, Str>) -> String {
     let iter = db.iter(rtxn).unwrap();
     for next in iter {
         let (key, value) = next.unwrap();
+        // the secret is drawn randomly on the first launch
+        if key == crate::db_keys::SEARCH_CURSOR_SECRET {
+            continue;
+        }
         snap.push_str(&format!("{key}: {value}\n"));
     }
     snap
@@ -234,6 +240,10 @@ pub fn snapshot_task(task: &Task) -> String {
         kind,
         network,
         custom_metadata,
+        priority,
+        run_at: _,
+        transaction_uid,
+        idempotency_key,
     } = task;
     snap.push('{');
     snap.push_str(&format!("uid: {uid}, "));
@@ -257,6 +267,15 @@ pub fn snapshot_task(task: &Task) -> String {
     if let Some(custom_metadata) = custom_metadata {
         snap.push_str(&format!("custom_metadata: {custom_metadata:?}"))
     }
+    if !priority.is_normal() {
+        snap.push_str(&format!("priority: {priority}"))
+    }
+    if let Some(transaction_uid) = transaction_uid {
+        snap.push_str(&format!("transaction_uid: {transaction_uid}"))
+    }
+    if let Some(idempotency_key) = idempotency_key {
+        snap.push_str(&format!("idempotency_key: {idempotency_key:?}"))
+    }
 
     snap.push('}');
     snap
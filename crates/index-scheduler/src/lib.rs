@@ -39,6 +39,7 @@ pub mod upgrade;
 mod utils;
 pub mod uuid_codec;
 pub mod versioning;
+mod webhook_deliveries;
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub type TaskId = u32;
@@ -56,7 +57,7 @@ pub use error::Error;
 pub use features::RoFeatures;
 use flate2::bufread::GzEncoder;
 use flate2::Compression;
-use meilisearch_types::batches::Batch;
+use meilisearch_types::batches::{Batch, BatchId};
 use meilisearch_types::features::{
     ChatCompletionSettings, InstanceTogglableFeatures, RuntimeTogglableFeatures,
 };
@@ -65,6 +66,7 @@ use meilisearch_types::heed::types::{DecodeIgnore, SerdeJson, Str, I128};
 use meilisearch_types::heed::{self, Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::index_uid::UserIndex;
 use meilisearch_types::milli::dynamic_search_rules::DsrFuel;
+use meilisearch_types::milli::progress::ProgressView;
 use meilisearch_types::milli::sharding::Shards;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::vector::json_template::JsonTemplate;
@@ -74,19 +76,22 @@ use meilisearch_types::milli::vector::{
 use meilisearch_types::milli::{self, Index};
 use meilisearch_types::network::route::Status;
 use meilisearch_types::network::{Network, RemoteAvailability};
+use meilisearch_types::task_retention::TaskRetentionPolicy;
 use meilisearch_types::task_view::TaskView;
 use meilisearch_types::tasks::network::{
     DbTaskNetwork, NetworkTopologyChange, Origin, TaskNetwork,
 };
-use meilisearch_types::tasks::{KindWithContent, Task};
-use meilisearch_types::webhooks::{Webhook, WebhooksDumpView, WebhooksView};
+use meilisearch_types::tasks::{KindWithContent, Priority, Task};
+use meilisearch_types::webhooks::{Webhook, WebhookDeliveryStatus, WebhooksDumpView, WebhooksView};
 use milli::vector::db::IndexEmbeddingConfig;
 pub use queue::Query;
-use queue::Queue;
+use queue::{IdempotencyCheck, IdempotencyKey, Queue};
 use roaring::RoaringBitmap;
 use scheduler::Scheduler;
 use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
+use tokio::sync::broadcast;
 pub use utils::{ReqwestRequestWrapper, UreqRequestWrapper};
 use uuid::Uuid;
 use versioning::Versioning;
@@ -95,10 +100,13 @@ use crate::dynamic_search_rules::DynamicSearchRules;
 use crate::index_mapper::IndexMapper;
 use crate::processing::ProcessingTasks;
 use crate::utils::clamp_to_page_size;
+use crate::webhook_deliveries::WebhookDeliveries;
 
 pub(crate) type BEI128 = I128<BE>;
 
 const TASK_SCHEDULER_SIZE_THRESHOLD_PERCENT_INT: u64 = 40;
+/// The number of task updates kept for the task streams that are late.
+const TASK_UPDATES_CAPACITY: usize = 1024;
 
 mod db_name {
     pub const CHAT_SETTINGS: &str = "chat-settings";
@@ -107,6 +115,9 @@ mod db_name {
 
 mod db_keys {
     pub const WEBHOOKS: &str = "webhooks";
+    pub const SCHEDULER_PAUSED_AT: &str = "scheduler-paused-at";
+    pub const TASK_RETENTION_POLICY: &str = "task-retention-policy";
+    pub const SEARCH_CURSOR_SECRET: &str = "search-cursor-secret";
 }
 
 #[derive(Debug)]
@@ -150,6 +161,16 @@ pub struct IndexSchedulerOptions {
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined number of tasks at once.
     pub max_number_of_batched_tasks: usize,
+    /// The maximum number of batches of different indexes that can be processed at the same time.
+    ///
+    /// Batches of global tasks, like dumps, snapshots or swaps, are always processed alone.
+    pub max_concurrent_batches: usize,
+    /// Set to `true` to make the indexes take turns when picking the next batch,
+    /// instead of always starting from the oldest enqueued task.
+    pub fair_index_scheduling: bool,
+    /// How long a retried request with the same idempotency key returns the task
+    /// it first enqueued instead of registering a new one.
+    pub idempotency_key_retention: Duration,
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined maximum size (in bytes) of tasks at once.
     pub batched_tasks_size_limit: u64,
@@ -165,6 +186,10 @@ pub struct IndexSchedulerOptions {
     pub ip_policy: http_client::policy::IpPolicy,
     /// Fuel for Dynamic Search Rules
     pub dsr_fuel: DsrFuel,
+    /// The secret from which the keys of the search cursors are derived, usually the master key.
+    ///
+    /// When `None`, a secret drawn on the first launch and persisted in the task database is used.
+    pub search_cursor_secret: Option<String>,
 }
 
 /// Structure which holds meilisearch's indexes and schedules the tasks
@@ -196,6 +221,10 @@ pub struct IndexScheduler {
 
     /// Webhook, loaded and stored in the `persisted` database
     webhooks: Arc<Webhooks>,
+    /// The notifications waiting to be delivered to the webhooks.
+    webhook_deliveries: WebhookDeliveries,
+    /// Broadcasts the uids of the tasks whose status changed to the task streams.
+    task_updates: broadcast::Sender<Arc<RoaringBitmap>>,
 
     /// A map to retrieve the runtime representation of an embedder depending on its configuration.
     ///
@@ -232,6 +261,9 @@ pub struct IndexScheduler {
 
     /// Fuel for dynamic search rules
     dsr_fuel: DsrFuel,
+
+    /// The secret from which the keys of the search cursors are derived.
+    search_cursor_secret: Arc<str>,
 }
 
 impl IndexScheduler {
@@ -248,6 +280,8 @@ impl IndexScheduler {
             export_default_payload_size_bytes: self.export_default_payload_size_bytes,
 
             webhooks: self.webhooks.clone(),
+            webhook_deliveries: self.webhook_deliveries.clone(),
+            task_updates: self.task_updates.clone(),
             embedders: self.embedders.clone(),
             #[cfg(test)]
             test_breakpoint_sdr: self.test_breakpoint_sdr.clone(),
@@ -260,6 +294,7 @@ impl IndexScheduler {
             runtime: self.runtime.clone(),
             web_client: self.web_client.clone(),
             dsr_fuel: self.dsr_fuel,
+            search_cursor_secret: self.search_cursor_secret.clone(),
         }
     }
 
@@ -268,6 +303,7 @@ impl IndexScheduler {
             + Queue::nb_db()
             + IndexMapper::nb_db()
             + features::FeatureData::nb_db()
+            + WebhookDeliveries::nb_db()
             + 1 // chat-prompts
             + 1 // persisted
             + 1 // legacy dynamic search rules
@@ -342,10 +378,33 @@ impl IndexScheduler {
         let mut webhooks = webhooks_db.get(&wtxn, db_keys::WEBHOOKS)?.unwrap_or_default();
         webhooks
             .with_cli(options.cli_webhook_url.clone(), options.cli_webhook_authorization.clone());
+        let webhook_deliveries = WebhookDeliveries::new(&env, &mut wtxn)?;
+        let paused_at = persisted
+            .get(&wtxn, db_keys::SCHEDULER_PAUSED_AT)?
+            .map(|paused_at| OffsetDateTime::parse(paused_at, &Rfc3339))
+            .transpose()
+            .map_err(|_| Error::CorruptedTaskQueue)?;
+        let task_retention_policy = persisted
+            .remap_data_type::<SerdeJson<TaskRetentionPolicy>>()
+            .get(&wtxn, db_keys::TASK_RETENTION_POLICY)?
+            .unwrap_or_default();
+        let search_cursor_secret = match options.search_cursor_secret.as_deref() {
+            Some(secret) => secret.into(),
+            None => match persisted.get(&wtxn, db_keys::SEARCH_CURSOR_SECRET)? {
+                Some(secret) => secret.into(),
+                None => {
+                    let secret = meilisearch_auth::generate_master_key();
+                    persisted.put(&mut wtxn, db_keys::SEARCH_CURSOR_SECRET, &secret)?;
+                    secret.into()
+                }
+            },
+        };
 
         wtxn.commit()?;
 
         let scheduler = Scheduler::new(&options, auth_env);
+        *scheduler.paused_at.write().unwrap() = paused_at;
+        *scheduler.task_retention_policy.write().unwrap() = task_retention_policy;
 
         let web_client = http_client::reqwest::ClientBuilder::new()
             .build_with_policies(scheduler.ip_policy.clone(), Default::default())
@@ -360,6 +419,8 @@ impl IndexScheduler {
             env,
             persisted,
             webhooks: Arc::new(webhooks),
+            webhook_deliveries,
+            task_updates: broadcast::channel(TASK_UPDATES_CAPACITY).0,
             embedders: Default::default(),
             export_default_payload_size_bytes: options.export_default_payload_size_bytes,
 
@@ -374,6 +435,7 @@ impl IndexScheduler {
             runtime,
             web_client,
             dsr_fuel: options.dsr_fuel,
+            search_cursor_secret,
         })
     }
 
@@ -483,6 +545,9 @@ impl IndexScheduler {
     /// This function will execute in a different thread and must be called
     /// only once per index scheduler.
     fn run(&self) {
+        // The notifications enqueued before a restart must be delivered even if no task is processed.
+        self.run_webhook_deliveries();
+
         // If the number of batched tasks is 0, we don't need to run the scheduler at all.
         // It will never be able to process any tasks.
         if self.scheduler.max_number_of_batched_tasks == 0 {
@@ -498,10 +563,16 @@ impl IndexScheduler {
                 run.scheduler.wake_up.wait_timeout(std::time::Duration::from_secs(60));
 
                 loop {
-                    let ret = catch_unwind(AssertUnwindSafe(|| run.tick()));
+                    let ret = catch_unwind(AssertUnwindSafe(|| {
+                        if run.scheduler.max_concurrent_batches > 1 {
+                            run.tick_concurrently()
+                        } else {
+                            run.tick()
+                        }
+                    }));
                     match ret {
                         Ok(Ok(TickOutcome::TickAgain(_))) => (),
-                        Ok(Ok(TickOutcome::WaitForSignal)) => run.scheduler.wake_up.wait(),
+                        Ok(Ok(TickOutcome::WaitForSignal)) => run.wait_for_signal(),
                         Ok(Ok(TickOutcome::StopProcessingForever)) => break,
                         Ok(Err(e)) => {
                             tracing::error!("{e}");
@@ -520,6 +591,33 @@ impl IndexScheduler {
             .unwrap();
     }
 
+    /// Wait to be woken up, until the next delayed task becomes eligible for batching,
+    /// or until the task retention policy must be applied again.
+    fn wait_for_signal(&self) {
+        let now = OffsetDateTime::now_utc();
+        let next_run_at = match self.read_txn() {
+            Ok(rtxn) => self.queue.tasks.next_run_at(&rtxn, now),
+            Err(e) => Err(e),
+        };
+        let mut timeout = match next_run_at {
+            Ok(run_at) => run_at.map(|run_at| (run_at - now).unsigned_abs()),
+            Err(e) => {
+                tracing::error!("Could not retrieve the next delayed task: {e}");
+                None
+            }
+        };
+        if !self.scheduler.task_retention_policy.read().unwrap().is_disabled() {
+            let interval = scheduler::TASK_RETENTION_INTERVAL;
+            timeout = Some(timeout.map_or(interval, |timeout| timeout.min(interval)));
+        }
+        match timeout {
+            Some(timeout) => {
+                self.scheduler.wake_up.wait_timeout(timeout);
+            }
+            None => self.scheduler.wake_up.wait(),
+        }
+    }
+
     pub fn indexer_config(&self) -> &IndexerConfig {
         self.index_mapper.indexer_config()
     }
@@ -665,6 +763,77 @@ impl IndexScheduler {
         self.queue.get_stats(&rtxn, &self.processing_tasks.read().unwrap())
     }
 
+    /// Return the date at which the processing of the tasks was paused, if it is paused.
+    pub fn paused_at(&self) -> Option<OffsetDateTime> {
+        *self.scheduler.paused_at.read().unwrap()
+    }
+
+    /// Stop picking new batches until [`Self::resume_processing`] is called, even after a restart.
+    ///
+    /// The tasks can still be registered while the processing is paused. The batches being processed
+    /// are finished, unless `abort_processing` is set, in which case they are aborted and their tasks
+    /// are enqueued again.
+    ///
+    /// Pausing an already paused scheduler keeps the original date.
+    pub fn pause_processing(&self, abort_processing: bool) -> Result<OffsetDateTime> {
+        let mut paused_at = self.scheduler.paused_at.write().unwrap();
+        let date = match *paused_at {
+            Some(date) => date,
+            None => {
+                let date = OffsetDateTime::now_utc();
+                let formatted = date.format(&Rfc3339).map_err(|_| Error::CorruptedTaskQueue)?;
+                let mut wtxn = self.env.write_txn()?;
+                self.persisted.put(&mut wtxn, db_keys::SCHEDULER_PAUSED_AT, &formatted)?;
+                wtxn.commit()?;
+                *paused_at = Some(date);
+                date
+            }
+        };
+        drop(paused_at);
+
+        if abort_processing {
+            let processing_tasks = self.processing_tasks.read().unwrap();
+            if processing_tasks.batch.is_some() {
+                self.scheduler.must_stop_processing.must_stop();
+            }
+            processing_tasks.abort_concurrent_processing();
+        }
+        self.scheduler.wake_up.signal();
+
+        Ok(date)
+    }
+
+    /// Resume the processing of the tasks paused by [`Self::pause_processing`].
+    pub fn resume_processing(&self) -> Result<()> {
+        let mut paused_at = self.scheduler.paused_at.write().unwrap();
+        let mut wtxn = self.env.write_txn()?;
+        self.persisted.delete(&mut wtxn, db_keys::SCHEDULER_PAUSED_AT)?;
+        wtxn.commit()?;
+        *paused_at = None;
+        drop(paused_at);
+
+        self.scheduler.wake_up.signal();
+        Ok(())
+    }
+
+    /// Return the policy defining the finished tasks that are periodically deleted.
+    pub fn task_retention_policy(&self) -> TaskRetentionPolicy {
+        *self.scheduler.task_retention_policy.read().unwrap()
+    }
+
+    /// Replace the task retention policy, the new policy is applied right away.
+    pub fn update_task_retention_policy(&self, policy: TaskRetentionPolicy) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        let policy_db = self.persisted.remap_data_type::<SerdeJson<TaskRetentionPolicy>>();
+        policy_db.put(&mut wtxn, db_keys::TASK_RETENTION_POLICY, &policy)?;
+        wtxn.commit()?;
+
+        *self.scheduler.task_retention_policy.write().unwrap() = policy;
+        *self.scheduler.task_retention_applied_at.write().unwrap() = None;
+        self.scheduler.wake_up.signal();
+        Ok(())
+    }
+
     // Return true if there is at least one task that is processing.
     pub fn is_task_processing(&self) -> Result<bool> {
         Ok(!self.processing_tasks.read().unwrap().processing.is_empty())
@@ -680,6 +849,35 @@ impl IndexScheduler {
         Ok(nbr_index_processing_tasks > 0)
     }
 
+    /// Returns the uid, the tasks and the progress of every batch being processed.
+    pub fn get_processing_batches_progress(&self) -> Vec<(BatchId, RoaringBitmap, ProgressView)> {
+        let processing = self.processing_tasks.read().unwrap();
+        processing
+            .batches()
+            .filter_map(|(batch, progress)| {
+                let tasks = processing.batch_tasks(batch.uid)?;
+                let progress = progress?.as_progress_view()?;
+                Some((batch.uid, tasks, progress))
+            })
+            .collect()
+    }
+
+    /// Subscribes to the uids of the tasks whose status changed.
+    ///
+    /// The receiver is notified when tasks are enqueued, start processing and finish processing.
+    /// A receiver that falls too far behind misses the oldest notifications.
+    pub fn subscribe_to_task_updates(&self) -> broadcast::Receiver<Arc<RoaringBitmap>> {
+        self.task_updates.subscribe()
+    }
+
+    /// Notifies the task streams that the status of the given tasks changed.
+    pub(crate) fn notify_task_updates(&self, updated: RoaringBitmap) {
+        if !updated.is_empty() {
+            // An error only means that nobody is listening.
+            let _ = self.task_updates.send(Arc::new(updated));
+        }
+    }
+
     /// Return the tasks matching the query from the user's point of view along
     /// with the total number of tasks matching the query, ignoring from and limit.
     ///
@@ -782,6 +980,56 @@ impl IndexScheduler {
         self.register_with_custom_metadata_and_network(kind, custom_metadata, task_network, None)
     }
 
+    /// Register a new task in the scheduler, with metadata and an optional idempotency key.
+    ///
+    /// If a task was enqueued with the same idempotency key within the retention window,
+    /// this task is returned and nothing is registered, see [`Self::register_scheduled`].
+    pub fn register_idempotent(
+        &self,
+        kind: KindWithContent,
+        custom_metadata: Option<String>,
+        task_network: Option<TaskNetwork>,
+        idempotency_key: Option<String>,
+    ) -> Result<Task> {
+        self.register_task(
+            kind,
+            custom_metadata,
+            Priority::Normal,
+            None,
+            task_network,
+            None,
+            idempotency_key,
+        )
+    }
+
+    /// Register a new task in the scheduler, with metadata, a priority and an optional date to run it.
+    ///
+    /// The priority is used to pick the index of the next batch, see [`Priority`].
+    /// The task is not eligible for batching before `run_at`.
+    ///
+    /// If a task was enqueued with the same idempotency key within the retention window, this task
+    /// is returned instead, and the data associated with the new task is deleted. Reusing an
+    /// idempotency key for another operation, index or payload is an error.
+    pub fn register_scheduled(
+        &self,
+        kind: KindWithContent,
+        custom_metadata: Option<String>,
+        priority: Priority,
+        run_at: Option<OffsetDateTime>,
+        task_network: Option<TaskNetwork>,
+        idempotency_key: Option<String>,
+    ) -> Result<Task> {
+        self.register_task(
+            kind,
+            custom_metadata,
+            priority,
+            run_at,
+            task_network,
+            None,
+            idempotency_key,
+        )
+    }
+
     /// Register a new task in the scheduler, with metadata.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
@@ -802,6 +1050,28 @@ impl IndexScheduler {
         custom_metadata: Option<String>,
         task_network: Option<TaskNetwork>,
         new_network: Option<Network>,
+    ) -> Result<Task> {
+        self.register_task(
+            kind,
+            custom_metadata,
+            Priority::Normal,
+            None,
+            task_network,
+            new_network,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn register_task(
+        &self,
+        kind: KindWithContent,
+        custom_metadata: Option<String>,
+        priority: Priority,
+        run_at: Option<OffsetDateTime>,
+        task_network: Option<TaskNetwork>,
+        new_network: Option<Network>,
+        idempotency_key: Option<String>,
     ) -> Result<Task> {
         // if the task doesn't delete or cancel anything and 40% of the task queue is full, we must refuse to enqueue the incoming task
         if !matches!(&kind, KindWithContent::TaskDeletion { tasks, .. } | KindWithContent::TaskCancelation { tasks, .. } if !tasks.is_empty())
@@ -811,8 +1081,49 @@ impl IndexScheduler {
             return Err(Error::NoSpaceLeftInTaskQueue);
         }
 
+        // The fingerprint reads the whole update file, it must not hold the write transaction.
+        let idempotency_key = match idempotency_key {
+            Some(key) => {
+                let kinds = std::slice::from_ref(&kind);
+                match self.queue.idempotency_fingerprint(kinds, false) {
+                    Ok(fingerprint) => Some(IdempotencyKey { key, fingerprint }),
+                    Err(e) => {
+                        self.delete_update_files(kinds)?;
+                        return Err(e);
+                    }
+                }
+            }
+            None => None,
+        };
+
         let mut wtxn = self.env.write_txn()?;
 
+        let idempotency_key = match idempotency_key {
+            Some(key) => {
+                let check = self.queue.check_idempotency_key(
+                    &wtxn,
+                    key,
+                    std::slice::from_ref(&kind),
+                    false,
+                );
+                match check {
+                    Ok(IdempotencyCheck::New(key)) => Some(key),
+                    // nothing was written yet, we can abort the write transaction
+                    Ok(IdempotencyCheck::Enqueued(task)) => {
+                        drop(wtxn);
+                        self.delete_update_files(std::slice::from_ref(&kind))?;
+                        return Ok(task);
+                    }
+                    Err(e) => {
+                        drop(wtxn);
+                        self.delete_update_files(std::slice::from_ref(&kind))?;
+                        return Err(e);
+                    }
+                }
+            }
+            None => None,
+        };
+
         if let Some(TaskNetwork::Import { import_from, network_change, metadata }) = &task_network {
             self.update_network_task(&mut wtxn, network_change, |network_topology_change| {
                 Ok(network_topology_change.receive_remote_task(
@@ -830,15 +1141,21 @@ impl IndexScheduler {
             &mut wtxn,
             &kind,
             custom_metadata,
+            priority,
+            run_at,
+            None,
+            idempotency_key,
             task_network.map(DbTaskNetwork::from),
         )?;
 
         // If the registered task is a task cancelation
         // we inform the processing tasks to stop (if necessary).
         if let KindWithContent::TaskCancelation { tasks, .. } = kind {
-            if self.processing_tasks.read().unwrap().must_cancel_processing_tasks(&tasks) {
+            let processing_tasks = self.processing_tasks.read().unwrap();
+            if processing_tasks.must_cancel_processing_tasks(&tasks) {
                 self.scheduler.must_stop_processing.must_stop();
             }
+            processing_tasks.cancel_concurrent_processing_tasks(&tasks);
         }
 
         let result = match new_network {
@@ -853,9 +1170,105 @@ impl IndexScheduler {
 
         // notify the scheduler loop to execute a new tick
         self.scheduler.wake_up.signal();
+        self.notify_task_updates(RoaringBitmap::from_iter([task.uid]));
         Ok(task)
     }
 
+    /// Register the tasks of a transaction in the scheduler.
+    ///
+    /// The tasks are registered in a single write transaction, get consecutive uids and share
+    /// the uid of the first task as their [`Task::transaction_uid`]. They are then processed
+    /// in the same batch and their changes are committed in all the indexes together, or not at all.
+    ///
+    /// If it fails, it tries to delete the data associated with the tasks.
+    ///
+    /// The idempotency key is recorded on the first task of the transaction. If the same
+    /// transaction was enqueued with this key within the retention window, its tasks are returned
+    /// instead. Reusing the key for another request is an error.
+    pub fn register_transaction(
+        &self,
+        kinds: Vec<KindWithContent>,
+        idempotency_key: Option<String>,
+    ) -> Result<Vec<Task>> {
+        if (self.env.non_free_pages_size()? * 100) / self.env.info().map_size as u64
+            > TASK_SCHEDULER_SIZE_THRESHOLD_PERCENT_INT
+        {
+            return Err(Error::NoSpaceLeftInTaskQueue);
+        }
+
+        // The fingerprint reads the whole update files, it must not hold the write transaction.
+        let idempotency_key = match idempotency_key {
+            Some(key) => match self.queue.idempotency_fingerprint(&kinds, true) {
+                Ok(fingerprint) => Some(IdempotencyKey { key, fingerprint }),
+                Err(e) => {
+                    self.delete_update_files(&kinds)?;
+                    return Err(e);
+                }
+            },
+            None => None,
+        };
+
+        let mut wtxn = self.env.write_txn()?;
+
+        let mut idempotency_key = match idempotency_key {
+            Some(key) => match self.queue.check_idempotency_key(&wtxn, key, &kinds, true) {
+                Ok(IdempotencyCheck::New(key)) => Some(key),
+                Ok(IdempotencyCheck::Enqueued(task)) => {
+                    self.delete_update_files(&kinds)?;
+                    let transaction_uid = task.transaction_uid.ok_or(Error::CorruptedTaskQueue)?;
+                    let transaction = self.queue.tasks.get_transaction(&wtxn, transaction_uid)?;
+                    return self.queue.tasks.get_existing_tasks(&wtxn, transaction);
+                }
+                Err(e) => {
+                    self.delete_update_files(&kinds)?;
+                    return Err(e);
+                }
+            },
+            None => None,
+        };
+
+        let transaction_uid = self.queue.tasks.next_task_id(&wtxn)?;
+
+        let mut tasks = Vec::with_capacity(kinds.len());
+        let result = kinds
+            .iter()
+            .try_for_each(|kind| {
+                let task = self.queue.register(
+                    &mut wtxn,
+                    kind,
+                    None,
+                    Priority::Normal,
+                    None,
+                    Some(transaction_uid),
+                    idempotency_key.take(),
+                    None,
+                )?;
+                tasks.push(task);
+                Ok(())
+            })
+            .and_then(|()| wtxn.commit().map_err(Error::from));
+
+        if let Err(e) = result {
+            self.delete_update_files(&kinds)?;
+            return Err(e);
+        }
+
+        // notify the scheduler loop to execute a new tick
+        self.scheduler.wake_up.signal();
+        self.notify_task_updates(tasks.iter().map(|task| task.uid).collect());
+        Ok(tasks)
+    }
+
+    /// Delete the update files of tasks that won't be registered.
+    fn delete_update_files(&self, kinds: &[KindWithContent]) -> Result<()> {
+        for kind in kinds {
+            if let KindWithContent::DocumentAdditionOrUpdate { content_file, .. } = kind {
+                self.queue.delete_update_file(*content_file)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn network_no_index_for_remote(
         &self,
         remote_name: String,
@@ -1045,48 +1458,178 @@ impl IndexScheduler {
         if webhooks.is_empty() {
             return;
         }
-        let this = self.private_clone();
-        // We must take the RoTxn before entering the thread::spawn otherwise another batch may be
-        // processed before we had the time to take our txn.
-        let rtxn = match self.env.clone().static_read_txn() {
-            Ok(rtxn) => rtxn,
-            Err(e) => {
-                tracing::error!("Couldn't get an rtxn to notify the webhook: {e}");
-                return;
+
+        let result = (|| -> Result<()> {
+            let rtxn = self.read_txn()?;
+
+            // The webhooks notified of the same tasks share the same payload
+            let mut groups: Vec<(RoaringBitmap, Vec<Uuid>)> = Vec::new();
+            if webhooks.values().all(|webhook| webhook.filters.is_empty()) {
+                groups.push((updated, webhooks.into_keys().collect()));
+            } else {
+                let webhooks: Vec<_> = webhooks.into_iter().collect();
+                let mut matching = vec![RoaringBitmap::new(); webhooks.len()];
+                for task_id in &updated {
+                    let task = self
+                        .queue
+                        .tasks
+                        .get_task(&rtxn, task_id)?
+                        .ok_or(Error::CorruptedTaskQueue)?;
+                    for ((_, webhook), tasks) in webhooks.iter().zip(&mut matching) {
+                        if webhook.filters.matches(&task) {
+                            tasks.insert(task_id);
+                        }
+                    }
+                }
+
+                for ((uuid, _), tasks) in webhooks.into_iter().zip(matching) {
+                    if tasks.is_empty() {
+                        continue;
+                    }
+                    match groups.iter_mut().find(|(group, _)| *group == tasks) {
+                        Some((_, uuids)) => uuids.push(uuid),
+                        None => groups.push((tasks, vec![uuid])),
+                    }
+                }
             }
-        };
-        let config = http_client::ureq::config::Config::builder()
-            .prepare(|config| config.timeout_global(Some(Duration::from_secs(30))))
-            .build();
-        let client =
-            http_client::ureq::Agent::new_with_config(config, self.scheduler.ip_policy.clone());
 
-        std::thread::spawn(move || {
-            for (uuid, Webhook { url, headers }) in webhooks.iter() {
+            let mut payloads = Vec::with_capacity(groups.len());
+            for (tasks, uuids) in groups {
                 let task_reader = TaskReader {
                     rtxn: &rtxn,
-                    index_scheduler: &this,
-                    tasks: &mut updated.iter(),
+                    index_scheduler: self,
+                    tasks: &mut tasks.iter(),
                     buffer: Vec::with_capacity(page_size::get()),
                     written: 0,
                 };
+                let mut payload = Vec::new();
+                GzEncoder::new(BufReader::new(task_reader), Compression::default())
+                    .read_to_end(&mut payload)?;
+                payloads.push((uuids, tasks.len(), payload));
+            }
+            drop(rtxn);
+
+            if payloads.is_empty() {
+                return Ok(());
+            }
+
+            // The payloads are persisted before being sent so that they survive a restart or an outage of the webhooks
+            let mut wtxn = self.env.write_txn()?;
+            let now = OffsetDateTime::now_utc();
+            for (uuids, nb_tasks, payload) in payloads {
+                self.webhook_deliveries.enqueue(&mut wtxn, uuids, nb_tasks, &payload, now)?;
+            }
+            wtxn.commit()?;
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => self.webhook_deliveries.wake_up.signal(),
+            Err(e) => tracing::error!("Couldn't enqueue the notification of the webhooks: {e}"),
+        }
+    }
 
-                let mut reader =
-                    GzEncoder::new(BufReader::new(task_reader), Compression::default());
+    /// Start the thread sending the notifications enqueued for the webhooks.
+    ///
+    /// A failed delivery is retried with an exponential backoff, the thread sleeps until the next
+    /// attempt or until new notifications are enqueued.
+    fn run_webhook_deliveries(&self) {
+        let run = self.private_clone();
+        let config = http_client::ureq::config::Config::builder()
+            .prepare(|config| config.timeout_global(Some(Duration::from_secs(30))))
+            .build();
+        let client =
+            http_client::ureq::Agent::new_with_config(config, self.scheduler.ip_policy.clone());
 
-                let mut request = client
-                    .post(url)
-                    .header("Content-Encoding", "gzip")
-                    .header("Content-Type", "application/x-ndjson");
-                for (header_name, header_value) in headers.iter() {
-                    request = request.header(header_name, header_value);
+        std::thread::Builder::new()
+            .name(String::from("webhook-deliveries"))
+            .spawn(move || loop {
+                match run.deliver_webhooks(&client) {
+                    Ok(Some(next_attempt_at)) => {
+                        let delay = next_attempt_at - OffsetDateTime::now_utc();
+                        if let Ok(delay) = Duration::try_from(delay) {
+                            run.webhook_deliveries.wake_up.wait_timeout(delay);
+                        }
+                    }
+                    Ok(None) => run.webhook_deliveries.wake_up.wait(),
+                    Err(e) => {
+                        tracing::error!("While delivering the notifications to the webhooks: {e}");
+                        std::thread::sleep(Duration::from_secs(10));
+                    }
                 }
-                if let Err(e) = request.send(http_client::ureq::SendBody::from_reader(&mut reader))
-                {
-                    tracing::error!("While sending data to the webhook {uuid}: {e}");
+            })
+            .unwrap();
+    }
+
+    /// Attempt the webhook deliveries that are due, and return when the deliveries must be attempted again.
+    fn deliver_webhooks(
+        &self,
+        client: &http_client::ureq::Agent,
+    ) -> Result<Option<OffsetDateTime>> {
+        let rtxn = self.read_txn()?;
+        let (due, next_attempt_at) =
+            self.webhook_deliveries.due(&rtxn, OffsetDateTime::now_utc())?;
+        if due.is_empty() {
+            return Ok(next_attempt_at);
+        }
+
+        let mut deliveries = Vec::with_capacity(due.len());
+        for delivery in due {
+            let payload =
+                self.webhook_deliveries.payload(&rtxn, delivery.payload)?.map(<[u8]>::to_vec);
+            deliveries.push((delivery, payload));
+        }
+        // We must not keep the rtxn open while waiting for the webhooks to answer
+        drop(rtxn);
+
+        let webhooks = self.webhooks.get_all();
+        for (delivery, payload) in deliveries {
+            let (Some(Webhook { url, headers, filters: _, secret }), Some(payload)) =
+                (webhooks.get(&delivery.webhook), payload)
+            else {
+                // the webhook was deleted since the delivery was enqueued
+                let mut wtxn = self.env.write_txn()?;
+                self.webhook_deliveries.remove(&mut wtxn, &delivery)?;
+                wtxn.commit()?;
+                continue;
+            };
+
+            let mut request = client
+                .post(url)
+                .header("Content-Encoding", "gzip")
+                .header("Content-Type", "application/x-ndjson");
+            for (header_name, header_value) in headers.iter() {
+                request = request.header(header_name, header_value);
+            }
+            if let Some(secret) = secret {
+                // The timestamp is part of the signature so that receivers can reject old payloads
+                let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+                let signature = webhook_deliveries::sign_payload(secret, timestamp, &payload);
+                request = request
+                    .header("X-Meilisearch-Timestamp", timestamp.to_string())
+                    .header("X-Meilisearch-Signature", format!("sha256={signature}"));
+            }
+            let result =
+                request.send(http_client::ureq::SendBody::from_reader(&mut payload.as_slice()));
+
+            let now = OffsetDateTime::now_utc();
+            let mut wtxn = self.env.write_txn()?;
+            match result {
+                Ok(_) => self.webhook_deliveries.succeeded(&mut wtxn, &delivery, now)?,
+                Err(e) => {
+                    tracing::error!(
+                        "While sending data to the webhook {} (attempt {}): {e}",
+                        delivery.webhook,
+                        delivery.attempts + 1
+                    );
+                    self.webhook_deliveries.failed(&mut wtxn, delivery, e.to_string(), now)?;
                 }
             }
-        });
+            wtxn.commit()?;
+        }
+
+        // the next deliveries of the webhooks may be due right away
+        Ok(Some(OffsetDateTime::now_utc()))
     }
 
     pub fn user_index_stats(&self, index_uid: &str) -> Result<IndexStats> {
@@ -1143,11 +1686,23 @@ impl IndexScheduler {
         self.dsr_fuel
     }
 
+    /// The secret from which the keys encrypting the search cursors are derived,
+    /// it is the same across restarts and, with a master key, across instances.
+    pub fn search_cursor_secret(&self) -> &str {
+        &self.search_cursor_secret
+    }
+
     pub fn update_runtime_webhooks(&self, runtime: RuntimeWebhooks) -> Result<()> {
         let webhooks = Webhooks::from_runtime(runtime);
         let mut wtxn = self.env.write_txn()?;
         let webhooks_db = self.persisted.remap_data_type::<SerdeJson<Webhooks>>();
         webhooks_db.put(&mut wtxn, db_keys::WEBHOOKS, &webhooks)?;
+        // The pending notifications of the deleted webhooks will never be delivered
+        let mut uuids: Vec<_> = webhooks.get_runtime().into_keys().collect();
+        if self.webhooks.cli.is_some() {
+            uuids.push(Uuid::nil());
+        }
+        self.webhook_deliveries.retain_webhooks(&mut wtxn, &uuids)?;
         wtxn.commit()?;
         self.webhooks.update_runtime(webhooks.into_runtime());
         Ok(())
@@ -1166,6 +1721,23 @@ impl IndexScheduler {
         self.webhooks.get_runtime()
     }
 
+    /// Returns the state of the deliveries of the notifications to the webhook.
+    pub fn webhook_delivery_status(&self, uuid: Uuid) -> Result<WebhookDeliveryStatus> {
+        let rtxn = self.read_txn()?;
+        self.webhook_deliveries.status(&rtxn, uuid)
+    }
+
+    /// Schedules the notifications that could not be delivered to the webhook to be sent again,
+    /// and returns how many there are.
+    pub fn replay_webhook_deliveries(&self, uuid: Uuid) -> Result<u64> {
+        let mut wtxn = self.env.write_txn()?;
+        let replayed =
+            self.webhook_deliveries.replay(&mut wtxn, uuid, OffsetDateTime::now_utc())?;
+        wtxn.commit()?;
+        self.webhook_deliveries.wake_up.signal();
+        Ok(replayed)
+    }
+
     pub fn embedders(
         &self,
         index_uid: String,
@@ -1364,6 +1936,6 @@ impl From<&CliWebhook> for Webhook {
         if let Some(ref auth) = webhook.auth {
             headers.insert("Authorization".to_string(), auth.to_string());
         }
-        Self { url: webhook.url.to_string(), headers }
+        Self { url: webhook.url.to_string(), headers, filters: Default::default(), secret: None }
     }
 }
@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::milli::progress::{AtomicSubStep, NamedStep, Progress, ProgressView};
-use meilisearch_types::milli::{make_atomic_progress, make_enum_progress};
+use meilisearch_types::milli::{make_atomic_progress, make_enum_progress, MustStopProcessing};
 use roaring::RoaringBitmap;
 
 use crate::utils::ProcessingBatch;
@@ -9,10 +10,28 @@ use crate::utils::ProcessingBatch;
 #[derive(Clone, Default)]
 pub struct ProcessingTasks {
     pub batch: Option<Arc<ProcessingBatch>>,
-    /// The list of tasks ids that are currently running.
+    /// The list of tasks ids that are currently running,
+    /// either in the main batch or in one of the concurrent batches.
     pub processing: Arc<RoaringBitmap>,
     /// The progress on processing tasks
     pub progress: Option<Progress>,
+    /// The batches of independent indexes that are processed at the same time.
+    pub concurrent: Vec<ConcurrentBatch>,
+}
+
+/// A batch processed at the same time as other batches working on different indexes.
+#[derive(Clone)]
+pub struct ConcurrentBatch {
+    pub batch: Arc<ProcessingBatch>,
+    /// The list of tasks ids processed by this batch.
+    pub processing: RoaringBitmap,
+    pub progress: Progress,
+    /// The indexes this batch works on, no other batch can touch them until it finishes.
+    pub indexes: Vec<String>,
+    /// Set to stop this batch only, without affecting the other ones.
+    pub must_stop_processing: MustStopProcessing,
+    /// The indexing memory allocated to this batch, if the indexing memory is limited.
+    pub max_memory: Option<usize>,
 }
 
 impl ProcessingTasks {
@@ -32,7 +51,7 @@ impl ProcessingTasks {
         processing: RoaringBitmap,
     ) -> Progress {
         self.batch = Some(Arc::new(processing_batch));
-        self.processing = Arc::new(processing);
+        self.processing = Arc::new(processing | self.concurrent_tasks());
         let progress = Progress::default();
         progress.update_progress(BatchProgress::ProcessingTasks);
         self.progress = Some(progress.clone());
@@ -40,18 +59,131 @@ impl ProcessingTasks {
         progress
     }
 
-    /// Set the processing tasks to an empty list
+    /// Set the processing tasks of the main batch to an empty list.
+    ///
+    /// The concurrent batches are left untouched and are not part of the returned value.
     pub fn stop_processing(&mut self) -> Self {
+        let concurrent_tasks = self.concurrent_tasks();
+        let processing = std::mem::replace(&mut self.processing, Arc::new(concurrent_tasks));
         Self {
             batch: std::mem::take(&mut self.batch),
-            processing: std::mem::take(&mut self.processing),
+            processing: Arc::new(&*processing - &*self.processing),
             progress: std::mem::take(&mut self.progress),
+            concurrent: Vec::new(),
+        }
+    }
+
+    /// Puts back a main batch previously removed by [`Self::stop_processing`].
+    pub fn resume_processing(&mut self, previous: Self) {
+        let Self { batch, processing, progress, concurrent: _ } = previous;
+        self.batch = batch;
+        self.processing = Arc::new(&*self.processing | &*processing);
+        self.progress = progress;
+    }
+
+    /// Stores a batch processed alongside the others, and returns its progress.
+    pub fn start_concurrent_processing(
+        &mut self,
+        processing_batch: ProcessingBatch,
+        processing: RoaringBitmap,
+        indexes: Vec<String>,
+        must_stop_processing: MustStopProcessing,
+        max_memory: Option<usize>,
+    ) -> Progress {
+        let progress = Progress::default();
+        progress.update_progress(BatchProgress::ProcessingTasks);
+        self.processing = Arc::new(&*self.processing | &processing);
+        self.concurrent.push(ConcurrentBatch {
+            batch: Arc::new(processing_batch),
+            processing,
+            progress: progress.clone(),
+            indexes,
+            must_stop_processing,
+            max_memory,
+        });
+
+        progress
+    }
+
+    /// Removes the concurrent batch with the given uid, if any.
+    pub fn stop_concurrent_processing(&mut self, batch_uid: BatchId) {
+        if let Some(position) = self.concurrent.iter().position(|c| c.batch.uid == batch_uid) {
+            let ConcurrentBatch { processing, .. } = self.concurrent.swap_remove(position);
+            self.processing = Arc::new(&*self.processing - &processing);
         }
     }
 
+    /// The tasks processed by the concurrent batches.
+    fn concurrent_tasks(&self) -> RoaringBitmap {
+        self.concurrent.iter().map(|c| &c.processing).fold(RoaringBitmap::new(), |acc, t| acc | t)
+    }
+
+    /// Iterates over all the processing batches along with their progress.
+    pub fn batches(&self) -> impl Iterator<Item = (&Arc<ProcessingBatch>, Option<&Progress>)> {
+        self.batch
+            .iter()
+            .map(|batch| (batch, self.progress.as_ref()))
+            .chain(self.concurrent.iter().map(|c| (&c.batch, Some(&c.progress))))
+    }
+
+    /// Returns the uids of all the processing batches.
+    pub fn batch_uids(&self) -> RoaringBitmap {
+        self.batches().map(|(batch, _)| batch.uid).collect()
+    }
+
+    /// Returns the processing batch with the given uid.
+    pub fn batch(&self, batch_uid: BatchId) -> Option<(&Arc<ProcessingBatch>, Option<&Progress>)> {
+        self.batches().find(|(batch, _)| batch.uid == batch_uid)
+    }
+
+    /// Returns the tasks processed by the batch with the given uid.
+    pub fn batch_tasks(&self, batch_uid: BatchId) -> Option<RoaringBitmap> {
+        if let Some(concurrent) = self.concurrent.iter().find(|c| c.batch.uid == batch_uid) {
+            return Some(concurrent.processing.clone());
+        }
+        match &self.batch {
+            Some(batch) if batch.uid == batch_uid => {
+                Some(&*self.processing - self.concurrent_tasks())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the batch processing the given task.
+    pub fn batch_of_task(&self, task_uid: u32) -> Option<&Arc<ProcessingBatch>> {
+        if !self.processing.contains(task_uid) {
+            return None;
+        }
+        match self.concurrent.iter().find(|c| c.processing.contains(task_uid)) {
+            Some(concurrent) => Some(&concurrent.batch),
+            None => self.batch.as_ref(),
+        }
+    }
+
+    /// Returns the indexes on which a concurrent batch is working.
+    pub fn busy_indexes(&self) -> impl Iterator<Item = &str> {
+        self.concurrent.iter().flat_map(|c| c.indexes.iter().map(String::as_str))
+    }
+
     /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
     pub fn must_cancel_processing_tasks(&self, canceled_tasks: &RoaringBitmap) -> bool {
-        !self.processing.is_disjoint(canceled_tasks)
+        !(&*self.processing - self.concurrent_tasks()).is_disjoint(canceled_tasks)
+    }
+
+    /// Stops the concurrent batches processing at least one of the canceled tasks.
+    pub fn cancel_concurrent_processing_tasks(&self, canceled_tasks: &RoaringBitmap) {
+        for concurrent in &self.concurrent {
+            if !concurrent.processing.is_disjoint(canceled_tasks) {
+                concurrent.must_stop_processing.must_stop();
+            }
+        }
+    }
+
+    /// Stops all the concurrent batches.
+    pub fn abort_concurrent_processing(&self) {
+        for concurrent in &self.concurrent {
+            concurrent.must_stop_processing.must_stop();
+        }
     }
 }
 
@@ -259,6 +391,7 @@ make_atomic_progress!(UpdateFile alias AtomicUpdateFileStep => "update file" );
 mod test {
     use std::sync::atomic::Ordering;
 
+    use big_s::S;
     use meili_snap::{json_string, snapshot};
 
     use super::*;
@@ -369,4 +502,42 @@ mod test {
         }
         "#);
     }
+
+    #[test]
+    fn concurrent_batches() {
+        let mut processing = ProcessingTasks::new();
+        processing.start_concurrent_processing(
+            ProcessingBatch::new(1),
+            RoaringBitmap::from_iter([1, 2]),
+            vec![S("doggos")],
+            MustStopProcessing::default(),
+            None,
+        );
+        let catto_must_stop = MustStopProcessing::default();
+        processing.start_concurrent_processing(
+            ProcessingBatch::new(2),
+            RoaringBitmap::from_iter([3]),
+            vec![S("cattos")],
+            catto_must_stop.clone(),
+            None,
+        );
+        snapshot!(format!("{:?}", processing.processing), @"RoaringBitmap<[1, 2, 3]>");
+        snapshot!(format!("{:?}", processing.batch_uids()), @"RoaringBitmap<[1, 2]>");
+        snapshot!(format!("{:?}", processing.batch_tasks(2)), @"Some(RoaringBitmap<[3]>)");
+        snapshot!(format!("{:?}", processing.batch_of_task(3).map(|batch| batch.uid)), @"Some(2)");
+        snapshot!(format!("{:?}", processing.busy_indexes().collect::<Vec<_>>()), @r#"["doggos", "cattos"]"#);
+
+        // the main batch doesn't see the tasks of the concurrent batches
+        assert!(!processing.must_cancel_processing_tasks(&RoaringBitmap::from_iter([3])));
+        processing.cancel_concurrent_processing_tasks(&RoaringBitmap::from_iter([3]));
+        assert!(catto_must_stop.get());
+
+        let previous = processing.stop_processing();
+        snapshot!(format!("{:?}", previous.processing), @"RoaringBitmap<[]>");
+        snapshot!(format!("{:?}", processing.processing), @"RoaringBitmap<[1, 2, 3]>");
+
+        processing.stop_concurrent_processing(1);
+        snapshot!(format!("{:?}", processing.processing), @"RoaringBitmap<[3]>");
+        snapshot!(format!("{:?}", processing.batch_tasks(1)), @"None");
+    }
 }
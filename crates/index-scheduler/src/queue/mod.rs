@@ -15,18 +15,21 @@ use file_store::FileStore;
 use meilisearch_types::batches::BatchId;
 use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
+use meilisearch_types::task_retention::TaskRetentionPolicy;
 use meilisearch_types::tasks::network::DbTaskNetwork;
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Priority, Status, Task};
 use roaring::RoaringBitmap;
+use sha2::{Digest, Sha256};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 pub(crate) use self::batches::BatchQueue;
-pub(crate) use self::tasks::TaskQueue;
+pub(crate) use self::tasks::{CommittingTransaction, IdempotencyKeyEntry, TaskQueue};
 use crate::processing::ProcessingTasks;
 use crate::utils::{
-    check_index_swap_validity, filter_out_references_to_newer_tasks, ProcessingBatch,
+    check_index_swap_validity, filter_out_references_to_newer_tasks, keep_ids_within_datetimes,
+    ProcessingBatch,
 };
 use crate::{Error, IndexSchedulerOptions, Result, TaskId};
 
@@ -82,6 +85,10 @@ pub struct Query {
     pub before_finished_at: Option<OffsetDateTime>,
     /// Exclusive lower bound of the matched tasks' [`finished_at`](meilisearch_types::tasks::Task::finished_at) field.
     pub after_finished_at: Option<OffsetDateTime>,
+    /// Exclusive upper bound of the matched tasks' [`run_at`](meilisearch_types::tasks::Task::run_at) field.
+    pub before_run_at: Option<OffsetDateTime>,
+    /// Exclusive lower bound of the matched tasks' [`run_at`](meilisearch_types::tasks::Task::run_at) field.
+    pub after_run_at: Option<OffsetDateTime>,
 }
 
 impl Query {
@@ -106,6 +113,8 @@ impl Query {
                 after_started_at: None,
                 before_finished_at: None,
                 after_finished_at: None,
+                before_run_at: None,
+                after_run_at: None,
             }
         )
     }
@@ -124,6 +133,22 @@ impl Query {
     }
 }
 
+/// An idempotency key along with the fingerprint of the request it was sent with.
+#[derive(Debug, Clone)]
+pub(crate) struct IdempotencyKey {
+    pub key: String,
+    pub fingerprint: String,
+}
+
+/// The outcome of [`Queue::check_idempotency_key`].
+#[derive(Debug)]
+pub(crate) enum IdempotencyCheck {
+    /// The key was not used within its retention window, the request must be enqueued.
+    New(IdempotencyKey),
+    /// The same request was already enqueued as this task.
+    Enqueued(Task),
+}
+
 /// Structure which holds meilisearch's indexes and schedules the tasks
 /// to be performed on them.
 pub struct Queue {
@@ -139,6 +164,9 @@ pub struct Queue {
     /// The max number of tasks allowed before the scheduler starts to delete
     /// the finished tasks automatically.
     pub(crate) max_number_of_tasks: usize,
+
+    /// How long an idempotency key returns the task it was first enqueued with.
+    pub(crate) idempotency_key_retention: Duration,
 }
 
 impl Queue {
@@ -149,6 +177,7 @@ impl Queue {
             batch_to_tasks_mapping: self.batch_to_tasks_mapping,
             file_store: self.file_store.clone(),
             max_number_of_tasks: self.max_number_of_tasks,
+            idempotency_key_retention: self.idempotency_key_retention,
         }
     }
 
@@ -170,6 +199,7 @@ impl Queue {
             tasks: TaskQueue::new(env, wtxn)?,
             batches: BatchQueue::new(env, wtxn)?,
             max_number_of_tasks: options.max_number_of_tasks,
+            idempotency_key_retention: options.idempotency_key_retention,
         })
     }
 
@@ -249,11 +279,16 @@ impl Queue {
         Ok(self.file_store.compute_total_size()?)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn register(
         &self,
         wtxn: &mut RwTxn,
         kind: &KindWithContent,
         custom_metadata: Option<String>,
+        priority: Priority,
+        run_at: Option<OffsetDateTime>,
+        transaction_uid: Option<TaskId>,
+        idempotency_key: Option<IdempotencyKey>,
         network: Option<DbTaskNetwork>,
     ) -> Result<Task> {
         let next_task_id = self.tasks.next_task_id(wtxn)?;
@@ -272,6 +307,10 @@ impl Queue {
             kind: kind.clone(),
             network,
             custom_metadata,
+            priority,
+            run_at,
+            transaction_uid,
+            idempotency_key: idempotency_key.as_ref().map(|key| key.key.clone()),
         };
         // For deletion and cancelation tasks, we want to make extra sure that they
         // don't attempt to delete/cancel tasks that are newer than themselves.
@@ -281,12 +320,131 @@ impl Queue {
         check_index_swap_validity(&task)?;
 
         self.tasks.register(wtxn, &task)?;
+        let fingerprint = idempotency_key.map(|key| key.fingerprint);
+        self.tasks.insert_idempotency_key(wtxn, &task, fingerprint)?;
 
         Ok(task)
     }
 
+    /// Checks whether a request with an idempotency key was already enqueued.
+    ///
+    /// Returns the task previously enqueued with this key if it was enqueued within the retention
+    /// window of the idempotency keys, otherwise the key to record. Reusing a key for a different
+    /// request is an error.
+    ///
+    /// The fingerprint of the request must be computed beforehand with
+    /// [`Self::idempotency_fingerprint`], as it reads the update files.
+    pub(crate) fn check_idempotency_key(
+        &self,
+        rtxn: &RoTxn,
+        key: IdempotencyKey,
+        kinds: &[KindWithContent],
+        in_transaction: bool,
+    ) -> Result<IdempotencyCheck> {
+        let Some(task_id) = self.tasks.get_task_id_by_idempotency_key(rtxn, &key.key)? else {
+            return Ok(IdempotencyCheck::New(key));
+        };
+        let task = self.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+        if OffsetDateTime::now_utc() - task.enqueued_at > self.idempotency_key_retention {
+            return Ok(IdempotencyCheck::New(key));
+        }
+
+        let entry = self.tasks.get_idempotency_key_entry(rtxn, task_id)?;
+        let same_request = match entry.and_then(|entry| entry.fingerprint) {
+            Some(enqueued) => enqueued == key.fingerprint,
+            // the fingerprints of the tasks imported from a dump are unknown
+            None => {
+                task.transaction_uid.is_some() == in_transaction
+                    && kinds.first().is_some_and(|kind| {
+                        kind.as_kind() == task.kind.as_kind() && kind.indexes() == task.indexes()
+                    })
+            }
+        };
+        if !same_request {
+            return Err(Error::IdempotencyKeyMismatch {
+                idempotency_key: key.key,
+                task_uid: task.uid,
+            });
+        }
+        Ok(IdempotencyCheck::Enqueued(task))
+    }
+
+    /// Computes the fingerprint of a request enqueuing tasks with an idempotency key.
+    ///
+    /// It covers the operation, the indexes and the payload of each task. The documents of a
+    /// document addition are read from its update file. The tasks matched by a cancelation or
+    /// deletion depend on the state of the queue, only their query is part of the request.
+    pub(crate) fn idempotency_fingerprint(
+        &self,
+        kinds: &[KindWithContent],
+        in_transaction: bool,
+    ) -> Result<String> {
+        let mut hasher = Sha256::new();
+        if in_transaction {
+            hasher.update(b"transaction\0");
+        }
+        for kind in kinds {
+            hasher.update(kind.as_kind().to_string());
+            for index in kind.indexes() {
+                hasher.update(b"\0");
+                hasher.update(index);
+            }
+            hasher.update(b"\0");
+
+            let payload = match kind {
+                KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => {
+                    let mut file = self.file_store.get_update(*content_file)?;
+                    std::io::copy(&mut file, &mut hasher)?;
+                    let mut kind = kind.clone();
+                    if let KindWithContent::DocumentAdditionOrUpdate { content_file, .. } =
+                        &mut kind
+                    {
+                        *content_file = Uuid::nil();
+                    }
+                    serde_json::to_vec(&kind)
+                }
+                KindWithContent::TaskCancelation { query, .. }
+                | KindWithContent::TaskDeletion { query, .. } => serde_json::to_vec(query),
+                // the keys and the instance uid of a dump don't come from the request
+                KindWithContent::DumpCreation { .. } => Ok(Vec::new()),
+                kind => serde_json::to_vec(kind),
+            };
+            let payload = payload.map_err(|e| {
+                Error::from_milli(
+                    meilisearch_types::milli::InternalError::SerdeJson(e).into(),
+                    None,
+                )
+            })?;
+            hasher.update(payload);
+            hasher.update(b"\0");
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Forget the idempotency keys enqueued before the retention window of the idempotency keys.
+    pub(crate) fn purge_expired_idempotency_keys(&self, wtxn: &mut RwTxn) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        let mut expired = Vec::new();
+        // The task ids are increasing along with their enqueue date,
+        // we can stop at the first idempotency key that did not expire.
+        for result in self.tasks.idempotency_key_tasks.iter(wtxn)? {
+            let (task_id, _) = result?;
+            let task = self.tasks.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            if now - task.enqueued_at <= self.idempotency_key_retention {
+                break;
+            }
+            expired.push(task_id);
+        }
+        for task_id in expired {
+            self.tasks.delete_idempotency_key(wtxn, task_id)?;
+        }
+        Ok(())
+    }
+
     /// Register a task to cleanup the task queue if needed
     pub fn cleanup_task_queue(&self, wtxn: &mut RwTxn) -> Result<()> {
+        self.purge_expired_idempotency_keys(wtxn)?;
+
         let nb_tasks = self.tasks.all_task_ids(wtxn)?.len();
         // if we have less than 1M tasks everything is fine
         if nb_tasks < self.max_number_of_tasks as u64 {
@@ -331,12 +489,90 @@ impl Queue {
                 tasks: to_delete,
             },
             None,
+            Priority::Normal,
+            None,
+            None,
+            None,
             None,
         )?;
 
         Ok(())
     }
 
+    /// Enqueue the deletion of the finished tasks that outlived the retention policy.
+    ///
+    /// The most recent finished tasks of each index are kept whatever their age, and nothing is
+    /// enqueued while a previous task deletion is waiting to be processed.
+    pub fn apply_task_retention_policy(
+        &self,
+        wtxn: &mut RwTxn,
+        policy: &TaskRetentionPolicy,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        let enqueued = self.tasks.get_status(wtxn, Status::Enqueued)?;
+        if !self.tasks.get_kind(wtxn, Kind::TaskDeletion)?.is_disjoint(&enqueued) {
+            return Ok(());
+        }
+
+        let mut kept = RoaringBitmap::new();
+        if let Some(keep_last) = policy.keep_last_tasks_per_index {
+            let finished = self.tasks.get_status(wtxn, Status::Succeeded)?
+                | self.tasks.get_status(wtxn, Status::Failed)?
+                | self.tasks.get_status(wtxn, Status::Canceled)?;
+            for result in self.tasks.index_tasks.iter(wtxn)? {
+                let (_index, tasks) = result?;
+                kept.extend((tasks & &finished).iter().rev().take(keep_last as usize));
+            }
+        }
+
+        for (status, days) in [
+            (Status::Succeeded, policy.succeeded_tasks_days),
+            (Status::Failed, policy.failed_tasks_days),
+        ] {
+            let Some(days) = days else { continue };
+            // a retention longer than the supported dates can't delete anything
+            let finished_before = now
+                .checked_sub(time::Duration::days(days.into()))
+                .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+            let mut to_delete = self.tasks.get_status(wtxn, status)?;
+            keep_ids_within_datetimes(
+                wtxn,
+                &mut to_delete,
+                self.tasks.finished_at,
+                None,
+                Some(finished_before),
+            )?;
+            to_delete -= &kept;
+            if to_delete.is_empty() {
+                continue;
+            }
+
+            tracing::info!(
+                "Deleting {} {status} tasks following the task retention policy.",
+                to_delete.len()
+            );
+
+            self.register(
+                wtxn,
+                &KindWithContent::TaskDeletion {
+                    query: format!(
+                        "?beforeFinishedAt={}&statuses={status}",
+                        finished_before.format(&Rfc3339).map_err(|_| Error::CorruptedTaskQueue)?,
+                    ),
+                    tasks: to_delete,
+                },
+                None,
+                Priority::Normal,
+                None,
+                None,
+                None,
+                None,
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn get_stats(
         &self,
         rtxn: &RoTxn,
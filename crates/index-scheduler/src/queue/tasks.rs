@@ -4,8 +4,9 @@ use meilisearch_types::heed::types::{DecodeIgnore, SerdeBincode, SerdeJson, Str}
 use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, RoaringBitmapCodec, BEU32};
 use meilisearch_types::tasks::network::DbTaskNetwork;
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Priority, Status, Task};
 use roaring::{MultiOps, RoaringBitmap};
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::{Query, Queue};
@@ -16,18 +17,43 @@ use crate::utils::{
 use crate::{Error, Result, TaskId, BEI128};
 
 /// The number of database used by the task queue
-const NUMBER_OF_DATABASES: u32 = 8;
+const NUMBER_OF_DATABASES: u32 = 14;
 /// Database const names for the `IndexScheduler`.
 mod db_name {
     pub const ALL_TASKS: &str = "all-tasks";
 
     pub const STATUS: &str = "status";
     pub const KIND: &str = "kind";
+    pub const PRIORITY: &str = "priority";
     pub const INDEX_TASKS: &str = "index-tasks";
     pub const CANCELED_BY: &str = "canceled_by";
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
+    pub const RUN_AT: &str = "run-at";
+    pub const TRANSACTIONS: &str = "transactions";
+    pub const COMMITTING_TRANSACTIONS: &str = "committing-transactions";
+    pub const IDEMPOTENCY_KEYS: &str = "idempotency-keys";
+    pub const IDEMPOTENCY_KEY_TASKS: &str = "idempotency-key-tasks";
+}
+
+/// The request a task was enqueued with an idempotency key for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct IdempotencyKeyEntry {
+    /// The idempotency key sent with the request.
+    pub key: String,
+    /// The fingerprint of the operation, indexes and payload of the request, see
+    /// [`Queue::idempotency_fingerprint`]. Unknown for the tasks imported from a dump.
+    pub fingerprint: Option<String>,
+}
+
+/// A transaction whose indexes are being committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CommittingTransaction {
+    /// The indexes of the transaction.
+    pub indexes: Vec<String>,
+    /// The number of times the commit of the indexes failed after some of them were committed.
+    pub failed_commits: u32,
 }
 
 pub struct TaskQueue {
@@ -39,6 +65,8 @@ pub struct TaskQueue {
     pub(crate) status: Database<SerdeBincode<Status>, RoaringBitmapCodec>,
     /// All the tasks ids grouped by their kind.
     pub(crate) kind: Database<SerdeBincode<Kind>, RoaringBitmapCodec>,
+    /// The ids of the tasks with a priority other than normal, grouped by their priority.
+    pub(crate) priority: Database<SerdeBincode<Priority>, RoaringBitmapCodec>,
     /// Store the tasks associated to an index.
     pub(crate) index_tasks: Database<Str, RoaringBitmapCodec>,
     /// Store the tasks that were canceled by a task uid
@@ -49,6 +77,17 @@ pub struct TaskQueue {
     pub(crate) started_at: Database<BEI128, CboRoaringBitmapCodec>,
     /// Store the task ids of tasks which finished at a specific date
     pub(crate) finished_at: Database<BEI128, CboRoaringBitmapCodec>,
+    /// Store the task ids of tasks which must not be processed before a specific date
+    pub(crate) run_at: Database<BEI128, CboRoaringBitmapCodec>,
+    /// Store the task ids of the tasks of a transaction by the uid of the transaction
+    pub(crate) transactions: Database<BEU32, RoaringBitmapCodec>,
+    /// Store the indexes of the transactions whose indexes are being committed, by the uid of the
+    /// transaction, until their tasks are committed
+    pub(crate) committing_transactions: Database<BEU32, SerdeJson<CommittingTransaction>>,
+    /// Store the id of the last task enqueued with an idempotency key
+    pub(crate) idempotency_keys: Database<Str, BEU32>,
+    /// Store the idempotency key and the request fingerprint of the tasks enqueued with one
+    pub(crate) idempotency_key_tasks: Database<BEU32, SerdeJson<IdempotencyKeyEntry>>,
 }
 
 impl TaskQueue {
@@ -57,11 +96,17 @@ impl TaskQueue {
             all_tasks: self.all_tasks,
             status: self.status,
             kind: self.kind,
+            priority: self.priority,
             index_tasks: self.index_tasks,
             canceled_by: self.canceled_by,
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
+            run_at: self.run_at,
+            transactions: self.transactions,
+            committing_transactions: self.committing_transactions,
+            idempotency_keys: self.idempotency_keys,
+            idempotency_key_tasks: self.idempotency_key_tasks,
         }
     }
 
@@ -74,11 +119,19 @@ impl TaskQueue {
             all_tasks: env.create_database(wtxn, Some(db_name::ALL_TASKS))?,
             status: env.create_database(wtxn, Some(db_name::STATUS))?,
             kind: env.create_database(wtxn, Some(db_name::KIND))?,
+            priority: env.create_database(wtxn, Some(db_name::PRIORITY))?,
             index_tasks: env.create_database(wtxn, Some(db_name::INDEX_TASKS))?,
             canceled_by: env.create_database(wtxn, Some(db_name::CANCELED_BY))?,
             enqueued_at: env.create_database(wtxn, Some(db_name::ENQUEUED_AT))?,
             started_at: env.create_database(wtxn, Some(db_name::STARTED_AT))?,
             finished_at: env.create_database(wtxn, Some(db_name::FINISHED_AT))?,
+            run_at: env.create_database(wtxn, Some(db_name::RUN_AT))?,
+            transactions: env.create_database(wtxn, Some(db_name::TRANSACTIONS))?,
+            committing_transactions: env
+                .create_database(wtxn, Some(db_name::COMMITTING_TRANSACTIONS))?,
+            idempotency_keys: env.create_database(wtxn, Some(db_name::IDEMPOTENCY_KEYS))?,
+            idempotency_key_tasks: env
+                .create_database(wtxn, Some(db_name::IDEMPOTENCY_KEY_TASKS))?,
         })
     }
 
@@ -278,6 +331,38 @@ impl TaskQueue {
         Ok(())
     }
 
+    /// Returns the ids of the tasks with this priority, the normal priority is not stored.
+    pub(crate) fn get_priority(&self, rtxn: &RoTxn, priority: Priority) -> Result<RoaringBitmap> {
+        Ok(self.priority.get(rtxn, &priority)?.unwrap_or_default())
+    }
+
+    pub(crate) fn put_priority(
+        &self,
+        wtxn: &mut RwTxn,
+        priority: Priority,
+        bitmap: &RoaringBitmap,
+    ) -> Result<()> {
+        if bitmap.is_empty() {
+            self.priority.delete(wtxn, &priority)?;
+        } else {
+            self.priority.put(wtxn, &priority, bitmap)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn update_priority(
+        &self,
+        wtxn: &mut RwTxn,
+        priority: Priority,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.get_priority(wtxn, priority)?;
+        f(&mut tasks);
+        self.put_priority(wtxn, priority, &tasks)?;
+
+        Ok(())
+    }
+
     /// Convert an iterator to a `Vec` of tasks. The tasks MUST exist or a
     /// `CorruptedTaskQueue` error will be thrown.
     pub(crate) fn get_existing_tasks(
@@ -310,10 +395,152 @@ impl TaskQueue {
             bitmap.insert(task.uid);
         })?;
 
+        if !task.priority.is_normal() {
+            self.update_priority(wtxn, task.priority, |bitmap| {
+                bitmap.insert(task.uid);
+            })?;
+        }
+
         utils::insert_task_datetime(wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
+        if let Some(run_at) = task.run_at {
+            utils::insert_task_datetime(wtxn, self.run_at, run_at, task.uid)?;
+        }
+        if let Some(transaction_uid) = task.transaction_uid {
+            self.update_transaction(wtxn, transaction_uid, |bitmap| {
+                bitmap.insert(task.uid);
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Record the idempotency key of the task along with the fingerprint of its request,
+    /// if it has one.
+    pub(crate) fn insert_idempotency_key(
+        &self,
+        wtxn: &mut RwTxn,
+        task: &Task,
+        fingerprint: Option<String>,
+    ) -> Result<()> {
+        if let Some(key) = &task.idempotency_key {
+            self.idempotency_keys.put(wtxn, key, &task.uid)?;
+            let entry = IdempotencyKeyEntry { key: key.clone(), fingerprint };
+            self.idempotency_key_tasks.put(wtxn, &task.uid, &entry)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the id of the last task enqueued with this idempotency key.
+    pub(crate) fn get_task_id_by_idempotency_key(
+        &self,
+        rtxn: &RoTxn,
+        key: &str,
+    ) -> Result<Option<TaskId>> {
+        Ok(self.idempotency_keys.get(rtxn, key)?)
+    }
+
+    /// Returns the idempotency key and the request fingerprint of a task, if it has one.
+    pub(crate) fn get_idempotency_key_entry(
+        &self,
+        rtxn: &RoTxn,
+        task_id: TaskId,
+    ) -> Result<Option<IdempotencyKeyEntry>> {
+        Ok(self.idempotency_key_tasks.get(rtxn, &task_id)?)
+    }
+
+    /// Forget the idempotency key of a task, unless it was reused by a newer task.
+    pub(crate) fn delete_idempotency_key(&self, wtxn: &mut RwTxn, task_id: TaskId) -> Result<()> {
+        let Some(entry) = self.idempotency_key_tasks.get(wtxn, &task_id)? else {
+            return Ok(());
+        };
+        self.idempotency_key_tasks.delete(wtxn, &task_id)?;
+        if self.get_task_id_by_idempotency_key(wtxn, &entry.key)? == Some(task_id) {
+            self.idempotency_keys.delete(wtxn, &entry.key)?;
+        }
+        Ok(())
+    }
 
+    pub(crate) fn get_transaction(
+        &self,
+        rtxn: &RoTxn,
+        transaction_uid: TaskId,
+    ) -> Result<RoaringBitmap> {
+        Ok(self.transactions.get(rtxn, &transaction_uid)?.unwrap_or_default())
+    }
+
+    pub(crate) fn update_transaction(
+        &self,
+        wtxn: &mut RwTxn,
+        transaction_uid: TaskId,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.get_transaction(wtxn, transaction_uid)?;
+        f(&mut tasks);
+        if tasks.is_empty() {
+            self.transactions.delete(wtxn, &transaction_uid)?;
+        } else {
+            self.transactions.put(wtxn, &transaction_uid, &tasks)?;
+        }
         Ok(())
     }
+
+    /// Returns the tasks of the transactions whose indexes are being committed.
+    pub(crate) fn committing_tasks(&self, rtxn: &RoTxn) -> Result<RoaringBitmap> {
+        let mut tasks = RoaringBitmap::new();
+        for result in self.committing_transactions.remap_data_type::<DecodeIgnore>().iter(rtxn)? {
+            let (transaction_uid, _) = result?;
+            tasks |= self.get_transaction(rtxn, transaction_uid)?;
+        }
+        Ok(tasks)
+    }
+
+    /// Returns the enqueued tasks that must not be processed before a date later than `now`.
+    ///
+    /// A delayed task is ordered by its `run_at` date: it doesn't block the tasks enqueued after
+    /// it on its indexes, which are processed before it. A transaction is delayed as a whole when
+    /// any of its tasks is.
+    pub(crate) fn delayed_tasks(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        now: OffsetDateTime,
+    ) -> Result<RoaringBitmap> {
+        let mut delayed = RoaringBitmap::new();
+        for result in self.run_at.range(rtxn, &(now.unix_timestamp_nanos() + 1..))? {
+            let (_, ids) = result?;
+            delayed |= ids & enqueued;
+        }
+
+        let mut transactions = RoaringBitmap::new();
+        let mut transaction_tasks = RoaringBitmap::new();
+        for task_id in &delayed {
+            let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            if let Some(transaction_uid) = task.transaction_uid {
+                if transactions.insert(transaction_uid) {
+                    transaction_tasks |= self.get_transaction(rtxn, transaction_uid)? & enqueued;
+                }
+            }
+        }
+        Ok(delayed | transaction_tasks)
+    }
+
+    /// Returns the earliest date after `now` at which an enqueued task becomes eligible for batching.
+    pub(crate) fn next_run_at(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<Option<OffsetDateTime>> {
+        let enqueued = self.get_status(rtxn, Status::Enqueued)?;
+        for result in self.run_at.range(rtxn, &(now.unix_timestamp_nanos() + 1..))? {
+            let (timestamp, ids) = result?;
+            if !ids.is_disjoint(&enqueued) {
+                return Ok(Some(
+                    OffsetDateTime::from_unix_timestamp_nanos(timestamp)
+                        .map_err(|_| Error::CorruptedTaskQueue)?,
+                ));
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl Queue {
@@ -324,8 +551,8 @@ impl Queue {
         query: &Query,
         processing_tasks: &ProcessingTasks,
     ) -> Result<RoaringBitmap> {
-        let ProcessingTasks { batch: processing_batch, processing: processing_tasks, progress: _ } =
-            processing_tasks;
+        let processing = processing_tasks;
+        let processing_tasks = &processing.processing;
         let Query {
             limit,
             from,
@@ -342,6 +569,8 @@ impl Queue {
             after_started_at,
             before_finished_at,
             after_finished_at,
+            before_run_at,
+            after_run_at,
         } = query;
 
         let mut tasks = self.tasks.all_task_ids(rtxn)?;
@@ -358,10 +587,9 @@ impl Queue {
         if let Some(batch_uids) = batch_uids {
             let mut batch_tasks = RoaringBitmap::new();
             for batch_uid in batch_uids {
-                if processing_batch.as_ref().is_some_and(|batch| batch.uid == *batch_uid) {
-                    batch_tasks |= &**processing_tasks;
-                } else {
-                    batch_tasks |= self.tasks_in_batch(rtxn, *batch_uid)?;
+                match processing.batch_tasks(*batch_uid) {
+                    Some(tasks) => batch_tasks |= tasks,
+                    None => batch_tasks |= self.tasks_in_batch(rtxn, *batch_uid)?,
                 }
             }
             tasks &= batch_tasks;
@@ -436,21 +664,24 @@ impl Queue {
                 (&tasks - &**processing_tasks, &tasks & &**processing_tasks);
 
             // special case for Processing tasks
-            // A closure that clears the filtered_processing_tasks if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_tasks whose batch started_at date falls outside the given bounds
             let mut clear_filtered_processing_tasks =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &processing_batch
-                            .as_ref()
-                            .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at)
-                            .unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_tasks.clear();
-                    }
+                    let is_within_dates = |started_at: OffsetDateTime| {
+                        RangeBounds::contains(&(start, end), &started_at.unix_timestamp_nanos())
+                    };
+                    filtered_processing_tasks = filtered_processing_tasks
+                        .iter()
+                        .filter(|task| {
+                            is_within_dates(
+                                processing
+                                    .batch_of_task(*task)
+                                    .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at),
+                            )
+                        })
+                        .collect();
                 };
 
             match (after_started_at, before_started_at) {
@@ -494,6 +725,14 @@ impl Queue {
             *before_finished_at,
         )?;
 
+        keep_ids_within_datetimes(
+            rtxn,
+            &mut tasks,
+            self.tasks.run_at,
+            *after_run_at,
+            *before_run_at,
+        )?;
+
         if let Some(limit) = limit {
             tasks = if query.reverse.unwrap_or_default() {
                 tasks.into_iter().take(*limit as usize).collect()
@@ -562,26 +801,19 @@ impl Queue {
             .tasks
             .get_existing_tasks(rtxn, tasks.take(query.limit.unwrap_or(u32::MAX) as usize))?;
 
-        let ProcessingTasks { batch, processing, progress: _ } = processing_tasks;
-
         let ret = tasks.into_iter();
-        if processing.is_empty() || batch.is_none() {
+        if processing_tasks.processing.is_empty() {
             Ok((ret.collect(), total))
         } else {
-            // Safe because we ensured there was a batch in the previous branch
-            let batch = batch.as_ref().unwrap();
             Ok((
-                ret.map(|task| {
-                    if processing.contains(task.uid) {
-                        Task {
-                            status: Status::Processing,
-                            batch_uid: Some(batch.uid),
-                            started_at: Some(batch.started_at),
-                            ..task
-                        }
-                    } else {
-                        task
-                    }
+                ret.map(|task| match processing_tasks.batch_of_task(task.uid) {
+                    Some(batch) => Task {
+                        status: Status::Processing,
+                        batch_uid: Some(batch.uid),
+                        started_at: Some(batch.started_at),
+                        ..task
+                    },
+                    None => task,
                 })
                 .collect(),
                 total,
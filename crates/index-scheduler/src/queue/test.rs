@@ -1,8 +1,10 @@
 use big_s::S;
 use meili_snap::{json_string, snapshot};
 use meilisearch_types::error::ErrorCode;
+use meilisearch_types::task_retention::TaskRetentionPolicy;
 use meilisearch_types::tasks::{KindWithContent, Status};
 use roaring::RoaringBitmap;
+use time::{Duration, OffsetDateTime};
 
 use crate::insta_snapshot::snapshot_index_scheduler;
 use crate::test_utils::Breakpoint::*;
@@ -202,3 +204,69 @@ fn test_task_queue_is_full() {
         .unwrap();
     handle.advance_one_failed_batch();
 }
+
+#[test]
+fn test_task_retention_policy() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler
+        .register(KindWithContent::IndexCreation { index_uid: S("doggo"), primary_key: None })
+        .unwrap();
+    handle.advance_one_successful_batch();
+    index_scheduler
+        .register(KindWithContent::IndexCreation { index_uid: S("doggo"), primary_key: None })
+        .unwrap();
+    handle.advance_one_failed_batch();
+    index_scheduler
+        .register(KindWithContent::IndexCreation { index_uid: S("catto"), primary_key: None })
+        .unwrap();
+    handle.advance_one_successful_batch();
+
+    // the last finished task of each index is kept, and the failed tasks are never deleted
+    let policy = TaskRetentionPolicy {
+        succeeded_tasks_days: Some(1),
+        failed_tasks_days: None,
+        keep_last_tasks_per_index: Some(1),
+    };
+    let in_two_days = OffsetDateTime::now_utc() + Duration::days(2);
+    let mut wtxn = index_scheduler.env.write_txn().unwrap();
+    index_scheduler.queue.apply_task_retention_policy(&mut wtxn, &policy, in_two_days).unwrap();
+    // nothing more is enqueued while the deletion is waiting to be processed
+    index_scheduler.queue.apply_task_retention_policy(&mut wtxn, &policy, in_two_days).unwrap();
+    wtxn.commit().unwrap();
+
+    let rtxn = index_scheduler.env.read_txn().unwrap();
+    snapshot!(index_scheduler.queue.tasks.next_task_id(&rtxn).unwrap(), @"4");
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 3).unwrap().unwrap();
+    let KindWithContent::TaskDeletion { tasks, .. } = task.kind else {
+        panic!("The retention policy must enqueue a task deletion, got {:?}", task.kind)
+    };
+    snapshot!(format!("{tasks:?}"), @"RoaringBitmap<[0]>");
+    drop(rtxn);
+
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    // the tasks that finished recently are kept
+    let mut wtxn = index_scheduler.env.write_txn().unwrap();
+    index_scheduler
+        .queue
+        .apply_task_retention_policy(&mut wtxn, &policy, OffsetDateTime::now_utc())
+        .unwrap();
+    let tasks = index_scheduler.queue.tasks.all_task_ids(&wtxn).unwrap();
+    snapshot!(format!("{tasks:?}"), @"RoaringBitmap<[1, 2, 3]>");
+
+    // a retention longer than the supported dates doesn't delete anything
+    let policy = TaskRetentionPolicy {
+        succeeded_tasks_days: Some(u32::MAX),
+        failed_tasks_days: Some(u32::MAX),
+        keep_last_tasks_per_index: None,
+    };
+    index_scheduler
+        .queue
+        .apply_task_retention_policy(&mut wtxn, &policy, OffsetDateTime::now_utc())
+        .unwrap();
+    let tasks = index_scheduler.queue.tasks.all_task_ids(&wtxn).unwrap();
+    snapshot!(format!("{tasks:?}"), @"RoaringBitmap<[1, 2, 3]>");
+    wtxn.commit().unwrap();
+}
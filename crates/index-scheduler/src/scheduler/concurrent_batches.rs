@@ -0,0 +1,110 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use meilisearch_types::milli::MustStopProcessing;
+use roaring::RoaringBitmap;
+
+use crate::{Error, IndexScheduler, Result, TickOutcome};
+
+impl IndexScheduler {
+    /// Perform one iteration of the run loop when batches of different indexes can be processed at the same time.
+    ///
+    /// 1. See if a concurrent batch can be started at all.
+    /// 2. Find the next batch of tasks that doesn't touch the indexes of the running batches.
+    /// 3. If it only works on its own indexes, process it in its own thread.
+    ///    Otherwise, wait for all the running batches to finish and process it alone with [`Self::tick`].
+    ///
+    /// A batch processed alone uses all the indexing memory. A concurrent batch is allocated the
+    /// memory left by the running batches, shared with the batches that can still start alongside it.
+    ///
+    /// Every batch processed in its own thread wakes the run loop up once it is finished.
+    pub(crate) fn tick_concurrently(&self) -> Result<TickOutcome> {
+        let (running, main_batch_pending) = {
+            let processing = self.processing_tasks.read().unwrap();
+            (processing.concurrent.clone(), processing.batch.is_some())
+        };
+
+        // A batch that was aborted or didn't make progress must be resumed by the regular tick.
+        if running.is_empty() && main_batch_pending {
+            return self.tick();
+        }
+        if self.scheduler.is_paused() {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+        if running.len() >= self.scheduler.max_concurrent_batches {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
+        let mut wtxn = self.env.write_txn()?;
+        self.queue.cleanup_task_queue(&mut wtxn)?;
+        self.apply_task_retention_policy(&mut wtxn)?;
+        wtxn.commit()?;
+
+        let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+        let Some((batch, processing_batch)) = self
+            .create_next_batch(&rtxn, &RoaringBitmap::new(), &running)
+            .map_err(|e| Error::CreateBatch(Box::new(e)))?
+        else {
+            return Ok(TickOutcome::WaitForSignal);
+        };
+        drop(rtxn);
+
+        let mut indexes: Vec<String> = processing_batch.indexes.iter().cloned().collect();
+        if let Some(index_uid) = batch.index_uid() {
+            if !indexes.iter().any(|index| index == index_uid) {
+                indexes.push(index_uid.to_string());
+            }
+        }
+        let is_busy = running.iter().flat_map(|c| &c.indexes).any(|index| indexes.contains(index));
+
+        if !batch.can_be_processed_concurrently() || is_busy {
+            // Global tasks are processed alone, once all the running batches are finished.
+            return if running.is_empty() { self.tick() } else { Ok(TickOutcome::WaitForSignal) };
+        }
+
+        let max_memory = self.index_mapper.indexer_config().max_memory.map(|max_memory| {
+            let allocated: usize = running.iter().filter_map(|c| c.max_memory).sum();
+            let free_slots = self.scheduler.max_concurrent_batches - running.len();
+            max_memory.saturating_sub(allocated) / free_slots
+        });
+
+        let batch_uid = processing_batch.uid;
+        let must_stop_processing = MustStopProcessing::default();
+        let progress = self.processing_tasks.write().unwrap().start_concurrent_processing(
+            processing_batch.clone(),
+            batch.ids(),
+            indexes,
+            must_stop_processing.clone(),
+            max_memory,
+        );
+        self.scheduler.record_scheduled_index(&batch);
+        self.notify_task_updates(batch.ids());
+
+        let mut lane = self.private_clone();
+        lane.scheduler.must_stop_processing = must_stop_processing;
+        lane.index_mapper = lane.index_mapper.with_max_memory(max_memory);
+        let spawned = std::thread::Builder::new()
+            .name(format!("concurrent-batch-{batch_uid}"))
+            .spawn(move || {
+                let ret = catch_unwind(AssertUnwindSafe(|| {
+                    lane.process_and_persist_batch(batch, processing_batch, progress, true)
+                }));
+                match ret {
+                    Ok(Ok(_)) => (),
+                    Ok(Err(e)) => tracing::error!("{e}"),
+                    Err(_panic) => tracing::error!(
+                        "Internal error: Unexpected panic while processing the concurrent batch {batch_uid}."
+                    ),
+                }
+                // Whatever happened, the batch doesn't hold its indexes anymore.
+                lane.processing_tasks.write().unwrap().stop_concurrent_processing(batch_uid);
+                lane.scheduler.wake_up.signal();
+            });
+
+        if let Err(error) = spawned {
+            self.processing_tasks.write().unwrap().stop_concurrent_processing(batch_uid);
+            return Err(error.into());
+        }
+
+        Ok(TickOutcome::TickAgain(0))
+    }
+}
@@ -1,3 +1,5 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::io::ErrorKind;
 
@@ -6,12 +8,16 @@ use meilisearch_types::index_uid::{AnyIndex, DsrIndex};
 use meilisearch_types::milli::update::{IndexDocumentsMethod, MissingDocumentPolicy};
 use meilisearch_types::settings::{Settings, Unchecked};
 use meilisearch_types::tasks::network::{DbTaskNetwork, NetworkTopologyState, Origin};
-use meilisearch_types::tasks::{BatchStopReason, Kind, KindWithContent, Status, Task};
-use roaring::RoaringBitmap;
+use meilisearch_types::tasks::{
+    BatchStopReason, Kind, KindWithContent, Priority, Status, Task, TaskId,
+};
+use roaring::{MultiOps, RoaringBitmap};
 use serde_json::Value;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use super::autobatcher::{self, BatchKind};
+use crate::processing::ConcurrentBatch;
 use crate::utils::ProcessingBatch;
 use crate::{Error, IndexScheduler, Result};
 
@@ -78,6 +84,14 @@ pub(crate) enum Batch {
     DsrClear {
         tasks: Vec<Task>,
     },
+    Transaction {
+        transaction_uid: TaskId,
+        /// The operations of the transaction, one per index unless the tasks of an index can't be
+        /// batched together, and whether they must create their index.
+        operations: Vec<(IndexOperation, bool)>,
+        /// Whether all the tasks of the transaction are still enqueued. An incomplete transaction must be rolled back.
+        complete: bool,
+    },
 }
 
 #[derive(Debug)]
@@ -146,22 +160,7 @@ impl Batch {
             | Batch::DsrClear { tasks } => {
                 RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
             }
-            Batch::IndexOperation { op, .. } => match op {
-                IndexOperation::DocumentOperation { tasks, .. }
-                | IndexOperation::Settings { tasks, .. }
-                | IndexOperation::DocumentDeletion { tasks, .. }
-                | IndexOperation::DocumentClear { tasks, .. } => {
-                    RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
-                }
-                IndexOperation::DocumentEdition { task, .. } => {
-                    RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
-                }
-                IndexOperation::DocumentClearAndSetting {
-                    cleared_tasks: tasks,
-                    settings_tasks: other,
-                    ..
-                } => RoaringBitmap::from_iter(tasks.iter().chain(other).map(|task| task.uid)),
-            },
+            Batch::IndexOperation { op, .. } => op.ids(),
             Batch::IndexSwap { task } | Batch::NetworkReady { task } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
@@ -170,6 +169,9 @@ impl Batch {
                 tasks.insert(network_task.uid);
                 tasks
             }
+            Batch::Transaction { operations, .. } => {
+                operations.iter().map(|(op, _)| op.ids()).union()
+            }
         }
     }
 
@@ -184,7 +186,8 @@ impl Batch {
             | Export { .. }
             | UpgradeDatabase { .. }
             | NetworkReady { .. }
-            | IndexSwap { .. } => None,
+            | IndexSwap { .. }
+            | Transaction { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
@@ -194,6 +197,33 @@ impl Batch {
             DsrUpdate { .. } | DsrClear { .. } => Some(DsrIndex::dsr_uid()),
         }
     }
+
+    /// Whether this batch only works on its own index and can be processed at the same time
+    /// as the batches of other indexes.
+    ///
+    /// Global tasks, like dumps, snapshots or swaps, must always be processed alone.
+    pub fn can_be_processed_concurrently(&self) -> bool {
+        use Batch::*;
+        match self {
+            IndexOperation { .. }
+            | IndexCreation { .. }
+            | IndexDeletion { .. }
+            | IndexCompaction { .. }
+            | DsrUpdate { .. }
+            | DsrClear { .. } => true,
+            IndexUpdate { new_index_uid, .. } => new_index_uid.is_none(),
+            TaskCancelation { .. }
+            | TaskDeletions(_)
+            | SnapshotCreation(_)
+            | Dump(_)
+            | IndexSwap { .. }
+            | Export { .. }
+            | UpgradeDatabase { .. }
+            | NetworkIndexBatch { .. }
+            | NetworkReady { .. }
+            | Transaction { .. } => false,
+        }
+    }
 }
 
 impl fmt::Display for Batch {
@@ -218,6 +248,7 @@ impl fmt::Display for Batch {
             Batch::NetworkReady { .. } => f.write_str("NetworkTopologyChange")?,
             Batch::DsrUpdate { .. } => f.write_str("DsrUpdate")?,
             Batch::DsrClear { .. } => f.write_str("DsrClear")?,
+            Batch::Transaction { .. } => f.write_str("Transaction")?,
         };
         match index_uid {
             Some(name) => f.write_fmt(format_args!(" on {name:?} from tasks: {tasks:?}")),
@@ -227,6 +258,45 @@ impl fmt::Display for Batch {
 }
 
 impl IndexOperation {
+    /// Return the task ids associated with this operation.
+    pub fn ids(&self) -> RoaringBitmap {
+        match self {
+            IndexOperation::DocumentOperation { tasks, .. }
+            | IndexOperation::Settings { tasks, .. }
+            | IndexOperation::DocumentDeletion { tasks, .. }
+            | IndexOperation::DocumentClear { tasks, .. } => {
+                RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
+            }
+            IndexOperation::DocumentEdition { task, .. } => {
+                RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
+            }
+            IndexOperation::DocumentClearAndSetting {
+                cleared_tasks: tasks,
+                settings_tasks: other,
+                ..
+            } => RoaringBitmap::from_iter(tasks.iter().chain(other).map(|task| task.uid)),
+        }
+    }
+
+    /// Return the tasks of this operation.
+    pub fn into_tasks(self) -> Vec<Task> {
+        match self {
+            IndexOperation::DocumentOperation { tasks, .. }
+            | IndexOperation::Settings { tasks, .. }
+            | IndexOperation::DocumentDeletion { tasks, .. }
+            | IndexOperation::DocumentClear { tasks, .. } => tasks,
+            IndexOperation::DocumentEdition { task, .. } => vec![task],
+            IndexOperation::DocumentClearAndSetting {
+                cleared_tasks: mut tasks,
+                settings_tasks: other,
+                ..
+            } => {
+                tasks.extend(other);
+                tasks
+            }
+        }
+    }
+
     pub fn index_uid(&self) -> &str {
         match self {
             IndexOperation::DocumentOperation { index_uid, .. }
@@ -520,19 +590,32 @@ impl IndexScheduler {
     /// 4. We get the *next* snapshot to process.
     /// 5. We get the *next* dump to process.
     /// 6. We get the *next* tasks to process for a specific index.
+    ///
+    /// The `concurrent` batches are still being processed: their tasks and batch ids are never
+    /// reused and the tasks of their indexes are kept for later, to preserve the order of the queue.
     #[tracing::instrument(level = "trace", skip(self, rtxn), target = "indexing::scheduler")]
     pub(crate) fn create_next_batch(
         &self,
         rtxn: &RoTxn,
         processing_network_tasks: &RoaringBitmap,
+        concurrent: &[ConcurrentBatch],
     ) -> Result<Option<(Batch, ProcessingBatch)>> {
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::InsideCreateBatch)?;
 
-        let batch_id = self.queue.batches.next_batch_id(rtxn)?;
+        let batch_id = concurrent
+            .iter()
+            .map(|c| c.batch.uid + 1)
+            .fold(self.queue.batches.next_batch_id(rtxn)?, u32::max);
         let mut current_batch = ProcessingBatch::new(batch_id);
 
-        let enqueued = &self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
+        let mut enqueued = self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
+        for c in concurrent {
+            enqueued -= &c.processing;
+        }
+        // The tasks with a `run_at` date in the future are not eligible yet, nor their transactions.
+        enqueued -= self.queue.tasks.delayed_tasks(rtxn, &enqueued, OffsetDateTime::now_utc())?;
+        let enqueued = &enqueued;
         let failed = &self.queue.tasks.get_status(rtxn, Status::Failed)?;
 
         // 0. we get the last task to cancel.
@@ -599,8 +682,11 @@ impl IndexScheduler {
             return Ok(Some((Batch::TaskDeletions(tasks), current_batch)));
         }
 
-        // 4. we get the next task to compact
-        let to_compact = self.queue.tasks.get_kind(rtxn, Kind::IndexCompaction)? & enqueued;
+        // 4. we get the next task to compact, on an index no concurrent batch is working on
+        let mut to_compact = self.queue.tasks.get_kind(rtxn, Kind::IndexCompaction)? & enqueued;
+        for index in concurrent.iter().flat_map(|c| &c.indexes) {
+            to_compact -= self.queue.tasks.index_tasks(rtxn, index)?;
+        }
         if let Some(task_id) = to_compact.min() {
             let mut task =
                 self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -650,9 +736,34 @@ impl IndexScheduler {
 
         let network = self.network();
 
-        // 8. We make a batch from the unprioritised tasks.
+        // The indexes that must not be touched, either because a concurrent batch is working on them
+        // or because one of their tasks was skipped and the order of the queue must be preserved.
+        let mut blocked_indexes: BTreeSet<String> =
+            concurrent.iter().flat_map(|c| c.indexes.iter().cloned()).collect();
+
+        // 8. We batch the oldest transaction that can be processed, all its tasks are committed together.
+        if let Some(batch) = self.create_next_transaction_batch(
+            rtxn,
+            enqueued,
+            &blocked_indexes,
+            &mut current_batch,
+        )? {
+            return Ok(Some((batch, current_batch)));
+        }
+
+        // 9. We make a batch from the unprioritised tasks.
         let (batch, current_batch) =
             self.create_next_batch_unprioritized(rtxn, enqueued, current_batch, |task| {
+                let indexes = task.indexes();
+                // The tasks of a transaction are only processed together, they also block the
+                // tasks enqueued after them on their indexes.
+                if task.transaction_uid.is_some()
+                    || indexes.iter().any(|index| blocked_indexes.contains(*index))
+                {
+                    blocked_indexes.extend(indexes.into_iter().map(String::from));
+                    return true;
+                }
+
                 // We want to execute all tasks, except those that have a version strictly higher than the network version
 
                 let Some(task_version) =
@@ -668,6 +779,158 @@ impl IndexScheduler {
         Ok(batch.map(|batch| (batch, current_batch)))
     }
 
+    /// Create a batch from the oldest transaction whose indexes are free, if any.
+    ///
+    /// A transaction can only be processed once all the older tasks of its indexes are processed,
+    /// and none of its indexes is blocked. The indexer only sees the committed state of an index,
+    /// so the tasks of each index are autobatched into a single operation. When they can't be,
+    /// the remaining tasks make more operations on the same index and the transaction fails.
+    fn create_next_transaction_batch(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        blocked_indexes: &BTreeSet<String>,
+        current_batch: &mut ProcessingBatch,
+    ) -> Result<Option<Batch>> {
+        let mut blocked_indexes = blocked_indexes.clone();
+        for result in self.queue.tasks.transactions.iter(rtxn)? {
+            let (transaction_uid, transaction_tasks) = result?;
+            let to_process = &transaction_tasks & enqueued;
+            let Some(first_task) = to_process.min() else { continue };
+
+            let tasks = self.queue.tasks.get_existing_tasks(rtxn, &to_process)?;
+            let indexes: BTreeSet<&str> = tasks.iter().flat_map(|task| task.indexes()).collect();
+            let mut is_blocked = false;
+            for index in &indexes {
+                let older_tasks = self.queue.tasks.index_tasks(rtxn, index)? & enqueued;
+                if blocked_indexes.contains(*index)
+                    || older_tasks.min().is_some_and(|uid| uid < first_task)
+                {
+                    is_blocked = true;
+                }
+            }
+            if is_blocked {
+                blocked_indexes.extend(indexes.into_iter().map(String::from));
+                continue;
+            }
+
+            let mut index_tasks: BTreeMap<String, Vec<(TaskId, KindWithContent)>> = BTreeMap::new();
+            for task in tasks {
+                let index_name = task.index_uid().ok_or(Error::CorruptedTaskQueue)?.to_owned();
+                index_tasks.entry(index_name).or_default().push((task.uid, task.kind));
+            }
+
+            let mut operations = Vec::with_capacity(index_tasks.len());
+            for (index_name, mut tasks) in index_tasks {
+                let index_uid = AnyIndex::new(&index_name);
+                let (mut index_exists, primary_key) =
+                    if self.index_mapper.exists(rtxn, index_uid)? {
+                        let index = self.index_mapper.index(rtxn, index_uid)?;
+                        let index_rtxn = index.read_txn()?;
+                        (true, index.primary_key(&index_rtxn)?.map(|pk| pk.to_string()))
+                    } else {
+                        (false, None)
+                    };
+
+                while !tasks.is_empty() {
+                    let Some((batch_kind, must_create_index, _)) =
+                        autobatcher::autobatch(tasks.clone(), index_exists, primary_key.as_deref())
+                    else {
+                        return Err(Error::CorruptedTaskQueue);
+                    };
+                    index_exists |= must_create_index;
+
+                    // a transaction only contains document and settings operations
+                    let Some(Batch::IndexOperation { op, must_create_index }) = self
+                        .create_next_batch_index(
+                            rtxn,
+                            index_name.clone(),
+                            batch_kind,
+                            current_batch,
+                            must_create_index,
+                        )?
+                    else {
+                        return Err(Error::CorruptedTaskQueue);
+                    };
+                    let batched = op.ids();
+                    if batched.is_empty() {
+                        return Err(Error::CorruptedTaskQueue);
+                    }
+                    tasks.retain(|(uid, _)| !batched.contains(*uid));
+                    operations.push((op, must_create_index));
+                }
+            }
+
+            current_batch.reason(BatchStopReason::Transaction { id: transaction_uid });
+            return Ok(Some(Batch::Transaction {
+                transaction_uid,
+                operations,
+                complete: to_process == transaction_tasks,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    /// Pick the index of the next batch among the indexes with enqueued tasks.
+    ///
+    /// The indexes are sorted by the priority of their next task. Then, with fair scheduling, the
+    /// indexes coming after the last scheduled one take their turn first. Otherwise, the index
+    /// with the oldest next task wins.
+    ///
+    /// The indexes are sorted from the priority bitmaps, only the next task of the picked index is
+    /// read. An index whose next task must be skipped is left out.
+    fn pick_next_index<F>(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        oldest: &Task,
+        skip_if: &mut F,
+    ) -> Result<String>
+    where
+        F: FnMut(&Task) -> bool,
+    {
+        let fair = self.scheduler.fair_index_scheduling;
+        let last_scheduled =
+            if fair { self.scheduler.last_scheduled_index.read().unwrap().clone() } else { None };
+
+        let high = self.queue.tasks.get_priority(rtxn, Priority::High)?;
+        let low = self.queue.tasks.get_priority(rtxn, Priority::Low)?;
+
+        let mut candidates = Vec::new();
+        for result in self.queue.tasks.index_tasks.iter(rtxn)? {
+            let (index, index_tasks) = result?;
+            let Some(next_task) = (index_tasks & enqueued).min() else { continue };
+            let priority = if high.contains(next_task) {
+                Priority::High
+            } else if low.contains(next_task) {
+                Priority::Low
+            } else {
+                Priority::Normal
+            };
+
+            let already_had_its_turn = last_scheduled.as_deref().is_some_and(|last| index <= last);
+            // with fair scheduling, the indexes are sorted by name to break the ties when taking turns.
+            let key = (Reverse(priority), already_had_its_turn, if fair { 0 } else { next_task });
+            candidates.push((key, index.to_owned(), next_task));
+        }
+        candidates.sort_unstable();
+
+        for (_, index, next_task) in candidates {
+            let next_task =
+                self.queue.tasks.get_task(rtxn, next_task)?.ok_or(Error::CorruptedTaskQueue)?;
+            if skip_if(&next_task) {
+                continue;
+            }
+            if !next_task.kind.as_kind().related_to_one_index() && next_task.uid != oldest.uid {
+                continue;
+            }
+            return Ok(index);
+        }
+
+        Ok(oldest.indexes()[0].to_owned())
+    }
+
     fn create_next_batch_unprioritized<F>(
         &self,
         rtxn: &RoTxn,
@@ -710,6 +973,16 @@ impl IndexScheduler {
             };
         };
 
+        // The oldest task gives way to the indexes with a task of higher priority or, with fair
+        // scheduling, to the index whose turn it is. A task working on several indexes is never
+        // overtaken, to keep the order of the tasks of all its indexes.
+        let index_name = if task.kind.as_kind().related_to_one_index() {
+            self.pick_next_index(rtxn, enqueued, &task, &mut skip_if)?
+        } else {
+            index_name.to_owned()
+        };
+        let index_name = index_name.as_str();
+
         let index_uid = AnyIndex::new(index_name);
 
         let index_already_exists = self.index_mapper.exists(rtxn, index_uid)?;
@@ -3,6 +3,7 @@ mod autobatcher;
 mod autobatcher_test;
 #[cfg(not(feature = "enterprise"))]
 pub mod community_edition;
+mod concurrent_batches;
 mod create_batch;
 #[cfg(feature = "enterprise")]
 pub mod enterprise_edition;
@@ -12,6 +13,7 @@ mod process_dump_creation;
 mod process_export;
 mod process_index_operation;
 mod process_snapshot_creation;
+mod process_transaction;
 mod process_upgrade;
 #[cfg(test)]
 mod test;
@@ -24,23 +26,32 @@ mod test_failure;
 
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 use meilisearch_types::error::ResponseError;
-use meilisearch_types::heed::{Env, WithoutTls};
+use meilisearch_types::heed::{Env, RwTxn, WithoutTls};
 use meilisearch_types::index_uid::AnyIndex;
+use meilisearch_types::milli::progress::Progress;
 use meilisearch_types::milli::update::S3SnapshotOptions;
 use meilisearch_types::milli::{self, MustStopProcessing};
+use meilisearch_types::task_retention::TaskRetentionPolicy;
 use meilisearch_types::tasks::Status;
 use process_batch::ProcessBatchInfo;
 use rayon::current_num_threads;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
+use time::OffsetDateTime;
 
+use self::create_batch::Batch;
 use crate::processing::{AtomicTaskStep, BatchProgress};
+use crate::utils::ProcessingBatch;
 use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, TickOutcome};
 
+/// The minimal duration between two applications of the task retention policy.
+pub(crate) const TASK_RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 pub struct Scheduler {
     /// A boolean that can be set to true to stop the currently processing tasks.
     pub must_stop_processing: MustStopProcessing,
@@ -54,6 +65,24 @@ pub struct Scheduler {
     /// The maximum number of tasks that will be batched together.
     pub(crate) max_number_of_batched_tasks: usize,
 
+    /// The maximum number of batches of different indexes processed at the same time.
+    pub(crate) max_concurrent_batches: usize,
+
+    /// Whether the indexes take turns when picking the next batch.
+    pub(crate) fair_index_scheduling: bool,
+
+    /// The index of the last batch that started processing, used to find whose turn it is.
+    pub(crate) last_scheduled_index: Arc<RwLock<Option<String>>>,
+
+    /// When the processing of the tasks was paused, `None` while the scheduler is running.
+    pub(crate) paused_at: Arc<RwLock<Option<OffsetDateTime>>>,
+
+    /// The finished tasks that are periodically deleted.
+    pub(crate) task_retention_policy: Arc<RwLock<TaskRetentionPolicy>>,
+
+    /// When the task retention policy was last applied.
+    pub(crate) task_retention_applied_at: Arc<RwLock<Option<Instant>>>,
+
     /// The maximum size, in bytes, of tasks in a batch.
     pub(crate) batched_tasks_size_limit: u64,
 
@@ -88,6 +117,12 @@ impl Scheduler {
             wake_up: self.wake_up.clone(),
             autobatching_enabled: self.autobatching_enabled,
             max_number_of_batched_tasks: self.max_number_of_batched_tasks,
+            max_concurrent_batches: self.max_concurrent_batches,
+            fair_index_scheduling: self.fair_index_scheduling,
+            last_scheduled_index: self.last_scheduled_index.clone(),
+            paused_at: self.paused_at.clone(),
+            task_retention_policy: self.task_retention_policy.clone(),
+            task_retention_applied_at: self.task_retention_applied_at.clone(),
             batched_tasks_size_limit: self.batched_tasks_size_limit,
             dumps_path: self.dumps_path.clone(),
             snapshots_path: self.snapshots_path.clone(),
@@ -119,12 +154,16 @@ impl Scheduler {
             autobatching_enabled,
             max_number_of_tasks: _,
             max_number_of_batched_tasks,
+            max_concurrent_batches,
+            fair_index_scheduling,
+            idempotency_key_retention: _,
             batched_tasks_size_limit,
             export_default_payload_size_bytes: _,
             instance_features: _,
             embedding_cache_cap,
             ip_policy,
             dsr_fuel: _,
+            search_cursor_secret: _,
         } = options;
 
         Scheduler {
@@ -133,6 +172,12 @@ impl Scheduler {
             wake_up: Arc::new(SignalEvent::auto(true)),
             autobatching_enabled: *autobatching_enabled,
             max_number_of_batched_tasks: *max_number_of_batched_tasks,
+            max_concurrent_batches: *max_concurrent_batches,
+            fair_index_scheduling: *fair_index_scheduling,
+            last_scheduled_index: Default::default(),
+            paused_at: Default::default(),
+            task_retention_policy: Default::default(),
+            task_retention_applied_at: Default::default(),
             batched_tasks_size_limit: *batched_tasks_size_limit,
             dumps_path: dumps_path.clone(),
             snapshots_path: snapshots_path.clone(),
@@ -143,6 +188,18 @@ impl Scheduler {
             s3_snapshot_options: indexer_config.s3_snapshot_options.clone(),
         }
     }
+
+    /// Whether the processing of the tasks is paused.
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused_at.read().unwrap().is_some()
+    }
+
+    /// Remember the index of the batch that starts processing, so the next one goes to another index.
+    pub(crate) fn record_scheduled_index(&self, batch: &Batch) {
+        if let Some(index_uid) = batch.index_uid() {
+            *self.last_scheduled_index.write().unwrap() = Some(index_uid.to_string());
+        }
+    }
 }
 
 impl IndexScheduler {
@@ -166,29 +223,34 @@ impl IndexScheduler {
             self.breakpoint(crate::test_utils::Breakpoint::Start);
         }
 
+        if self.scheduler.is_paused() {
+            // An aborted batch is dropped here, its tasks are batched again once the processing resumes.
+            self.processing_tasks.write().unwrap().stop_processing();
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
         let previous_processing_batch = self.processing_tasks.write().unwrap().stop_processing();
 
         let mut wtxn = self.env.write_txn()?;
         self.queue.cleanup_task_queue(&mut wtxn)?;
+        self.apply_task_retention_policy(&mut wtxn)?;
         wtxn.commit()?;
 
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
-        let (batch, mut processing_batch) = match self
-            .create_next_batch(&rtxn, &previous_processing_batch.processing)
+        let (batch, processing_batch) = match self
+            .create_next_batch(&rtxn, &previous_processing_batch.processing, &[])
             .map_err(|e| Error::CreateBatch(Box::new(e)))?
         {
             Some(batch) => batch,
             None => {
-                *self.processing_tasks.write().unwrap() = previous_processing_batch;
+                self.processing_tasks.write().unwrap().resume_processing(previous_processing_batch);
                 return Ok(TickOutcome::WaitForSignal);
             }
         };
-        let index_uid = batch.index_uid().map(ToOwned::to_owned);
         drop(rtxn);
 
         // 1. store the starting date with the bitmap of processing tasks.
-        let mut ids = batch.ids();
-        let processed_tasks = ids.len();
+        let ids = batch.ids();
 
         // We reset the must_stop flag to be sure that we don't stop processing tasks
         self.scheduler.must_stop_processing.reset();
@@ -197,11 +259,52 @@ impl IndexScheduler {
             .write()
             .unwrap()
             // We can clone the processing batch here because we don't want its modification to affect the view of the processing batches
-            .start_processing(processing_batch.clone(), ids.clone());
+            .start_processing(processing_batch.clone(), ids);
+        self.scheduler.record_scheduled_index(&batch);
+        self.notify_task_updates(batch.ids());
 
         #[cfg(test)]
         self.breakpoint(crate::test_utils::Breakpoint::BatchCreated);
 
+        self.process_and_persist_batch(batch, processing_batch, progress, false)
+    }
+
+    /// Enqueue the deletion of the tasks that outlived the task retention policy,
+    /// at most once every [`TASK_RETENTION_INTERVAL`].
+    pub(crate) fn apply_task_retention_policy(&self, wtxn: &mut RwTxn) -> Result<()> {
+        let policy = *self.scheduler.task_retention_policy.read().unwrap();
+        if policy.is_disabled() {
+            return Ok(());
+        }
+        let applied_at = *self.scheduler.task_retention_applied_at.read().unwrap();
+        if applied_at.is_some_and(|applied_at| applied_at.elapsed() < TASK_RETENTION_INTERVAL) {
+            return Ok(());
+        }
+        // The attempt is recorded before applying the policy so a failure isn't retried on every tick.
+        *self.scheduler.task_retention_applied_at.write().unwrap() = Some(Instant::now());
+        self.queue.apply_task_retention_policy(wtxn, &policy, OffsetDateTime::now_utc())
+    }
+
+    /// Process a batch and write the outcome of its tasks to disk.
+    ///
+    /// When `concurrent` is `true`, the batch is one of the batches processed alongside the others,
+    /// and only releases the resources it owns once done.
+    pub(crate) fn process_and_persist_batch(
+        &self,
+        batch: Batch,
+        mut processing_batch: ProcessingBatch,
+        progress: Progress,
+        concurrent: bool,
+    ) -> Result<TickOutcome> {
+        let index_uid = batch.index_uid().map(ToOwned::to_owned);
+        let transaction_uid = match &batch {
+            Batch::Transaction { transaction_uid, .. } => Some(*transaction_uid),
+            _ => None,
+        };
+        let batch_uid = processing_batch.uid;
+        let mut ids = batch.ids();
+        let processed_tasks = ids.len();
+
         // 2. Process the tasks
         let res = {
             let cloned_index_scheduler = self.private_clone();
@@ -251,8 +354,12 @@ impl IndexScheduler {
             })
         };
 
-        // Reset the currently updating index to relinquish the index handle
-        self.index_mapper.set_currently_updating_index(None);
+        // Reset the currently updating index to relinquish the index handle, the handles of the
+        // indexes updated by the other batches must be kept, even when this index gets resized.
+        match &index_uid {
+            Some(index_uid) => self.index_mapper.unset_currently_updating_index(index_uid),
+            None => self.index_mapper.set_currently_updating_index(None),
+        }
 
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::AcquiringWtxn)?;
@@ -316,6 +423,10 @@ impl IndexScheduler {
                 if let Some(canceled_by) = canceled_by {
                     self.queue.tasks.canceled_by.put(&mut wtxn, &canceled_by, &canceled)?;
                 }
+                // the tasks of the transaction are committed, it doesn't need to be rolled forward
+                if let Some(transaction_uid) = transaction_uid {
+                    self.queue.tasks.committing_transactions.delete(&mut wtxn, &transaction_uid)?;
+                }
                 tracing::info!("A batch of tasks was successfully completed with {success} successful tasks and {failure} failed tasks.");
             }
             // If we have an abortion error we must stop the tick here and re-schedule tasks.
@@ -334,8 +445,21 @@ impl IndexScheduler {
                 // the `started_at` date times and `processings` of the current processing tasks.
                 // This date time is used by the task cancelation to store the right `started_at`
                 // date in the task on disk.
+                // A concurrent batch has no next tick and releases its tasks right away.
+                if concurrent {
+                    self.processing_tasks.write().unwrap().stop_concurrent_processing(batch_uid);
+                }
                 return Ok(TickOutcome::TickAgain(0));
             }
+            // If a transaction was partially committed, its tasks stay enqueued and it is rolled
+            // forward on a later tick.
+            Err(err @ Error::TransactionPartiallyCommitted { .. }) => {
+                wtxn.abort();
+                if concurrent {
+                    self.processing_tasks.write().unwrap().stop_concurrent_processing(batch_uid);
+                }
+                return Err(err);
+            }
             // If an index said it was full, we need to:
             // 1. identify which index is full
             // 2. close the associated environment
@@ -360,6 +484,9 @@ impl IndexScheduler {
             Err(err) => {
                 // always persist failed batches
                 batch_made_progress = true;
+                if let Some(transaction_uid) = transaction_uid {
+                    self.queue.tasks.committing_transactions.delete(&mut wtxn, &transaction_uid)?;
+                }
 
                 #[cfg(test)]
                 self.breakpoint(crate::test_utils::Breakpoint::ProcessBatchFailed);
@@ -440,7 +567,11 @@ impl IndexScheduler {
         if batch_made_progress {
             // We should stop processing AFTER everything is processed and written to disk otherwise, a batch (which only lives in RAM) may appear in the processing task
             // and then become « not found » for some time until the commit everything is written and the final commit is made.
-            self.processing_tasks.write().unwrap().stop_processing();
+            if concurrent {
+                self.processing_tasks.write().unwrap().stop_concurrent_processing(batch_uid);
+            } else {
+                self.processing_tasks.write().unwrap().stop_processing();
+            }
 
             // Once the tasks are committed, we should delete all the update files associated ASAP to avoid leaking files in case of a restart
             tracing::debug!("Deleting the update files");
@@ -466,6 +597,7 @@ impl IndexScheduler {
                 Ok(())
             })?;
 
+            self.notify_task_updates(ids.clone());
             self.notify_webhooks(ids);
         }
 
@@ -13,7 +13,7 @@ use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::progress::{Progress, VariableNameStep};
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, ChannelCongestion};
 use meilisearch_types::network::Network;
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Priority, Status, Task};
 use meilisearch_types::versioning::{VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH};
 use milli::update::Settings as MilliSettings;
 use roaring::{MultiOps, RoaringBitmap};
@@ -681,6 +681,15 @@ impl IndexScheduler {
                 // but it would mean opening the index and that's costly.
                 Ok((tasks, ProcessBatchInfo::default()))
             }
+            Batch::Transaction { transaction_uid, operations, complete } => self
+                .process_transaction(
+                    transaction_uid,
+                    operations,
+                    complete,
+                    current_batch,
+                    &progress,
+                    network,
+                ),
         }
     }
 
@@ -732,7 +741,7 @@ impl IndexScheduler {
         progress.update_progress(IndexCompaction::CloseTheIndex);
 
         // unmark that the index is the processing one so we don't keep a handle to it, preventing its closing
-        self.index_mapper.set_currently_updating_index(None);
+        self.index_mapper.unset_currently_updating_index(index_uid.uid());
 
         self.index_mapper.close_index(rtxn, index_uid)?;
         drop(index);
@@ -963,11 +972,14 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut affected_transactions = RoaringBitmap::new();
+        let mut affected_idempotency_keys = RoaringBitmap::new();
         // The tasks that have been removed *per batches*.
         let mut affected_batches: HashMap<BatchId, RoaringBitmap> = HashMap::new();
         let mut tasks_enqueued_to_remove: HashMap<i128, RoaringBitmap> = HashMap::new();
         let mut tasks_started_to_remove: HashMap<i128, RoaringBitmap> = HashMap::new();
         let mut tasks_finished_to_remove: HashMap<i128, RoaringBitmap> = HashMap::new();
+        let mut tasks_run_at_to_remove: HashMap<i128, RoaringBitmap> = HashMap::new();
         let (atomic_progress, task_progress) = AtomicTaskStep::new(to_delete_tasks.len() as u32);
         progress.update_progress(task_progress);
 
@@ -996,9 +1008,22 @@ impl IndexScheduler {
                         .insert(task_id);
                 }
 
+                if let Some(run_at) = task.run_at {
+                    tasks_run_at_to_remove
+                        .entry(run_at.unix_timestamp_nanos())
+                        .or_default()
+                        .insert(task_id);
+                }
+
                 if let Some(canceled_by) = task.canceled_by {
                     affected_canceled_by.insert(canceled_by);
                 }
+                if let Some(transaction_uid) = task.transaction_uid {
+                    affected_transactions.insert(transaction_uid);
+                }
+                if task.idempotency_key.is_some() {
+                    affected_idempotency_keys.insert(task_id);
+                }
                 if let Some(batch_uid) = task.batch_uid {
                     affected_batches.entry(batch_uid).or_default().insert(task_id);
                 }
@@ -1100,6 +1125,7 @@ impl IndexScheduler {
         remove_task_datetimes(wtxn, tasks_enqueued_to_remove, self.queue.tasks.enqueued_at)?;
         remove_task_datetimes(wtxn, tasks_started_to_remove, self.queue.tasks.started_at)?;
         remove_task_datetimes(wtxn, tasks_finished_to_remove, self.queue.tasks.finished_at)?;
+        remove_task_datetimes(wtxn, tasks_run_at_to_remove, self.queue.tasks.run_at)?;
 
         // 8. Delete batches datetimes
         progress.update_progress(TaskDeletionProgress::DeletingBatchesDateTime);
@@ -1144,6 +1170,20 @@ impl IndexScheduler {
             atomic_progress.fetch_add(1, Ordering::Relaxed);
         }
 
+        for priority in [Priority::Low, Priority::High] {
+            self.queue.tasks.update_priority(wtxn, priority, |tasks| *tasks -= &to_delete_tasks)?;
+        }
+
+        for transaction_uid in affected_transactions {
+            self.queue
+                .tasks
+                .update_transaction(wtxn, transaction_uid, |tasks| *tasks -= &to_delete_tasks)?;
+        }
+
+        for task_id in affected_idempotency_keys {
+            self.queue.tasks.delete_idempotency_key(wtxn, task_id)?;
+        }
+
         // 11. Delete tasks
         progress.update_progress(TaskDeletionProgress::DeletingTasks);
         let (atomic_progress, task_progress) =
@@ -1211,6 +1251,8 @@ impl IndexScheduler {
         //    Notice that only the _enqueued_ ones are cancelable and we should
         //    have already aborted the indexation of the _processing_ ones
         tasks_to_cancel |= enqueued_tasks & matched_tasks;
+        //    The tasks of a transaction whose indexes are being committed must be committed too.
+        tasks_to_cancel -= self.queue.tasks.committing_tasks(rtxn)?;
 
         // 2. If we're canceling an upgrade, attempt the rollback
         if let Some(latest_upgrade_task) = (&tasks_to_cancel & upgrade_tasks).max() {
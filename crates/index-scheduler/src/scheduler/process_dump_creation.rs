@@ -268,6 +268,7 @@ impl IndexScheduler {
         progress.update_progress(DumpCreationProgress::DumpTheWebhooks);
         let webhooks = self.webhooks_dump_view();
         dump.create_webhooks(webhooks)?;
+        dump.create_task_retention_policy(self.task_retention_policy())?;
 
         let dump_uid = started_at.format(format_description!(
                     "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
@@ -846,6 +846,7 @@ fn eq_attr_pattern(pattern: String) -> FilterableAttributesRule {
             facet_search: false,
             filter: FilterFeatures { equality: true, comparison: false },
         },
+        hierarchy_separator: None,
     })
 }
 
@@ -856,5 +857,6 @@ fn cmp_attr_pattern(pattern: String) -> FilterableAttributesRule {
             facet_search: false,
             filter: FilterFeatures { equality: true, comparison: true },
         },
+        hierarchy_separator: None,
     })
 }
@@ -34,15 +34,28 @@ unsafe fn remove_tasks(
         all_tasks,
         status,
         kind,
+        priority,
         index_tasks: _, // snapshot creation tasks are not index tasks
         canceled_by,
         enqueued_at,
         started_at,
         finished_at,
-    } = task_queue;
+        run_at,
+        transactions: _, // snapshot creation tasks are not part of transactions
+        committing_transactions: _,
+        idempotency_keys: _,
+        idempotency_key_tasks: _,
+    } = task_queue.private_clone();
 
     for task in tasks {
         all_tasks.delete(&mut wtxn, &task.uid)?;
+        task_queue.delete_idempotency_key(&mut wtxn, task.uid)?;
+
+        if !task.priority.is_normal() {
+            let mut tasks = priority.get(&wtxn, &task.priority)?.unwrap_or_default();
+            tasks.remove(task.uid);
+            priority.put(&mut wtxn, &task.priority, &tasks)?;
+        }
 
         let mut tasks = status.get(&wtxn, &task.status)?.unwrap_or_default();
         tasks.remove(task.uid);
@@ -72,6 +85,13 @@ unsafe fn remove_tasks(
             tasks.remove(task.uid);
             finished_at.put(&mut wtxn, &timestamp, &tasks)?;
         }
+
+        if let Some(task_run_at) = task.run_at {
+            let timestamp = task_run_at.unix_timestamp_nanos();
+            let mut tasks = run_at.get(&wtxn, &timestamp)?.unwrap_or_default();
+            tasks.remove(task.uid);
+            run_at.put(&mut wtxn, &timestamp, &tasks)?;
+        }
     }
     wtxn.commit()?;
     Ok(())
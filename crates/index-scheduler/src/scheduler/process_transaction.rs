@@ -0,0 +1,289 @@
+use std::collections::BTreeSet;
+
+use meilisearch_types::index_uid::UserIndex;
+use meilisearch_types::milli::progress::Progress;
+use meilisearch_types::network::Network;
+use meilisearch_types::tasks::{Status, Task, TaskId};
+use meilisearch_types::versioning::{VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH};
+
+use super::create_batch::IndexOperation;
+use super::process_batch::ProcessBatchInfo;
+use crate::processing::FinalizingIndexStep;
+use crate::queue::CommittingTransaction;
+use crate::utils::ProcessingBatch;
+use crate::{Error, IndexScheduler, Result};
+
+/// The number of times the commit of a partially committed transaction is attempted
+/// before its tasks fail.
+const MAX_TRANSACTION_COMMIT_ATTEMPTS: u32 = 3;
+
+impl IndexScheduler {
+    /// Apply all the operations of a transaction and commit them together.
+    ///
+    /// The write transactions of all the indexes of the transaction are kept open while the
+    /// operations are applied, and are only committed once all the operations succeeded.
+    /// If a single task fails, or if some tasks of the transaction were canceled, nothing is
+    /// committed and every task of the transaction fails.
+    ///
+    /// LMDB cannot commit several environments atomically, so the indexes of the transaction are
+    /// first recorded in the task queue, then committed back to back along with the uid of the
+    /// transaction. If a commit fails or the engine stops before the tasks are committed, the
+    /// transaction is rolled forward: it is processed again, its operations are only applied to
+    /// the indexes that don't have its uid yet, and its tasks can no longer be canceled.
+    /// After [`MAX_TRANSACTION_COMMIT_ATTEMPTS`] failed commits, the failure is deemed permanent:
+    /// the tasks fail with an error listing the indexes that were committed.
+    ///
+    /// Note that, as for the other index operations, the missing indexes are created before
+    /// applying the operations and are not deleted when the transaction is rolled back.
+    pub(super) fn process_transaction(
+        &self,
+        transaction_uid: TaskId,
+        operations: Vec<(IndexOperation, bool)>,
+        complete: bool,
+        current_batch: &mut ProcessingBatch,
+        progress: &Progress,
+        network: &Network,
+    ) -> Result<(Vec<Task>, ProcessBatchInfo)> {
+        let committing = {
+            let rtxn = self.env.read_txn()?;
+            self.queue.tasks.committing_transactions.get(&rtxn, &transaction_uid)?.is_some()
+        };
+        if !complete && !committing {
+            return Err(Error::TransactionIncomplete(transaction_uid));
+        }
+
+        // The indexer reads from the last committed state of an index, so it wouldn't see the
+        // previous operations of the transaction on the same index.
+        let mut operated_indexes = BTreeSet::new();
+        for (op, _) in &operations {
+            if !operated_indexes.insert(op.index_uid()) {
+                return Err(Error::TransactionNotBatchable {
+                    transaction_uid,
+                    index: op.index_uid().to_string(),
+                });
+            }
+        }
+
+        // 1. Retrieve or create all the indexes of the transaction.
+        let mut indexes = Vec::new();
+        for (op, must_create_index) in &operations {
+            let index_name = op.index_uid();
+            if indexes.iter().any(|(name, _)| name == index_name) {
+                continue;
+            }
+            let index_uid = UserIndex::try_from_uid(index_name)?;
+            let index = if *must_create_index {
+                let wtxn = self.env.write_txn()?;
+                self.index_mapper.create_index(wtxn, index_uid, None, network.shards())?
+            } else {
+                let rtxn = self.env.read_txn()?;
+                self.index_mapper.index(&rtxn, index_uid)?
+            };
+            indexes.push((index_name.to_string(), index));
+        }
+
+        // 2. Open the write transactions of all the indexes.
+        let mut index_wtxns = Vec::with_capacity(indexes.len());
+        let mut committed = Vec::new();
+        for (index_name, index) in &indexes {
+            let index_wtxn = index.write_txn()?;
+            let index_version = index.get_version(&index_wtxn)?.unwrap_or((1, 12, 0));
+            let package_version = (VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH);
+            if index_version != package_version {
+                return Err(Error::IndexVersionMismatch {
+                    index: index_name.clone(),
+                    index_version,
+                    package_version,
+                });
+            }
+            if committing && index.last_transaction_uid(&index_wtxn)? == Some(transaction_uid) {
+                committed.push(index_name.clone());
+            }
+            index_wtxns.push(index_wtxn);
+        }
+
+        // 3. Apply the operations in the order of their tasks, the first failure rolls back everything.
+        let mut tasks = Vec::new();
+        let mut congestion = None;
+        for (op, _) in operations {
+            let position = indexes
+                .iter()
+                .position(|(name, _)| name == op.index_uid())
+                .expect("all the indexes of the transaction must have been opened");
+            let (index_name, index) = &indexes[position];
+
+            // the index already has the changes of the transaction, we are rolling it forward
+            if committed.contains(index_name) {
+                tasks.extend(op.into_tasks().into_iter().map(|mut task| {
+                    task.status = Status::Succeeded;
+                    task
+                }));
+                continue;
+            }
+
+            // the index operation can take a long time, so save this handle to make it available to the search for the duration of the tick
+            self.index_mapper
+                .set_currently_updating_index(Some((index_name.clone(), index.clone())));
+
+            let result = self.apply_index_operation(
+                &mut index_wtxns[position],
+                index,
+                op,
+                progress,
+                current_batch.embedder_stats.clone(),
+                network,
+            );
+            // when rolling forward, the operations already succeeded once and are retried until
+            // the transaction is fully committed
+            let (op_tasks, op_congestion) = match result {
+                Ok(result) => result,
+                Err(error) if committing => {
+                    return Err(self.partially_committed(
+                        transaction_uid,
+                        committed,
+                        index_name.clone(),
+                        error,
+                    )?)
+                }
+                Err(error) => return Err(error),
+            };
+
+            if let Some(task) = op_tasks.iter().find(|task| task.status == Status::Failed) {
+                let error = Error::TransactionAborted {
+                    transaction_uid,
+                    task_uid: task.uid,
+                    message: task.error.as_ref().map_or_else(String::new, |e| e.message.clone()),
+                };
+                if committing {
+                    return Err(self.partially_committed(
+                        transaction_uid,
+                        committed,
+                        index_name.clone(),
+                        error,
+                    )?);
+                }
+                return Err(error);
+            }
+
+            congestion = op_congestion.or(congestion);
+            tasks.extend(op_tasks);
+        }
+
+        // 4. Record the indexes of the transaction, then commit them together.
+        {
+            progress.update_progress(FinalizingIndexStep::Committing);
+            let span = tracing::trace_span!(target: "indexing::scheduler", "commit");
+            let _entered = span.enter();
+
+            if !committing {
+                let index_names: Vec<_> = indexes.iter().map(|(name, _)| name.clone()).collect();
+                let mut wtxn = self.env.write_txn()?;
+                let transaction = CommittingTransaction { indexes: index_names, failed_commits: 0 };
+                self.queue.tasks.committing_transactions.put(
+                    &mut wtxn,
+                    &transaction_uid,
+                    &transaction,
+                )?;
+                wtxn.commit()?;
+            }
+
+            for ((index_name, index), mut index_wtxn) in indexes.iter().zip(index_wtxns) {
+                if committed.contains(index_name) {
+                    continue;
+                }
+                let result = || -> Result<()> {
+                    #[cfg(test)]
+                    if !committed.is_empty() {
+                        self.maybe_fail(
+                            crate::test_utils::FailureLocation::CommittingTransactionIndex,
+                        )?;
+                    }
+                    index.put_last_transaction_uid(&mut index_wtxn, transaction_uid)?;
+                    Ok(index_wtxn.commit()?)
+                }();
+                match result {
+                    Ok(()) => committed.push(index_name.clone()),
+                    Err(e) if committed.is_empty() => {
+                        // nothing was committed, the transaction can still be rolled back
+                        let mut wtxn = self.env.write_txn()?;
+                        self.queue
+                            .tasks
+                            .committing_transactions
+                            .delete(&mut wtxn, &transaction_uid)?;
+                        wtxn.commit()?;
+                        return Err(e);
+                    }
+                    Err(e) => {
+                        return Err(self.partially_committed(
+                            transaction_uid,
+                            committed,
+                            index_name.clone(),
+                            e,
+                        )?)
+                    }
+                }
+            }
+        }
+
+        // 5. Store the new stats of the indexes, a failure must not fail the transaction.
+        let res = || -> Result<()> {
+            progress.update_progress(FinalizingIndexStep::ComputingStats);
+            let mut wtxn = self.env.write_txn()?;
+            for (index_name, index) in &indexes {
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(index, &index_rtxn)
+                    .map_err(|e| Error::from_milli(e, Some(index_name.clone())))?;
+                let index_uid = UserIndex::try_from_uid(index_name)?;
+                self.index_mapper.store_stats_of(&mut wtxn, index_uid, &stats)?;
+            }
+            wtxn.commit()?;
+            Ok(())
+        }();
+
+        if let Err(e) = res {
+            tracing::error!(
+                error = &e as &dyn std::error::Error,
+                "Could not write the stats of the indexes"
+            );
+        }
+
+        Ok((tasks, ProcessBatchInfo { congestion, ..Default::default() }))
+    }
+    /// Count a failed commit of a partially committed transaction.
+    ///
+    /// Returns the error to roll the transaction forward on a later tick, or the error failing its
+    /// tasks once the commit failed [`MAX_TRANSACTION_COMMIT_ATTEMPTS`] times.
+    fn partially_committed(
+        &self,
+        transaction_uid: TaskId,
+        committed: Vec<String>,
+        index: String,
+        error: Error,
+    ) -> Result<Error> {
+        let mut wtxn = self.env.write_txn()?;
+        let mut transaction = self
+            .queue
+            .tasks
+            .committing_transactions
+            .get(&wtxn, &transaction_uid)?
+            .ok_or(Error::CorruptedTaskQueue)?;
+        transaction.failed_commits += 1;
+        if transaction.failed_commits >= MAX_TRANSACTION_COMMIT_ATTEMPTS {
+            return Ok(Error::TransactionCommitFailed {
+                transaction_uid,
+                committed,
+                index,
+                attempts: transaction.failed_commits,
+                error: Box::new(error),
+            });
+        }
+        self.queue.tasks.committing_transactions.put(&mut wtxn, &transaction_uid, &transaction)?;
+        wtxn.commit()?;
+        Ok(Error::TransactionPartiallyCommitted {
+            transaction_uid,
+            committed,
+            index,
+            error: Box::new(error),
+        })
+    }
+}
@@ -1,21 +1,24 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
 
 use big_s::S;
 use meili_snap::{json_string, snapshot};
 use meilisearch_auth::AuthFilter;
 use meilisearch_types::index_uid::{AnyIndex, DsrIndex, UserIndex};
 use meilisearch_types::milli::update::IndexDocumentsMethod::*;
-use meilisearch_types::milli::update::MissingDocumentPolicy;
+use meilisearch_types::milli::update::{IndexerConfig, MissingDocumentPolicy};
 use meilisearch_types::milli::{self};
 use meilisearch_types::settings::SettingEmbeddingSettings;
-use meilisearch_types::tasks::{IndexSwap, KindWithContent};
+use meilisearch_types::tasks::{IndexSwap, KindWithContent, Priority};
 use milli::vector::db::IndexEmbeddingConfig;
 use roaring::RoaringBitmap;
+use time::{Duration, OffsetDateTime};
 
 use crate::insta_snapshot::snapshot_index_scheduler;
 use crate::test_utils::Breakpoint::*;
 use crate::test_utils::{
-    index_creation_task, read_json, replace_document_import_task, sample_documents,
+    index_creation_task, read_json, replace_document_import_task, sample_documents, FailureLocation,
 };
 use crate::{IndexScheduler, Query};
 
@@ -1042,3 +1045,587 @@ fn test_index_uid_count() {
     snapshot!(index_scheduler.index_mapper.index_count::<DsrIndex>(&rtxn).unwrap(), @"1");
     snapshot!(index_scheduler.index_mapper.index_count::<AnyIndex>(&rtxn).unwrap(), @"4");
 }
+
+#[test]
+fn process_indexes_by_priority() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    for (name, priority) in
+        [("doggos", Priority::Normal), ("cattos", Priority::Low), ("girafos", Priority::High)]
+    {
+        index_scheduler
+            .register_scheduled(
+                KindWithContent::IndexCreation { index_uid: name.to_string(), primary_key: None },
+                None,
+                priority,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        index_scheduler.assert_internally_consistent();
+    }
+
+    handle.advance_n_successful_batches(3);
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.env.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..3).unwrap();
+    let batches: Vec<_> = tasks.iter().map(|task| (task.uid, task.batch_uid)).collect();
+    // girafos first, then doggos and finally cattos
+    snapshot!(format!("{batches:?}"), @"[(0, Some(1)), (1, Some(2)), (2, Some(0))]");
+}
+
+#[test]
+fn indexes_take_turns_with_fair_scheduling() {
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.autobatching_enabled = false;
+        config.fair_index_scheduling = true;
+        None
+    });
+
+    let tasks = [
+        KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None },
+        KindWithContent::DocumentClear { index_uid: S("doggos") },
+        KindWithContent::DocumentClear { index_uid: S("doggos") },
+        KindWithContent::IndexCreation { index_uid: S("cattos"), primary_key: None },
+        KindWithContent::DocumentClear { index_uid: S("cattos") },
+    ];
+    for task in tasks {
+        index_scheduler.register(task).unwrap();
+        index_scheduler.assert_internally_consistent();
+    }
+
+    handle.advance_n_successful_batches(5);
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.env.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..5).unwrap();
+    let batches: Vec<_> = tasks.iter().map(|task| (task.uid, task.batch_uid)).collect();
+    // the doggos and cattos alternate instead of processing all the doggos first
+    snapshot!(format!("{batches:?}"), @"[(0, Some(1)), (1, Some(3)), (2, Some(4)), (3, Some(0)), (4, Some(2))]");
+}
+
+#[test]
+fn delayed_tasks_wait_for_their_run_at_date() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let run_at = OffsetDateTime::now_utc() + Duration::hours(1);
+    index_scheduler
+        .register_scheduled(
+            KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None },
+            None,
+            Priority::Normal,
+            Some(run_at),
+            None,
+            None,
+        )
+        .unwrap();
+    index_scheduler
+        .register(KindWithContent::IndexCreation { index_uid: S("cattos"), primary_key: None })
+        .unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    // the delayed task doesn't block the other tasks
+    handle.advance_one_successful_batch();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..2).unwrap();
+    let statuses: Vec<_> = tasks.iter().map(|task| (task.uid, task.status)).collect();
+    snapshot!(format!("{statuses:?}"), @"[(0, Enqueued), (1, Succeeded)]");
+
+    let query = Query { after_run_at: Some(run_at - Duration::minutes(1)), ..Default::default() };
+    let (tasks, _) = index_scheduler
+        .get_task_ids_from_authorized_indexes(&query, &AuthFilter::default())
+        .unwrap();
+    snapshot!(format!("{tasks:?}"), @"RoaringBitmap<[0]>");
+    let query = Query { before_run_at: Some(run_at), ..Default::default() };
+    let (tasks, _) = index_scheduler
+        .get_task_ids_from_authorized_indexes(&query, &AuthFilter::default())
+        .unwrap();
+    snapshot!(format!("{tasks:?}"), @"RoaringBitmap<[]>");
+    drop(rtxn);
+
+    // it can be canceled like any enqueued task
+    index_scheduler
+        .register(KindWithContent::TaskCancelation {
+            query: S("test_query"),
+            tasks: RoaringBitmap::from_iter([0]),
+        })
+        .unwrap();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 0).unwrap().unwrap();
+    snapshot!(format!("{:?}", (task.status, task.canceled_by)), @"(Canceled, Some(2))");
+}
+
+#[test]
+fn transaction_commits_its_tasks_together() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let (file0, count0) = sample_documents(&index_scheduler, 0, 0);
+    let (file1, count1) = sample_documents(&index_scheduler, 1, 1);
+    file0.persist().unwrap();
+    file1.persist().unwrap();
+    let tasks = index_scheduler
+        .register_transaction(
+            vec![
+                replace_document_import_task("doggos", Some("id"), 0, count0),
+                replace_document_import_task("cattos", Some("id"), 1, count1),
+            ],
+            None,
+        )
+        .unwrap();
+    let transactions: Vec<_> = tasks.iter().map(|task| (task.uid, task.transaction_uid)).collect();
+    snapshot!(format!("{transactions:?}"), @"[(0, Some(0)), (1, Some(0))]");
+    index_scheduler.assert_internally_consistent();
+
+    // all the tasks of the transaction are processed in the same batch
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..2).unwrap();
+    let batches: Vec<_> =
+        tasks.iter().map(|task| (task.uid, task.status, task.batch_uid)).collect();
+    snapshot!(format!("{batches:?}"), @"[(0, Succeeded, Some(0)), (1, Succeeded, Some(0))]");
+    drop(rtxn);
+
+    // the second task can't be processed because of its primary key, none of the changes are committed
+    let (file2, count2) = sample_documents(&index_scheduler, 2, 2);
+    let (file3, count3) = sample_documents(&index_scheduler, 3, 3);
+    file2.persist().unwrap();
+    file3.persist().unwrap();
+    index_scheduler
+        .register_transaction(
+            vec![
+                replace_document_import_task("doggos", Some("id"), 2, count2),
+                replace_document_import_task("cattos", Some("nope"), 3, count3),
+            ],
+            None,
+        )
+        .unwrap();
+    handle.advance_one_failed_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 2..4).unwrap();
+    let batches: Vec<_> =
+        tasks.iter().map(|task| (task.uid, task.status, task.batch_uid)).collect();
+    snapshot!(format!("{batches:?}"), @"[(2, Failed, Some(1)), (3, Failed, Some(1))]");
+    drop(rtxn);
+
+    let index = index_scheduler.user_index("doggos").unwrap();
+    let rtxn = index.read_txn().unwrap();
+    snapshot!(index.number_of_documents(&rtxn).unwrap(), @"1");
+}
+
+#[test]
+fn transaction_applies_the_tasks_of_an_index_together() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let (file0, count0) = sample_documents(&index_scheduler, 0, 0);
+    let (file1, count1) = sample_documents(&index_scheduler, 1, 1);
+    file0.persist().unwrap();
+    file1.persist().unwrap();
+    index_scheduler
+        .register_transaction(
+            vec![
+                replace_document_import_task("doggos", Some("id"), 0, count0),
+                replace_document_import_task("doggos", Some("id"), 1, count1),
+            ],
+            None,
+        )
+        .unwrap();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    // both additions are applied by a single operation, so none of them is lost
+    let index = index_scheduler.user_index("doggos").unwrap();
+    let rtxn = index.read_txn().unwrap();
+    snapshot!(index.number_of_documents(&rtxn).unwrap(), @"2");
+}
+
+#[test]
+fn transaction_rolled_forward_after_a_partial_commit() {
+    let (index_scheduler, mut handle) =
+        IndexScheduler::test(true, vec![(1, FailureLocation::CommittingTransactionIndex)]);
+
+    let (file0, count0) = sample_documents(&index_scheduler, 0, 0);
+    let (file1, count1) = sample_documents(&index_scheduler, 1, 1);
+    file0.persist().unwrap();
+    file1.persist().unwrap();
+    index_scheduler
+        .register_transaction(
+            vec![
+                replace_document_import_task("doggos", Some("id"), 0, count0),
+                replace_document_import_task("cattos", Some("id"), 1, count1),
+            ],
+            None,
+        )
+        .unwrap();
+    // the indexes are committed in alphabetical order and the second commit fails
+    handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+    handle.advance_till([Start]);
+
+    // the tasks stay enqueued and can't be canceled anymore
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..2).unwrap();
+    for task in &tasks {
+        snapshot!(format!("{:?}", task.status), @"Enqueued");
+    }
+    snapshot!(format!("{:?}", index_scheduler.queue.tasks.committing_tasks(&rtxn).unwrap()), @"RoaringBitmap<[0, 1]>");
+    drop(rtxn);
+    let cattos = index_scheduler.user_index("cattos").unwrap();
+    let rtxn = cattos.read_txn().unwrap();
+    snapshot!(cattos.number_of_documents(&rtxn).unwrap(), @"1");
+    snapshot!(format!("{:?}", cattos.last_transaction_uid(&rtxn).unwrap()), @"Some(0)");
+    drop(rtxn);
+    let doggos = index_scheduler.user_index("doggos").unwrap();
+    let rtxn = doggos.read_txn().unwrap();
+    snapshot!(doggos.number_of_documents(&rtxn).unwrap(), @"0");
+    drop(rtxn);
+
+    // the transaction is only applied to the index that wasn't committed on the next tick
+    handle.advance_till([BatchCreated, InsideProcessBatch, ProcessBatchSucceeded, AfterProcessing]);
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..2).unwrap();
+    for task in &tasks {
+        snapshot!(format!("{:?}", task.status), @"Succeeded");
+    }
+    snapshot!(format!("{:?}", index_scheduler.queue.tasks.committing_tasks(&rtxn).unwrap()), @"RoaringBitmap<[]>");
+    drop(rtxn);
+    let rtxn = cattos.read_txn().unwrap();
+    snapshot!(cattos.number_of_documents(&rtxn).unwrap(), @"1");
+    let rtxn = doggos.read_txn().unwrap();
+    snapshot!(doggos.number_of_documents(&rtxn).unwrap(), @"1");
+    snapshot!(format!("{:?}", doggos.last_transaction_uid(&rtxn).unwrap()), @"Some(0)");
+}
+
+#[test]
+fn transaction_fails_after_repeated_partial_commits() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(
+        true,
+        (1..=3).map(|iteration| (iteration, FailureLocation::CommittingTransactionIndex)).collect(),
+    );
+
+    let (file0, count0) = sample_documents(&index_scheduler, 0, 0);
+    let (file1, count1) = sample_documents(&index_scheduler, 1, 1);
+    file0.persist().unwrap();
+    file1.persist().unwrap();
+    index_scheduler
+        .register_transaction(
+            vec![
+                replace_document_import_task("doggos", Some("id"), 0, count0),
+                replace_document_import_task("cattos", Some("id"), 1, count1),
+            ],
+            None,
+        )
+        .unwrap();
+    // the commit of the second index fails, then its roll forward fails twice
+    handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+    handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+    handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+    handle.advance_till([ProcessBatchFailed, AfterProcessing]);
+    index_scheduler.assert_internally_consistent();
+
+    // the tasks fail and report the committed indexes
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..2).unwrap();
+    for task in &tasks {
+        snapshot!(format!("{:?}", task.status), @"Failed");
+    }
+    snapshot!(tasks[0].error.as_ref().unwrap().message, @r###"Transaction `0` was partially committed: the changes of the indexes ["cattos"] were committed but the commit of the index `doggos` failed 3 times and won't be retried: Planned failure for tests."###);
+    snapshot!(format!("{:?}", index_scheduler.queue.tasks.committing_tasks(&rtxn).unwrap()), @"RoaringBitmap<[]>");
+    drop(rtxn);
+    let cattos = index_scheduler.user_index("cattos").unwrap();
+    let rtxn = cattos.read_txn().unwrap();
+    snapshot!(cattos.number_of_documents(&rtxn).unwrap(), @"1");
+    drop(rtxn);
+    let doggos = index_scheduler.user_index("doggos").unwrap();
+    let rtxn = doggos.read_txn().unwrap();
+    snapshot!(doggos.number_of_documents(&rtxn).unwrap(), @"0");
+}
+
+#[test]
+fn idempotency_key_returns_the_original_task() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let kind = || KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None };
+    let task = index_scheduler.register_idempotent(kind(), None, None, Some(S("retry"))).unwrap();
+    let retried =
+        index_scheduler.register_idempotent(kind(), None, None, Some(S("retry"))).unwrap();
+    snapshot!(format!("{:?}", (task.uid, retried.uid)), @"(0, 0)");
+    index_scheduler.assert_internally_consistent();
+
+    // a retry returns the current state of the original task
+    handle.advance_one_successful_batch();
+    let retried =
+        index_scheduler.register_idempotent(kind(), None, None, Some(S("retry"))).unwrap();
+    snapshot!(format!("{:?}", (retried.uid, retried.status)), @"(0, Succeeded)");
+
+    // once the original task is deleted, the key enqueues a new task
+    index_scheduler
+        .register(KindWithContent::TaskDeletion {
+            query: "test_query".to_owned(),
+            tasks: RoaringBitmap::from_iter([0]),
+        })
+        .unwrap();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+    let task = index_scheduler.register_idempotent(kind(), None, None, Some(S("retry"))).unwrap();
+    snapshot!(task.uid, @"2");
+    index_scheduler.assert_internally_consistent();
+}
+
+#[test]
+fn idempotency_key_reused_for_another_request() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    let creation = |index_uid: &str| KindWithContent::IndexCreation {
+        index_uid: S(index_uid),
+        primary_key: None,
+    };
+    let task =
+        index_scheduler.register_idempotent(creation("doggos"), None, None, Some(S("retry")));
+    snapshot!(task.unwrap().uid, @"0");
+
+    // another index
+    let err = index_scheduler
+        .register_idempotent(creation("cattos"), None, None, Some(S("retry")))
+        .unwrap_err();
+    snapshot!(err, @"The idempotency key `retry` was already used to enqueue the task `0` with a different request.");
+    // another operation
+    let err = index_scheduler
+        .register_idempotent(
+            KindWithContent::IndexDeletion { index_uid: S("doggos") },
+            None,
+            None,
+            Some(S("retry")),
+        )
+        .unwrap_err();
+    snapshot!(err, @"The idempotency key `retry` was already used to enqueue the task `0` with a different request.");
+    // another payload
+    let err = index_scheduler
+        .register_idempotent(
+            KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: Some(S("id")) },
+            None,
+            None,
+            Some(S("retry")),
+        )
+        .unwrap_err();
+    snapshot!(err, @"The idempotency key `retry` was already used to enqueue the task `0` with a different request.");
+
+    // the same request returns the original task
+    let retried =
+        index_scheduler.register_idempotent(creation("doggos"), None, None, Some(S("retry")));
+    snapshot!(retried.unwrap().uid, @"0");
+    index_scheduler.assert_internally_consistent();
+}
+
+#[test]
+fn idempotency_key_compares_the_documents() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    let register = |id: u128, content: &str| {
+        let (uuid, mut file) = index_scheduler.queue.create_update_file_with_uuid(id).unwrap();
+        let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+        file.persist().unwrap();
+        let kind =
+            replace_document_import_task("doggos", Some("id"), uuid.as_u128(), documents_count);
+        index_scheduler.register_idempotent(kind, None, None, Some(S("retry")))
+    };
+
+    let task = register(0, r#"{ "id": 1, "doggo": "bob" }"#).unwrap();
+    snapshot!(task.uid, @"0");
+    // the update files of the retried requests are not kept
+    let retried = register(1, r#"{ "id": 1, "doggo": "bob" }"#).unwrap();
+    snapshot!(retried.uid, @"0");
+    let err = register(2, r#"{ "id": 1, "doggo": "bobby" }"#).unwrap_err();
+    snapshot!(err, @"The idempotency key `retry` was already used to enqueue the task `0` with a different request.");
+
+    let files = index_scheduler.queue.file_store.all_uuids().unwrap().count();
+    snapshot!(files, @"1");
+    index_scheduler.assert_internally_consistent();
+}
+
+#[test]
+fn expired_idempotency_keys_are_purged() {
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.idempotency_key_retention = std::time::Duration::ZERO;
+        None
+    });
+
+    let kind = || KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None };
+    index_scheduler.register_idempotent(kind(), None, None, Some(S("retry"))).unwrap();
+    let rtxn = index_scheduler.env.read_txn().unwrap();
+    snapshot!(index_scheduler.queue.tasks.idempotency_keys.len(&rtxn).unwrap(), @"1");
+    snapshot!(index_scheduler.queue.tasks.idempotency_key_tasks.len(&rtxn).unwrap(), @"1");
+    drop(rtxn);
+
+    let mut wtxn = index_scheduler.env.write_txn().unwrap();
+    index_scheduler.queue.cleanup_task_queue(&mut wtxn).unwrap();
+    wtxn.commit().unwrap();
+
+    let rtxn = index_scheduler.env.read_txn().unwrap();
+    snapshot!(index_scheduler.queue.tasks.idempotency_keys.len(&rtxn).unwrap(), @"0");
+    snapshot!(index_scheduler.queue.tasks.idempotency_key_tasks.len(&rtxn).unwrap(), @"0");
+    drop(rtxn);
+    index_scheduler.assert_internally_consistent();
+
+    // an expired key enqueues a new task
+    let task = index_scheduler.register_idempotent(kind(), None, None, Some(S("retry"))).unwrap();
+    snapshot!(task.uid, @"1");
+}
+
+#[test]
+fn pause_and_resume_processing() {
+    // the run loop doesn't start until the restart
+    let (index_scheduler, handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_number_of_batched_tasks = 0;
+        None
+    });
+    let paused_at = index_scheduler.pause_processing(false).unwrap();
+    // pausing again keeps the original date
+    assert_eq!(index_scheduler.pause_processing(false).unwrap(), paused_at);
+
+    // the pause survives a restart
+    let (index_scheduler, mut handle) = handle.restart(index_scheduler, true, vec![], |_| None);
+    assert_eq!(index_scheduler.paused_at(), Some(paused_at));
+
+    // the tasks are still registered, but no batch is created
+    index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+    handle.advance_till([Start]);
+    handle.scheduler_is_waiting();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 0).unwrap().unwrap();
+    snapshot!(format!("{:?}", task.status), @"Enqueued");
+    drop(rtxn);
+
+    index_scheduler.resume_processing().unwrap();
+    assert_eq!(index_scheduler.paused_at(), None);
+    let rtxn = index_scheduler.read_txn().unwrap();
+    assert!(index_scheduler
+        .persisted
+        .get(&rtxn, crate::db_keys::SCHEDULER_PAUSED_AT)
+        .unwrap()
+        .is_none());
+    drop(rtxn);
+
+    handle.advance_one_successful_batch();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 0).unwrap().unwrap();
+    snapshot!(format!("{:?}", task.status), @"Succeeded");
+    index_scheduler.assert_internally_consistent();
+}
+
+#[test]
+fn task_updates_are_broadcasted() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+    let mut updates = index_scheduler.subscribe_to_task_updates();
+
+    index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+    snapshot!(format!("{:?}", updates.try_recv().unwrap()), @"RoaringBitmap<[0]>");
+
+    handle.advance_one_successful_batch();
+    // once when the task starts processing and once when it is finished
+    snapshot!(format!("{:?}", updates.try_recv().unwrap()), @"RoaringBitmap<[0]>");
+    snapshot!(format!("{:?}", updates.try_recv().unwrap()), @"RoaringBitmap<[0]>");
+    assert!(updates.try_recv().is_err());
+}
+
+#[test]
+fn delayed_tasks_dont_block_the_later_tasks_of_their_index() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler
+        .register(KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None })
+        .unwrap();
+    index_scheduler
+        .register_scheduled(
+            KindWithContent::IndexUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                new_index_uid: None,
+            },
+            None,
+            Priority::Normal,
+            Some(OffsetDateTime::now_utc() + Duration::hours(1)),
+            None,
+            None,
+        )
+        .unwrap();
+    index_scheduler.register(KindWithContent::IndexDeletion { index_uid: S("doggos") }).unwrap();
+    index_scheduler
+        .register(KindWithContent::IndexCreation { index_uid: S("cattos"), primary_key: None })
+        .unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    // the tasks enqueued after the delayed task are processed before it
+    handle.advance_one_successful_batch();
+    handle.advance_one_successful_batch();
+    handle.advance_one_successful_batch();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..4).unwrap();
+    let statuses: Vec<_> = tasks.iter().map(|task| (task.uid, task.status)).collect();
+    snapshot!(format!("{statuses:?}"), @"[(0, Succeeded), (1, Enqueued), (2, Succeeded), (3, Succeeded)]");
+}
+
+#[test]
+fn concurrent_batches_share_the_indexing_memory() {
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_concurrent_batches = 2;
+        config.indexer_config = Arc::new(IndexerConfig {
+            max_memory: Some(1000),
+            skip_index_budget: true,
+            ..Default::default()
+        });
+        None
+    });
+
+    index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+    index_scheduler.register(index_creation_task("cattos", "id")).unwrap();
+    index_scheduler
+        .register(KindWithContent::IndexSwap {
+            swaps: vec![IndexSwap { indexes: (S("doggos"), S("cattos")), rename: false }],
+        })
+        .unwrap();
+    snapshot!(format!("{:?}", handle.next_breakpoint()), @"(Init, true)");
+
+    // the first batch waits on its first breakpoint while the second one starts
+    snapshot!(format!("{:?}", handle.next_breakpoint()), @"(InsideProcessBatch, false)");
+    let started = Instant::now();
+    while index_scheduler.processing_tasks.read().unwrap().concurrent.len() < 2 {
+        assert!(started.elapsed().as_secs() < 10, "The batches are not processed concurrently.");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    {
+        let processing = index_scheduler.processing_tasks.read().unwrap();
+        snapshot!(format!("{:?}", processing.processing), @"RoaringBitmap<[0, 1]>");
+        // the memory is split between the batches that can run at the same time
+        let memory: Vec<_> = processing.concurrent.iter().map(|c| c.max_memory).collect();
+        snapshot!(format!("{memory:?}"), @"[Some(500), Some(500)]");
+    }
+
+    // the index swap waits for both batches to finish and is processed alone
+    let mut finished_batches = 0;
+    while finished_batches < 3 {
+        match handle.next_breakpoint() {
+            (Start, false) => {
+                let processing = index_scheduler.processing_tasks.read().unwrap();
+                assert!(processing.concurrent.is_empty());
+                snapshot!(finished_batches, @"2");
+            }
+            (AfterProcessing, true) => finished_batches += 1,
+            _ => (),
+        }
+    }
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let tasks = index_scheduler.queue.tasks.get_existing_tasks(&rtxn, 0..3).unwrap();
+    let statuses: Vec<_> = tasks.iter().map(|task| (task.uid, task.status)).collect();
+    snapshot!(format!("{statuses:?}"), @"[(0, Succeeded), (1, Succeeded), (2, Succeeded)]");
+}
@@ -46,6 +46,7 @@ pub(crate) enum FailureLocation {
     UpdatingTaskAfterProcessBatchSuccess { task_uid: u32 },
     UpdatingTaskAfterProcessBatchFailure,
     CommittingWtxn,
+    CommittingTransactionIndex,
 }
 
 impl IndexScheduler {
@@ -112,6 +113,9 @@ impl IndexScheduler {
             autobatching_enabled: true,
             max_number_of_tasks: 1_000_000,
             max_number_of_batched_tasks: usize::MAX,
+            max_concurrent_batches: 1,
+            fair_index_scheduling: false,
+            idempotency_key_retention: Duration::from_secs(24 * 60 * 60),
             batched_tasks_size_limit: u64::MAX,
             instance_features: Default::default(),
             export_default_payload_size_bytes: byte_unit::Byte::parse_str("20MiB", false).unwrap(),
@@ -119,6 +123,7 @@ impl IndexScheduler {
             // NO DANGER: test code
             ip_policy: IpPolicy::danger_always_allow(),
             dsr_fuel: DsrFuel::new(3, 10, 3, 128, 128, FilterConstraintFuel::new(100, 100, 25)),
+            search_cursor_secret: None,
         };
         let version = configuration(&mut options).unwrap_or({
             (versioning::VERSION_MAJOR, versioning::VERSION_MINOR, versioning::VERSION_PATCH)
@@ -151,6 +156,7 @@ impl IndexScheduler {
             index_scheduler: index_scheduler.private_clone(),
             test_breakpoint_rcv: receiver,
             last_breakpoint: breakpoint.0,
+            released: false,
         };
 
         (index_scheduler, index_scheduler_handle)
@@ -260,6 +266,8 @@ pub struct IndexSchedulerHandle {
     index_scheduler: IndexScheduler,
     test_breakpoint_rcv: crossbeam_channel::Receiver<(Breakpoint, bool)>,
     last_breakpoint: Breakpoint,
+    /// Whether the scheduler was already released from the last breakpoint.
+    released: bool,
 }
 
 impl IndexSchedulerHandle {
@@ -276,8 +284,7 @@ impl IndexSchedulerHandle {
         configuration: impl Fn(&mut IndexSchedulerOptions) -> Option<(u32, u32, u32)>,
     ) -> (IndexScheduler, Self) {
         drop(index_scheduler);
-        let Self { _tempdir: tempdir, index_scheduler, test_breakpoint_rcv, last_breakpoint: _ } =
-            self;
+        let Self { _tempdir: tempdir, index_scheduler, test_breakpoint_rcv, .. } = self;
         let env = index_scheduler.env.clone();
         drop(index_scheduler);
 
@@ -318,30 +325,33 @@ impl IndexSchedulerHandle {
     /// * If the breakpoint queue is in a bad state.
     #[track_caller]
     pub(crate) fn advance(&mut self) -> Breakpoint {
-        let (breakpoint_1, b) = match self
-            .test_breakpoint_rcv
-            .recv_timeout(std::time::Duration::from_secs(50))
-        {
-            Ok(b) => b,
-            Err(RecvTimeoutError::Timeout) => {
-                let state = snapshot_index_scheduler(&self.index_scheduler);
-                panic!("The scheduler seems to be waiting for a new task while your test is waiting for a breakpoint.\n{state}")
-            }
-            Err(RecvTimeoutError::Disconnected) => {
-                let state = snapshot_index_scheduler(&self.index_scheduler);
-                panic!("The scheduler crashed.\n{state}")
-            }
-        };
-        // if we've already encountered a breakpoint we're supposed to be stuck on the false
-        // and we expect the same variant with the true to come now.
-        assert_eq!(
-                (breakpoint_1, b),
-                (self.last_breakpoint, true),
-                "Internal error in the test suite. In the previous iteration I got `({:?}, false)` and now I got `({:?}, {:?})`.",
-                self.last_breakpoint,
-                breakpoint_1,
-                b,
-            );
+        // the scheduler may already have been released from its last breakpoint by `scheduler_is_waiting`
+        if !std::mem::take(&mut self.released) {
+            let (breakpoint_1, b) = match self
+                .test_breakpoint_rcv
+                .recv_timeout(std::time::Duration::from_secs(50))
+            {
+                Ok(b) => b,
+                Err(RecvTimeoutError::Timeout) => {
+                    let state = snapshot_index_scheduler(&self.index_scheduler);
+                    panic!("The scheduler seems to be waiting for a new task while your test is waiting for a breakpoint.\n{state}")
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    let state = snapshot_index_scheduler(&self.index_scheduler);
+                    panic!("The scheduler crashed.\n{state}")
+                }
+            };
+            // if we've already encountered a breakpoint we're supposed to be stuck on the false
+            // and we expect the same variant with the true to come now.
+            assert_eq!(
+                    (breakpoint_1, b),
+                    (self.last_breakpoint, true),
+                    "Internal error in the test suite. In the previous iteration I got `({:?}, false)` and now I got `({:?}, {:?})`.",
+                    self.last_breakpoint,
+                    breakpoint_1,
+                    b,
+                );
+        }
 
         let (breakpoint_2, b) = match self
             .test_breakpoint_rcv
@@ -364,6 +374,21 @@ impl IndexSchedulerHandle {
         breakpoint_2
     }
 
+    /// Receive the next breakpoint message, whichever batch sends it.
+    ///
+    /// The batches processed concurrently send their breakpoints in any order, so the other
+    /// helpers, which expect the breakpoints of a single batch, can't be used alongside this one.
+    #[track_caller]
+    pub(crate) fn next_breakpoint(&mut self) -> (Breakpoint, bool) {
+        match self.test_breakpoint_rcv.recv_timeout(Duration::from_secs(50)) {
+            Ok(b) => b,
+            Err(_) => {
+                let state = snapshot_index_scheduler(&self.index_scheduler);
+                panic!("The scheduler crashed or is waiting for a new task.\n{state}")
+            }
+        }
+    }
+
     /// Advance the scheduler until all the provided breakpoints are reached in order.
     #[track_caller]
     pub(crate) fn advance_till(&mut self, breakpoints: impl IntoIterator<Item = Breakpoint>) {
@@ -438,6 +463,26 @@ impl IndexSchedulerHandle {
         self.advance_till([AfterProcessing]);
     }
 
+    /// Release the scheduler from its last breakpoint and ensure it then waits for a signal
+    /// instead of reaching another breakpoint.
+    #[track_caller]
+    pub(crate) fn scheduler_is_waiting(&mut self) {
+        match self.test_breakpoint_rcv.recv_timeout(std::time::Duration::from_secs(50)) {
+            Ok(b) => assert_eq!(
+                b,
+                (self.last_breakpoint, true),
+                "Found the breakpoint handle in a bad state. Check your test suite"
+            ),
+            Err(_) => panic!("The scheduler crashed or is stuck on its last breakpoint."),
+        }
+        match self.test_breakpoint_rcv.recv_timeout(std::time::Duration::from_secs(1)) {
+            Ok((b, _)) => panic!("The scheduler was supposed to wait for a signal but moved to the next breakpoint: {b:?}"),
+            Err(RecvTimeoutError::Timeout) => (),
+            Err(RecvTimeoutError::Disconnected) => panic!("The scheduler crashed."),
+        }
+        self.released = true;
+    }
+
     // Wait for one failed batch.
     #[track_caller]
     pub(crate) fn scheduler_is_down(&mut self) {
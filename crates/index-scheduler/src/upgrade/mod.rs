@@ -1,6 +1,6 @@
 use anyhow::bail;
 use meilisearch_types::heed::{Env, RwTxn, WithoutTls};
-use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Details, KindWithContent, Priority, Status, Task};
 use meilisearch_types::versioning;
 use time::OffsetDateTime;
 use tracing::info;
@@ -114,6 +114,10 @@ pub fn upgrade_index_scheduler(
             kind: KindWithContent::UpgradeDatabase { from: initial_version },
             network: None,
             custom_metadata: None,
+            priority: Priority::Normal,
+            run_at: None,
+            transaction_uid: None,
+            idempotency_key: None,
         },
     )?;
     wtxn.commit()?;
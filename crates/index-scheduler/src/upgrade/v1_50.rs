@@ -1,5 +1,5 @@
 use meilisearch_types::heed::{Env, RwTxn, WithoutTls};
-use meilisearch_types::tasks::{KindWithContent, Status, Task};
+use meilisearch_types::tasks::{KindWithContent, Priority, Status, Task};
 use time::OffsetDateTime;
 
 use super::UpgradeIndexScheduler;
@@ -65,6 +65,10 @@ impl UpgradeIndexScheduler for MigrateDynamicSearchRules {
                     kind,
                     network: None,
                     custom_metadata: None,
+                    priority: Priority::Normal,
+                    run_at: None,
+                    transaction_uid: None,
+                    idempotency_key: None,
                 },
             )?;
         }
@@ -457,6 +457,10 @@ impl crate::IndexScheduler {
                 kind,
                 network: _,
                 custom_metadata: _,
+                priority,
+                run_at,
+                transaction_uid,
+                idempotency_key,
             } = task;
             assert_eq!(uid, task.uid);
             if task.status != Status::Enqueued {
@@ -519,6 +523,39 @@ impl crate::IndexScheduler {
                     .unwrap();
                 assert!(db_finished_at.contains(task_id));
             }
+            if let Some(run_at) = run_at {
+                let db_run_at = self
+                    .queue
+                    .tasks
+                    .run_at
+                    .get(&rtxn, &run_at.unix_timestamp_nanos())
+                    .unwrap()
+                    .unwrap();
+                assert!(db_run_at.contains(task_id));
+            }
+            for db_priority in
+                [meilisearch_types::tasks::Priority::Low, meilisearch_types::tasks::Priority::High]
+            {
+                let db_tasks = self.queue.tasks.get_priority(&rtxn, db_priority).unwrap();
+                assert_eq!(db_tasks.contains(task_id), priority == db_priority);
+            }
+            if let Some(transaction_uid) = transaction_uid {
+                let db_transaction =
+                    self.queue.tasks.transactions.get(&rtxn, &transaction_uid).unwrap().unwrap();
+                assert!(db_transaction.contains(task_id));
+            }
+            if let Some(idempotency_key) = idempotency_key {
+                // once purged, the key of the task is forgotten
+                if let Some(entry) =
+                    self.queue.tasks.idempotency_key_tasks.get(&rtxn, &task_id).unwrap()
+                {
+                    assert_eq!(entry.key, idempotency_key);
+                    // once expired, a key can be reused by a newer task
+                    let db_task_id =
+                        self.queue.tasks.idempotency_keys.get(&rtxn, &entry.key).unwrap().unwrap();
+                    assert!(db_task_id >= task_id);
+                }
+            }
             if let Some(canceled_by) = canceled_by {
                 let db_canceled_tasks =
                     self.queue.tasks.get_status(&rtxn, Status::Canceled).unwrap();
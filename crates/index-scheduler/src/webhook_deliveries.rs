@@ -0,0 +1,501 @@
+//! The persisted queue of the task notifications to deliver to the webhooks.
+//!
+//! Every time tasks are processed, their views are rendered once into a gzipped ndjson payload and a
+//! delivery is enqueued for each webhook. The deliveries are sent in order for each webhook by a
+//! dedicated thread: a failed delivery is retried with an exponential backoff and blocks the
+//! following deliveries of its webhook, until it reaches [`MAX_DELIVERY_ATTEMPTS`] and is kept
+//! aside as a failed delivery that can be replayed. The failed deliveries are kept for
+//! [`FAILED_DELIVERY_RETENTION`], and at most [`MAX_FAILED_DELIVERIES`] of them per webhook.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use meilisearch_types::heed::types::{Bytes, DecodeIgnore, SerdeJson};
+use meilisearch_types::heed::{
+    BoxedError, BytesDecode, BytesEncode, Database, Env, RoTxn, RwTxn, WithoutTls,
+};
+use meilisearch_types::milli::BEU64;
+use meilisearch_types::webhooks::WebhookDeliveryStatus;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use synchronoise::SignalEvent;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::uuid_codec::UuidCodec;
+use crate::Result;
+
+/// The number of database used by the webhook deliveries
+const NUMBER_OF_DATABASES: u32 = 3;
+/// Database const names for the `WebhookDeliveries`.
+mod db_name {
+    pub const WEBHOOK_DELIVERIES: &str = "webhook-deliveries";
+    pub const WEBHOOK_PAYLOADS: &str = "webhook-payloads";
+    pub const WEBHOOK_STATUSES: &str = "webhook-statuses";
+}
+
+/// The number of times a delivery is attempted before being marked as failed.
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 10;
+/// The delay before the first retry, doubled after each failed attempt.
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(1);
+/// The maximum delay between two attempts of the same delivery.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+/// How long a failed delivery is kept to be replayed, from the date it was enqueued.
+pub const FAILED_DELIVERY_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// The maximum number of failed deliveries kept for each webhook, the oldest are removed first.
+pub const MAX_FAILED_DELIVERIES: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    /// The webhook the payload must be delivered to.
+    pub webhook: Uuid,
+    /// The id of the payload in the payloads database, shared by the deliveries of the same tasks.
+    pub payload: u64,
+    /// The number of tasks described in the payload.
+    pub nb_tasks: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    pub enqueued_at: OffsetDateTime,
+    /// The number of failed attempts.
+    pub attempts: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_attempt_at: OffsetDateTime,
+    pub last_error: Option<String>,
+    /// Whether all the attempts failed, a failed delivery is never retried unless it is replayed.
+    pub failed: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookStatus {
+    #[serde(with = "time::serde::rfc3339::option")]
+    last_success_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    last_error_at: Option<OffsetDateTime>,
+    last_error: Option<String>,
+}
+
+/// A heed codec for the key of a delivery: the id of its payload followed by its webhook.
+///
+/// The payload ids are increasing, the deliveries are ordered by enqueue order.
+struct DeliveryKeyCodec;
+
+impl<'a> BytesDecode<'a> for DeliveryKeyCodec {
+    type DItem = (u64, Uuid);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        if bytes.len() != size_of::<u64>() + size_of::<Uuid>() {
+            return Err("invalid webhook delivery key length".into());
+        }
+        let (payload, webhook) = bytes.split_at(size_of::<u64>());
+        Ok((u64::from_be_bytes(payload.try_into()?), Uuid::from_bytes(webhook.try_into()?)))
+    }
+}
+
+impl BytesEncode<'_> for DeliveryKeyCodec {
+    type EItem = (u64, Uuid);
+
+    fn bytes_encode((payload, webhook): &Self::EItem) -> Result<Cow<'_, [u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(size_of::<u64>() + size_of::<Uuid>());
+        bytes.extend_from_slice(&payload.to_be_bytes());
+        bytes.extend_from_slice(webhook.as_bytes());
+        Ok(Cow::Owned(bytes))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct WebhookDeliveries {
+    /// The deliveries waiting to be sent or kept after failing, keyed by payload and webhook.
+    deliveries: Database<DeliveryKeyCodec, SerdeJson<WebhookDelivery>>,
+    /// The gzipped ndjson payloads, removed once all their deliveries are gone.
+    payloads: Database<BEU64, Bytes>,
+    /// The outcome of the last deliveries of each webhook.
+    statuses: Database<UuidCodec, SerdeJson<WebhookStatus>>,
+    /// Wakes up the delivery thread when new deliveries can be sent.
+    pub(crate) wake_up: Arc<SignalEvent>,
+}
+
+impl WebhookDeliveries {
+    pub(crate) const fn nb_db() -> u32 {
+        NUMBER_OF_DATABASES
+    }
+
+    pub fn new(env: &Env<WithoutTls>, wtxn: &mut RwTxn) -> Result<Self> {
+        Ok(Self {
+            deliveries: env.create_database(wtxn, Some(db_name::WEBHOOK_DELIVERIES))?,
+            payloads: env.create_database(wtxn, Some(db_name::WEBHOOK_PAYLOADS))?,
+            statuses: env.create_database(wtxn, Some(db_name::WEBHOOK_STATUSES))?,
+            wake_up: Arc::new(SignalEvent::auto(true)),
+        })
+    }
+
+    /// Stores the payload and enqueues one delivery of it for each webhook.
+    pub fn enqueue(
+        &self,
+        wtxn: &mut RwTxn,
+        webhooks: impl IntoIterator<Item = Uuid>,
+        nb_tasks: u64,
+        payload: &[u8],
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        // The deliveries of the previous payloads use lower ids, even once the last payload is removed
+        let payload_id = self.payloads.last(wtxn)?.map_or(0, |(id, _)| id + 1);
+        self.payloads.put(wtxn, &payload_id, payload)?;
+
+        for webhook in webhooks {
+            let delivery = WebhookDelivery {
+                webhook,
+                payload: payload_id,
+                nb_tasks,
+                enqueued_at: now,
+                attempts: 0,
+                next_attempt_at: now,
+                last_error: None,
+                failed: false,
+            };
+            self.deliveries.put(wtxn, &(payload_id, webhook), &delivery)?;
+        }
+        self.purge_failed(wtxn, now)
+    }
+
+    /// Returns the deliveries to attempt now, at most one per webhook to keep them in order,
+    /// and the date of the next attempt that is not due yet.
+    pub fn due(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<(Vec<WebhookDelivery>, Option<OffsetDateTime>)> {
+        let mut due = Vec::new();
+        let mut blocked = Vec::new();
+        let mut next_attempt_at: Option<OffsetDateTime> = None;
+
+        for entry in self.deliveries.iter(rtxn)? {
+            let (_, delivery) = entry?;
+            if delivery.failed || blocked.contains(&delivery.webhook) {
+                continue;
+            }
+            // the following deliveries of this webhook wait for this one
+            blocked.push(delivery.webhook);
+            if delivery.next_attempt_at <= now {
+                due.push(delivery);
+            } else {
+                next_attempt_at = Some(match next_attempt_at {
+                    Some(next) => next.min(delivery.next_attempt_at),
+                    None => delivery.next_attempt_at,
+                });
+            }
+        }
+
+        Ok((due, next_attempt_at))
+    }
+
+    pub fn payload<'t>(&self, rtxn: &'t RoTxn, payload_id: u64) -> Result<Option<&'t [u8]>> {
+        Ok(self.payloads.get(rtxn, &payload_id)?)
+    }
+
+    /// Removes the delivery and records the success in the status of its webhook.
+    pub fn succeeded(
+        &self,
+        wtxn: &mut RwTxn,
+        delivery: &WebhookDelivery,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        self.remove(wtxn, delivery)?;
+        let mut status = self.statuses.get(wtxn, &delivery.webhook)?.unwrap_or_default();
+        status.last_success_at = Some(now);
+        self.statuses.put(wtxn, &delivery.webhook, &status)?;
+        Ok(())
+    }
+
+    /// Schedules the next attempt of the delivery, or marks it as failed if it was its last attempt.
+    pub fn failed(
+        &self,
+        wtxn: &mut RwTxn,
+        mut delivery: WebhookDelivery,
+        error: String,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        delivery.attempts += 1;
+        delivery.failed = delivery.attempts >= MAX_DELIVERY_ATTEMPTS;
+        delivery.next_attempt_at = now + retry_delay(delivery.attempts);
+        delivery.last_error = Some(error.clone());
+        self.deliveries.put(wtxn, &(delivery.payload, delivery.webhook), &delivery)?;
+
+        let mut status = self.statuses.get(wtxn, &delivery.webhook)?.unwrap_or_default();
+        status.last_error_at = Some(now);
+        status.last_error = Some(error);
+        self.statuses.put(wtxn, &delivery.webhook, &status)?;
+
+        if delivery.failed {
+            self.purge_failed(wtxn, now)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the delivery, and its payload if no other delivery uses it.
+    pub fn remove(&self, wtxn: &mut RwTxn, delivery: &WebhookDelivery) -> Result<()> {
+        self.deliveries.delete(wtxn, &(delivery.payload, delivery.webhook))?;
+        let payload_used = self
+            .deliveries
+            .remap_types::<Bytes, DecodeIgnore>()
+            .prefix_iter(wtxn, &delivery.payload.to_be_bytes())?
+            .next()
+            .transpose()?
+            .is_some();
+        if !payload_used {
+            self.payloads.delete(wtxn, &delivery.payload)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the failed deliveries enqueued more than [`FAILED_DELIVERY_RETENTION`] ago, and the
+    /// oldest failed deliveries of the webhooks with more than [`MAX_FAILED_DELIVERIES`].
+    fn purge_failed(&self, wtxn: &mut RwTxn, now: OffsetDateTime) -> Result<()> {
+        let mut to_remove = Vec::new();
+        let mut failed_per_webhook: HashMap<Uuid, usize> = HashMap::new();
+        for entry in self.deliveries.rev_iter(wtxn)? {
+            let (_, delivery) = entry?;
+            if !delivery.failed {
+                continue;
+            }
+            let failed = failed_per_webhook.entry(delivery.webhook).or_default();
+            *failed += 1;
+            if *failed > MAX_FAILED_DELIVERIES
+                || delivery.enqueued_at + FAILED_DELIVERY_RETENTION < now
+            {
+                to_remove.push(delivery);
+            }
+        }
+        for delivery in to_remove {
+            self.remove(wtxn, &delivery)?;
+        }
+        Ok(())
+    }
+
+    /// Removes all the deliveries and the status of the webhooks that are not in the list.
+    pub fn retain_webhooks(&self, wtxn: &mut RwTxn, webhooks: &[Uuid]) -> Result<()> {
+        let mut to_remove = Vec::new();
+        for entry in self.deliveries.iter(wtxn)? {
+            let (_, delivery) = entry?;
+            if !webhooks.contains(&delivery.webhook) {
+                to_remove.push(delivery);
+            }
+        }
+        for delivery in to_remove {
+            self.remove(wtxn, &delivery)?;
+        }
+
+        let mut statuses_to_remove = Vec::new();
+        for entry in self.statuses.iter(wtxn)? {
+            let (webhook, _) = entry?;
+            if !webhooks.contains(&webhook) {
+                statuses_to_remove.push(webhook);
+            }
+        }
+        for webhook in statuses_to_remove {
+            self.statuses.delete(wtxn, &webhook)?;
+        }
+        Ok(())
+    }
+
+    /// Schedules the failed deliveries of the webhook to be attempted again, and returns how many there are.
+    pub fn replay(&self, wtxn: &mut RwTxn, webhook: Uuid, now: OffsetDateTime) -> Result<u64> {
+        let mut replayed = Vec::new();
+        for entry in self.deliveries.iter(wtxn)? {
+            let (_, delivery) = entry?;
+            if delivery.webhook == webhook && delivery.failed {
+                replayed.push(delivery);
+            }
+        }
+
+        let count = replayed.len() as u64;
+        for mut delivery in replayed {
+            delivery.failed = false;
+            delivery.attempts = 0;
+            delivery.next_attempt_at = now;
+            self.deliveries.put(wtxn, &(delivery.payload, delivery.webhook), &delivery)?;
+        }
+        Ok(count)
+    }
+
+    pub fn status(&self, rtxn: &RoTxn, webhook: Uuid) -> Result<WebhookDeliveryStatus> {
+        let WebhookStatus { last_success_at, last_error_at, last_error } =
+            self.statuses.get(rtxn, &webhook)?.unwrap_or_default();
+
+        let mut pending = 0;
+        let mut failed = 0;
+        for entry in self.deliveries.iter(rtxn)? {
+            let (_, delivery) = entry?;
+            if delivery.webhook == webhook {
+                if delivery.failed {
+                    failed += 1;
+                } else {
+                    pending += 1;
+                }
+            }
+        }
+
+        Ok(WebhookDeliveryStatus { pending, failed, last_success_at, last_error_at, last_error })
+    }
+}
+
+/// The delay before the next attempt of a delivery that failed `attempts` times.
+fn retry_delay(attempts: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    BASE_RETRY_DELAY.saturating_mul(factor).min(MAX_RETRY_DELAY)
+}
+
+/// Returns the hex encoded HMAC-SHA256 of `{timestamp}.{payload}` keyed with the secret of the webhook.
+///
+/// The payload is the gzipped body exactly as it is sent.
+pub fn sign_payload(secret: &str, timestamp: i64, payload: &[u8]) -> String {
+    // new_from_slice function never fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+
+    let result = mac.finalize();
+    format!("{:x}", result.into_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use meilisearch_types::heed::{self, Env, WithoutTls};
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::{
+        retry_delay, sign_payload, WebhookDeliveries, FAILED_DELIVERY_RETENTION,
+        MAX_DELIVERY_ATTEMPTS, MAX_FAILED_DELIVERIES, MAX_RETRY_DELAY,
+    };
+
+    fn deliveries() -> (tempfile::TempDir, Env<WithoutTls>, WebhookDeliveries) {
+        let dir = tempfile::tempdir().unwrap();
+        let env = unsafe {
+            heed::EnvOpenOptions::new()
+                .read_txn_without_tls()
+                .max_dbs(WebhookDeliveries::nb_db())
+                .open(dir.path())
+        }
+        .unwrap();
+        let mut wtxn = env.write_txn().unwrap();
+        let deliveries = WebhookDeliveries::new(&env, &mut wtxn).unwrap();
+        wtxn.commit().unwrap();
+        (dir, env, deliveries)
+    }
+
+    /// Enqueues a payload for the webhooks and fails all its deliveries.
+    fn fail_payload(
+        env: &Env<WithoutTls>,
+        deliveries: &WebhookDeliveries,
+        webhooks: &[Uuid],
+        now: OffsetDateTime,
+    ) {
+        let mut wtxn = env.write_txn().unwrap();
+        deliveries.enqueue(&mut wtxn, webhooks.iter().copied(), 1, b"payload", now).unwrap();
+        for _ in 0..MAX_DELIVERY_ATTEMPTS {
+            let (due, _) =
+                deliveries.due(&wtxn, OffsetDateTime::now_utc() + MAX_RETRY_DELAY).unwrap();
+            for delivery in due {
+                deliveries.failed(&mut wtxn, delivery, String::from("unreachable"), now).unwrap();
+            }
+        }
+        wtxn.commit().unwrap();
+    }
+
+    #[test]
+    fn payload_removed_with_its_last_delivery() {
+        let (_dir, env, deliveries) = deliveries();
+        let (doggo, catto) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = OffsetDateTime::now_utc();
+
+        let mut wtxn = env.write_txn().unwrap();
+        deliveries.enqueue(&mut wtxn, [doggo, catto], 1, b"first", now).unwrap();
+        deliveries.enqueue(&mut wtxn, [doggo], 1, b"second", now).unwrap();
+
+        // the deliveries of a webhook are sent in order
+        let (due, _) = deliveries.due(&wtxn, now).unwrap();
+        let due: Vec<_> = due.iter().map(|delivery| (delivery.webhook, delivery.payload)).collect();
+        assert_eq!(due, [(doggo, 0), (catto, 0)]);
+
+        let (due, _) = deliveries.due(&wtxn, now).unwrap();
+        deliveries.succeeded(&mut wtxn, &due[0], now).unwrap();
+        assert!(deliveries.payload(&wtxn, 0).unwrap().is_some());
+        deliveries.succeeded(&mut wtxn, &due[1], now).unwrap();
+        assert!(deliveries.payload(&wtxn, 0).unwrap().is_none());
+        assert_eq!(deliveries.payload(&wtxn, 1).unwrap(), Some(&b"second"[..]));
+
+        let (due, _) = deliveries.due(&wtxn, now).unwrap();
+        let due: Vec<_> = due.iter().map(|delivery| (delivery.webhook, delivery.payload)).collect();
+        assert_eq!(due, [(doggo, 1)]);
+    }
+
+    #[test]
+    fn failed_deliveries_are_capped() {
+        let (_dir, env, deliveries) = deliveries();
+        let (doggo, catto) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = OffsetDateTime::now_utc();
+
+        fail_payload(&env, &deliveries, &[doggo, catto], now);
+        for _ in 0..MAX_FAILED_DELIVERIES {
+            fail_payload(&env, &deliveries, &[doggo], now);
+        }
+
+        // the oldest failed delivery of the doggo is gone, its payload is still used by the catto
+        let rtxn = env.read_txn().unwrap();
+        let status = deliveries.status(&rtxn, doggo).unwrap();
+        assert_eq!(status.failed, MAX_FAILED_DELIVERIES as u64);
+        let status = deliveries.status(&rtxn, catto).unwrap();
+        assert_eq!(status.failed, 1);
+        assert!(deliveries.payload(&rtxn, 0).unwrap().is_some());
+        drop(rtxn);
+
+        let mut wtxn = env.write_txn().unwrap();
+        deliveries.retain_webhooks(&mut wtxn, &[doggo]).unwrap();
+        assert!(deliveries.payload(&wtxn, 0).unwrap().is_none());
+        assert!(deliveries.payload(&wtxn, 1).unwrap().is_some());
+    }
+
+    #[test]
+    fn failed_deliveries_expire() {
+        let (_dir, env, deliveries) = deliveries();
+        let doggo = Uuid::new_v4();
+        let now = OffsetDateTime::now_utc();
+
+        fail_payload(&env, &deliveries, &[doggo], now - FAILED_DELIVERY_RETENTION);
+        fail_payload(&env, &deliveries, &[doggo], now);
+
+        // the failed deliveries are purged when new payloads are enqueued
+        let mut wtxn = env.write_txn().unwrap();
+        let status = deliveries.status(&wtxn, doggo).unwrap();
+        assert_eq!(status.failed, 2);
+        let later = now + Duration::from_secs(1);
+        deliveries.enqueue(&mut wtxn, [doggo], 1, b"payload", later).unwrap();
+        let status = deliveries.status(&wtxn, doggo).unwrap();
+        assert_eq!((status.failed, status.pending), (1, 1));
+        assert!(deliveries.payload(&wtxn, 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(5), Duration::from_secs(16));
+        assert_eq!(retry_delay(20), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn payload_signature() {
+        assert_eq!(
+            sign_payload("a-secret", 1700000000, b"hello"),
+            "c3cd9c6260c831eacde89cde5288ff0e9aaec85add722aaf4b14598355fb2a15"
+        );
+    }
+}
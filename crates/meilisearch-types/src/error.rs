@@ -273,6 +273,12 @@ InvalidMultiSearchFederationOptions            , InvalidRequest       , BAD_REQU
 InvalidMultiSearchMaxValuesPerFacet            , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchMergeFacets                  , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryFacets                  , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryDisjunctiveFacets       , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryFacetAggregations       , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryGroupBy                 , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryGeoGrid                 , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryExplain                 , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQuerySearchAfter             , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchDistinct                     , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryPagination              , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryRankingRules            , InvalidRequest       , BAD_REQUEST ;
@@ -286,8 +292,10 @@ InvalidNetworkSelf                             , InvalidRequest       , BAD_REQU
 InvalidNetworkSearchApiKey                     , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkWriteApiKey                      , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkUrl                              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchAttributeWeights                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToSearchOn              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToCrop                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchAttributesToSnippet               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToHighlight             , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarAttributesToRetrieve             , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarRetrieveVectors                  , InvalidRequest       , BAD_REQUEST ;
@@ -298,6 +306,12 @@ InvalidSearchRetrieveVectors                   , InvalidRequest       , BAD_REQU
 InvalidSearchCropLength                        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker                        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                            , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetAggregations                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGroupBy                           , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGeoGrid                           , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchExplain                           , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSearchAfter                       , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                           , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchExhaustiveFacetCount         , InvalidRequest       , BAD_REQUEST ;
@@ -309,12 +323,16 @@ InvalidSearchHighlightPostTag                  , InvalidRequest       , BAD_REQU
 InvalidSearchHighlightPreTag                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHitsPerPage                       , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarLimit                            , InvalidRequest       , BAD_REQUEST ;
+InvalidSimilarMode                             , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLimit                             , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarOffset                           , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                            , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchQuerySyntax                       , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchTypoTolerance                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchUseSynonyms                       , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery                        , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                         , InvalidRequest       , BAD_REQUEST ;
 FacetSearchDisabled                            , InvalidRequest       , BAD_REQUEST ;
@@ -328,11 +346,13 @@ InvalidSearchShowPerformanceDetails            , InvalidRequest       , BAD_REQU
 InvalidSearchUseNetwork                        , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails          , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowPerformanceDetails           , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSnippetCount                      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPersonalize                       , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPersonalizeUserContext            , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMediaAndVector                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSemanticQueries                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision              , InvalidRequest       , BAD_REQUEST ;
@@ -361,17 +381,29 @@ InvalidStoreFile                               , Internal             , INTERNAL
 InvalidSwapDuplicateIndexFound                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapIndexes                             , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapRename                              , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskAbortProcessing                     , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterEnqueuedAt                     , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterFinishedAt                     , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskAfterRunAt                          , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterStartedAt                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeEnqueuedAt                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeFinishedAt                    , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskBeforeRunAt                         , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeStartedAt                     , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskCanceledBy                          , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskFrom                                , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskLimit                               , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskReverse                             , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskStatuses                            , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskPriority                            , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskRetentionFailedTasksDays            , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskRetentionKeepLastTasksPerIndex      , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskRetentionSucceededTasksDays         , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskRunAt                               , InvalidRequest       , BAD_REQUEST ;
+InvalidTransactionOperations                   , InvalidRequest       , BAD_REQUEST ;
+TransactionAborted                             , InvalidRequest       , BAD_REQUEST ;
+InvalidIdempotencyKey                          , InvalidRequest       , BAD_REQUEST ;
+IdempotencyKeyMismatch                         , InvalidRequest       , UNPROCESSABLE_ENTITY ;
 InvalidTaskTypes                               , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskUids                                , InvalidRequest       , BAD_REQUEST  ;
 InvalidBatchUids                               , InvalidRequest       , BAD_REQUEST  ;
@@ -473,6 +505,8 @@ TemplateRenderingError                         , InvalidRequest       , BAD_REQU
 InvalidWebhooks                                , InvalidRequest       , BAD_REQUEST ;
 InvalidWebhookUrl                              , InvalidRequest       , BAD_REQUEST ;
 InvalidWebhookHeaders                          , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookFilters                          , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookSecret                           , InvalidRequest       , BAD_REQUEST ;
 ImmutableWebhook                               , InvalidRequest       , BAD_REQUEST ;
 InvalidWebhookUuid                             , InvalidRequest       , BAD_REQUEST ;
 WebhookNotFound                                , InvalidRequest       , NOT_FOUND ;
@@ -553,9 +587,13 @@ impl ErrorCode for milli::Error {
                 UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
                 UserError::InvalidSearchSortableAttribute { .. } => Code::InvalidSearchSort,
                 UserError::InvalidDocumentSortableAttribute { .. } => Code::InvalidDocumentSort,
+                UserError::InvalidSearchCursor => Code::InvalidSearchSearchAfter,
+                UserError::TooManyFacetBuckets { .. } => Code::InvalidSearchFacetAggregations,
+                UserError::TooManyGeoGridCells { .. } => Code::InvalidSearchGeoGrid,
                 UserError::InvalidSearchableAttribute { .. } => {
                     Code::InvalidSearchAttributesToSearchOn
                 }
+                UserError::InvalidSearchQueryAttribute { .. } => Code::InvalidSearchQ,
                 UserError::InvalidFacetSearchFacetName { .. } => Code::InvalidFacetSearchFacetName,
                 UserError::CriterionError(_) | UserError::MixedAttributeRankingRulesUsage => {
                     Code::InvalidSettingsRankingRules
@@ -688,6 +726,12 @@ impl fmt::Display for deserr_codes::InvalidSearchSemanticRatio {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchSemanticQueries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the value of `semanticQueries` is invalid, expected at least one query.")
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidMultiSearchWeight {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "the value of `weight` is invalid, expected a positive float (>= 0.0).")
@@ -25,6 +25,7 @@ pub mod locales;
 pub mod network;
 pub mod settings;
 pub mod star_or;
+pub mod task_retention;
 pub mod task_view;
 pub mod tasks;
 pub mod versioning;
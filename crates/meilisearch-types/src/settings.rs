@@ -15,7 +15,7 @@ pub use milli::update::ChatSettings;
 use milli::update::Setting;
 use milli::vector::db::IndexEmbeddingConfig;
 use milli::{
-    Criterion, CriterionError, FilterableAttributesRule, ForeignKey, Index,
+    Criterion, CriterionError, DecayRule, FilterableAttributesRule, ForeignKey, Index,
     DEFAULT_VALUES_PER_FACET,
 };
 use serde::{Deserialize, Serialize, Serializer};
@@ -1162,6 +1162,9 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Blends the relevancy with a score computed from the numeric value of the field specified,
+    /// e.g. `release_date:gauss(now, 86400)` or `popularity:log(0.5)`.
+    Decay(#[schema(value_type = String)] DecayRule),
 }
 
 impl Serialize for RankingRuleView {
@@ -1222,6 +1225,7 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Decay(x) => RankingRuleView::Decay(x),
         }
     }
 }
@@ -1238,6 +1242,7 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Decay(x) => Criterion::Decay(x),
         }
     }
 }
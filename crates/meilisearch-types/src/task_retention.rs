@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The finished tasks periodically deleted from the task queue by the scheduler.
+///
+/// The tasks are only deleted when a number of days is defined for their status.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct TaskRetentionPolicy {
+    /// Number of days the succeeded tasks are kept once finished.
+    #[serde(default)]
+    pub succeeded_tasks_days: Option<u32>,
+    /// Number of days the failed tasks are kept once finished.
+    #[serde(default)]
+    pub failed_tasks_days: Option<u32>,
+    /// Number of the most recent finished tasks of each index that are kept whatever their age.
+    #[serde(default)]
+    pub keep_last_tasks_per_index: Option<u32>,
+}
+
+impl TaskRetentionPolicy {
+    /// The maximum number of days the tasks can be kept, about a hundred years.
+    pub const MAX_DAYS: u32 = 36_500;
+
+    /// Returns `true` if the policy never deletes any task.
+    pub fn is_disabled(&self) -> bool {
+        self.succeeded_tasks_days.is_none() && self.failed_tasks_days.is_none()
+    }
+}
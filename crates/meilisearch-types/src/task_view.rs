@@ -11,7 +11,8 @@ use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
 use crate::tasks::network::DbTaskNetwork;
 use crate::tasks::{
-    serialize_duration, Details, DetailsExportIndexSettings, IndexSwap, Kind, Status, Task, TaskId,
+    serialize_duration, Details, DetailsExportIndexSettings, IndexSwap, Kind, Priority, Status,
+    Task, TaskId,
 };
 
 /// Represents the current state and details of an asynchronous task.
@@ -103,6 +104,27 @@ pub struct TaskView {
     /// track task origins, or add any application-specific information.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_metadata: Option<String>,
+    /// Priority given to this task when it was created. Only present when
+    /// it differs from the `normal` priority.
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
+    /// Date before which this task can't be processed. Only present when
+    /// the task was enqueued with a `runAt` date.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, example = json!("2024-08-08T16:37:09.971Z"))]
+    pub run_at: Option<OffsetDateTime>,
+    /// Uid of the first task of the transaction this task was enqueued in,
+    /// only present when the task is part of a transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_uid: Option<TaskId>,
+    /// Idempotency key the task was enqueued with, only present when the
+    /// request contained an `Idempotency-Key` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 impl TaskView {
@@ -122,6 +144,10 @@ impl TaskView {
             finished_at: task.finished_at,
             network: task.network.clone(),
             custom_metadata: task.custom_metadata.clone(),
+            priority: task.priority,
+            run_at: task.run_at,
+            transaction_uid: task.transaction_uid,
+            idempotency_key: task.idempotency_key.clone(),
         }
     }
 }
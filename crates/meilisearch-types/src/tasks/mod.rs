@@ -52,6 +52,27 @@ pub struct Task {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_metadata: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
+
+    /// The task is not eligible for batching before this date.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub run_at: Option<OffsetDateTime>,
+
+    /// The uid of the first task of the transaction this task is part of.
+    /// All the tasks of a transaction are committed together or not at all.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_uid: Option<TaskId>,
+
+    /// The key given by the client in the `Idempotency-Key` header when enqueuing this task.
+    /// Retrying a request with the same key returns this task instead of enqueuing a new one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 impl Task {
@@ -581,6 +602,13 @@ impl Display for Status {
     }
 }
 
+impl Status {
+    /// Whether the task is done, its status can't change anymore.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Status::Succeeded | Status::Failed | Status::Canceled)
+    }
+}
+
 impl FromStr for Status {
     type Err = ParseTaskStatusError;
 
@@ -618,6 +646,83 @@ impl fmt::Display for ParseTaskStatusError {
 }
 impl std::error::Error for ParseTaskStatusError {}
 
+/// The priority of a task.
+///
+/// When picking the index of the next batch, the indexes whose next task has
+/// the highest priority go first.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Sequence,
+    PartialOrd,
+    Ord,
+    ToSchema,
+)]
+#[schema(example = json!(Priority::High))]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    pub fn is_normal(&self) -> bool {
+        *self == Priority::Normal
+    }
+}
+
+impl Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Priority::Low => write!(f, "low"),
+            Priority::Normal => write!(f, "normal"),
+            Priority::High => write!(f, "high"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = ParseTaskPriorityError;
+
+    fn from_str(priority: &str) -> Result<Self, Self::Err> {
+        if priority.eq_ignore_ascii_case("low") {
+            Ok(Priority::Low)
+        } else if priority.eq_ignore_ascii_case("normal") {
+            Ok(Priority::Normal)
+        } else if priority.eq_ignore_ascii_case("high") {
+            Ok(Priority::High)
+        } else {
+            Err(ParseTaskPriorityError(priority.to_owned()))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseTaskPriorityError(pub String);
+impl fmt::Display for ParseTaskPriorityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid task priority. Available priorities are {}.",
+            self.0,
+            enum_iterator::all::<Priority>()
+                .map(|p| format!("`{p}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+impl std::error::Error for ParseTaskPriorityError {}
+
 /// The type of the task.
 #[derive(
     Debug,
@@ -954,6 +1059,9 @@ pub enum BatchStopReason {
     SettingsWithDsrUpdate {
         id: TaskId,
     },
+    Transaction {
+        id: TaskId,
+    },
 }
 
 impl BatchStopReason {
@@ -1060,6 +1168,12 @@ impl Display for BatchStopReason {
             BatchStopReason::SettingsWithDsrUpdate { id } => {
                 write!(f, "stopped before task with id {id} because it is a dynamic search rule update or clear, which cannot be batched with settings changes")
             }
+            BatchStopReason::Transaction { id } => {
+                write!(
+                    f,
+                    "batched all the tasks of transaction {id}, which must be committed together"
+                )
+            }
         }
     }
 }
@@ -1,18 +1,74 @@
 use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::index_uid_pattern::IndexUidPattern;
+use crate::tasks::{Kind, Status, Task};
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Webhook {
     pub url: String,
     #[serde(default)]
     pub headers: BTreeMap<String, String>,
+    /// Restricts the tasks sent to the webhook.
+    #[serde(default, skip_serializing_if = "WebhookFilters::is_empty")]
+    pub filters: WebhookFilters,
+    /// Shared secret used to sign the payloads sent to the webhook.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// The tasks a webhook is notified of, every task matches the filters that are `None`.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookFilters {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<Kind>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statuses: Option<Vec<Status>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_uids: Option<Vec<IndexUidPattern>>,
+}
+
+impl WebhookFilters {
+    pub fn is_empty(&self) -> bool {
+        self.types.is_none() && self.statuses.is_none() && self.index_uids.is_none()
+    }
+
+    /// Returns `true` if the task must be sent to the webhook.
+    ///
+    /// A task concerning several indexes matches if any of them matches the index uid patterns,
+    /// and a task that doesn't concern any index never matches them.
+    pub fn matches(&self, task: &Task) -> bool {
+        let Self { types, statuses, index_uids } = self;
+
+        if types.as_ref().is_some_and(|types| !types.contains(&task.kind.as_kind())) {
+            return false;
+        }
+        if statuses.as_ref().is_some_and(|statuses| !statuses.contains(&task.status)) {
+            return false;
+        }
+        if let Some(patterns) = index_uids {
+            let indexes = task.indexes();
+            if !patterns.iter().any(|pattern| indexes.iter().any(|uid| pattern.matches_str(uid))) {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 impl Webhook {
+    /// Hides the values of the authorization headers and the signing secret.
     pub fn redact_authorization_header(&mut self) {
+        if let Some(secret) = &mut self.secret {
+            crate::settings::hide_secret(secret, 0);
+        }
+
         // headers are case insensitive, so to make the redaction robust we iterate over qualifying headers
         // rather than getting one canonical `Authorization` header.
         for value in self
@@ -44,3 +100,27 @@ pub struct WebhooksDumpView {
     #[serde(default)]
     pub webhooks: BTreeMap<Uuid, Webhook>,
 }
+
+/// State of the deliveries of the task notifications to a webhook.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookDeliveryStatus {
+    /// Number of notifications waiting to be delivered, or to be retried.
+    pub pending: u64,
+    /// Number of notifications that could not be delivered after all the attempts.
+    ///
+    /// They are kept until they are replayed or the webhook is deleted, for a week at most,
+    /// and only the last 100 of them.
+    pub failed: u64,
+    /// Date of the last successful delivery.
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[schema(value_type = Option<String>)]
+    pub last_success_at: Option<OffsetDateTime>,
+    /// Date of the last failed delivery attempt.
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[schema(value_type = Option<String>)]
+    pub last_error_at: Option<OffsetDateTime>,
+    /// Error of the last failed delivery attempt.
+    pub last_error: Option<String>,
+}
@@ -201,6 +201,9 @@ struct Infos {
     experimental_reduce_indexing_memory_usage: bool,
     experimental_max_number_of_batched_tasks: usize,
     experimental_limit_batched_tasks_total_size: Option<u64>,
+    experimental_max_concurrent_batches: usize,
+    experimental_fair_index_scheduling: bool,
+    experimental_idempotency_key_retention_secs: u64,
     experimental_network: bool,
     experimental_multimodal: bool,
     experimental_chat_completions: bool,
@@ -263,6 +266,9 @@ impl Infos {
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
+            experimental_max_concurrent_batches,
+            experimental_fair_index_scheduling,
+            experimental_idempotency_key_retention_secs,
             experimental_embedding_cache_entries,
             experimental_allowed_ip_networks,
             http_addr,
@@ -371,6 +377,9 @@ impl Infos {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size:
                 experimental_limit_batched_tasks_total_size.map(|size| size.as_u64()),
+            experimental_max_concurrent_batches,
+            experimental_fair_index_scheduling,
+            experimental_idempotency_key_retention_secs,
             task_queue_webhook: task_webhook_url.is_some(),
             task_webhook_authorization_header: task_webhook_authorization_header.is_some(),
             log_level: log_level.to_string(),
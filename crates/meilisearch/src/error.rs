@@ -41,6 +41,18 @@ pub enum MeilisearchHttpError {
     PaginationInFederatedQuery(&'static str),
     #[error("Using facet options is not allowed in federated queries.\n - Hint: remove `facets` from the query or remove `federation` from the request\n - Hint: pass `federation.facetsByIndex.{0}: {1:?}` for facets in federated search")]
     FacetsInFederatedQuery(String, AttributePatterns),
+    #[error("Using `.disjunctiveFacets` is not allowed in federated queries.\n - Hint: remove `disjunctiveFacets` from the query or remove `federation` from the request")]
+    DisjunctiveFacetsInFederatedQuery,
+    #[error("Using `.facetAggregations` is not allowed in federated queries.\n - Hint: remove `facetAggregations` from the query or remove `federation` from the request")]
+    FacetAggregationsInFederatedQuery,
+    #[error("Using `.geoGrid` is not allowed in federated queries.\n - Hint: remove `geoGrid` from the query or remove `federation` from the request")]
+    GeoGridInFederatedQuery,
+    #[error("Using `.explain` is not allowed in federated queries.\n - Hint: remove `explain` from the query or remove `federation` from the request")]
+    ExplainInFederatedQuery,
+    #[error("Using `.groupBy` is not allowed in federated queries.\n - Hint: remove `groupBy` from the query or remove `federation` from the request")]
+    GroupByInFederatedQuery,
+    #[error("Using `.searchAfter` is not allowed in federated queries.\n - Hint: remove `searchAfter` from the query or remove `federation` from the request")]
+    SearchAfterInFederatedQuery,
     #[error("Using `.personalize` is not allowed in federated queries.\n - Hint: remove `personalize` from the query or remove `federation` from the request\n - Hint: pass `federation.personalize` for personalization in federated search")]
     PersonalizationInFederatedQuery,
     #[error("Using `.showPerformanceDetails` is not allowed in federated queries.\n - Hint: remove `showPerformanceDetails` from the query or remove `federation` from the request")]
@@ -92,6 +104,12 @@ pub enum MeilisearchHttpError {
     MissingSearchHybrid,
     #[error("Invalid request: both `media` and `vector` parameters are present.")]
     MediaAndVector,
+    #[error("Invalid request: `semanticQueries` cannot be used together with the `vector` or `media` parameters.")]
+    SemanticQueriesAndVectorOrMedia,
+    #[error(
+        "Invalid request: the weighted embeddings of `semanticQueries` cancel each other out."
+    )]
+    SemanticQueriesCancelOut,
     #[error("Inconsistent `Origin` headers: {} was provided but {} is missing.\n  - Hint: Either both headers should be provided, or none of them", if *is_remote_missing {
         PROXY_ORIGIN_TASK_UID_HEADER
     } else { PROXY_ORIGIN_REMOTE_HEADER },
@@ -191,6 +209,8 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::Join(_) => Code::Internal,
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
             MeilisearchHttpError::MediaAndVector => Code::InvalidSearchMediaAndVector,
+            MeilisearchHttpError::SemanticQueriesAndVectorOrMedia
+            | MeilisearchHttpError::SemanticQueriesCancelOut => Code::InvalidSearchSemanticQueries,
             MeilisearchHttpError::FederationOptionsInNonFederatedRequest
             | MeilisearchHttpError::RemoteAndUseNetwork => {
                 Code::InvalidMultiSearchFederationOptions
@@ -202,6 +222,18 @@ impl ErrorCode for MeilisearchHttpError {
                 Code::InvalidMultiSearchDistinct
             }
             MeilisearchHttpError::FacetsInFederatedQuery(..) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::DisjunctiveFacetsInFederatedQuery => {
+                Code::InvalidMultiSearchQueryDisjunctiveFacets
+            }
+            MeilisearchHttpError::FacetAggregationsInFederatedQuery => {
+                Code::InvalidMultiSearchQueryFacetAggregations
+            }
+            MeilisearchHttpError::GeoGridInFederatedQuery => Code::InvalidMultiSearchQueryGeoGrid,
+            MeilisearchHttpError::ExplainInFederatedQuery => Code::InvalidMultiSearchQueryExplain,
+            MeilisearchHttpError::GroupByInFederatedQuery => Code::InvalidMultiSearchQueryGroupBy,
+            MeilisearchHttpError::SearchAfterInFederatedQuery => {
+                Code::InvalidMultiSearchQuerySearchAfter
+            }
             MeilisearchHttpError::InconsistentFacetOrder { .. } => {
                 Code::InvalidMultiSearchFacetOrder
             }
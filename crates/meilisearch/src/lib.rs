@@ -321,6 +321,11 @@ pub fn setup_meilisearch(
         max_number_of_tasks: 1_000_000,
         export_default_payload_size_bytes: almost_as_big_as(opt.http_payload_size_limit),
         max_number_of_batched_tasks: opt.experimental_max_number_of_batched_tasks,
+        max_concurrent_batches: opt.experimental_max_concurrent_batches.max(1),
+        fair_index_scheduling: opt.experimental_fair_index_scheduling,
+        idempotency_key_retention: Duration::from_secs(
+            opt.experimental_idempotency_key_retention_secs,
+        ),
         batched_tasks_size_limit: opt.experimental_limit_batched_tasks_total_size.map_or_else(
             || {
                 opt.indexer_options
@@ -338,6 +343,7 @@ pub fn setup_meilisearch(
         embedding_cache_cap: opt.experimental_embedding_cache_entries,
         ip_policy,
         dsr_fuel,
+        search_cursor_secret: opt.master_key.clone(),
     };
     let binary_version = (VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH);
 
@@ -618,10 +624,14 @@ fn import_dump(
         index_scheduler.put_chat_settings(&name, &settings)?;
     }
 
-    // 5. Import the runtime features and network
+    // 5. Import the runtime features, task retention policy and network
     let features = dump_reader.features()?.unwrap_or_default();
     index_scheduler.put_runtime_features(features)?;
 
+    if let Some(policy) = dump_reader.task_retention_policy() {
+        index_scheduler.update_task_retention_policy(policy)?;
+    }
+
     let network = dump_reader.network()?.cloned().unwrap_or_default();
     let wtxn = index_scheduler.env.write_txn()?;
     index_scheduler.put_network(wtxn, network)?;
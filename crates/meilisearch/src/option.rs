@@ -67,6 +67,10 @@ const MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS: &str =
     "MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS";
 const MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE: &str =
     "MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE";
+const MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES: &str = "MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES";
+const MEILI_EXPERIMENTAL_FAIR_INDEX_SCHEDULING: &str = "MEILI_EXPERIMENTAL_FAIR_INDEX_SCHEDULING";
+const MEILI_EXPERIMENTAL_IDEMPOTENCY_KEY_RETENTION_SECS: &str =
+    "MEILI_EXPERIMENTAL_IDEMPOTENCY_KEY_RETENTION_SECS";
 const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES: &str =
     "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES";
 const MEILI_EXPERIMENTAL_PERSONALIZATION_API_KEY: &str =
@@ -479,6 +483,32 @@ pub struct Opt {
     #[serde(default)]
     pub experimental_limit_batched_tasks_total_size: Option<Byte>,
 
+    /// Experimentally sets the maximum number of batches of different indexes that are processed
+    /// at the same time.
+    ///
+    /// Dumps, snapshots, index swaps and the other tasks that don't concern a single index are
+    /// always processed alone. The indexing memory is shared between the concurrent batches.
+    /// `0` is treated as `1`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES, default_value_t = default_max_concurrent_batches())]
+    #[serde(default = "default_max_concurrent_batches")]
+    pub experimental_max_concurrent_batches: usize,
+
+    /// Experimentally makes the indexes take turns when picking the next batch to process,
+    /// instead of always processing the oldest enqueued task first.
+    ///
+    /// This prevents a large backfill on one index from delaying the updates of the other indexes.
+    #[clap(long, env = MEILI_EXPERIMENTAL_FAIR_INDEX_SCHEDULING)]
+    #[serde(default)]
+    pub experimental_fair_index_scheduling: bool,
+
+    /// Experimentally sets for how many seconds a request retried with the same `Idempotency-Key`
+    /// header returns the task it first enqueued, instead of enqueuing a new one.
+    ///
+    /// The default value is 86400, one day.
+    #[clap(long, env = MEILI_EXPERIMENTAL_IDEMPOTENCY_KEY_RETENTION_SECS, default_value_t = default_idempotency_key_retention_secs())]
+    #[serde(default = "default_idempotency_key_retention_secs")]
+    pub experimental_idempotency_key_retention_secs: u64,
+
     /// Enables experimental caching of search query embeddings. The value represents the maximal number of entries in the cache of each
     /// distinct embedder.
     ///
@@ -607,6 +637,9 @@ impl Opt {
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
+            experimental_max_concurrent_batches,
+            experimental_fair_index_scheduling,
+            experimental_idempotency_key_retention_secs,
             experimental_embedding_cache_entries,
             experimental_personalization_api_key,
             experimental_allowed_ip_networks,
@@ -702,6 +735,18 @@ impl Opt {
                 limit.to_string(),
             );
         }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES,
+            experimental_max_concurrent_batches.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_FAIR_INDEX_SCHEDULING,
+            experimental_fair_index_scheduling.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_IDEMPOTENCY_KEY_RETENTION_SECS,
+            experimental_idempotency_key_retention_secs.to_string(),
+        );
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES,
             experimental_embedding_cache_entries.to_string(),
@@ -1269,6 +1314,14 @@ fn default_limit_batched_tasks() -> usize {
     usize::MAX
 }
 
+fn default_max_concurrent_batches() -> usize {
+    1
+}
+
+fn default_idempotency_key_retention_secs() -> u64 {
+    24 * 60 * 60
+}
+
 fn default_embedding_cache_entries() -> usize {
     0
 }
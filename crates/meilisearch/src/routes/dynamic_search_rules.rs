@@ -23,7 +23,10 @@ use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::GuardedData;
 use crate::proxy::{proxy, task_network_and_check_leader_and_version, Body};
 use crate::routes::indexes::documents::CustomMetadataQuery;
-use crate::routes::{Pagination, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
+use crate::routes::{
+    parse_idempotency_key_header, Pagination, PaginationView, SummarizedTaskView,
+    PAGINATION_DEFAULT_LIMIT,
+};
 
 #[routes::routes(
     routes(
@@ -193,6 +196,7 @@ async fn list_rules(
         degraded: _,
         used_negative_operator: _,
         query_vector: _,
+        explanation: _,
     } = dsrs.search_in_description_and_words(query, &rule_ids, limit, offset)?;
 
     let rules = dsrs
@@ -319,13 +323,14 @@ async fn update_or_create_rule(
     let uid = uid.into_inner();
     let rule = body.into_inner();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let mut task = {
         let kind = KindWithContent::DsrUpdate(DsrUpdate::CreateOrUpdate {
             rule_id: uid,
             update: rule.clone(),
         });
-        index_scheduler.register_with_custom_metadata(kind, custom_metadata, task_network)
+        index_scheduler.register_idempotent(kind, custom_metadata, task_network, idempotency_key)
     }?;
 
     if let Some(task_network) = task.network.take() {
@@ -380,6 +385,7 @@ async fn delete_rule(
         .check_dynamic_search_rules("Using the `/dynamic-search-rules` routes")?;
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let CustomMetadataQuery { custom_metadata } = query.into_inner();
 
@@ -387,7 +393,7 @@ async fn delete_rule(
 
     let mut task = {
         let kind = KindWithContent::DsrUpdate(DsrUpdate::Deletion(uid));
-        index_scheduler.register_with_custom_metadata(kind, custom_metadata, task_network)?
+        index_scheduler.register_idempotent(kind, custom_metadata, task_network, idempotency_key)?
     };
 
     if let Some(task_network) = task.network.take() {
@@ -439,12 +445,13 @@ async fn clear_rules(
         .check_dynamic_search_rules("Using the `/dynamic-search-rules` routes")?;
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let CustomMetadataQuery { custom_metadata } = query.into_inner();
 
     let mut task = {
         let kind = KindWithContent::DsrClear;
-        index_scheduler.register_with_custom_metadata(kind, custom_metadata, task_network)?
+        index_scheduler.register_idempotent(kind, custom_metadata, task_network, idempotency_key)?
     };
 
     if let Some(task_network) = task.network.take() {
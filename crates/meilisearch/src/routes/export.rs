@@ -21,7 +21,7 @@ use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::GuardedData;
 use crate::routes::export_analytics::ExportAnalytics;
-use crate::routes::SummarizedTaskView;
+use crate::routes::{parse_idempotency_key_header, SummarizedTaskView};
 
 #[routes::routes(
     routes(
@@ -63,6 +63,7 @@ async fn export(
 ) -> Result<HttpResponse, ResponseError> {
     let export = export.into_inner();
     debug!(returns = ?export, "Trigger export");
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let analytics_aggregate = ExportAnalytics::from_export(&export);
 
@@ -87,8 +88,11 @@ async fn export(
         payload_size: payload_size.map(|ByteWithDeserr(bytes)| bytes),
         indexes,
     };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_idempotent(task, None, None, idempotency_key)
+    })
+    .await??
+    .into();
 
     analytics.publish(analytics_aggregate, &req);
 
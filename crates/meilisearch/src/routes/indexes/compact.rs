@@ -10,7 +10,7 @@ use tracing::debug;
 use super::ActionPolicy;
 use crate::analytics::Analytics;
 use crate::extractors::authentication::GuardedData;
-use crate::routes::SummarizedTaskView;
+use crate::routes::{parse_idempotency_key_header, SummarizedTaskView};
 
 #[routes::routes(
     routes(""=>post(compact)),
@@ -62,11 +62,16 @@ pub async fn compact(
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     analytics.publish(IndexCompacted::default(), &req);
 
     let task = KindWithContent::IndexCompaction { index_uid: index_uid.to_string() };
-    let task = match tokio::task::spawn_blocking(move || index_scheduler.register(task)).await? {
+    let task = match tokio::task::spawn_blocking(move || {
+        index_scheduler.register_idempotent(task, None, None, idempotency_key)
+    })
+    .await?
+    {
         Ok(task) => task,
         Err(e) => return Err(e.into()),
     };
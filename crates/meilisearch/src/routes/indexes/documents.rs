@@ -58,6 +58,7 @@ use crate::extractors::payload::Payload;
 use crate::proxy::{proxy, task_network_and_check_leader_and_version, Body};
 use crate::routes::indexes::search::fix_sort_query_parameters;
 use crate::routes::{
+    parse_idempotency_key_header, parse_task_priority_header, parse_task_run_at_header,
     PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT, PAGINATION_DEFAULT_LIMIT_FN,
 };
 use crate::search::federated::weighted_scores;
@@ -411,6 +412,9 @@ pub async fn delete_document(
     let index_uid = IndexUid::try_from(index_uid)?;
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let priority = parse_task_priority_header(&req)?;
+    let run_at = parse_task_run_at_header(&req)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     analytics.publish(
         DocumentsDeletionAggregator {
@@ -429,7 +433,14 @@ pub async fn delete_document(
     let mut task = {
         let index_scheduler = index_scheduler.clone();
         tokio::task::spawn_blocking(move || {
-            index_scheduler.register_with_custom_metadata(task, custom_metadata, task_network)
+            index_scheduler.register_scheduled(
+                task,
+                custom_metadata,
+                priority,
+                run_at,
+                task_network,
+                idempotency_key,
+            )
         })
         .await??
     };
@@ -1519,6 +1530,9 @@ async fn document_addition(
     let mime_type = extract_mime_type(req)?;
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(req, &network)?;
+    let priority = parse_task_priority_header(req)?;
+    let run_at = parse_task_run_at_header(req)?;
+    let idempotency_key = parse_idempotency_key_header(req)?;
 
     let format = match (
         mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())),
@@ -1641,7 +1655,14 @@ async fn document_addition(
     // FIXME: not new to #6000, but _any_ error here will cause the payload to unduly persist
     let scheduler = index_scheduler.clone();
     let mut task = match tokio::task::spawn_blocking(move || {
-        scheduler.register_with_custom_metadata(task, custom_metadata, task_network)
+        scheduler.register_scheduled(
+            task,
+            custom_metadata,
+            priority,
+            run_at,
+            task_network,
+            idempotency_key,
+        )
     })
     .await?
     {
@@ -1753,6 +1774,9 @@ pub async fn delete_documents_batch(
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let priority = parse_task_priority_header(&req)?;
+    let run_at = parse_task_run_at_header(&req)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     analytics.publish(
         DocumentsDeletionAggregator {
@@ -1774,7 +1798,14 @@ pub async fn delete_documents_batch(
     let mut task = {
         let index_scheduler = index_scheduler.clone();
         tokio::task::spawn_blocking(move || {
-            index_scheduler.register_with_custom_metadata(task, custom_metadata, task_network)
+            index_scheduler.register_scheduled(
+                task,
+                custom_metadata,
+                priority,
+                run_at,
+                task_network,
+                idempotency_key,
+            )
         })
         .await??
     };
@@ -1863,6 +1894,9 @@ pub async fn delete_documents_by_filter(
     let filter = body.into_inner();
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let priority = parse_task_priority_header(&req)?;
+    let run_at = parse_task_run_at_header(&req)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     analytics.publish(
         DocumentsDeletionAggregator {
@@ -1891,7 +1925,14 @@ pub async fn delete_documents_by_filter(
     let mut task = {
         let index_scheduler = index_scheduler.clone();
         tokio::task::spawn_blocking(move || {
-            index_scheduler.register_with_custom_metadata(task, custom_metadata, task_network)
+            index_scheduler.register_scheduled(
+                task,
+                custom_metadata,
+                priority,
+                run_at,
+                task_network,
+                idempotency_key,
+            )
         })
         .await??
     };
@@ -2020,6 +2061,9 @@ pub async fn edit_documents_by_function(
 
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let priority = parse_task_priority_header(&req)?;
+    let run_at = parse_task_run_at_header(&req)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
     let index_uid = index_uid.into_inner();
@@ -2068,7 +2112,14 @@ pub async fn edit_documents_by_function(
     let mut task = {
         let index_scheduler = index_scheduler.clone();
         tokio::task::spawn_blocking(move || {
-            index_scheduler.register_with_custom_metadata(task, custom_metadata, task_network)
+            index_scheduler.register_scheduled(
+                task,
+                custom_metadata,
+                priority,
+                run_at,
+                task_network,
+                idempotency_key,
+            )
         })
         .await??
     };
@@ -2140,6 +2191,9 @@ pub async fn clear_all_documents(
     let network = index_scheduler.network();
     let CustomMetadataQuery { custom_metadata } = params.into_inner();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let priority = parse_task_priority_header(&req)?;
+    let run_at = parse_task_run_at_header(&req)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     analytics.publish(
         DocumentsDeletionAggregator {
@@ -2157,7 +2211,14 @@ pub async fn clear_all_documents(
         let index_scheduler = index_scheduler.clone();
 
         tokio::task::spawn_blocking(move || {
-            index_scheduler.register_with_custom_metadata(task, custom_metadata, task_network)
+            index_scheduler.register_scheduled(
+                task,
+                custom_metadata,
+                priority,
+                run_at,
+                task_network,
+                idempotency_key,
+            )
         })
         .await??
     };
@@ -2420,6 +2481,8 @@ fn build_weighted_score_value<D: VisitFacetValues>(
 
             WeightedScoreValue::GeoSort { asc, distance }
         }
+        // the documents can't be sorted by the distance to their shape
+        Member::GeoShape(_) => WeightedScoreValue::GeoSort { asc, distance: None },
     }
 }
 
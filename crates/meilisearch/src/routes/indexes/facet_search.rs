@@ -24,9 +24,9 @@ use crate::routes::indexes::search::search_kind;
 use crate::search::proxy::{json_proxy, ProxySearchError, ProxySearchParams};
 use crate::search::{
     add_search_rules, fuse_filters, perform_facet_search, prepare_search, FacetSearchResult,
-    HybridQuery, MatchingStrategy, NetworkableQuery, Partition, RankingScoreThreshold, SearchQuery,
-    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
-    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    HybridQuery, MatchingStrategy, NetworkableQuery, Partition, QuerySyntax, RankingScoreThreshold,
+    SearchQuery, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SNIPPET_COUNT,
 };
 use crate::search_queue::SearchQueue;
 
@@ -667,29 +667,42 @@ impl From<FacetSearchQuery> for SearchQuery {
         SearchQuery {
             q,
             media,
+            semantic_queries: None,
             offset: DEFAULT_SEARCH_OFFSET(),
             limit: DEFAULT_SEARCH_LIMIT(),
             page,
             hits_per_page: None,
+            search_after: None,
             attributes_to_retrieve: None,
             retrieve_vectors: false,
             attributes_to_crop: None,
             crop_length: DEFAULT_CROP_LENGTH(),
             attributes_to_highlight: None,
             show_matches_position: false,
+            attributes_to_snippet: None,
+            snippet_count: DEFAULT_SNIPPET_COUNT(),
             show_ranking_score: false,
             show_ranking_score_details: false,
             show_performance_details: false,
+            explain: false,
             filter,
             sort: None,
             distinct: None,
+            group_by: None,
             facets: None,
+            disjunctive_facets: None,
+            facet_aggregations: None,
+            geo_grid: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
+            query_syntax: QuerySyntax::default(),
+            typo_tolerance: None,
+            use_synonyms: None,
             vector,
             attributes_to_search_on,
+            attribute_weights: None,
             hybrid,
             ranking_score_threshold,
             locales,
@@ -18,7 +18,10 @@ use time::OffsetDateTime;
 use tracing::debug;
 use utoipa::{IntoParams, ToSchema};
 
-use super::{Pagination, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
+use super::{
+    parse_idempotency_key_header, Pagination, PaginationView, SummarizedTaskView,
+    PAGINATION_DEFAULT_LIMIT,
+};
 use crate::analytics::{Aggregate, Analytics};
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
@@ -238,6 +241,7 @@ pub async fn create_index(
 
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let IndexCreateRequest { primary_key, uid } = body.into_inner();
 
@@ -254,7 +258,7 @@ pub async fn create_index(
         };
         let scheduler = index_scheduler.clone();
         let mut task = tokio::task::spawn_blocking(move || {
-            scheduler.register_with_custom_metadata(task, None, task_network)
+            scheduler.register_idempotent(task, None, task_network, idempotency_key)
         })
         .await??;
 
@@ -422,6 +426,7 @@ pub async fn update_index(
 
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
     let body = body.into_inner();
@@ -444,7 +449,7 @@ pub async fn update_index(
 
     let scheduler = index_scheduler.clone();
     let mut task = tokio::task::spawn_blocking(move || {
-        scheduler.register_with_custom_metadata(task, None, task_network)
+        scheduler.register_idempotent(task, None, task_network, idempotency_key)
     })
     .await??;
 
@@ -508,13 +513,14 @@ pub async fn delete_index(
 ) -> Result<HttpResponse, ResponseError> {
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
     let task = KindWithContent::IndexDeletion { index_uid: index_uid.clone().into_inner() };
     let scheduler = index_scheduler.clone();
 
     let mut task = tokio::task::spawn_blocking(move || {
-        scheduler.register_with_custom_metadata(task, None, task_network)
+        scheduler.register_idempotent(task, None, task_network, idempotency_key)
     })
     .await??;
 
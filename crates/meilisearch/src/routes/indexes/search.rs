@@ -27,11 +27,11 @@ use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, Sear
 use crate::routes::parse_include_metadata_header;
 use crate::search::{
     add_search_rules, perform_federated_search, perform_search, Federation, HybridQuery,
-    MatchingStrategy, NetworkableQuery as _, Partition, Personalize, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchParams, SearchQuery, SearchQueryWithIndex, SearchResult,
-    SemanticRatio, ShowFederationInfo, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
-    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
-    DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    MatchingStrategy, NetworkableQuery as _, Partition, Personalize, QuerySyntax,
+    RankingScoreThreshold, RetrieveVectors, SearchKind, SearchParams, SearchQuery,
+    SearchQueryWithIndex, SearchResult, SemanticRatio, ShowFederationInfo, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO, DEFAULT_SNIPPET_COUNT,
 };
 use crate::search_queue::SearchQueue;
 
@@ -112,6 +112,14 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchHitsPerPage>)]
     #[param(required = false, value_type = Option<usize>)]
     hits_per_page: Option<Param<usize>>,
+    /// Return the documents ranked after the position described by this cursor.
+    ///
+    /// Use the `nextSearchAfter` value of the previous response to get the next page of results, or an empty string to get the first page.
+    ///
+    /// Cannot be used with `offset`, `page`, `hitsPerPage`, or hybrid search.
+    #[param(required = false)]
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSearchAfter>)]
+    search_after: Option<String>,
     /// List of attributes to include in each returned document.
     ///
     /// Use `["*"]` to return all attributes; if not set, the index [displayed attributes](https://www.meilisearch.com/docs/learn/relevancy/displayed_searchable_attributes) list is used.
@@ -186,6 +194,20 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     #[param(required = false, value_type = bool)]
     show_matches_position: Param<bool>,
+    /// Attributes from which the best passages matching the query should be extracted.
+    ///
+    /// The passages appear in each hit's `_snippets` object, along with their byte offset (`start` and `length`) in the attribute value and their score.
+    ///
+    /// Length of each passage is controlled by `cropLength`, or you can override it per attribute with the `attribute:length` syntax.
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToSnippet>)]
+    #[param(required = false, value_type = Vec<String>, explode = false)]
+    attributes_to_snippet: Option<CS<String>>,
+    /// Maximum number of passages to extract from each attribute.
+    ///
+    /// This parameter only applies when `attributesToSnippet` is set.
+    #[deserr(default = Param(DEFAULT_SNIPPET_COUNT()), error = DeserrQueryParamError<InvalidSearchSnippetCount>)]
+    #[param(required = false, value_type = usize, default = DEFAULT_SNIPPET_COUNT)]
+    snippet_count: Param<usize>,
     /// A [filter](https://www.meilisearch.com/docs/learn/filtering_and_sorting/filter_search_results) expression to narrow results.
     ///
     /// All attributes used in the expression must be in [filterableAttributes](https://www.meilisearch.com/docs/reference/api/settings/update-all-settings#body-filterable-attributes-one-of-0).
@@ -232,6 +254,12 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     #[param(required = false, value_type = Vec<String>, explode = false)]
     facets: Option<CS<String>>,
+    /// Return the count of matches per facet value for the listed attributes, computed as if the filter did not contain the clauses on that same attribute.
+    ///
+    /// This is what multi-select facet interfaces need: the values of a facet stay selectable even when the filter already selects some of them.
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    #[param(required = false, value_type = Vec<String>, explode = false)]
+    disjunctive_facets: Option<CS<String>>,
     /// How to match query terms when there are not enough results to satisfy `limit`.
     ///
     /// **`last`**: Returns documents containing all query terms first. If there are not enough such results, Meilisearch removes one query term at a time, starting from the end of the query (e.g. for "big fat cat", then "big fat", then "big").
@@ -244,6 +272,20 @@ pub struct SearchQueryGet {
     #[param(required = false)]
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
+    /// Syntax used to parse `q`.
+    ///
+    /// **`simple`**: Words, phrases between double quotes and words or phrases excluded with a `-` prefix.
+    ///
+    /// **`advanced`**: The simple syntax, plus terms restricted to a searchable attribute (`title:cat`), alternatives separated by `OR`, groups between parentheses and terms made mandatory with a `+` prefix.
+    ///
+    /// Default: `simple`.
+    #[param(required = false)]
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchQuerySyntax>)]
+    query_syntax: QuerySyntax,
+    /// When `false`, the synonyms of the index are ignored for this query only.
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchUseSynonyms>)]
+    #[param(required = false, value_type = Option<bool>)]
+    use_synonyms: Option<Param<bool>>,
     /// Restrict the search to the listed attributes only.
     ///
     /// Each attribute must be in the index [searchable attributes](https://www.meilisearch.com/docs/learn/relevancy/displayed_searchable_attributes) list.
@@ -344,6 +386,10 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowPerformanceDetails>)]
     #[param(required = false, value_type = bool)]
     show_performance_details: Param<bool>,
+    /// When true, the response includes an `explain` object describing how the ranking rules sorted the documents.
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchExplain>)]
+    #[param(required = false, value_type = bool)]
+    explain: Param<bool>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
@@ -426,20 +472,32 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             limit: other.limit.0,
             page: other.page.as_deref().copied(),
             hits_per_page: other.hits_per_page.as_deref().copied(),
+            search_after: other.search_after,
             attributes_to_retrieve: other.attributes_to_retrieve.map(|o| o.into_iter().collect()),
             attributes_to_crop: other.attributes_to_crop.map(|o| o.into_iter().collect()),
             crop_length: other.crop_length.0,
             crop_marker: other.crop_marker,
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             show_matches_position: other.show_matches_position.0,
+            attributes_to_snippet: other.attributes_to_snippet.map(|o| o.into_iter().collect()),
+            snippet_count: other.snippet_count.0,
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
             distinct: other.distinct,
+            group_by: None,
             facets: other.facets.map(|o| o.into_iter().collect::<Vec<_>>().into()),
+            disjunctive_facets: other.disjunctive_facets.map(|o| o.into_iter().collect()),
+            facet_aggregations: None,
+            geo_grid: None,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             matching_strategy: other.matching_strategy,
+            query_syntax: other.query_syntax,
+            // `typoTolerance` not supported for `GET`
+            typo_tolerance: None,
+            use_synonyms: other.use_synonyms.map(|use_synonyms| use_synonyms.0),
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
+            attribute_weights: None,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
             locales: other.locales.map(|o| o.into_iter().collect()),
             hybrid,
@@ -447,11 +505,14 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             retrieve_vectors: other.retrieve_vectors.0,
             // `media` not supported for `GET`
             media: None,
+            // `semanticQueries` not supported for `GET`
+            semantic_queries: None,
             personalize,
             use_network,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
             show_performance_details: other.show_performance_details.0,
+            explain: other.explain.0,
         })
     }
 }
@@ -987,7 +1048,11 @@ pub fn search_kind(
     let is_placeholder_query =
         if let Some(q) = query.q.as_deref() { q.trim().is_empty() } else { true };
     let non_placeholder_query = !is_placeholder_query;
-    let is_media = query.media.is_some();
+    if query.semantic_queries.is_some() && (query.media.is_some() || query.vector.is_some()) {
+        return Err(MeilisearchHttpError::SemanticQueriesAndVectorOrMedia.into());
+    }
+    // the semantic queries are embedded like media, and require an embedder
+    let is_media = query.media.is_some() || query.semantic_queries.is_some();
     // handle with care, the order of cases matters, the semantics is subtle
     match (is_media, non_placeholder_query, &query.hybrid, query.vector.as_deref()) {
         // media + vector => error
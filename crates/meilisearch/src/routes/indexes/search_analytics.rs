@@ -65,6 +65,8 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     retrieve_vectors: bool,
     // Number of requests containing `media`
     total_media: usize,
+    // Number of requests containing `semanticQueries`
+    total_semantic_queries: usize,
 
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
@@ -114,6 +116,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             limit,
             page,
             hits_per_page,
+            search_after: _,
             attributes_to_retrieve: _,
             attributes_to_crop: _,
             crop_length,
@@ -122,18 +125,30 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             highlight_pre_tag,
             highlight_post_tag,
             show_matches_position,
+            attributes_to_snippet: _,
+            snippet_count: _,
             filter,
             sort,
             distinct,
+            group_by: _,
             facets: _,
+            disjunctive_facets: _,
+            facet_aggregations: _,
+            geo_grid: _,
+            explain: _,
             matching_strategy,
+            query_syntax: _,
+            typo_tolerance: _,
+            use_synonyms: _,
             attributes_to_search_on,
+            attribute_weights: _,
             ranking_score_threshold,
             locales,
             hybrid,
             vector,
             retrieve_vectors,
             media,
+            semantic_queries,
             personalize,
             use_network,
             show_ranking_score,
@@ -195,6 +210,10 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             ret.total_media = 1;
         }
 
+        if semantic_queries.is_some() {
+            ret.total_semantic_queries = 1;
+        }
+
         ret.retrieve_vectors |= retrieve_vectors;
 
         if query.is_finite_pagination() {
@@ -245,12 +264,17 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
     pub fn succeed(&mut self, result: &SearchResult) {
         let SearchResult {
             hits: _,
+            groups: _,
             query: _,
             query_vector: _,
             processing_time_ms,
             hits_info: _,
+            next_search_after: _,
             facet_distribution: _,
             facet_stats: _,
+            facet_aggregations: _,
+            geo_grid: _,
+            explain: _,
             request_uid: _,
             metadata: _,
             remote_errors: _,
@@ -314,6 +338,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             semantic_ratio,
             hybrid,
             total_media,
+            total_semantic_queries,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -368,6 +393,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.semantic_ratio |= semantic_ratio;
         self.hybrid |= hybrid;
         self.total_media += total_media;
+        self.total_semantic_queries += total_semantic_queries;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
@@ -453,6 +479,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             semantic_ratio,
             hybrid,
             total_media,
+            total_semantic_queries,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -504,6 +531,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
                 "enabled": hybrid,
                 "semantic_ratio": semantic_ratio,
                 "total_media": total_media,
+                "total_semantic_queries": total_semantic_queries,
             },
             "pagination": {
                "max_limit": max_limit,
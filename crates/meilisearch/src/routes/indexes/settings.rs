@@ -18,7 +18,10 @@ use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::proxy::{proxy, task_network_and_check_leader_and_version, Body, OverrideEndpoint};
-use crate::routes::SummarizedTaskView;
+use crate::routes::{
+    parse_idempotency_key_header, parse_task_priority_header, parse_task_run_at_header,
+    SummarizedTaskView,
+};
 
 /// This macro generates the routes for the settings.
 ///
@@ -618,6 +621,9 @@ async fn register_new_settings(
 ) -> Result<SummarizedTaskView, ResponseError> {
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(req, &network)?;
+    let priority = parse_task_priority_header(req)?;
+    let run_at = parse_task_run_at_header(req)?;
+    let idempotency_key = parse_idempotency_key_header(req)?;
 
     // validate settings unless this is a duplicated task
     let new_settings = if task_network.is_none() {
@@ -638,7 +644,7 @@ async fn register_new_settings(
     };
     let scheduler = index_scheduler.clone();
     let mut task = tokio::task::spawn_blocking(move || {
-        scheduler.register_with_custom_metadata(task, None, task_network)
+        scheduler.register_scheduled(task, None, priority, run_at, task_network, idempotency_key)
     })
     .await??;
 
@@ -795,7 +801,7 @@ pub async fn delete_all(
     Ok(HttpResponse::Accepted().json(task))
 }
 
-fn validate_settings(
+pub(crate) fn validate_settings(
     settings: Settings<Unchecked>,
     index_scheduler: &IndexScheduler,
 ) -> Result<Settings<Unchecked>, ResponseError> {
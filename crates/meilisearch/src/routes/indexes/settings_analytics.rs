@@ -281,6 +281,7 @@ impl RankingRulesAnalytics {
                             s,
                             meilisearch_types::settings::RankingRuleView::Asc(_)
                                 | meilisearch_types::settings::RankingRuleView::Desc(_)
+                                | meilisearch_types::settings::RankingRuleView::Decay(_)
                         )
                     })
                     .map(|x| x.to_string())
@@ -1,11 +1,14 @@
+use std::convert::Infallible;
+
 use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::{DeserializeError, ErrorKind, ValuePointerRef};
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::keys::actions;
 use meilisearch_types::milli::progress::Progress;
@@ -20,8 +23,8 @@ use crate::analytics::Analytics;
 use crate::extractors::authentication::GuardedData;
 use crate::routes::indexes::similar_analytics::{SimilarAggregator, SimilarGET, SimilarPOST};
 use crate::search::{
-    add_search_rules, perform_similar, RankingScoreThresholdSimilar, SimilarQuery, SimilarResult,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, perform_similar, RankingScoreThresholdSimilar, SimilarEmbedder, SimilarMode,
+    SimilarQuery, SimilarResult, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 
 #[routes::routes(
@@ -229,7 +232,7 @@ async fn similar(
 }
 
 #[derive(Debug, deserr::Deserr, IntoParams)]
-#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields, validate = validate_similar_query_get -> DeserrQueryParamError)]
 #[into_params(parameter_in = Query)]
 pub struct SimilarQueryGet {
     /// The unique identifier ([primary key](https://www.meilisearch.com/docs/learn/getting_started/primary_key) value) of the target document.
@@ -242,10 +245,20 @@ pub struct SimilarQueryGet {
     /// The name of the embedder to use for finding similar documents. This
     /// must match one of the embedders configured in your index settings. The
     /// embedder determines how document similarity is calculated based on
-    /// vector embeddings.
-    #[param(required = true)]
-    #[deserr(error = DeserrQueryParamError<InvalidSimilarEmbedder>)]
-    pub embedder: String,
+    /// vector embeddings. Required unless the `mode` is `keyword`.
+    #[param(required = false)]
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarEmbedder>)]
+    pub embedder: Option<String>,
+    /// How the similarity between documents is computed.
+    ///
+    /// **`semantic`**: Returns the documents with the closest embeddings, computed by the `embedder`.
+    ///
+    /// **`keyword`**: Returns the documents sharing the most discriminative words of the target document. It works on indexes without embedders and cannot be used with an `embedder`.
+    ///
+    /// Default: `semantic`.
+    #[param(required = false)]
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarMode>)]
+    pub mode: SimilarMode,
     /// Number of similar documents to skip in the response. Use together with
     /// `limit` for pagination through large result sets. For example, to get
     /// similar documents 21-40, set `offset=20` and `limit=20`. Defaults to
@@ -321,6 +334,27 @@ impl std::convert::TryFrom<String> for RankingScoreThresholdGet {
     }
 }
 
+fn validate_similar_query_get(
+    query: SimilarQueryGet,
+    location: ValuePointerRef,
+) -> Result<SimilarQueryGet, DeserrQueryParamError> {
+    match (query.mode, &query.embedder) {
+        (SimilarMode::Semantic, None) => {
+            Err(deserr::take_cf_content(DeserrQueryParamError::error::<Infallible>(
+                None,
+                ErrorKind::MissingField { field: "embedder" },
+                location,
+            )))
+        }
+        (SimilarMode::Keyword, Some(_)) => Err(DeserrQueryParamError::new(
+            "Invalid value in parameter `embedder`: the `keyword` mode does not use an embedder."
+                .to_string(),
+            Code::InvalidSimilarEmbedder,
+        )),
+        _ => Ok(query),
+    }
+}
+
 impl From<SimilarQueryGet> for SimilarQuery {
     fn from(
         SimilarQueryGet {
@@ -334,6 +368,7 @@ impl From<SimilarQueryGet> for SimilarQuery {
             show_ranking_score_details,
             show_performance_details,
             embedder,
+            mode,
             ranking_score_threshold,
         }: SimilarQueryGet,
     ) -> Self {
@@ -350,7 +385,8 @@ impl From<SimilarQueryGet> for SimilarQuery {
             offset: offset.0,
             limit: limit.0,
             filter,
-            embedder,
+            embedder: SimilarEmbedder(embedder),
+            mode,
             attributes_to_retrieve: attributes_to_retrieve.map(|o| o.into_iter().collect()),
             retrieve_vectors: retrieve_vectors.0,
             show_ranking_score: show_ranking_score.0,
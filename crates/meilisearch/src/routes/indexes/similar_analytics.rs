@@ -53,6 +53,7 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
         let SimilarQuery {
             id: _,
             embedder: _,
+            mode: _,
             offset,
             limit,
             attributes_to_retrieve: _,
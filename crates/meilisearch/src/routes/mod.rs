@@ -23,8 +23,9 @@ use meilisearch_types::settings::{
     Unchecked,
 };
 use meilisearch_types::task_view::{DetailsView, TaskView};
-use meilisearch_types::tasks::{Kind, Status, Task, TaskId};
+use meilisearch_types::tasks::{Kind, Priority, Status, Task, TaskId};
 use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use tracing::debug;
 use utoipa::ToSchema;
@@ -82,6 +83,7 @@ mod swap_indexes;
 pub mod tasks;
 #[cfg(test)]
 mod tasks_test;
+mod transactions;
 mod webhooks;
 
 #[routes::routes(
@@ -101,6 +103,7 @@ mod webhooks;
         "/logs"=> sub(logs::LogsApi),
         "/multi-search"=> sub(multi_search::MultiSearchApi),
         "/swap-indexes"=> sub(swap_indexes::SwapIndexesApi),
+        "/transactions"=> sub(transactions::TransactionsApi),
         "/experimental-features"=> sub(features::ExperimentalFeaturesApi),
         "/export"=> sub(export::ExportApi),
         "/network"=> sub(network::NetworkApi),
@@ -139,6 +142,82 @@ pub fn parse_include_metadata_header(req: &HttpRequest) -> bool {
         .unwrap_or(false)
 }
 
+/// The header used to give a priority to the task enqueued by a request.
+pub const TASK_PRIORITY_HEADER: &str = "Meili-Task-Priority";
+
+/// Parse the `Meili-Task-Priority` header from an HTTP request.
+///
+/// Returns the `normal` priority if the header is not present.
+pub fn parse_task_priority_header(req: &HttpRequest) -> Result<Priority, ResponseError> {
+    let Some(header) = req.headers().get(TASK_PRIORITY_HEADER) else {
+        return Ok(Priority::Normal);
+    };
+    header
+        .to_str()
+        .map_err(|_| {
+            ResponseError::from_msg(
+                format!("The `{TASK_PRIORITY_HEADER}` header must be a valid string."),
+                Code::InvalidTaskPriority,
+            )
+        })?
+        .parse()
+        .map_err(|error: meilisearch_types::tasks::ParseTaskPriorityError| {
+            ResponseError::from_msg(error.to_string(), Code::InvalidTaskPriority)
+        })
+}
+
+/// The header used to delay the processing of the task enqueued by a request.
+pub const TASK_RUN_AT_HEADER: &str = "Meili-Task-Run-At";
+
+/// Parse the `Meili-Task-Run-At` header from an HTTP request.
+///
+/// Returns `None` if the header is not present, the task can then be processed right away.
+pub fn parse_task_run_at_header(
+    req: &HttpRequest,
+) -> Result<Option<OffsetDateTime>, ResponseError> {
+    let Some(header) = req.headers().get(TASK_RUN_AT_HEADER) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        ResponseError::from_msg(
+            format!(
+                "The `{TASK_RUN_AT_HEADER}` header must be a valid RFC 3339 date, e.g. `2024-08-08T16:37:09.971Z`."
+            ),
+            Code::InvalidTaskRunAt,
+        )
+    };
+    let header = header.to_str().map_err(|_| invalid())?;
+    OffsetDateTime::parse(header.trim(), &Rfc3339).map(Some).map_err(|_| invalid())
+}
+
+/// The header used to retry a request without enqueuing its task twice.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// The maximum length of an idempotency key, in bytes.
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// Parse the `Idempotency-Key` header from an HTTP request.
+///
+/// Returns `None` if the header is not present, a new task is then always enqueued.
+pub fn parse_idempotency_key_header(req: &HttpRequest) -> Result<Option<String>, ResponseError> {
+    let Some(header) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        ResponseError::from_msg(
+            format!(
+                "The `{IDEMPOTENCY_KEY_HEADER}` header must be a non-empty string of at most {MAX_IDEMPOTENCY_KEY_LENGTH} visible ASCII characters."
+            ),
+            Code::InvalidIdempotencyKey,
+        )
+    };
+    let key = header.to_str().map_err(|_| invalid())?.trim();
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(invalid());
+    }
+    Ok(Some(key.to_string()))
+}
+
 /// A summarized view of a task, returned when a task is enqueued
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -164,6 +243,22 @@ pub struct SummarizedTaskView {
     /// tasks with external systems or add application-specific information.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_metadata: Option<String>,
+    /// Priority of the task, only present when it differs from `normal`.
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
+    /// Date before which the task won't be processed, only present when it was
+    /// enqueued with a `runAt` date.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>)]
+    pub run_at: Option<OffsetDateTime>,
+    /// Uid of the first task of the transaction this task was enqueued in,
+    /// only present when the task is part of a transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_uid: Option<TaskId>,
 }
 
 impl From<Task> for SummarizedTaskView {
@@ -175,6 +270,9 @@ impl From<Task> for SummarizedTaskView {
             kind: task.kind.as_kind(),
             enqueued_at: task.enqueued_at,
             custom_metadata: task.custom_metadata,
+            priority: task.priority,
+            run_at: task.run_at,
+            transaction_uid: task.transaction_uid,
         }
     }
 }
@@ -397,6 +495,11 @@ pub struct Stats {
     /// processed
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     pub last_update: Option<OffsetDateTime>,
+    /// Date when the processing of the tasks was paused in RFC 3339 format. Absent while the
+    /// tasks are processed
+    #[serde(skip_serializing_if = "Option::is_none", with = "time::serde::rfc3339::option")]
+    #[schema(value_type = Option<String>, example = "2024-08-08T17:05:55.791772Z")]
+    pub paused_at: Option<OffsetDateTime>,
     /// Statistics for each index
     #[schema(value_type = HashMap<String, indexes::IndexStats>)]
     pub indexes: BTreeMap<String, indexes::IndexStats>,
@@ -498,7 +601,10 @@ pub fn create_all_stats(
     let database_size = Size::new(database_size, size_format);
     let used_database_size = Size::new(used_database_size, size_format);
 
-    let stats = Stats { database_size, used_database_size, last_update: last_task, indexes };
+    let paused_at = index_scheduler.paused_at();
+
+    let stats =
+        Stats { database_size, used_database_size, last_update: last_task, paused_at, indexes };
     Ok(stats)
 }
 
@@ -43,10 +43,12 @@ impl MultiSearchAggregator {
             q: _,
             vector: _,
             media: _,
+            semantic_queries: _,
             offset: _,
             limit: _,
             page: _,
             hits_per_page: _,
+            search_after: _,
             attributes_to_retrieve: _,
             retrieve_vectors: _,
             attributes_to_crop: _,
@@ -56,15 +58,26 @@ impl MultiSearchAggregator {
             show_ranking_score_details: _,
             show_performance_details: _,
             show_matches_position: _,
+            attributes_to_snippet: _,
+            snippet_count: _,
             filter: _,
             sort: _,
             distinct: _,
+            group_by: _,
             facets: _,
+            disjunctive_facets: _,
+            facet_aggregations: _,
+            geo_grid: _,
+            explain: _,
             highlight_pre_tag: _,
             highlight_post_tag: _,
             crop_marker: _,
             matching_strategy: _,
+            query_syntax: _,
+            typo_tolerance: _,
+            use_synonyms: _,
             attributes_to_search_on: _,
+            attribute_weights: _,
             hybrid: _,
             ranking_score_threshold: _,
             locales: _,
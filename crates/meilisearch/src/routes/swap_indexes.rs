@@ -9,7 +9,7 @@ use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::tasks::{IndexSwap, KindWithContent};
 use serde::Serialize;
 
-use super::SummarizedTaskView;
+use super::{parse_idempotency_key_header, SummarizedTaskView};
 use crate::analytics::{Aggregate, Analytics};
 use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
@@ -99,6 +99,7 @@ pub async fn swap_indexes(
 
     let network = index_scheduler.network();
     let task_network = task_network_and_check_leader_and_version(&req, &network)?;
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     analytics.publish(
         IndexSwappedAnalytics {
@@ -129,7 +130,7 @@ pub async fn swap_indexes(
     let task = KindWithContent::IndexSwap { swaps };
     let scheduler = index_scheduler.clone();
     let mut task = tokio::task::spawn_blocking(move || {
-        scheduler.register_with_custom_metadata(task, None, task_network)
+        scheduler.register_idempotent(task, None, task_network, idempotency_key)
     })
     .await??;
 
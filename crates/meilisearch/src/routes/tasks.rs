@@ -1,4 +1,7 @@
 pub mod compact;
+pub mod pause;
+pub mod retention;
+pub mod stream;
 
 use std::io::ErrorKind;
 
@@ -24,7 +27,7 @@ use tokio::io::AsyncReadExt;
 use tokio::task;
 use utoipa::{IntoParams, ToSchema};
 
-use super::{SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
+use super::{parse_idempotency_key_header, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
 use crate::aggregate_methods;
 use crate::analytics::{Aggregate, AggregateMethod, Analytics};
 use crate::extractors::authentication::policies::*;
@@ -35,6 +38,11 @@ use crate::extractors::authentication::GuardedData;
         "" => [get(get_tasks), delete(delete_tasks)],
         "/cancel" => post(cancel_tasks),
         "/compact" => post(compact::compact_task_queue),
+        "/pause" => post(pause::pause_processing),
+        "/processing" => get(pause::get_processing_state),
+        "/resume" => post(pause::resume_processing),
+        "/retention-policy" => [get(retention::get_task_retention_policy), patch(retention::patch_task_retention_policy)],
+        "/stream" => get(stream::stream_tasks),
         "/{task_id}" => get(get_task),
         "/{task_id}/documents" => get(get_task_documents_file),
     ),
@@ -134,6 +142,16 @@ pub struct TasksFilterQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeFinishedAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
     #[param(required = false, value_type = Option<String>, example = "2024-08-08T16:37:09.971Z")]
     pub before_finished_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter tasks based on their runAt time. Matches tasks
+    /// delayed after the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterRunAt>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = "2024-08-08T16:37:09.971Z")]
+    pub after_run_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter tasks based on their runAt time. Matches tasks
+    /// delayed before the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeRunAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = "2024-08-08T16:37:09.971Z")]
+    pub before_run_at: OptionStarOr<OffsetDateTime>,
 }
 
 impl TasksFilterQuery {
@@ -154,6 +172,8 @@ impl TasksFilterQuery {
             after_started_at: self.after_started_at.merge_star_and_none(),
             before_finished_at: self.before_finished_at.merge_star_and_none(),
             after_finished_at: self.after_finished_at.merge_star_and_none(),
+            before_run_at: self.before_run_at.merge_star_and_none(),
+            after_run_at: self.after_run_at.merge_star_and_none(),
         }
     }
 }
@@ -271,6 +291,8 @@ impl TaskDeletionOrCancelationQuery {
             after_started_at: self.after_started_at.merge_star_and_none(),
             before_finished_at: self.before_finished_at.merge_star_and_none(),
             after_finished_at: self.after_finished_at.merge_star_and_none(),
+            before_run_at: None,
+            after_run_at: None,
         }
     }
 }
@@ -406,13 +428,17 @@ async fn cancel_tasks(
     );
 
     let query = params.into_query();
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let (tasks, _) =
         index_scheduler.get_task_ids_from_authorized_indexes(&query, index_scheduler.filters())?;
     let task_cancelation =
         KindWithContent::TaskCancelation { query: format!("?{}", req.query_string()), tasks };
 
-    let task = task::spawn_blocking(move || index_scheduler.register(task_cancelation)).await??;
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_idempotent(task_cancelation, None, None, idempotency_key)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     // FIXME: This should be 202 Accepted, but changing would be breaking so we need to wait 2.0
@@ -500,13 +526,17 @@ async fn delete_tasks(
     );
 
     let query = params.into_query();
+    let idempotency_key = parse_idempotency_key_header(&req)?;
 
     let (tasks, _) =
         index_scheduler.get_task_ids_from_authorized_indexes(&query, index_scheduler.filters())?;
     let task_deletion =
         KindWithContent::TaskDeletion { query: format!("?{}", req.query_string()), tasks };
 
-    let task = task::spawn_blocking(move || index_scheduler.register(task_deletion)).await??;
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_idempotent(task_deletion, None, None, idempotency_key)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     // FIXME: This should be 202 Accepted, but changing would be breaking so we need to wait 2.0
@@ -526,6 +556,20 @@ pub struct AllTasks {
     pub from: Option<u32>,
     /// Value to pass as `from` parameter to get the next page. When `null`, there are no more tasks to retrieve.
     pub next: Option<u32>,
+    /// Whether the processing of the tasks is paused, see the `/tasks/pause` route. Absent while
+    /// the tasks are processed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+    /// Date when the processing of the tasks was paused in RFC 3339 format. Absent while the
+    /// tasks are processed
+    #[serde(
+        default,
+        rename = "pausedAt",
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::rfc3339::option"
+    )]
+    #[schema(value_type = Option<String>, example = "2024-08-08T17:05:55.791772Z")]
+    pub paused_at: Option<OffsetDateTime>,
 }
 
 /// List tasks
@@ -589,7 +633,16 @@ async fn get_tasks(
     let next = if results.len() == limit as usize { results.pop().map(|t| t.uid) } else { None };
 
     let from = results.first().map(|t| t.uid);
-    let tasks = AllTasks { results, limit: limit.saturating_sub(1), total, from, next };
+    let paused_at = index_scheduler.paused_at();
+    let tasks = AllTasks {
+        results,
+        limit: limit.saturating_sub(1),
+        total,
+        from,
+        next,
+        paused: paused_at.is_some(),
+        paused_at,
+    };
 
     Ok(HttpResponse::Ok().json(tasks))
 }
@@ -0,0 +1,159 @@
+use actix_web::web::Data;
+use actix_web::HttpResponse;
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::InvalidTaskAbortProcessing;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::keys::actions;
+use serde::Serialize;
+use time::OffsetDateTime;
+use tokio::task;
+use tracing::debug;
+use utoipa::{IntoParams, ToSchema};
+
+use super::ActionPolicy;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+
+#[derive(Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct PauseProcessingQuery {
+    /// If `true`, the batches being processed are aborted and their tasks are enqueued again,
+    /// otherwise they are finished before the processing pauses.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAbortProcessing>)]
+    #[param(required = false, value_type = bool, example = true, default = false)]
+    pub abort_processing: Param<bool>,
+}
+
+/// State of the processing of the tasks
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct ProcessingStateView {
+    /// Whether the processing of the tasks is paused.
+    pub paused: bool,
+    /// When the processing of the tasks was paused.
+    #[serde(skip_serializing_if = "Option::is_none", with = "time::serde::rfc3339::option")]
+    #[schema(value_type = Option<String>, example = "2024-08-08T17:05:55.791772Z")]
+    pub paused_at: Option<OffsetDateTime>,
+}
+
+/// Pause task processing
+///
+/// Stop processing new batches of [tasks](https://www.meilisearch.com/docs/learn/async/asynchronous_operations) until the processing is resumed, even across restarts.
+/// The `/tasks/processing` route returns whether the processing is paused.
+///
+/// The tasks can still be enqueued and the indexes can still be searched while the processing is paused.
+/// The batch being processed is finished first, unless `abortProcessing` is set.
+///
+/// **Note:** This route requires an API key with access to all the indexes.
+#[routes::path(
+    security(("Bearer" = ["tasks.*", "*"])),
+    no_request_body,
+    params(PauseProcessingQuery),
+    responses(
+        (status = 200, description = "The processing of the tasks is paused.", body = ProcessingStateView, content_type = "application/json", example = json!(
+            {
+                "paused": true,
+                "pausedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn pause_processing(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_ALL }>, Data<IndexScheduler>>,
+    params: AwebQueryParameter<PauseProcessingQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    if !index_scheduler.filters().all_indexes_authorized() {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+    let abort_processing = params.into_inner().abort_processing.0;
+    debug!(abort_processing, "Pause task processing");
+
+    let paused_at =
+        task::spawn_blocking(move || index_scheduler.pause_processing(abort_processing)).await??;
+
+    let view = ProcessingStateView { paused: true, paused_at: Some(paused_at) };
+    debug!(returns = ?view, "Pause task processing");
+    Ok(HttpResponse::Ok().json(view))
+}
+
+/// Resume task processing
+///
+/// Resume the processing of the [tasks](https://www.meilisearch.com/docs/learn/async/asynchronous_operations) paused with the `/tasks/pause` route.
+///
+/// **Note:** This route requires an API key with access to all the indexes.
+#[routes::path(
+    security(("Bearer" = ["tasks.*", "*"])),
+    no_request_body,
+    responses(
+        (status = 200, description = "The processing of the tasks is resumed.", body = ProcessingStateView, content_type = "application/json", example = json!(
+            {
+                "paused": false
+            }
+        )),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn resume_processing(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_ALL }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    if !index_scheduler.filters().all_indexes_authorized() {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    task::spawn_blocking(move || index_scheduler.resume_processing()).await??;
+
+    let view = ProcessingStateView { paused: false, paused_at: None };
+    debug!(returns = ?view, "Resume task processing");
+    Ok(HttpResponse::Ok().json(view))
+}
+
+/// Get the task processing state
+///
+/// Return whether the processing of the [tasks](https://www.meilisearch.com/docs/learn/async/asynchronous_operations) is paused with the `/tasks/pause` route, and since when.
+#[routes::path(
+    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    responses(
+        (status = 200, description = "The processing state of the tasks is returned.", body = ProcessingStateView, content_type = "application/json", example = json!(
+            {
+                "paused": true,
+                "pausedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_processing_state(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let paused_at = index_scheduler.paused_at();
+    let view = ProcessingStateView { paused: paused_at.is_some(), paused_at };
+    debug!(returns = ?view, "Get task processing state");
+    Ok(HttpResponse::Ok().json(view))
+}
@@ -0,0 +1,149 @@
+use actix_web::web::Data;
+use actix_web::HttpResponse;
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::update::Setting;
+use meilisearch_types::task_retention::TaskRetentionPolicy;
+use tokio::task;
+use tracing::debug;
+
+use super::ActionPolicy;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+
+/// Update of the finished tasks periodically deleted from the task queue
+#[routes::request]
+#[derive(Debug)]
+pub struct TaskRetentionPolicySettings {
+    /// Number of days the succeeded tasks are kept once finished, at most 36500, `null` to never delete them.
+    #[request(default, error = DeserrJsonError<InvalidTaskRetentionSucceededTasksDays>, schema_type = Option<u32>, example = json!(30))]
+    pub succeeded_tasks_days: Setting<u32>,
+    /// Number of days the failed tasks are kept once finished, at most 36500, `null` to never delete them.
+    #[request(default, error = DeserrJsonError<InvalidTaskRetentionFailedTasksDays>, schema_type = Option<u32>, example = json!(90))]
+    pub failed_tasks_days: Setting<u32>,
+    /// Number of the most recent finished tasks of each index kept whatever their age.
+    #[request(default, error = DeserrJsonError<InvalidTaskRetentionKeepLastTasksPerIndex>, schema_type = Option<u32>, example = json!(100))]
+    pub keep_last_tasks_per_index: Setting<u32>,
+}
+
+/// Get the task retention policy
+///
+/// Return the policy defining which finished [tasks](https://www.meilisearch.com/docs/learn/async/asynchronous_operations) are periodically deleted from the task queue.
+#[routes::path(
+    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    responses(
+        (status = 200, description = "The task retention policy is returned.", body = TaskRetentionPolicy, content_type = "application/json", example = json!(
+            {
+                "succeededTasksDays": 30,
+                "failedTasksDays": 90,
+                "keepLastTasksPerIndex": 100
+            }
+        )),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_task_retention_policy(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let policy = index_scheduler.task_retention_policy();
+    debug!(returns = ?policy, "Get task retention policy");
+    Ok(HttpResponse::Ok().json(policy))
+}
+
+/// Update the task retention policy
+///
+/// Define which finished [tasks](https://www.meilisearch.com/docs/learn/async/asynchronous_operations) are periodically deleted from the task queue.
+///
+/// The scheduler enqueues a `taskDeletion` for the succeeded and failed tasks that finished more than the
+/// configured number of days ago. The most recent finished tasks of each index are never deleted if
+/// `keepLastTasksPerIndex` is set. Setting a field to `null` disables it.
+///
+/// This route is **synchronous**, the new policy is applied right away.
+///
+/// **Note:** This route requires an API key with access to all the indexes.
+#[routes::path(
+    security(("Bearer" = ["tasks.*", "*"])),
+    request_body = TaskRetentionPolicySettings,
+    responses(
+        (status = 200, description = "The task retention policy is updated.", body = TaskRetentionPolicy, content_type = "application/json", example = json!(
+            {
+                "succeededTasksDays": 30,
+                "failedTasksDays": null,
+                "keepLastTasksPerIndex": 100
+            }
+        )),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn patch_task_retention_policy(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_ALL }>, Data<IndexScheduler>>,
+    new_policy: AwebJson<TaskRetentionPolicySettings, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    if !index_scheduler.filters().all_indexes_authorized() {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+    let new_policy = new_policy.into_inner();
+    debug!(parameters = ?new_policy, "Patch task retention policy");
+
+    let TaskRetentionPolicySettings {
+        succeeded_tasks_days,
+        failed_tasks_days,
+        keep_last_tasks_per_index,
+    } = new_policy;
+    for (days, code, field) in [
+        (&succeeded_tasks_days, Code::InvalidTaskRetentionSucceededTasksDays, "succeededTasksDays"),
+        (&failed_tasks_days, Code::InvalidTaskRetentionFailedTasksDays, "failedTasksDays"),
+    ] {
+        if let Setting::Set(days) = days {
+            if *days > TaskRetentionPolicy::MAX_DAYS {
+                return Err(ResponseError::from_msg(
+                    format!(
+                        "Invalid value for `{field}`: expected at most {} days, but found {days}.",
+                        TaskRetentionPolicy::MAX_DAYS
+                    ),
+                    code,
+                ));
+            }
+        }
+    }
+
+    let old_policy = index_scheduler.task_retention_policy();
+    let policy = TaskRetentionPolicy {
+        succeeded_tasks_days: apply(succeeded_tasks_days, old_policy.succeeded_tasks_days),
+        failed_tasks_days: apply(failed_tasks_days, old_policy.failed_tasks_days),
+        keep_last_tasks_per_index: apply(
+            keep_last_tasks_per_index,
+            old_policy.keep_last_tasks_per_index,
+        ),
+    };
+
+    task::spawn_blocking(move || index_scheduler.update_task_retention_policy(policy)).await??;
+
+    debug!(returns = ?policy, "Patch task retention policy");
+    Ok(HttpResponse::Ok().json(policy))
+}
+
+fn apply(setting: Setting<u32>, old: Option<u32>) -> Option<u32> {
+    match setting {
+        Setting::Set(value) => Some(value),
+        Setting::Reset => None,
+        Setting::NotSet => old,
+    }
+}
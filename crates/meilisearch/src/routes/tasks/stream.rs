@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::web::Data;
+use actix_web::Responder;
+use actix_web_lab::sse::{self, Event, Sse};
+use deserr::actix_web::AwebQueryParameter;
+use index_scheduler::{IndexScheduler, Query, TaskId};
+use meilisearch_types::batches::BatchId;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::progress::ProgressView;
+use meilisearch_types::task_view::TaskView;
+use meilisearch_types::tasks::Status;
+use roaring::RoaringBitmap;
+use serde::Serialize;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::mpsc::Sender;
+
+use super::{ActionPolicy, TasksFilterQuery};
+use crate::extractors::authentication::GuardedData;
+
+/// How often the progress of the processing batches is checked.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Progress of a batch processing some of the streamed tasks
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchProgressView {
+    /// The uid of the batch being processed.
+    pub batch_uid: BatchId,
+    /// The progress of the batch.
+    pub progress: ProgressView,
+}
+
+/// Stream tasks
+///
+/// Stream the status changes of the [tasks](https://www.meilisearch.com/docs/learn/async/asynchronous_operations) matching the query as [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events).
+///
+/// The stream starts with a `task` event for each task currently matching the query, then
+/// sends a `task` event every time one of the tasks matching the query is enqueued or changes
+/// status. The `progress` events report the progress of the batches processing these tasks.
+#[routes::path(
+    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    params(TasksFilterQuery),
+    responses(
+        (status = 200, description = "The stream of task events is opened.", body = TaskView, content_type = "text/event-stream"),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn stream_tasks(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    params: AwebQueryParameter<TasksFilterQuery, DeserrQueryParamError>,
+) -> Result<impl Responder, ResponseError> {
+    let query = params.into_inner().into_query();
+    // We subscribe before reading the current state of the tasks to not miss any update in between.
+    let updates = index_scheduler.subscribe_to_task_updates();
+    let (tx, rx) = tokio::sync::mpsc::channel(10);
+    tokio::spawn(stream_task_events(index_scheduler, query, updates, tx));
+
+    Ok(Sse::from_infallible_receiver(rx)
+        .with_retry_duration(Duration::from_secs(10))
+        .with_keep_alive(Duration::from_secs(15))
+        .customize()
+        .insert_header(("X-Accel-Buffering", "no")))
+}
+
+/// Sends the task and progress events until the client disconnects.
+async fn stream_task_events(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    query: Query,
+    mut updates: Receiver<Arc<RoaringBitmap>>,
+    tx: Sender<Event>,
+) {
+    // The tasks are read on a blocking thread that must own the scheduler and the filters.
+    let index_scheduler = Arc::new(index_scheduler);
+    // The last status sent for the unfinished tasks, the finished ones can't change anymore.
+    let mut statuses: HashMap<TaskId, Status> = HashMap::new();
+    let mut finished = RoaringBitmap::new();
+    let mut progresses: HashMap<BatchId, serde_json::Value> = HashMap::new();
+    let mut interval = tokio::time::interval(PROGRESS_INTERVAL);
+    // `None` means that all the tasks matching the query must be checked.
+    let mut next_updated: Option<RoaringBitmap> = None;
+
+    loop {
+        let updated = next_updated.take().map(|updated| updated - &finished);
+        let task_query = match &updated {
+            None => Some(query.clone()),
+            Some(updated) => updated_tasks_query(&query, updated.clone()),
+        };
+
+        if let Some(task_query) = task_query {
+            let scheduler = index_scheduler.clone();
+            let tasks = tokio::task::spawn_blocking(move || {
+                scheduler.get_tasks_from_authorized_indexes(&task_query, scheduler.filters())
+            })
+            .await
+            .map_err(ResponseError::from)
+            .and_then(|tasks| tasks.map_err(ResponseError::from));
+            let tasks = match tasks {
+                Ok((tasks, _)) => tasks,
+                Err(e) => {
+                    send_event(&tx, "error", e).await;
+                    return;
+                }
+            };
+            let mut returned = RoaringBitmap::new();
+            for task in tasks.iter().map(TaskView::from_task) {
+                if finished.contains(task.uid) {
+                    continue;
+                }
+                returned.insert(task.uid);
+                let (uid, status) = (task.uid, task.status);
+                if statuses.get(&uid) != Some(&status) && !send_event(&tx, "task", task).await {
+                    return;
+                }
+                if status.is_finished() {
+                    statuses.remove(&uid);
+                    finished.insert(uid);
+                } else {
+                    statuses.insert(uid, status);
+                }
+            }
+            // The updated tasks that are not returned don't match the query anymore.
+            for uid in updated.iter().flat_map(|updated| updated - &returned) {
+                statuses.remove(&uid);
+            }
+        }
+
+        let batches = index_scheduler.get_processing_batches_progress();
+        progresses.retain(|batch_uid, _| batches.iter().any(|(uid, _, _)| uid == batch_uid));
+        for (batch_uid, tasks, progress) in batches {
+            if !tasks.iter().any(|task| statuses.contains_key(&task)) {
+                continue;
+            }
+            let value = match serde_json::to_value(&progress) {
+                Ok(value) => value,
+                Err(error) => {
+                    tracing::error!(%error, batch_uid, "Could not serialize the batch progress");
+                    continue;
+                }
+            };
+            if progresses.insert(batch_uid, value.clone()) != Some(value) {
+                let progress = BatchProgressView { batch_uid, progress };
+                if !send_event(&tx, "progress", progress).await {
+                    return;
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = tx.closed() => return,
+            _ = interval.tick() => next_updated = Some(RoaringBitmap::new()),
+            update = updates.recv() => match update {
+                Ok(tasks) => next_updated = Some(RoaringBitmap::clone(&tasks)),
+                Err(RecvError::Lagged(_)) => next_updated = None,
+                Err(RecvError::Closed) => return,
+            },
+        }
+
+        // We merge the updates that arrived in the meantime to query the tasks only once.
+        while let Some(tasks) = next_updated.as_mut() {
+            match updates.try_recv() {
+                Ok(more) => *tasks |= &*more,
+                Err(TryRecvError::Lagged(_)) => next_updated = None,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+/// Restricts the query to the updated tasks, returns `None` if none of them can match.
+fn updated_tasks_query(query: &Query, mut updated: RoaringBitmap) -> Option<Query> {
+    if let Some(uids) = &query.uids {
+        updated &= uids.iter().copied().collect::<RoaringBitmap>();
+    }
+    if updated.is_empty() {
+        return None;
+    }
+    Some(Query {
+        limit: None,
+        from: None,
+        reverse: Some(true),
+        uids: Some(updated.into_iter().collect()),
+        ..query.clone()
+    })
+}
+
+/// Sends an event to the client, returns `false` once the client is disconnected.
+///
+/// An event that can't be serialized is dropped.
+async fn send_event(tx: &Sender<Event>, name: &'static str, data: impl Serialize) -> bool {
+    match sse::Data::new_json(data) {
+        Ok(data) => tx.send(Event::Data(data.event(name))).await.is_ok(),
+        Err(error) => {
+            tracing::error!(%error, "Could not serialize the `{name}` event");
+            true
+        }
+    }
+}
@@ -0,0 +1,425 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::filter::parse_local_index_filter;
+use index_scheduler::IndexScheduler;
+use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::document_formats::read_json;
+use meilisearch_types::error::deserr_codes::{
+    InvalidDocumentFilter, InvalidIndexUid, InvalidTransactionOperations,
+};
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::{IndexDocumentsMethod, MissingDocumentPolicy};
+use meilisearch_types::settings::{Settings, Unchecked};
+use meilisearch_types::tasks::{KindWithContent, Task};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::debug;
+
+use super::indexes::settings::validate_settings;
+use super::{parse_idempotency_key_header, SummarizedTaskView};
+use crate::analytics::{Aggregate, Analytics};
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{
+    extract_token_from_request, AuthenticationError, Policy as _,
+};
+
+#[routes::routes(
+    routes(
+        "" => post(create_transaction),
+    ),
+    tag = "Transactions",
+    tags((
+        name = "Transactions",
+        description = "The `/transactions` route allows you to update several indexes at once, the changes become visible together or not at all.",
+    )),
+)]
+pub struct TransactionsApi;
+
+/// Type of an operation of a transaction
+#[routes::request(override_error = DeserrJsonError<InvalidTransactionOperations>)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionOperationType {
+    /// Add or replace documents, requires `documents`
+    AddDocuments,
+    /// Add or update documents, requires `documents`
+    UpdateDocuments,
+    /// Delete documents by id, requires `documentIds`
+    DeleteDocuments,
+    /// Delete the documents matching a filter, requires `filter`
+    DeleteDocumentsByFilter,
+    /// Update the settings of the index, requires `settings`
+    UpdateSettings,
+}
+
+/// An operation of a transaction
+#[routes::request]
+#[derive(Debug)]
+pub struct TransactionOperation {
+    /// Type of the operation.
+    #[request(required, rename = "type", error = DeserrJsonError<InvalidTransactionOperations>)]
+    pub kind: TransactionOperationType,
+    /// Index targeted by the operation.
+    #[request(required, example = "movies", error = DeserrJsonError<InvalidIndexUid>)]
+    pub index_uid: IndexUid,
+    /// Documents to add or update.
+    #[request(default, error = DeserrJsonError<InvalidTransactionOperations>, example = json!([{ "id": 1, "title": "Carol" }]))]
+    pub documents: Option<Vec<Value>>,
+    /// Primary key of the documents to add or update.
+    #[request(default, error = DeserrJsonError<InvalidTransactionOperations>)]
+    pub primary_key: Option<String>,
+    /// Ids of the documents to delete.
+    #[request(default, error = DeserrJsonError<InvalidTransactionOperations>)]
+    pub document_ids: Option<Vec<Value>>,
+    /// Filter matching the documents to delete.
+    #[request(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    pub filter: Option<Value>,
+    /// Settings to update.
+    #[request(default)]
+    pub settings: Option<Settings<Unchecked>>,
+}
+
+#[derive(Serialize)]
+struct TransactionEnqueuedAnalytics {
+    max_operations: usize,
+    max_indexes: usize,
+}
+
+impl Aggregate for TransactionEnqueuedAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Transaction Enqueued"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self {
+            max_operations: self.max_operations.max(new.max_operations),
+            max_indexes: self.max_indexes.max(new.max_indexes),
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Create a transaction
+///
+/// Enqueue several document and settings operations, possibly on different indexes, that are committed together.
+///
+/// The operations are processed in order, in a single batch. If any of them fails, none of them is applied and all
+/// the tasks of the transaction fail. Missing indexes are created when an operation allows it.
+///
+/// The operations of an index are applied together, so they must all be settings updates or all be document
+/// operations using the same primary key, and the deletions by filter must come before the document additions.
+///
+/// Each operation requires the action of its own route on its index: `documents.add` to add or update documents,
+/// `documents.delete` to delete documents and `settings.update` to update the settings.
+#[routes::path(
+    security(("Bearer" = ["documents.add", "documents.delete", "settings.update", "documents.*", "settings.*", "*"])),
+    request_body(content = Vec<TransactionOperation>),
+    responses(
+        (status = 202, description = "The tasks of the transaction are enqueued.", body = Vec<SummarizedTaskView>, content_type = "application/json", example = json!(
+            [
+                {
+                    "taskUid": 12,
+                    "indexUid": "movies",
+                    "status": "enqueued",
+                    "type": "documentAdditionOrUpdate",
+                    "enqueuedAt": "2021-08-12T10:00:00.000000Z",
+                    "transactionUid": 12
+                },
+                {
+                    "taskUid": 13,
+                    "indexUid": "directors",
+                    "status": "enqueued",
+                    "type": "documentDeletion",
+                    "enqueuedAt": "2021-08-12T10:00:00.000000Z",
+                    "transactionUid": 12
+                }
+            ]
+        )),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn create_transaction(
+    index_scheduler: Data<IndexScheduler>,
+    auth_controller: Data<AuthController>,
+    params: AwebJson<Vec<TransactionOperation>, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let operations = params.into_inner();
+    debug!(parameters = ?operations, "Create transaction");
+    let idempotency_key = parse_idempotency_key_header(&req)?;
+
+    if operations.is_empty() {
+        return Err(ResponseError::from_msg(
+            "A transaction must contain at least one operation.".to_string(),
+            Code::InvalidTransactionOperations,
+        ));
+    }
+
+    ensure_operations_can_be_merged(&operations)?;
+
+    let auth_token = extract_token_from_request(&req)?;
+    let mut operations_with_creation = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let filters = authorize_operation(auth_controller.clone(), auth_token, &operation)?;
+        let allow_index_creation = filters.allow_index_creation(&operation.index_uid);
+        operations_with_creation.push((operation, allow_index_creation));
+    }
+
+    let indexes: BTreeSet<&str> =
+        operations_with_creation.iter().map(|(op, _)| op.index_uid.as_str()).collect();
+    analytics.publish(
+        TransactionEnqueuedAnalytics {
+            max_operations: operations_with_creation.len(),
+            max_indexes: indexes.len(),
+        },
+        &req,
+    );
+
+    let scheduler = index_scheduler.clone();
+    let tasks = tokio::task::spawn_blocking(move || {
+        register_transaction(&scheduler, operations_with_creation, idempotency_key)
+    })
+    .await??;
+
+    let tasks: Vec<SummarizedTaskView> = tasks.into_iter().map(SummarizedTaskView::from).collect();
+    debug!(returns = ?tasks, "Create transaction");
+    Ok(HttpResponse::Accepted().json(tasks))
+}
+
+/// Authenticate an operation with the action required by the route of the same operation on its index.
+///
+/// A transaction can operate on any index with any operation, so its actions can't be checked
+/// by a [`GuardedData`](crate::extractors::authentication::GuardedData) policy.
+fn authorize_operation(
+    auth: Data<AuthController>,
+    token: Option<&str>,
+    operation: &TransactionOperation,
+) -> Result<AuthFilter, ResponseError> {
+    use TransactionOperationType::*;
+
+    let index = Some(operation.index_uid.as_str());
+    let token = token.unwrap_or_default();
+    let missing_master_key = auth.get_master_key().is_none();
+    let result = match operation.kind {
+        AddDocuments | UpdateDocuments => {
+            ActionPolicy::<{ actions::DOCUMENTS_ADD }>::authenticate(auth, token, index)
+        }
+        DeleteDocuments | DeleteDocumentsByFilter => {
+            ActionPolicy::<{ actions::DOCUMENTS_DELETE }>::authenticate(auth, token, index)
+        }
+        UpdateSettings => {
+            ActionPolicy::<{ actions::SETTINGS_UPDATE }>::authenticate(auth, token, index)
+        }
+    };
+    result.map_err(|e| {
+        if missing_master_key {
+            AuthenticationError::MissingMasterKey.into()
+        } else if token.is_empty() {
+            AuthenticationError::MissingAuthorizationHeader.into()
+        } else {
+            ResponseError::from_msg(e.to_string(), Code::InvalidApiKey)
+        }
+    })
+}
+
+/// Ensure the operations of each index can be merged into a single index operation.
+///
+/// The indexer only sees the committed state of an index, so the scheduler applies all the
+/// operations of an index at once and can't apply a transaction whose operations don't merge.
+fn ensure_operations_can_be_merged(
+    operations: &[TransactionOperation],
+) -> Result<(), ResponseError> {
+    use TransactionOperationType::*;
+
+    /// What the previous operations of an index did.
+    #[derive(Default)]
+    struct IndexOperations<'a> {
+        settings: bool,
+        documents: bool,
+        additions: bool,
+        primary_key: Option<&'a str>,
+    }
+
+    let mut indexes: BTreeMap<&str, IndexOperations> = BTreeMap::new();
+    for (position, operation) in operations.iter().enumerate() {
+        let index_uid = operation.index_uid.as_str();
+        let conflict = |reason: &str| {
+            Err(ResponseError::from_msg(
+                format!("The operation at position {position} cannot be applied along with the previous operations on the index `{index_uid}`: {reason}"),
+                Code::InvalidTransactionOperations,
+            ))
+        };
+
+        let previous = indexes.entry(index_uid).or_default();
+        match operation.kind {
+            UpdateSettings if previous.documents => {
+                return conflict("the settings updates cannot be mixed with document operations.")
+            }
+            UpdateSettings => previous.settings = true,
+            _ if previous.settings => {
+                return conflict("the settings updates cannot be mixed with document operations.")
+            }
+            DeleteDocumentsByFilter if previous.additions => {
+                return conflict(
+                    "the deletions by filter must come before the document additions and updates.",
+                )
+            }
+            DeleteDocuments | DeleteDocumentsByFilter => previous.documents = true,
+            AddDocuments | UpdateDocuments => {
+                previous.documents = true;
+                previous.additions = true;
+            }
+        }
+
+        if let Some(primary_key) = operation.primary_key.as_deref() {
+            match previous.primary_key.replace(primary_key) {
+                Some(other) if other != primary_key => {
+                    return conflict(
+                        "all the operations of an index must use the same primary key.",
+                    )
+                }
+                _ => (),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert the operations into tasks and register them as a single transaction.
+///
+/// The update files created for the documents are deleted if an operation is invalid.
+fn register_transaction(
+    index_scheduler: &IndexScheduler,
+    operations: Vec<(TransactionOperation, bool)>,
+    idempotency_key: Option<String>,
+) -> Result<Vec<Task>, ResponseError> {
+    let mut kinds = Vec::with_capacity(operations.len());
+    for (position, (operation, allow_index_creation)) in operations.into_iter().enumerate() {
+        match operation_into_kind(index_scheduler, position, operation, allow_index_creation) {
+            Ok(kind) => kinds.push(kind),
+            Err(e) => {
+                for kind in &kinds {
+                    if let KindWithContent::DocumentAdditionOrUpdate { content_file, .. } = kind {
+                        if let Err(e) = index_scheduler.queue.delete_update_file(*content_file) {
+                            tracing::warn!(
+                                index_uuid = %content_file,
+                                "Unknown error happened while deleting the update file of a transaction: {e}"
+                            );
+                        }
+                    }
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    // the scheduler deletes the update files itself if it can't register the transaction
+    Ok(index_scheduler.register_transaction(kinds, idempotency_key)?)
+}
+
+fn operation_into_kind(
+    index_scheduler: &IndexScheduler,
+    position: usize,
+    operation: TransactionOperation,
+    allow_index_creation: bool,
+) -> Result<KindWithContent, ResponseError> {
+    let TransactionOperation {
+        kind,
+        index_uid,
+        documents,
+        primary_key,
+        document_ids,
+        filter,
+        settings,
+    } = operation;
+    let index_uid = index_uid.into_inner();
+    let missing = |field: &str| {
+        ResponseError::from_msg(
+            format!("The operation at position {position} is missing the `{field}` field required by its type."),
+            Code::InvalidTransactionOperations,
+        )
+    };
+
+    Ok(match kind {
+        TransactionOperationType::AddDocuments | TransactionOperationType::UpdateDocuments => {
+            let documents = documents.ok_or_else(|| missing("documents"))?;
+            let (content_file, mut update_file) = index_scheduler.queue.create_update_file()?;
+            let documents_count = (|| -> Result<u64, MeilisearchHttpError> {
+                let mut payload = tempfile::tempfile().map_err(index_scheduler::Error::from)?;
+                serde_json::to_writer(&mut payload, &documents)?;
+                let documents_count = read_json(&payload, &mut update_file)?;
+                update_file.persist()?;
+                Ok(documents_count)
+            })();
+            let documents_count = match documents_count {
+                Ok(documents_count) => documents_count,
+                Err(e) => {
+                    // the file may not have been persisted, we ignore the errors
+                    let _ = index_scheduler.queue.delete_update_file(content_file);
+                    return Err(e.into());
+                }
+            };
+
+            KindWithContent::DocumentAdditionOrUpdate {
+                index_uid,
+                primary_key,
+                method: if kind == TransactionOperationType::AddDocuments {
+                    IndexDocumentsMethod::ReplaceDocuments
+                } else {
+                    IndexDocumentsMethod::UpdateDocuments
+                },
+                content_file,
+                documents_count,
+                allow_index_creation,
+                on_missing_document: MissingDocumentPolicy::Create,
+            }
+        }
+        TransactionOperationType::DeleteDocuments => {
+            let documents_ids = document_ids
+                .ok_or_else(|| missing("documentIds"))?
+                .iter()
+                .map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))
+                .collect();
+            KindWithContent::DocumentDeletion { index_uid, documents_ids }
+        }
+        TransactionOperationType::DeleteDocumentsByFilter => {
+            let filter_expr = filter.ok_or_else(|| missing("filter"))?;
+            // we ensure the filter is well formed before enqueuing it
+            parse_local_index_filter(
+                &filter_expr,
+                None,
+                index_scheduler.features(),
+                Code::InvalidDocumentFilter,
+            )?
+            .ok_or(MeilisearchHttpError::EmptyFilter)?;
+            KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr }
+        }
+        TransactionOperationType::UpdateSettings => {
+            let settings = settings.ok_or_else(|| missing("settings"))?;
+            let new_settings = validate_settings(settings, index_scheduler)?;
+            KindWithContent::SettingsUpdate {
+                index_uid,
+                new_settings: Box::new(new_settings),
+                is_deletion: false,
+                allow_index_creation,
+            }
+        }
+    })
+}
@@ -13,12 +13,16 @@ use deserr::{DeserializeError, ValuePointerRef};
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::{immutable_field_error, DeserrJsonError};
 use meilisearch_types::error::deserr_codes::{
-    BadRequest, InvalidWebhookHeaders, InvalidWebhookUrl,
+    BadRequest, InvalidWebhookFilters, InvalidWebhookHeaders, InvalidWebhookSecret,
+    InvalidWebhookUrl,
 };
 use meilisearch_types::error::{Code, ErrorCode, ResponseError};
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
 use meilisearch_types::keys::actions;
 use meilisearch_types::milli::update::Setting;
-use meilisearch_types::webhooks::Webhook;
+use meilisearch_types::star_or::StarOr;
+use meilisearch_types::tasks::{Kind, Status};
+use meilisearch_types::webhooks::{Webhook, WebhookDeliveryStatus, WebhookFilters};
 use serde::Serialize;
 use tracing::debug;
 use url::Url;
@@ -34,6 +38,8 @@ use crate::extractors::authentication::GuardedData;
     routes(
         "" => [get(get_webhooks), post(post_webhook)],
         "/{uuid}" => [get(get_webhook), patch(patch_webhook), delete(delete_webhook)],
+        "/{uuid}/deliveries" => [get(get_webhook_deliveries)],
+        "/{uuid}/deliveries/replay" => [post(replay_webhook_deliveries)],
     ),
     tag = "Webhooks",
     tags((
@@ -53,6 +59,53 @@ pub(super) struct WebhookSettings {
     /// HTTP headers to include in webhook requests.
     #[request(default, error = DeserrJsonError<InvalidWebhookHeaders>, schema_type = Option<BTreeMap<String, String>>, example = json!({"Authorization":"Bearer a-secret-token"}))]
     headers: Setting<BTreeMap<String, Setting<String>>>,
+    /// Restricts the tasks sent to the webhook. All the finished tasks are sent when `null`.
+    #[request(default, error = DeserrJsonError<InvalidWebhookFilters>, schema_type = Option<WebhookFiltersSettings>, example = json!({"types": ["documentAdditionOrUpdate"], "statuses": ["failed"], "indexUids": ["movies*"]}))]
+    filters: Setting<WebhookFiltersSettings>,
+    /// Shared secret used to sign the payloads.
+    ///
+    /// When set, every request contains an `X-Meilisearch-Timestamp` header with the Unix timestamp of the
+    /// request and an `X-Meilisearch-Signature` header of the form `sha256=<signature>`, where the signature
+    /// is the hex encoded HMAC-SHA256 of `<timestamp>.<body>`, keyed with the secret. The body is the gzipped
+    /// payload, exactly as received.
+    #[request(default, error = DeserrJsonError<InvalidWebhookSecret>, schema_type = Option<String>, example = "a-signing-secret")]
+    secret: Setting<String>,
+}
+
+/// Tasks sent to a webhook
+///
+/// A task is sent if it matches all the filters that are not `null`.
+#[routes::request(override_error = DeserrJsonError<InvalidWebhookFilters>)]
+#[derive(Debug)]
+pub(super) struct WebhookFiltersSettings {
+    /// Types of the tasks to send. `*` matches all types.
+    #[request(default, error = DeserrJsonError<InvalidWebhookFilters>, schema_type = Option<Vec<String>>, example = json!(["documentAdditionOrUpdate", "settingsUpdate"]))]
+    types: Option<Vec<StarOr<Kind>>>,
+    /// Statuses of the tasks to send. `*` matches all statuses.
+    #[request(default, error = DeserrJsonError<InvalidWebhookFilters>, schema_type = Option<Vec<String>>, example = json!(["succeeded", "failed"]))]
+    statuses: Option<Vec<StarOr<Status>>>,
+    /// Index uid patterns of the tasks to send. Tasks that don't concern any index, like dumps, never match.
+    #[request(default, error = DeserrJsonError<InvalidWebhookFilters>, schema_type = Option<Vec<String>>, example = json!(["movies", "products_*"]))]
+    index_uids: Option<Vec<IndexUidPattern>>,
+}
+
+impl From<WebhookFiltersSettings> for WebhookFilters {
+    fn from(filters: WebhookFiltersSettings) -> Self {
+        fn without_star<T>(list: Option<Vec<StarOr<T>>>) -> Option<Vec<T>> {
+            let mut filtered = Vec::new();
+            for value in list? {
+                match value {
+                    StarOr::Star => return None,
+                    StarOr::Other(value) => filtered.push(value),
+                }
+            }
+            Some(filtered)
+        }
+
+        let WebhookFiltersSettings { types, statuses, index_uids } = filters;
+        let index_uids = index_uids.filter(|patterns| !patterns.iter().any(|p| p.matches_all()));
+        WebhookFilters { types: without_star(types), statuses: without_star(statuses), index_uids }
+    }
 }
 
 fn deny_immutable_fields_webhook(
@@ -80,7 +133,7 @@ pub(super) struct WebhookWithMetadataRedactedAuthorization {
     uuid: Uuid,
     /// Whether the webhook can be edited.
     is_editable: bool,
-    /// URL and headers. Authorization header values are redacted in the response.
+    /// URL, headers and filters. Authorization header values and the secret are redacted in the response.
     #[schema(value_type = WebhookSettings)]
     #[serde(flatten)]
     webhook: Webhook,
@@ -203,6 +256,8 @@ enum WebhooksError {
     InvalidUrl(String, url::ParseError),
     #[error("Invalid UUID: {0}")]
     InvalidUuid(uuid::Error),
+    #[error("The secret of the webhook `{0}` cannot be empty. Hint: To stop signing the payloads set the secret to `null`")]
+    EmptySecret(Uuid),
 }
 
 impl ErrorCode for WebhooksError {
@@ -217,6 +272,7 @@ impl ErrorCode for WebhooksError {
             InvalidHeaderValue(_, _) => meilisearch_types::error::Code::InvalidWebhookHeaders,
             InvalidUrl(_, _) => meilisearch_types::error::Code::InvalidWebhookUrl,
             InvalidUuid(_) => meilisearch_types::error::Code::InvalidWebhookUuid,
+            EmptySecret(_) => meilisearch_types::error::Code::InvalidWebhookSecret,
         }
     }
 }
@@ -226,7 +282,8 @@ fn patch_webhook_inner(
     old_webhook: Webhook,
     new_webhook: WebhookSettings,
 ) -> Result<Webhook, WebhooksError> {
-    let Webhook { url: old_url, mut headers } = old_webhook;
+    let Webhook { url: old_url, mut headers, filters: old_filters, secret: old_secret } =
+        old_webhook;
 
     let url = match new_webhook.url {
         Setting::Set(url) => url,
@@ -257,7 +314,19 @@ fn patch_webhook_inner(
         return Err(TooManyHeaders(uuid.to_owned()));
     }
 
-    Ok(Webhook { url, headers })
+    let filters = match new_webhook.filters {
+        Setting::Set(filters) => filters.into(),
+        Setting::Reset => WebhookFilters::default(),
+        Setting::NotSet => old_filters,
+    };
+
+    let secret = match new_webhook.secret {
+        Setting::Set(secret) => Some(secret),
+        Setting::Reset => None,
+        Setting::NotSet => old_secret,
+    };
+
+    Ok(Webhook { url, headers, filters, secret })
 }
 
 fn check_changed(uuid: Uuid, webhook: &Webhook) -> Result<(), WebhooksError> {
@@ -283,6 +352,10 @@ fn check_changed(uuid: Uuid, webhook: &Webhook) -> Result<(), WebhooksError> {
         return Err(InvalidUrl(webhook.url.to_owned(), e));
     }
 
+    if webhook.secret.as_ref().is_some_and(|secret| secret.is_empty()) {
+        return Err(EmptySecret(uuid));
+    }
+
     Ok(())
 }
 
@@ -333,7 +406,7 @@ async fn get_webhook(
 
 /// Create webhook
 ///
-/// Register a new webhook to receive task completion notifications. You can optionally set custom headers (e.g. for authentication) and configure the callback URL.
+/// Register a new webhook to receive task completion notifications. You can optionally set custom headers (e.g. for authentication), restrict the notified tasks with filters, and set a secret used to sign the payloads.
 #[routes::path(
     request_body = WebhookSettings,
     security(("Bearer" = ["webhooks.create", "webhooks.*", "*"])),
@@ -386,6 +459,8 @@ async fn post_webhook(
             .set()
             .map(|h| h.into_iter().map(|(k, v)| (k, v.set().unwrap_or_default())).collect())
             .unwrap_or_default(),
+        filters: webhook_settings.filters.set().map(WebhookFilters::from).unwrap_or_default(),
+        secret: webhook_settings.secret.set(),
     };
 
     check_changed(uuid, &webhook)?;
@@ -401,7 +476,7 @@ async fn post_webhook(
 
 /// Update webhook
 ///
-/// Update the URL or headers of an existing webhook identified by its UUID.
+/// Update the URL, headers, filters or secret of an existing webhook identified by its UUID.
 #[routes::path(
     request_body = WebhookSettings,
     security(("Bearer" = ["webhooks.update", "webhooks.*", "*"])),
@@ -509,3 +584,100 @@ async fn delete_webhook(
     debug!(returns = "No Content", "Delete webhook");
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// Get webhook deliveries
+///
+/// Return the state of the deliveries of the task notifications to a webhook. A notification that cannot be delivered is retried with an exponential backoff, and is counted as failed after 10 attempts.
+#[routes::path(
+    security(("Bearer" = ["webhooks.get", "webhooks.*", "*.get", "*"])),
+    responses(
+        (status = 200, description = "Deliveries of the webhook.", body = WebhookDeliveryStatus, content_type = "application/json", example = json!({
+            "pending": 2,
+            "failed": 1,
+            "lastSuccessAt": "2025-01-01T00:00:00Z",
+            "lastErrorAt": "2025-01-01T00:01:00Z",
+            "lastError": "http status: 503"
+        })),
+        (status = 404, description = "Webhook not found.", body = ResponseError, content_type = "application/json", example = json!({
+            "message": "The webhook was not found.",
+            "code": "webhook_not_found",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+        })),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!({
+            "message": "The Authorization header is missing. It must use the bearer authorization method.",
+            "code": "missing_authorization_header",
+            "type": "auth",
+            "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+        })),
+    ),
+    params(
+        ("uuid" = Uuid, Path, description = "Universally unique identifier of the webhook.")
+    )
+)]
+async fn get_webhook_deliveries(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_GET }>, Data<IndexScheduler>>,
+    uuid: Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = Uuid::from_str(&uuid.into_inner()).map_err(InvalidUuid)?;
+    if !index_scheduler.webhooks_view().webhooks.contains_key(&uuid) {
+        return Err(WebhookNotFound(uuid).into());
+    }
+
+    let status = index_scheduler.webhook_delivery_status(uuid)?;
+
+    debug!(returns = ?status, "Get webhook deliveries");
+    Ok(HttpResponse::Ok().json(status))
+}
+
+/// Number of failed deliveries scheduled to be sent again.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ReplayedDeliveries {
+    /// Number of notifications that will be delivered again.
+    replayed: u64,
+}
+
+/// Replay webhook deliveries
+///
+/// Send again the task notifications that could not be delivered to a webhook after all the attempts, in the order they were enqueued.
+#[routes::path(
+    security(("Bearer" = ["webhooks.update", "webhooks.*", "*"])),
+    responses(
+        (status = 202, description = "The failed deliveries are scheduled to be sent again.", body = ReplayedDeliveries, content_type = "application/json", example = json!({
+            "replayed": 1
+        })),
+        (status = 404, description = "Webhook not found.", body = ResponseError, content_type = "application/json", example = json!({
+            "message": "The webhook was not found.",
+            "code": "webhook_not_found",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+        })),
+        (status = 401, description = "The authorization header is missing.", body = ResponseError, content_type = "application/json", example = json!({
+            "message": "The Authorization header is missing. It must use the bearer authorization method.",
+            "code": "missing_authorization_header",
+            "type": "auth",
+            "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+        })),
+    ),
+    params(
+        ("uuid" = Uuid, Path, description = "Universally unique identifier of the webhook.")
+    )
+)]
+async fn replay_webhook_deliveries(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<IndexScheduler>>,
+    uuid: Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = Uuid::from_str(&uuid.into_inner()).map_err(InvalidUuid)?;
+    debug!(parameters = ?uuid, "Replay webhook deliveries");
+
+    if !index_scheduler.webhooks_view().webhooks.contains_key(&uuid) {
+        return Err(WebhookNotFound(uuid).into());
+    }
+
+    let replayed = index_scheduler.replay_webhook_deliveries(uuid)?;
+    let response = ReplayedDeliveries { replayed };
+
+    debug!(returns = ?response, "Replay webhook deliveries");
+    Ok(HttpResponse::Accepted().json(response))
+}
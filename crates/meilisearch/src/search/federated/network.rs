@@ -142,11 +142,13 @@ fn fixup_query_federation(
         q: _,
         vector: _,
         media: _,
+        semantic_queries: _,
         hybrid: _,
         offset,
         limit,
         page,
         hits_per_page,
+        search_after: _,
         attributes_to_retrieve: _,
         retrieve_vectors: _,
         attributes_to_crop: _,
@@ -157,15 +159,26 @@ fn fixup_query_federation(
         show_performance_details,
         use_network: _,
         show_matches_position: _,
+        attributes_to_snippet: _,
+        snippet_count: _,
         filter: _,
         sort: _,
         distinct,
+        group_by: _,
         facets,
+        disjunctive_facets: _,
+        facet_aggregations: _,
+        geo_grid: _,
+        explain: _,
         highlight_pre_tag: _,
         highlight_post_tag: _,
         crop_marker: _,
         matching_strategy: _,
+        query_syntax: _,
+        typo_tolerance: _,
+        use_synonyms: _,
         attributes_to_search_on: _,
+        attribute_weights: _,
         ranking_score_threshold: _,
         locales: _,
         personalize,
@@ -1056,6 +1056,30 @@ impl PartitionedQueries {
             .into());
         }
 
+        if federated_query.has_disjunctive_facets() {
+            return Err(MeilisearchHttpError::DisjunctiveFacetsInFederatedQuery.into());
+        }
+
+        if federated_query.has_facet_aggregations() {
+            return Err(MeilisearchHttpError::FacetAggregationsInFederatedQuery.into());
+        }
+
+        if federated_query.has_geo_grid() {
+            return Err(MeilisearchHttpError::GeoGridInFederatedQuery.into());
+        }
+
+        if federated_query.has_explain() {
+            return Err(MeilisearchHttpError::ExplainInFederatedQuery.into());
+        }
+
+        if federated_query.has_group_by() {
+            return Err(MeilisearchHttpError::GroupByInFederatedQuery.into());
+        }
+
+        if federated_query.has_search_after() {
+            return Err(MeilisearchHttpError::SearchAfterInFederatedQuery.into());
+        }
+
         if federated_query.has_personalize() {
             return Err(MeilisearchHttpError::PersonalizationInFederatedQuery.into());
         }
@@ -1553,6 +1577,8 @@ impl SearchByIndex {
                     highlight_pre_tag: query.highlight_pre_tag,
                     highlight_post_tag: query.highlight_post_tag,
                     show_matches_position: query.show_matches_position,
+                    attributes_to_snippet: query.attributes_to_snippet,
+                    snippet_count: query.snippet_count,
                     sort: query.sort,
                     show_ranking_score: query.show_ranking_score,
                     show_ranking_score_details: query.show_ranking_score_details,
@@ -1567,6 +1593,7 @@ impl SearchByIndex {
                     degraded: query_degraded,
                     used_negative_operator: query_used_negative_operator,
                     query_vector,
+                    explanation: _,
                 } = result;
 
                 if query.retrieve_vectors {
@@ -1713,6 +1740,8 @@ impl SearchByIndex {
                     &rtxn,
                     &fidmap,
                     candidates,
+                    // the queries of an index are merged, there is no single selected node
+                    None,
                 )
             })
             .transpose()
@@ -1788,6 +1817,7 @@ impl SearchByIndex {
                     &rtxn,
                     &fidmap,
                     Default::default(),
+                    None,
                 ) {
                     if self.show_federation_info == ShowFederationInfo::Always {
                         error.message = format!(
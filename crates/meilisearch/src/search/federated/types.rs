@@ -265,12 +265,17 @@ impl FederatedSearchResult {
         }
         SearchResult {
             hits,
+            groups: None,
             query,
             query_vector,
             processing_time_ms,
             hits_info,
+            next_search_after: None,
             facet_distribution,
             facet_stats,
+            facet_aggregations: None,
+            geo_grid: None,
+            explain: None,
             request_uid,
             metadata,
             remote_errors,
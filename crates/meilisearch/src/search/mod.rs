@@ -1,13 +1,17 @@
 use core::fmt;
+use std::borrow::Cow;
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::Infallible;
 use std::ops::Not as _;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Instant;
 
-use deserr::Deserr;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 pub use federated::ProxyQuery;
 use index_scheduler::filter::{
     filter_into_index_filter, filters_into_index_filters, parse_filter,
@@ -26,7 +30,7 @@ use meilisearch_types::milli::index::{self, EmbeddingsWithMetadata, SearchParame
 use meilisearch_types::milli::progress::Progress;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::vector::Embedder;
+use meilisearch_types::milli::vector::{Embedder, Embedding};
 use meilisearch_types::milli::{
     filtered_matching_patterns, filtered_universe, make_document, AttributePatterns,
     AttributeState, Deadline, Error, FacetValueHit, Filter, IndexFilter, InternalError,
@@ -37,12 +41,16 @@ use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, FormatOptions, Index, LocalizedAttributesRule, MatchBounds,
-    MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, DistinctGroup, DocumentId, FieldId, FieldsIdsMap, FormatOptions, Index,
+    LocalizedAttributesRule, MatchBounds, MatcherBuilder, SearchCursor, Snippet, SortError,
+    TermsMatchingStrategy, Weight, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 #[cfg(test)]
 mod mod_test;
 use utoipa::ToSchema;
@@ -61,14 +69,18 @@ use hydration::hydrate_documents;
 mod ranking_rules;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
+type Snippets = BTreeMap<String, Vec<Snippet>>;
 
 pub const DEFAULT_SEARCH_OFFSET: fn() -> usize = || 0;
 pub const DEFAULT_SEARCH_LIMIT: fn() -> usize = || 20;
 pub const DEFAULT_CROP_LENGTH: fn() -> usize = || 10;
 pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
+pub const DEFAULT_SNIPPET_COUNT: fn() -> usize = || 3;
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_HITS_PER_GROUP: fn() -> usize = || 3;
+pub const DEFAULT_SEMANTIC_QUERY_WEIGHT: fn() -> f32 = || 1.0;
 pub const INCLUDE_METADATA_HEADER: &str = "Meili-Include-Metadata";
 
 /// Configuration for [personalized search](https://www.meilisearch.com/docs/learn/personalization/making_personalized_search_queries) results.
@@ -134,6 +146,15 @@ pub struct SearchQuery {
     /// Set to 0 to obtain the exhaustive `totalHits` count without returning any documents.
     #[request(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    /// Return the documents ranked after the position described by this cursor.
+    ///
+    /// Use the `nextSearchAfter` value of the previous response to get the next page of results, or an empty string to get the first page.
+    ///
+    /// Unlike `offset`, the cost of a page does not grow with its depth, and `maxTotalHits` does not limit how deep you can paginate.
+    ///
+    /// Cannot be used with `offset`, `page`, `hitsPerPage`, or hybrid search.
+    #[request(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
     /// List of attributes to include in each returned document.
     ///
     /// Use `["*"]` to return all attributes; if not set, the index [displayed attributes](https://www.meilisearch.com/docs/learn/relevancy/displayed_searchable_attributes) list is used.
@@ -200,6 +221,22 @@ pub struct SearchQuery {
     /// Note: reports match positions in all attributes, even non-searchable ones. Positions are measured in bytes, not characters.
     #[request(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>)]
     pub show_matches_position: bool,
+    /// Attributes from which the best passages matching the query should be extracted.
+    ///
+    /// The passages appear in each hit's `_snippets` object, along with their byte offset (`start` and `length`) in the attribute value and their score.
+    ///
+    /// Length of each passage is controlled by `cropLength`, or you can override it per attribute with the `attribute:length` syntax.
+    ///
+    /// Use `["*"]` to extract passages from all attributes in `attributesToRetrieve`.
+    ///
+    /// Passages never overlap, are highlighted with `highlightPreTag` and `highlightPostTag`, and are returned in the order they appear in the attribute value.
+    #[request(default, error = DeserrJsonError<InvalidSearchAttributesToSnippet>)]
+    pub attributes_to_snippet: Option<Vec<String>>,
+    /// Maximum number of passages to extract from each attribute.
+    ///
+    /// This parameter only applies when `attributesToSnippet` is set.
+    #[request(error = DeserrJsonError<InvalidSearchSnippetCount>, default = DEFAULT_SNIPPET_COUNT(), schema_default = DEFAULT_SNIPPET_COUNT)]
+    pub snippet_count: usize,
     /// A [filter](https://www.meilisearch.com/docs/learn/filtering_and_sorting/filter_search_results) expression to narrow results.
     ///
     /// All attributes used in the expression must be in [filterableAttributes](https://www.meilisearch.com/docs/reference/api/settings/update-all-settings#body-filterable-attributes-one-of-0).
@@ -207,6 +244,8 @@ pub struct SearchQuery {
     /// You can pass a string (e.g. `"(genres = horror OR genres = mystery) AND director = 'Jordan Peele'"`) or an array (e.g. `[["genres = horror", "genres = mystery"], "director = 'Jordan Peele'"]`).
     ///
     /// For [geo search](https://www.meilisearch.com/docs/learn/filtering_and_sorting/geosearch), use `_geoRadius(lat, lng, distance_in_meters)`, `_geoBoundingBox([lat,lng],[lat,lng])`, or `_geoPolygon([lat,lng], ...)` (GeoJSON only for polygon).
+    ///
+    /// Use `_geoShape(relation, 'geojson')` to match the `_geojson` shapes that `intersects`, are `within`, `contains` or are `disjoint` from any GeoJSON geometry.
     #[request(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
     /// Sort results by one or more attributes and their order.
@@ -215,6 +254,8 @@ pub struct SearchQuery {
     ///
     /// For [geo search](https://www.meilisearch.com/docs/learn/filtering_and_sorting/geosearch), use `_geoPoint(lat,lng):asc` or `:desc`; the response then includes `_geoDistance` in meters.
     ///
+    /// Use `_geoShapePoint(lat,lng):asc` or `:desc` to sort by the distance to the nearest edge of the `_geojson` shapes, a shape containing the point being at a distance of zero.
+    ///
     /// The first attribute in the list has precedence.
     ///
     /// See [sorting search results](https://www.meilisearch.com/docs/learn/filtering_and_sorting/sort_search_results).
@@ -229,6 +270,16 @@ pub struct SearchQuery {
     /// See [distinct attribute](https://www.meilisearch.com/docs/learn/relevancy/distinct_attribute).
     #[request(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    /// Group the results by the values of an attribute and return the best documents of each group.
+    ///
+    /// `hits` behaves as with a `distinct` on `attribute`: it contains the best document of each group, and `offset`/`limit` or `page`/`hitsPerPage` paginate the groups.
+    /// The response includes `groups`, ranked like their best document, each with the grouped `value`, its `totalHits` and its `hitsPerGroup` best documents.
+    ///
+    /// The attribute must be in [filterableAttributes](https://www.meilisearch.com/docs/reference/api/settings/update-all-settings#body-filterable-attributes-one-of-0). Cannot be used with `distinct`.
+    ///
+    /// POST only.
+    #[request(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<GroupBy>,
     /// Return the count of matches per facet value for the listed attributes.
     ///
     /// The response includes `facetDistribution` and, for numeric facets, `facetStats` (min/max).
@@ -240,6 +291,37 @@ pub struct SearchQuery {
     /// More info: [faceting](https://www.meilisearch.com/docs/learn/filtering_and_sorting/search_with_facet_filters).
     #[request(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<AttributePatterns>,
+    /// Return the count of matches per facet value for the listed attributes, computed as if the filter did not contain the clauses on that same attribute.
+    ///
+    /// This is what multi-select facet interfaces need: the values of a facet stay selectable even when the filter already selects some of them.
+    /// Only the clauses combined with `AND` are removed; an `OR` or `NOT` mixing several attributes is kept.
+    ///
+    /// The results are merged into `facetDistribution` and `facetStats`, and override the distribution computed for `facets` for these attributes.
+    #[request(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
+    /// Count the matching documents in buckets of numeric facet values, for each listed attribute.
+    ///
+    /// Each attribute takes either `ranges`, a list of `{ "from": 10, "to": 20 }` objects where `from` is inclusive, `to` is exclusive and both are optional,
+    /// or `interval`, the width of consecutive buckets covering all the values of the matching documents.
+    ///
+    /// Use timestamps to aggregate dates. The number of ranges or histogram buckets of an attribute is limited to [maxValuesPerFacet](https://www.meilisearch.com/docs/reference/api/settings/update-faceting#body-max-values-per-facet-one-of-0).
+    ///
+    /// The response includes `facetAggregations`. The attributes must be in [filterableAttributes](https://www.meilisearch.com/docs/reference/api/settings/update-all-settings#body-filterable-attributes-one-of-0).
+    ///
+    /// POST only.
+    #[request(default, error = DeserrJsonError<InvalidSearchFacetAggregations>)]
+    pub facet_aggregations: Option<BTreeMap<String, FacetAggregation>>,
+    /// Count the matching documents per cell of a [geohash](https://en.wikipedia.org/wiki/Geohash) grid, according to their `_geo` point.
+    ///
+    /// `precision` is the length of the geohash of the cells, from 1 (cells about 5000km wide) to 12 (cells about 4cm wide).
+    ///
+    /// The response includes `geoGrid`, the most populated cells first with the mean position of their documents, limited to [maxValuesPerFacet](https://www.meilisearch.com/docs/reference/api/settings/update-faceting#body-max-values-per-facet-one-of-0) cells.
+    ///
+    /// `_geo` must be in [filterableAttributes](https://www.meilisearch.com/docs/reference/api/settings/update-all-settings#body-filterable-attributes-one-of-0).
+    ///
+    /// POST only.
+    #[request(default, error = DeserrJsonError<InvalidSearchGeoGrid>)]
+    pub geo_grid: Option<GeoGrid>,
     /// How to match query terms when there are not enough results to satisfy `limit`.
     ///
     /// **`last`**: Returns documents containing all query terms first. If there are not enough such results, Meilisearch removes one query term at a time, starting from the end of the query (e.g. for "big fat cat", then "big fat", then "big").
@@ -251,6 +333,27 @@ pub struct SearchQuery {
     /// Default: `last`.
     #[request(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>)]
     pub matching_strategy: MatchingStrategy,
+    /// Syntax used to parse `q`.
+    ///
+    /// **`simple`**: Words, phrases between double quotes (e.g. `"big fat cat"`) and words or phrases excluded with a `-` prefix.
+    ///
+    /// **`advanced`**: The simple syntax, plus terms restricted to a searchable attribute (`title:cat`, `title:"fat cat"`, `title:(cat OR dog)`), alternatives separated by `OR`, groups between parentheses and terms made mandatory with a `+` prefix (e.g. `+(cat OR dog) title:big -overview:"fat cat"`). Attribute-restricted terms are always mandatory.
+    ///
+    /// Default: `simple`.
+    #[request(default, error = DeserrJsonError<InvalidSearchQuerySyntax>)]
+    pub query_syntax: QuerySyntax,
+    /// Override the [typo tolerance settings](https://www.meilisearch.com/docs/reference/api/settings/update-all-settings#body-typo-tolerance) of the index for this query only, other queries are not affected.
+    ///
+    /// Accepts `enabled` and `minWordSizeForTypos` (with `oneTypo` and `twoTypos`), the omitted fields keep the value of the settings.
+    ///
+    /// Useful to disable typos for exact lookups, such as SKUs or titles.
+    #[request(default, error = DeserrJsonError<InvalidSearchTypoTolerance>)]
+    pub typo_tolerance: Option<TypoToleranceQuery>,
+    /// When `false`, the [synonyms](https://www.meilisearch.com/docs/reference/api/settings/update-all-settings#body-synonyms) of the index are ignored for this query only.
+    ///
+    /// Default: `true`.
+    #[request(default, error = DeserrJsonError<InvalidSearchUseSynonyms>)]
+    pub use_synonyms: Option<bool>,
     /// Restrict the search to the listed attributes only.
     ///
     /// Each attribute must be in the index [searchable attributes](https://www.meilisearch.com/docs/learn/relevancy/displayed_searchable_attributes) list.
@@ -258,6 +361,15 @@ pub struct SearchQuery {
     /// The order of attributes in this parameter does not affect relevancy.
     #[request(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    /// Override the importance of the searchable attributes for the `attribute` and `attributeRank` ranking rules, without updating the settings.
+    ///
+    /// Maps attributes to weights (e.g. `{ "title": 2, "overview": 1 }`): the greater the weight, the more important the attribute. The weight of an attribute applies to its nested fields.
+    ///
+    /// Searchable attributes missing from the object have a weight of 0. When set, the effective weights appear in the `_rankingScoreDetails` of the attribute rule.
+    ///
+    /// POST only.
+    #[request(default, error = DeserrJsonError<InvalidSearchAttributeWeights>)]
+    pub attribute_weights: Option<BTreeMap<String, Weight>>,
     /// Exclude from the results any document whose [ranking score](https://www.meilisearch.com/docs/learn/relevancy/ranking_score) is below this value (between 0.0 and 1.0).
     ///
     /// Excluded hits do not count toward `estimatedTotalHits`, `totalHits`, or facet distribution.
@@ -310,6 +422,15 @@ pub struct SearchQuery {
     /// POST only.
     #[request(default, error = DeserrJsonError<InvalidSearchMedia>)]
     pub media: Option<serde_json::Value>,
+    /// Texts, vectors or media combined into the query vector of a semantic or hybrid search, each with a `weight` (defaults to `1.0`).
+    ///
+    /// The embeddings are normalized and summed according to their weights: a negative weight moves the results away from its embedding, allowing queries like "similar to A and B but not to C".
+    ///
+    /// An embedder is required; this parameter is incompatible with `vector` and `media`.
+    ///
+    /// POST only.
+    #[request(default, error = DeserrJsonError<InvalidSearchSemanticQueries>, try_from(Option<Vec<SemanticQuery>>) = validate_semantic_queries -> InvalidSearchSemanticQueries)]
+    pub semantic_queries: Option<Vec<SemanticQuery>>,
     /// [Personalized search](https://www.meilisearch.com/docs/learn/personalization/making_personalized_search_queries): provide an object with a `userContext` field (a string describing the user, e.g. preferences or behavior).
     ///
     /// Results are then tailored to that profile.
@@ -348,6 +469,13 @@ pub struct SearchQuery {
     /// When true, the response includes a `performanceDetails` object with a timing breakdown of the query processing.
     #[request(default, error = DeserrJsonError<InvalidSearchShowPerformanceDetails>)]
     pub show_performance_details: bool,
+    /// When true, the response includes an `explain` object describing how the [ranking rules](https://www.meilisearch.com/docs/learn/relevancy/ranking_rules) sorted the documents.
+    ///
+    /// For each ranking rule: the sizes of its buckets and how many candidates it sorted. For each query term: the typos, synonyms and prefixes it matched. For each hit: the rank of its bucket for each ranking rule.
+    ///
+    /// In a hybrid search, only the keyword search is explained.
+    #[request(default, error = DeserrJsonError<InvalidSearchExplain>)]
+    pub explain: bool,
 }
 
 /// Helper trait for queries that can be networked.
@@ -433,6 +561,7 @@ impl From<SearchParameters> for SearchQuery {
             limit: limit.unwrap_or_else(DEFAULT_SEARCH_LIMIT),
             page: None,
             hits_per_page: None,
+            search_after: None,
             attributes_to_retrieve: None,
             attributes_to_crop: None,
             crop_length: DEFAULT_CROP_LENGTH(),
@@ -441,12 +570,22 @@ impl From<SearchParameters> for SearchQuery {
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             show_matches_position: false,
+            attributes_to_snippet: None,
+            snippet_count: DEFAULT_SNIPPET_COUNT(),
             filter: None,
             sort,
             distinct,
+            group_by: None,
             facets: None,
+            disjunctive_facets: None,
+            facet_aggregations: None,
+            geo_grid: None,
             matching_strategy: matching_strategy.map(MatchingStrategy::from).unwrap_or_default(),
+            query_syntax: QuerySyntax::default(),
+            typo_tolerance: None,
+            use_synonyms: None,
             attributes_to_search_on,
+            attribute_weights: None,
             ranking_score_threshold: ranking_score_threshold.map(RankingScoreThreshold::from),
             locales: None,
             hybrid: hybrid.map(|index::HybridQuery { semantic_ratio, embedder }| HybridQuery {
@@ -458,12 +597,14 @@ impl From<SearchParameters> for SearchQuery {
             vector: None,
             retrieve_vectors: false,
             media: None,
+            semantic_queries: None,
             personalize: None,
             // TODO: support `use_network` in chat route (not trivial)
             use_network: None,
             show_ranking_score: false,
             show_ranking_score_details: false,
             show_performance_details: false,
+            explain: false,
         }
     }
 }
@@ -523,6 +664,7 @@ impl fmt::Debug for SearchQuery {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             attributes_to_crop,
             crop_length,
@@ -531,23 +673,35 @@ impl fmt::Debug for SearchQuery {
             highlight_pre_tag,
             highlight_post_tag,
             show_matches_position,
+            attributes_to_snippet,
+            snippet_count,
             filter,
             sort,
             distinct,
+            group_by,
             facets,
+            disjunctive_facets,
+            facet_aggregations,
+            geo_grid,
             matching_strategy,
+            query_syntax,
+            typo_tolerance,
+            use_synonyms,
             attributes_to_search_on,
+            attribute_weights,
             ranking_score_threshold,
             locales,
             hybrid,
             vector,
             retrieve_vectors,
             media,
+            semantic_queries,
             personalize,
             use_network,
             show_ranking_score,
             show_ranking_score_details,
             show_performance_details,
+            explain,
         } = self;
 
         let mut debug = f.debug_struct("SearchQuery");
@@ -560,6 +714,9 @@ impl fmt::Debug for SearchQuery {
         if let Some(hits_per_page) = hits_per_page {
             debug.field("hits_per_page", &hits_per_page);
         }
+        if let Some(search_after) = search_after {
+            debug.field("search_after", &search_after);
+        }
 
         // Then, everything related to the queries
         if let Some(q) = q {
@@ -581,12 +738,18 @@ impl fmt::Debug for SearchQuery {
         if let Some(media) = media {
             debug.field("media", media);
         }
+        if let Some(semantic_queries) = semantic_queries {
+            debug.field("semantic_queries", &semantic_queries);
+        }
         if let Some(hybrid) = hybrid {
             debug.field("hybrid", &hybrid);
         }
         if let Some(attributes_to_search_on) = attributes_to_search_on {
             debug.field("attributes_to_search_on", &attributes_to_search_on);
         }
+        if let Some(attribute_weights) = attribute_weights {
+            debug.field("attribute_weights", &attribute_weights);
+        }
         if let Some(filter) = filter {
             debug.field("filter", &filter);
         }
@@ -596,10 +759,29 @@ impl fmt::Debug for SearchQuery {
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
+        if let Some(group_by) = group_by {
+            debug.field("group_by", &group_by);
+        }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if let Some(disjunctive_facets) = disjunctive_facets {
+            debug.field("disjunctive_facets", &disjunctive_facets);
+        }
+        if let Some(facet_aggregations) = facet_aggregations {
+            debug.field("facet_aggregations", &facet_aggregations);
+        }
+        if let Some(geo_grid) = geo_grid {
+            debug.field("geo_grid", &geo_grid);
+        }
         debug.field("matching_strategy", &matching_strategy);
+        debug.field("query_syntax", &query_syntax);
+        if let Some(typo_tolerance) = typo_tolerance {
+            debug.field("typo_tolerance", &typo_tolerance);
+        }
+        if let Some(use_synonyms) = use_synonyms {
+            debug.field("use_synonyms", &use_synonyms);
+        }
 
         // Then everything related to the formatting
         debug.field("crop_length", &crop_length);
@@ -615,6 +797,9 @@ impl fmt::Debug for SearchQuery {
         if *show_performance_details {
             debug.field("show_performance_details", show_performance_details);
         }
+        if *explain {
+            debug.field("explain", explain);
+        }
         debug.field("crop_length", &crop_length);
         if let Some(facets) = facets {
             debug.field("facets", &facets);
@@ -625,6 +810,10 @@ impl fmt::Debug for SearchQuery {
         if let Some(attributes_to_crop) = attributes_to_crop {
             debug.field("attributes_to_crop", &attributes_to_crop);
         }
+        if let Some(attributes_to_snippet) = attributes_to_snippet {
+            debug.field("attributes_to_snippet", &attributes_to_snippet);
+            debug.field("snippet_count", &snippet_count);
+        }
         if let Some(attributes_to_highlight) = attributes_to_highlight {
             debug.field("attributes_to_highlight", &attributes_to_highlight);
         }
@@ -665,6 +854,240 @@ pub struct HybridQuery {
     pub embedder: String,
 }
 
+/// Typo tolerance overriding the one of the settings for a single query.
+///
+/// The omitted fields keep the value of the `typoTolerance` settings of the index.
+#[routes::request(proxied, validate = validate_typo_tolerance -> DeserrJsonError<InvalidSearchTypoTolerance>, override_error = DeserrJsonError<InvalidSearchTypoTolerance>)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TypoToleranceQuery {
+    /// Whether typos are allowed.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Minimum length of the words to accept one or two typos.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub min_word_size_for_typos: Option<MinWordSizeForTyposQuery>,
+}
+
+/// Minimum length of the words to accept typos.
+#[routes::request(proxied, override_error = DeserrJsonError<InvalidSearchTypoTolerance>)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MinWordSizeForTyposQuery {
+    /// Minimum length of a word to accept one typo.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub one_typo: Option<u8>,
+    /// Minimum length of a word to accept two typos.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub two_typos: Option<u8>,
+}
+
+fn validate_typo_tolerance<E: DeserializeError>(
+    typo_tolerance: TypoToleranceQuery,
+    location: ValuePointerRef,
+) -> Result<TypoToleranceQuery, E> {
+    let sizes = typo_tolerance.min_word_size_for_typos.unwrap_or_default();
+    match sizes.one_typo.zip(sizes.two_typos) {
+        Some((one_typo, two_typos)) if one_typo > two_typos => {
+            Err(deserr::take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: format!(
+                        "`oneTypo` (`{one_typo}`) must be lower than or equal to `twoTypos` (`{two_typos}`)"
+                    ),
+                },
+                location.push_key("minWordSizeForTypos"),
+            )))
+        }
+        _ => Ok(typo_tolerance),
+    }
+}
+
+impl From<&TypoToleranceQuery> for milli::TypoToleranceOverride {
+    fn from(typo_tolerance: &TypoToleranceQuery) -> Self {
+        let sizes = typo_tolerance.min_word_size_for_typos.unwrap_or_default();
+        milli::TypoToleranceOverride {
+            enabled: typo_tolerance.enabled,
+            min_word_len_one_typo: sizes.one_typo,
+            min_word_len_two_typos: sizes.two_typos,
+        }
+    }
+}
+
+/// Text, vector or media embedded and combined with the others into the query vector of a semantic search.
+///
+/// Exactly one of `vector` or `q`/`media` must be set.
+#[routes::request(proxied, validate = validate_semantic_query -> DeserrJsonError<InvalidSearchSemanticQueries>, override_error = DeserrJsonError<InvalidSearchSemanticQueries>)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticQuery {
+    /// Text embedded with the embedder of the search.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    /// Vector used as is, its length must match the dimensions of the embedder.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub vector: Option<Vec<f32>>,
+    /// Media embedded with the search fragments of the embedder.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<serde_json::Value>,
+    /// Weight of the embedding in the combination, a negative weight moves the results away from it.
+    #[request(default = DEFAULT_SEMANTIC_QUERY_WEIGHT(), schema_default = DEFAULT_SEMANTIC_QUERY_WEIGHT)]
+    pub weight: f32,
+}
+
+fn validate_semantic_query<E: DeserializeError>(
+    query: SemanticQuery,
+    location: ValuePointerRef,
+) -> Result<SemanticQuery, E> {
+    let error = |msg: String, location: ValuePointerRef| {
+        deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg },
+            location,
+        ))
+    };
+
+    if !query.weight.is_finite() || query.weight == 0.0 {
+        return Err(error(
+            format!("`weight` must be a non-zero number, found `{}`", query.weight),
+            location.push_key("weight"),
+        ));
+    }
+    match (&query.vector, query.q.is_some() || query.media.is_some()) {
+        (Some(_), true) => Err(error(
+            "`vector` cannot be specified together with `q` or `media`".to_string(),
+            location,
+        )),
+        (None, false) => {
+            Err(error("one of `q`, `vector` or `media` must be specified".to_string(), location))
+        }
+        _ => Ok(query),
+    }
+}
+
+fn validate_semantic_queries(
+    semantic_queries: Option<Vec<SemanticQuery>>,
+) -> Result<Option<Vec<SemanticQuery>>, InvalidSearchSemanticQueries> {
+    match semantic_queries {
+        Some(semantic_queries) if semantic_queries.is_empty() => Err(InvalidSearchSemanticQueries),
+        semantic_queries => Ok(semantic_queries),
+    }
+}
+
+/// Buckets of numeric values to count the matching documents in for a facet.
+///
+/// Exactly one of `ranges` or `interval` must be set.
+#[routes::request(proxied, validate = validate_facet_aggregation -> DeserrJsonError<InvalidSearchFacetAggregations>, override_error = DeserrJsonError<InvalidSearchFacetAggregations>)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetAggregation {
+    /// User-defined ranges of values, returned in the same order.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub ranges: Option<Vec<FacetRange>>,
+    /// Width of the buckets of a histogram.
+    ///
+    /// The first bucket starts at the multiple of `interval` right below the smallest value.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<f64>,
+}
+
+/// Range of numeric values, `from` is inclusive and `to` is exclusive.
+#[routes::request(proxied, override_error = DeserrJsonError<InvalidSearchFacetAggregations>)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRange {
+    /// Lower bound of the range, unbounded when omitted.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    /// Upper bound of the range, unbounded when omitted.
+    #[request(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+}
+
+fn validate_facet_aggregation<E: DeserializeError>(
+    aggregation: FacetAggregation,
+    location: ValuePointerRef,
+) -> Result<FacetAggregation, E> {
+    let error = |msg: String, location: ValuePointerRef| {
+        deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg },
+            location,
+        ))
+    };
+
+    match (&aggregation.ranges, aggregation.interval) {
+        (Some(_), Some(_)) | (None, None) => Err(error(
+            "exactly one of `ranges` or `interval` must be specified".to_string(),
+            location,
+        )),
+        (None, Some(interval)) if !interval.is_finite() || interval <= 0.0 => Err(error(
+            format!("`interval` must be a positive number, found `{interval}`"),
+            location.push_key("interval"),
+        )),
+        (Some(ranges), None) => {
+            for (i, range) in ranges.iter().enumerate() {
+                if let Some((from, to)) = range.from.zip(range.to).filter(|(from, to)| from >= to) {
+                    return Err(error(
+                        format!("`to` (`{to}`) must be greater than `from` (`{from}`)"),
+                        location.push_key("ranges").push_index(i),
+                    ));
+                }
+            }
+            Ok(aggregation)
+        }
+        (None, Some(_)) => Ok(aggregation),
+    }
+}
+
+impl From<&FacetAggregation> for milli::FacetAggregation {
+    fn from(aggregation: &FacetAggregation) -> Self {
+        match (&aggregation.ranges, aggregation.interval) {
+            (None, Some(interval)) => milli::FacetAggregation::Histogram { interval },
+            (ranges, _) => milli::FacetAggregation::Ranges(
+                ranges.iter().flatten().map(|range| (range.from, range.to)).collect(),
+            ),
+        }
+    }
+}
+
+/// Precision of the geohash grid to count the matching documents in.
+#[routes::request(proxied, validate = validate_geo_grid -> DeserrJsonError<InvalidSearchGeoGrid>, override_error = DeserrJsonError<InvalidSearchGeoGrid>)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoGrid {
+    /// Length of the geohash of the cells, from 1 to 12.
+    #[request(required)]
+    pub precision: usize,
+}
+
+fn validate_geo_grid<E: DeserializeError>(
+    geo_grid: GeoGrid,
+    location: ValuePointerRef,
+) -> Result<GeoGrid, E> {
+    if (1..=milli::MAX_GEOHASH_PRECISION).contains(&geo_grid.precision) {
+        Ok(geo_grid)
+    } else {
+        Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!(
+                    "`precision` must be between 1 and {}, found `{}`",
+                    milli::MAX_GEOHASH_PRECISION,
+                    geo_grid.precision
+                ),
+            },
+            location.push_key("precision"),
+        )))
+    }
+}
+
+/// Attribute to group the results by, and number of documents to return per group.
+#[routes::request(proxied, override_error = DeserrJsonError<InvalidSearchGroupBy>)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupBy {
+    /// Attribute whose values identify the groups.
+    #[request(required)]
+    pub attribute: String,
+    /// Maximum number of documents returned in each group.
+    #[request(default = DEFAULT_HITS_PER_GROUP(), schema_default = DEFAULT_HITS_PER_GROUP)]
+    pub hits_per_group: usize,
+}
+
 #[derive(Clone)]
 pub enum SearchKind {
     KeywordOnly,
@@ -814,6 +1237,9 @@ pub struct SearchQueryWithIndex {
     /// Maximum number of documents returned for a page
     #[request(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    /// Return the documents ranked after this cursor
+    #[request(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
     /// Attributes to display in the returned documents
     #[request(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
@@ -838,6 +1264,12 @@ pub struct SearchQueryWithIndex {
     /// Return matching terms location
     #[request(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
+    /// Attributes from which the best matching passages have to be extracted
+    #[request(default, error = DeserrJsonError<InvalidSearchAttributesToSnippet>)]
+    pub attributes_to_snippet: Option<Vec<String>>,
+    /// Maximum number of passages extracted from each attribute
+    #[request(default, error = DeserrJsonError<InvalidSearchSnippetCount>, default = DEFAULT_SNIPPET_COUNT())]
+    pub snippet_count: usize,
     /// Filter queries by an attribute's value
     #[request(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
@@ -848,15 +1280,39 @@ pub struct SearchQueryWithIndex {
     /// attribute
     #[request(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    /// Group the results by the values of an attribute
+    #[request(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<GroupBy>,
     /// Display the count of matches per facet
     #[request(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<AttributePatterns>,
+    /// Display the count of matches per facet, ignoring the filter clauses on that same facet
+    #[request(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
+    /// Count the matching documents in buckets of numeric facet values
+    #[request(default, error = DeserrJsonError<InvalidSearchFacetAggregations>)]
+    pub facet_aggregations: Option<BTreeMap<String, FacetAggregation>>,
+    /// Count the matching documents per cell of a geohash grid
+    #[request(default, error = DeserrJsonError<InvalidSearchGeoGrid>)]
+    pub geo_grid: Option<GeoGrid>,
     /// Strategy used to match query terms within documents
     #[request(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>)]
     pub matching_strategy: MatchingStrategy,
+    /// Syntax used to parse the query
+    #[request(default, error = DeserrJsonError<InvalidSearchQuerySyntax>)]
+    pub query_syntax: QuerySyntax,
+    /// Override the typo tolerance settings for this query
+    #[request(default, error = DeserrJsonError<InvalidSearchTypoTolerance>)]
+    pub typo_tolerance: Option<TypoToleranceQuery>,
+    /// Whether to use the synonyms of the index for this query
+    #[request(default, error = DeserrJsonError<InvalidSearchUseSynonyms>)]
+    pub use_synonyms: Option<bool>,
     /// Restrict search to the specified attributes
     #[request(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    /// Override the weights of the searchable attributes
+    #[request(default, error = DeserrJsonError<InvalidSearchAttributeWeights>)]
+    pub attribute_weights: Option<BTreeMap<String, Weight>>,
     /// Exclude results below the specified ranking score
     #[request(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, schema_type = Option<f64>)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
@@ -877,6 +1333,9 @@ pub struct SearchQueryWithIndex {
     /// Perform AI-powered search queries with multimodal content
     #[request(default, error = DeserrJsonError<InvalidSearchMedia>)]
     pub media: Option<serde_json::Value>,
+    /// Weighted texts, vectors or media combined into the query vector
+    #[request(default, error = DeserrJsonError<InvalidSearchSemanticQueries>, try_from(Option<Vec<SemanticQuery>>) = validate_semantic_queries -> InvalidSearchSemanticQueries)]
+    pub semantic_queries: Option<Vec<SemanticQuery>>,
     /// Personalize search results
     #[request(default, error = DeserrJsonError<InvalidSearchPersonalize>, skip_serializing_if = "Option::is_none", )]
     pub personalize: Option<Personalize>,
@@ -905,6 +1364,9 @@ pub struct SearchQueryWithIndex {
     /// Adds a detailed performance details field
     #[request(default, error = DeserrJsonError<InvalidSearchShowPerformanceDetails>)]
     pub show_performance_details: Option<bool>,
+    /// Explains how the ranking rules sorted the documents
+    #[request(default, error = DeserrJsonError<InvalidSearchExplain>)]
+    pub explain: bool,
     /// Federation options for multi-index search
     #[request(default)]
     pub federation_options: Option<FederationOptions>,
@@ -929,6 +1391,26 @@ impl SearchQueryWithIndex {
         self.facets.as_ref().filter(|v| !v.is_empty())
     }
 
+    pub fn has_disjunctive_facets(&self) -> bool {
+        self.disjunctive_facets.as_ref().is_some_and(|facets| !facets.is_empty())
+    }
+
+    pub fn has_facet_aggregations(&self) -> bool {
+        self.facet_aggregations.as_ref().is_some_and(|aggregations| !aggregations.is_empty())
+    }
+
+    pub fn has_geo_grid(&self) -> bool {
+        self.geo_grid.is_some()
+    }
+
+    pub fn has_group_by(&self) -> bool {
+        self.group_by.is_some()
+    }
+
+    pub fn has_search_after(&self) -> bool {
+        self.search_after.is_some()
+    }
+
     pub fn has_personalize(&self) -> bool {
         self.personalize.is_some()
     }
@@ -941,6 +1423,10 @@ impl SearchQueryWithIndex {
         self.show_performance_details.is_some()
     }
 
+    pub fn has_explain(&self) -> bool {
+        self.explain
+    }
+
     fn has_distinct(&self) -> bool {
         self.distinct.is_some()
     }
@@ -956,6 +1442,7 @@ impl SearchQueryWithIndex {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             attributes_to_crop,
             crop_length,
@@ -964,23 +1451,35 @@ impl SearchQueryWithIndex {
             highlight_pre_tag,
             highlight_post_tag,
             show_matches_position,
+            attributes_to_snippet,
+            snippet_count,
             filter,
             sort,
             distinct,
+            group_by,
             facets,
+            disjunctive_facets,
+            facet_aggregations,
+            geo_grid,
             matching_strategy,
+            query_syntax,
+            typo_tolerance,
+            use_synonyms,
             attributes_to_search_on,
+            attribute_weights,
             ranking_score_threshold,
             locales,
             hybrid,
             vector,
             retrieve_vectors,
             media,
+            semantic_queries,
             personalize,
             use_network,
             show_ranking_score,
             show_ranking_score_details,
             show_performance_details,
+            explain,
         } = query;
 
         SearchQueryWithIndex {
@@ -990,6 +1489,7 @@ impl SearchQueryWithIndex {
             limit: if limit == DEFAULT_SEARCH_LIMIT() { None } else { Some(limit) },
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             attributes_to_crop,
             crop_length,
@@ -998,23 +1498,35 @@ impl SearchQueryWithIndex {
             highlight_pre_tag,
             highlight_post_tag,
             show_matches_position,
+            attributes_to_snippet,
+            snippet_count,
             filter,
             sort,
             distinct,
+            group_by,
             facets,
+            disjunctive_facets,
+            facet_aggregations,
+            geo_grid,
             matching_strategy,
+            query_syntax,
+            typo_tolerance,
+            use_synonyms,
             attributes_to_search_on,
+            attribute_weights,
             ranking_score_threshold,
             locales,
             hybrid,
             vector,
             retrieve_vectors,
             media,
+            semantic_queries,
             personalize,
             use_network,
             show_ranking_score,
             show_ranking_score_details,
             show_performance_details: show_performance_details.then_some(true),
+            explain,
             federation_options,
         }
     }
@@ -1027,6 +1539,7 @@ impl SearchQueryWithIndex {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             attributes_to_crop,
             crop_length,
@@ -1035,23 +1548,35 @@ impl SearchQueryWithIndex {
             highlight_pre_tag,
             highlight_post_tag,
             show_matches_position,
+            attributes_to_snippet,
+            snippet_count,
             filter,
             sort,
             distinct,
+            group_by,
             facets,
+            disjunctive_facets,
+            facet_aggregations,
+            geo_grid,
             matching_strategy,
+            query_syntax,
+            typo_tolerance,
+            use_synonyms,
             attributes_to_search_on,
+            attribute_weights,
             ranking_score_threshold,
             locales,
             hybrid,
             vector,
             retrieve_vectors,
             media,
+            semantic_queries,
             personalize,
             use_network,
             show_ranking_score,
             show_ranking_score_details,
             show_performance_details,
+            explain,
             federation_options,
         } = self;
         (
@@ -1062,6 +1587,7 @@ impl SearchQueryWithIndex {
                 limit: limit.unwrap_or(DEFAULT_SEARCH_LIMIT()),
                 page,
                 hits_per_page,
+                search_after,
                 attributes_to_retrieve,
                 attributes_to_crop,
                 crop_length,
@@ -1070,23 +1596,35 @@ impl SearchQueryWithIndex {
                 highlight_pre_tag,
                 highlight_post_tag,
                 show_matches_position,
+                attributes_to_snippet,
+                snippet_count,
                 filter,
                 sort,
                 distinct,
+                group_by,
                 facets,
+                disjunctive_facets,
+                facet_aggregations,
+                geo_grid,
                 matching_strategy,
+                query_syntax,
+                typo_tolerance,
+                use_synonyms,
                 attributes_to_search_on,
+                attribute_weights,
                 ranking_score_threshold,
                 locales,
                 hybrid,
                 vector,
                 retrieve_vectors,
                 media,
+                semantic_queries,
                 personalize,
                 use_network,
                 show_ranking_score,
                 show_ranking_score_details,
                 show_performance_details: show_performance_details.unwrap_or_default(),
+                explain,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -1096,7 +1634,7 @@ impl SearchQueryWithIndex {
 }
 
 /// Request body for similar document search
-#[routes::request]
+#[routes::request(validate = validate_similar_query -> DeserrJsonError)]
 #[derive(Debug, Clone, PartialEq)]
 pub struct SimilarQuery {
     /// Document ID to find similar documents for
@@ -1111,9 +1649,12 @@ pub struct SimilarQuery {
     /// Filter queries by an attribute's value
     #[request(default, error = DeserrJsonError<InvalidSimilarFilter>)]
     pub filter: Option<Value>,
-    /// Name of the embedder to use for semantic similarity
-    #[request(required, error = DeserrJsonError<InvalidSimilarEmbedder>)]
-    pub embedder: String,
+    /// Name of the embedder to use for semantic similarity, required unless the `mode` is `keyword`
+    #[request(default, error = DeserrJsonError<InvalidSimilarEmbedder>, schema_type = Option<String>)]
+    pub embedder: SimilarEmbedder,
+    /// How the similarity between documents is computed
+    #[request(default, error = DeserrJsonError<InvalidSimilarMode>)]
+    pub mode: SimilarMode,
     /// Attributes to display in the returned documents
     #[request(default, error = DeserrJsonError<InvalidSimilarAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
@@ -1134,6 +1675,54 @@ pub struct SimilarQuery {
     pub ranking_score_threshold: Option<RankingScoreThresholdSimilar>,
 }
 
+/// The embedder of a similar query, only omitted in the `keyword` mode.
+///
+/// Contrary to an `Option`, an explicit `null` is rejected like any other value that is not a string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimilarEmbedder(pub Option<String>);
+
+impl<E: DeserializeError> Deserr<E> for SimilarEmbedder {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        String::deserialize_from_value(value, location)
+            .map(|embedder| SimilarEmbedder(Some(embedder)))
+    }
+}
+
+/// How the similarity between documents is computed
+#[routes::request(no_error)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SimilarMode {
+    /// The documents with the closest embeddings, computed by the `embedder`
+    #[default]
+    Semantic,
+    /// The documents sharing the most discriminative words of the target document, works without embedders
+    Keyword,
+}
+
+fn validate_similar_query(
+    query: SimilarQuery,
+    location: ValuePointerRef,
+) -> Result<SimilarQuery, DeserrJsonError> {
+    match (query.mode, &query.embedder.0) {
+        (SimilarMode::Semantic, None) => {
+            Err(deserr::take_cf_content(DeserrJsonError::error::<Infallible>(
+                None,
+                ErrorKind::MissingField { field: "embedder" },
+                location,
+            )))
+        }
+        (SimilarMode::Keyword, Some(_)) => Err(DeserrJsonError::new(
+            "Invalid value at `.embedder`: the `keyword` mode does not use an embedder."
+                .to_string(),
+            Code::InvalidSimilarEmbedder,
+        )),
+        _ => Ok(query),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExternalDocumentId(String);
 
@@ -1188,6 +1777,26 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
     }
 }
 
+/// Syntax used to parse the search query
+#[routes::request(no_error, proxied)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuerySyntax {
+    /// Words, phrases and `-` exclusions
+    #[default]
+    Simple,
+    /// Also supports `attribute:term` restrictions, `OR` alternatives, parentheses and `+` mandatory terms
+    Advanced,
+}
+
+impl From<QuerySyntax> for milli::QuerySyntax {
+    fn from(other: QuerySyntax) -> Self {
+        match other {
+            QuerySyntax::Simple => Self::Simple,
+            QuerySyntax::Advanced => Self::Advanced,
+        }
+    }
+}
+
 impl From<index::MatchingStrategy> for MatchingStrategy {
     fn from(other: index::MatchingStrategy) -> Self {
         match other {
@@ -1253,6 +1862,12 @@ pub struct SearchHit {
     #[serde(default, rename = "_matchesPosition", skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Option<BTreeMap<String, Vec<MatchBounds>>>)]
     pub matches_position: Option<MatchesPosition>,
+    /// Best passages (`text`, `start`, `length`, `score`) matching the query per attribute.
+    ///
+    /// Present when `attributesToSnippet` was set.
+    #[serde(default, rename = "_snippets", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<BTreeMap<String, Vec<Snippet>>>)]
+    pub snippets: Option<Snippets>,
     /// Global [ranking score](https://www.meilisearch.com/docs/learn/relevancy/ranking_score) from 0.0 to 1.0.
     ///
     /// Present when `showRankingScore` was true.
@@ -1373,6 +1988,11 @@ pub struct SearchResult {
     ///
     /// Each hit contains document fields and, when requested, `_formatted`, `_matchesPosition`, `_rankingScore`, `_rankingScoreDetails`, `_geoDistance`.
     pub hits: Vec<SearchHit>,
+    /// Best documents of each group, ranked like the best document of the group.
+    ///
+    /// Present when `groupBy` was set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<SearchGroup>>,
     /// Query string that produced this response.
     pub query: String,
     /// Query embedding used for the search.
@@ -1387,6 +2007,11 @@ pub struct SearchResult {
     /// Either offset/limit with `estimatedTotalHits`, or page/hitsPerPage with `totalHits` and `totalPages`.
     #[serde(flatten)]
     pub hits_info: HitsInfo,
+    /// Cursor to pass as `searchAfter` to get the next page of results.
+    ///
+    /// Present when `searchAfter` was set and the page is full.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_search_after: Option<String>,
     /// Count of matching documents per facet value for each requested facet.
     ///
     /// Present when `facets` was set.
@@ -1398,6 +2023,21 @@ pub struct SearchResult {
     /// Present for numeric facets when `facets` was set.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    /// Count of matching documents per bucket of numeric values for each requested facet.
+    ///
+    /// Present when `facetAggregations` was set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_aggregations: Option<BTreeMap<String, Vec<FacetBucket>>>,
+    /// Count of matching documents per geohash cell.
+    ///
+    /// Present when `geoGrid` was set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_grid: Option<Vec<GeoGridBucket>>,
+    /// How the ranking rules sorted the documents.
+    ///
+    /// Present when `explain` was true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<Explanation>,
     /// UUID v7 identifying this search request.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_uid: Option<Uuid>,
@@ -1434,12 +2074,17 @@ impl fmt::Debug for SearchResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let SearchResult {
             hits,
+            groups,
             query,
             query_vector,
             processing_time_ms,
             hits_info,
+            next_search_after,
             facet_distribution,
             facet_stats,
+            facet_aggregations,
+            geo_grid,
+            explain,
             request_uid,
             metadata,
             semantic_hit_count,
@@ -1453,11 +2098,17 @@ impl fmt::Debug for SearchResult {
         // The most important thing when looking at a search result is the time it took to process
         debug.field("processing_time_ms", &processing_time_ms);
         debug.field("hits", &format!("[{} hits returned]", hits.len()));
+        if let Some(groups) = groups {
+            debug.field("groups", &format!("[{} groups returned]", groups.len()));
+        }
         debug.field("query", &query);
         if query_vector.is_some() {
             debug.field("query_vector", &"[...]");
         }
         debug.field("hits_info", &hits_info);
+        if let Some(next_search_after) = next_search_after {
+            debug.field("next_search_after", &next_search_after);
+        }
         if *used_negative_operator {
             debug.field("used_negative_operator", used_negative_operator);
         }
@@ -1470,6 +2121,15 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_aggregations) = facet_aggregations {
+            debug.field("facet_aggregations", &facet_aggregations);
+        }
+        if let Some(geo_grid) = geo_grid {
+            debug.field("geo_grid", &geo_grid);
+        }
+        if explain.is_some() {
+            debug.field("explain", &"{...}");
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
@@ -1565,6 +2225,211 @@ pub struct FacetStats {
     pub max: f64,
 }
 
+/// Number of matching documents whose `_geo` point is in a geohash cell.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct GeoGridBucket {
+    /// Geohash of the cell.
+    pub cell: String,
+    /// Number of matching documents in this cell.
+    pub count: u64,
+    /// Mean position of the matching documents in this cell.
+    pub centroid: GeoGridCentroid,
+}
+
+/// Coordinates of a point, in degrees.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, ToSchema)]
+pub struct GeoGridCentroid {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+impl From<milli::GeoGridBucket> for GeoGridBucket {
+    fn from(
+        milli::GeoGridBucket { cell, count, centroid: [lat, lng] }: milli::GeoGridBucket,
+    ) -> Self {
+        GeoGridBucket { cell, count, centroid: GeoGridCentroid { lat, lng } }
+    }
+}
+
+/// How the ranking rules sorted the documents of a search.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Explanation {
+    /// Number of candidates given to the first ranking rule.
+    pub initial_candidates: u64,
+    /// The ranking rules, in the order they were applied.
+    pub ranking_rules: Vec<RankingRuleExplanation>,
+    /// The words each query term matched. Empty for a placeholder or a vector search.
+    pub query_terms: Vec<QueryTermExplanation>,
+    /// The bucket of each hit for each ranking rule, in the order of the hits.
+    pub hits: Vec<HitExplanation>,
+}
+
+/// What a ranking rule did during the search.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RankingRuleExplanation {
+    pub name: String,
+    /// Number of times the ranking rule sorted a bucket of the previous ranking rule.
+    pub iterations: u64,
+    /// Number of candidates the ranking rule sorted, over all its iterations.
+    pub candidates: u64,
+    /// Number of buckets the ranking rule returned.
+    pub buckets: u64,
+    /// Sizes of the first 100 buckets the ranking rule returned.
+    pub bucket_sizes: Vec<u64>,
+    /// Number of candidates skipped because they are ranked before `offset`.
+    pub skipped: u64,
+}
+
+/// The words and phrases a query term matched.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTermExplanation {
+    pub original: String,
+    /// First and last positions of the term in the query, a term spans several words when they are concatenated.
+    #[schema(value_type = Vec<u16>)]
+    pub positions: [u16; 2],
+    pub is_prefix: bool,
+    pub exact: Option<String>,
+    /// Words starting with the term, when it is the last word of the query.
+    pub prefix_of: Vec<String>,
+    pub synonyms: Vec<String>,
+    pub split_words: Option<String>,
+    /// Words one typo away from the term, when the typos were computed.
+    pub one_typo: Vec<String>,
+    /// Words two typos away from the term, when the typos were computed.
+    pub two_typos: Vec<String>,
+}
+
+/// The buckets a hit was ranked in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HitExplanation {
+    /// Rank of the bucket of the hit for each ranking rule, down to the one that returned it.
+    ///
+    /// Empty for pinned hits, a rank is `null` when the hit was returned along with the rest of the candidates of the ranking rule.
+    pub buckets: Vec<HitBucket>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HitBucket {
+    pub ranking_rule: String,
+    pub rank: Option<u64>,
+}
+
+impl Explanation {
+    fn new(explanation: milli::SearchExplanation, documents_ids: &[DocumentId]) -> Self {
+        let milli::SearchExplanation { initial_candidates, ranking_rules, query_terms, documents } =
+            explanation;
+
+        let hits = documents_ids
+            .iter()
+            .map(|docid| {
+                let ranks = documents.get(docid).map(Vec::as_slice).unwrap_or_default();
+                let buckets = ranking_rules
+                    .iter()
+                    .zip(ranks)
+                    .map(|(ranking_rule, &rank)| HitBucket {
+                        ranking_rule: ranking_rule.name.clone(),
+                        rank,
+                    })
+                    .collect();
+                HitExplanation { buckets }
+            })
+            .collect();
+
+        let ranking_rules = ranking_rules
+            .into_iter()
+            .map(
+                |milli::RankingRuleExplanation {
+                     name,
+                     iterations,
+                     candidates,
+                     buckets,
+                     bucket_sizes,
+                     skipped,
+                 }| {
+                    RankingRuleExplanation {
+                        name,
+                        iterations,
+                        candidates,
+                        buckets,
+                        bucket_sizes,
+                        skipped,
+                    }
+                },
+            )
+            .collect();
+
+        let query_terms = query_terms
+            .into_iter()
+            .map(
+                |milli::QueryTermExplanation {
+                     original,
+                     positions,
+                     is_prefix,
+                     exact,
+                     prefix_of,
+                     synonyms,
+                     split_words,
+                     one_typo,
+                     two_typos,
+                 }| {
+                    QueryTermExplanation {
+                        original,
+                        positions: [*positions.start(), *positions.end()],
+                        is_prefix,
+                        exact,
+                        prefix_of,
+                        synonyms,
+                        split_words,
+                        one_typo,
+                        two_typos,
+                    }
+                },
+            )
+            .collect();
+
+        Explanation { initial_candidates, ranking_rules, query_terms, hits }
+    }
+}
+
+/// Documents sharing a value of the `groupBy` attribute.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct SearchGroup {
+    /// Value of the `groupBy` attribute shared by the documents of the group.
+    ///
+    /// `null` for the documents without value, which are each in their own group.
+    pub value: Value,
+    /// Number of documents of the group matching the search.
+    pub total_hits: u64,
+    /// Best documents of the group, at most `hitsPerGroup`.
+    pub hits: Vec<SearchHit>,
+}
+
+/// Number of matching documents whose facet values are within a range.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct FacetBucket {
+    /// Lower bound of the bucket, inclusive. Absent when unbounded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    /// Upper bound of the bucket, exclusive. Absent when unbounded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+    /// Number of matching documents with a value in this bucket.
+    pub count: u64,
+}
+
+impl From<milli::FacetBucket> for FacetBucket {
+    fn from(milli::FacetBucket { from, to, count }: milli::FacetBucket) -> Self {
+        FacetBucket { from, to, count }
+    }
+}
+
 /// Schema representation of a facet value hit (for OpenAPI documentation only).
 #[derive(ToSchema)]
 #[schema(rename_all = "camelCase")]
@@ -1618,6 +2483,48 @@ pub fn fuse_filters(left: Option<Value>, right: Option<Value>) -> Option<Value>
     }
 }
 
+/// Embeds each of the semantic queries and combines their embeddings according to their weights.
+fn embed_semantic_queries(
+    semantic_queries: &[SemanticQuery],
+    embedder: &Embedder,
+    deadline: &Deadline,
+) -> Result<Embedding, ResponseError> {
+    let span = tracing::trace_span!(target: "search::vector", "embed_semantic_queries");
+    let _entered = span.enter();
+
+    let mut embeddings = Vec::with_capacity(semantic_queries.len());
+    for SemanticQuery { q, vector, media, weight } in semantic_queries {
+        let embedding = match vector {
+            Some(vector) => {
+                if vector.len() != embedder.dimensions() {
+                    return Err(milli::Error::UserError(
+                        milli::UserError::InvalidVectorDimensions {
+                            expected: embedder.dimensions(),
+                            found: vector.len(),
+                        },
+                    )
+                    .into());
+                }
+                vector.clone()
+            }
+            None => {
+                let search_query = match (q.as_deref(), media.as_ref()) {
+                    (Some(text), None) => milli::vector::SearchQuery::Text(text),
+                    (q, media) => milli::vector::SearchQuery::Media { q, media },
+                };
+                embedder
+                    .embed_search(search_query, deadline.to_instant())
+                    .map_err(milli::vector::Error::from)
+                    .map_err(milli::Error::from)?
+            }
+        };
+        embeddings.push((embedding, *weight));
+    }
+
+    milli::vector::combine_weighted_embeddings(&embeddings)
+        .ok_or_else(|| MeilisearchHttpError::SemanticQueriesCancelOut.into())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn prepare_search<'t>(
     index: &'t Index,
@@ -1632,7 +2539,9 @@ pub fn prepare_search<'t>(
     features: RoFeatures,
     progress: &'t Progress,
 ) -> Result<(milli::Search<'t>, bool, usize, usize), ResponseError> {
-    if query.media.is_some() {
+    if query.media.is_some()
+        || query.semantic_queries.iter().flatten().any(|query| query.media.is_some())
+    {
         features.check_multimodal("passing `media` in a search query")?;
     }
     let mut search = index.search(rtxn, index_uid, fields_ids_map, before_search, progress);
@@ -1641,8 +2550,20 @@ pub fn prepare_search<'t>(
         search.ranking_score_threshold(ranking_score_threshold.0);
     }
 
-    if let Some(distinct) = &query.distinct {
-        search.distinct(distinct.clone());
+    match (&query.distinct, &query.group_by) {
+        (Some(_), Some(_)) => {
+            return Err(ResponseError::from_msg(
+                "`distinct` and `groupBy` cannot be used together.\n - Hint: `groupBy` already returns one hit per distinct value of its attribute".to_string(),
+                Code::InvalidSearchGroupBy,
+            ));
+        }
+        (Some(distinct), None) => {
+            search.distinct(distinct.clone());
+        }
+        (None, Some(group_by)) => {
+            search.distinct(group_by.attribute.clone());
+        }
+        (None, None) => (),
     }
 
     match search_kind {
@@ -1652,9 +2573,13 @@ pub fn prepare_search<'t>(
             }
         }
         SearchKind::SemanticOnly { embedder_name, embedder, quantized } => {
-            let vector = match query.vector.clone() {
-                Some(vector) => vector,
-                None => {
+            let vector = match (query.vector.clone(), &query.semantic_queries) {
+                (Some(vector), _) => vector,
+                (None, Some(semantic_queries)) => {
+                    let _step = progress.update_progress_scoped(SearchStep::EmbedQuery);
+                    embed_semantic_queries(semantic_queries, embedder, &deadline)?
+                }
+                (None, None) => {
                     let _step = progress.update_progress_scoped(SearchStep::EmbedQuery);
                     let span = tracing::trace_span!(target: "search::vector", "embed_one");
                     let _entered = span.enter();
@@ -1685,12 +2610,20 @@ pub fn prepare_search<'t>(
             if let Some(q) = &query.q {
                 search.query(q);
             }
+            let vector = match (query.vector.clone(), &query.semantic_queries) {
+                (Some(vector), _) => Some(vector),
+                (None, Some(semantic_queries)) => {
+                    let _step = progress.update_progress_scoped(SearchStep::EmbedQuery);
+                    Some(embed_semantic_queries(semantic_queries, embedder, &deadline)?)
+                }
+                (None, None) => None,
+            };
             // will be embedded in hybrid search if necessary
             search.semantic(
                 embedder_name.clone(),
                 embedder.clone(),
                 *quantized,
-                query.vector.clone(),
+                vector,
                 query.media.clone(),
             );
         }
@@ -1700,8 +2633,18 @@ pub fn prepare_search<'t>(
         search.searchable_attributes(searchable);
     }
 
+    if let Some(ref attribute_weights) = query.attribute_weights {
+        check_attribute_weights(index, rtxn, attribute_weights)?;
+        search.attribute_weights(attribute_weights);
+    }
+
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
+    search.query_syntax(query.query_syntax.into());
+    if let Some(typo_tolerance) = &query.typo_tolerance {
+        search.typo_tolerance(typo_tolerance.into());
+    }
+    search.ignore_synonyms(query.use_synonyms == Some(false));
 
     let max_total_hits = index
         .pagination_max_total_hits(rtxn)
@@ -1762,9 +2705,207 @@ pub fn prepare_search<'t>(
     Ok((search, is_finite_pagination, max_total_hits, offset))
 }
 
-pub struct SearchParams {
-    pub index_uid: String,
-    pub query: SearchQuery,
+/// Ensures that each attribute of the `attributeWeights` parameter is searchable.
+fn check_attribute_weights(
+    index: &Index,
+    rtxn: &RoTxn,
+    attribute_weights: &BTreeMap<String, Weight>,
+) -> Result<(), ResponseError> {
+    let Some(searchable) = index.user_defined_searchable_fields(rtxn)? else {
+        return Ok(());
+    };
+
+    for attribute in attribute_weights.keys() {
+        let is_searchable = searchable.iter().any(|searchable| {
+            milli::is_faceted_by(searchable, attribute)
+                || milli::is_faceted_by(attribute, searchable)
+        });
+        if !is_searchable {
+            let (valid_fields, hidden_fields) = index.remove_hidden_fields(rtxn, &searchable)?;
+            let valid_fields = valid_fields.into_iter().collect::<Vec<_>>().join(", ");
+            let hint = if hidden_fields {
+                " Some searchable attributes are hidden by the `displayedAttributes` setting."
+            } else {
+                ""
+            };
+            return Err(ResponseError::from_msg(
+                format!(
+                    "Attribute `{attribute}` of `attributeWeights` is not searchable. Available searchable attributes are: `{valid_fields}`.{hint}"
+                ),
+                Code::InvalidSearchAttributeWeights,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// A search cursor bound to the search that returned it.
+#[derive(Serialize, Deserialize)]
+struct BoundSearchCursor {
+    /// The hash of the parameters ranking the documents of the search, see [`search_cursor_hash`].
+    hash: String,
+    /// The time from which the decay ranking rules scored the documents of the first page.
+    #[serde(with = "time::serde::timestamp")]
+    now: time::OffsetDateTime,
+    cursor: SearchCursor,
+}
+
+/// The key encrypting the cursors, they contain the sort values of the last hit that may not be
+/// displayed. It is derived from the master key, or from a secret persisted in the task database,
+/// so that the cursors survive a restart and can be used on every instance sharing the master key.
+fn search_cursor_key(index_scheduler: &IndexScheduler) -> LessSafeKey {
+    let key = Sha256::new()
+        .chain_update("meilisearch search cursor key")
+        .chain_update(index_scheduler.search_cursor_secret())
+        .finalize();
+    LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &key).unwrap())
+}
+
+/// Hashes everything that changes the ranking of the documents of a search,
+/// a cursor can't be used with a search that ranks the documents differently.
+fn search_cursor_hash(
+    index_uid: &str,
+    query: &SearchQuery,
+    criteria: &[milli::Criterion],
+) -> String {
+    let SearchQuery {
+        q,
+        filter,
+        sort,
+        distinct,
+        matching_strategy,
+        query_syntax,
+        typo_tolerance,
+        use_synonyms,
+        attributes_to_search_on,
+        attribute_weights,
+        ranking_score_threshold,
+        locales,
+        vector,
+        media,
+        semantic_queries,
+        ..
+    } = query;
+    // the parameters are hashed in JSON rather than with their `Debug` representation
+    // that may change between versions and invalidate the cursors of a rolling upgrade
+    let parameters = serde_json::json!({
+        "indexUid": index_uid,
+        "rankingRules": criteria,
+        "q": q,
+        "filter": filter,
+        "sort": sort,
+        "distinct": distinct,
+        "matchingStrategy": matching_strategy,
+        "querySyntax": query_syntax,
+        "typoTolerance": typo_tolerance,
+        "useSynonyms": use_synonyms,
+        "attributesToSearchOn": attributes_to_search_on,
+        "attributeWeights": attribute_weights,
+        "rankingScoreThreshold": ranking_score_threshold,
+        "locales": locales,
+        "vector": vector,
+        "media": media,
+        "semanticQueries": semantic_queries,
+    });
+    format!("{:x}", Sha256::digest(parameters.to_string()))
+}
+
+/// Decodes the `searchAfter` cursor, an empty cursor starts the pagination from the first result.
+fn parse_search_after(
+    index_scheduler: &IndexScheduler,
+    search_after: &str,
+    hash: &str,
+    query: &SearchQuery,
+    search_kind: &SearchKind,
+) -> Result<Option<BoundSearchCursor>, ResponseError> {
+    if query.offset != DEFAULT_SEARCH_OFFSET() || query.is_finite_pagination() {
+        return Err(ResponseError::from_msg(
+            "`searchAfter` cannot be used with `offset`, `page` or `hitsPerPage`.\n - Hint: pass the `nextSearchAfter` of the previous response to get the next page".to_string(),
+            Code::InvalidSearchSearchAfter,
+        ));
+    }
+    if let SearchKind::Hybrid { .. } = search_kind {
+        return Err(ResponseError::from_msg(
+            "`searchAfter` cannot be used with `hybrid`.".to_string(),
+            Code::InvalidSearchSearchAfter,
+        ));
+    }
+    if search_after.is_empty() {
+        return Ok(None);
+    }
+
+    let cursor: BoundSearchCursor = decrypt_search_cursor(index_scheduler, search_after).ok_or_else(|| {
+        ResponseError::from_msg(
+            format!("Invalid `searchAfter`: `{search_after}` is not a `nextSearchAfter` returned by a previous search.\n - Hint: the `nextSearchAfter` are only valid on the instances sharing the master key of the one that returned them"),
+            Code::InvalidSearchSearchAfter,
+        )
+    })?;
+    if cursor.hash != hash {
+        return Err(ResponseError::from_msg(
+            "Invalid `searchAfter`: the `nextSearchAfter` was returned by a search with other parameters.\n - Hint: the query, filter, sort, distinct and other parameters ranking the documents must stay the same while paginating with `searchAfter`".to_string(),
+            Code::InvalidSearchSearchAfter,
+        ));
+    }
+    Ok(Some(cursor))
+}
+
+/// The position of the last ranked hit, pinned documents are not ranked.
+fn next_search_cursor(
+    documents_ids: &[DocumentId],
+    document_scores: &[Vec<ScoreDetails>],
+) -> Option<SearchCursor> {
+    documents_ids
+        .iter()
+        .zip(document_scores)
+        .rev()
+        .find(|(_, scores)| !matches!(scores.first(), Some(ScoreDetails::Pin { .. })))
+        .map(|(&docid, scores)| SearchCursor::new(scores, docid))
+}
+
+/// Encrypts the cursor with a random nonce, returned before the sealed cursor.
+fn encrypt_search_cursor(
+    index_scheduler: &IndexScheduler,
+    cursor: &BoundSearchCursor,
+) -> Result<String, ResponseError> {
+    let mut nonce = [0; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce).map_err(|_| {
+        ResponseError::from_msg(
+            "Could not draw the nonce of the `nextSearchAfter`.".to_string(),
+            Code::Internal,
+        )
+    })?;
+    let mut bytes = serde_json::to_vec(cursor).map_err(MeilisearchHttpError::from)?;
+    search_cursor_key(index_scheduler)
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut bytes)
+        .map_err(|_| {
+            ResponseError::from_msg(
+                "Could not encrypt the `nextSearchAfter`.".to_string(),
+                Code::Internal,
+            )
+        })?;
+    Ok(BASE64_URL_SAFE_NO_PAD.encode([nonce.as_slice(), &bytes].concat()))
+}
+
+/// Returns `None` if the cursor was not encrypted by [`encrypt_search_cursor`] with the same key.
+fn decrypt_search_cursor(
+    index_scheduler: &IndexScheduler,
+    search_after: &str,
+) -> Option<BoundSearchCursor> {
+    let mut bytes = BASE64_URL_SAFE_NO_PAD.decode(search_after).ok()?;
+    if bytes.len() < NONCE_LEN {
+        return None;
+    }
+    let mut sealed = bytes.split_off(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(&bytes).ok()?;
+    let opened =
+        search_cursor_key(index_scheduler).open_in_place(nonce, Aad::empty(), &mut sealed).ok()?;
+    serde_json::from_slice(opened).ok()
+}
+
+pub struct SearchParams {
+    pub index_uid: String,
+    pub query: SearchQuery,
     pub search_kind: SearchKind,
     pub retrieve_vectors: RetrieveVectors,
     pub features: RoFeatures,
@@ -1806,6 +2947,13 @@ pub fn perform_search(
         None => None,
     };
 
+    // the disjunctive facets need the original filter to remove their own clauses from it,
+    // the hierarchical facets to find their selected node, and the groups to rank their documents
+    let search_filter =
+        (query.facets.is_some() || query.disjunctive_facets.is_some() || query.group_by.is_some())
+            .then(|| filter.clone())
+            .flatten();
+
     let (mut search, is_finite_pagination, max_total_hits, offset) = prepare_search(
         index,
         &rtxn,
@@ -1831,6 +2979,37 @@ pub fn perform_search(
         search.dynamic_search_rules(dsrs, index_scheduler.dsr_fuel());
     }
 
+    if query.group_by.is_some() {
+        // the groups are ranked with the embedding of the query instead of embedding it for each group
+        search.retrieve_vectors(true);
+    }
+
+    search.explain(query.explain);
+
+    let search_cursor_binding = match &query.search_after {
+        Some(search_after) => {
+            let hash = search_cursor_hash(&index_uid, &query, &index.criteria(&rtxn)?);
+            // the cursors are made of the detailed scores of the last hit, including on the first page
+            search.scoring_strategy(ScoringStrategy::Detailed);
+            let now = match parse_search_after(
+                index_scheduler,
+                search_after,
+                &hash,
+                &query,
+                &search_kind,
+            )? {
+                Some(BoundSearchCursor { hash: _, now, cursor }) => {
+                    // the decay rules keep scoring the documents from the time of the first page
+                    search.search_after(cursor).decay_now(now);
+                    now
+                }
+                None => before_search,
+            };
+            Some((hash, now))
+        }
+        None => None,
+    };
+
     let (
         milli::SearchResult {
             documents_ids,
@@ -1840,9 +3019,73 @@ pub fn perform_search(
             degraded,
             used_negative_operator,
             query_vector,
+            explanation,
         },
         semantic_hit_count,
-    ) = search_from_kind(search_kind, search)?;
+    ) = search_from_kind(search_kind.clone(), search)?;
+
+    let explain = explanation.map(|explanation| Explanation::new(explanation, &documents_ids));
+
+    let disjunctive_facets = query
+        .disjunctive_facets
+        .as_deref()
+        .filter(|facets| !facets.is_empty())
+        .map(|facets| {
+            let _step = progress.update_progress_scoped(SearchStep::FacetDistribution);
+            compute_disjunctive_facets(
+                facets,
+                SearchAgainContext {
+                    index,
+                    rtxn: &rtxn,
+                    fields_ids_map: &fields_ids_map,
+                    index_uid: &index_uid,
+                    before_search,
+                    query: &query,
+                    filter: search_filter.as_ref(),
+                    search_kind: &search_kind,
+                    deadline: deadline.clone(),
+                    features,
+                    progress,
+                },
+                &candidates,
+            )
+        })
+        .transpose()?;
+
+    let groups = query
+        .group_by
+        .as_ref()
+        .map(|group_by| {
+            let _step = progress.update_progress_scoped(SearchStep::GroupHits);
+            rank_groups(
+                group_by,
+                SearchAgainContext {
+                    index,
+                    rtxn: &rtxn,
+                    fields_ids_map: &fields_ids_map,
+                    index_uid: &index_uid,
+                    before_search,
+                    query: &query,
+                    filter: search_filter.as_ref(),
+                    search_kind: &search_kind,
+                    deadline: deadline.clone(),
+                    features,
+                    progress,
+                },
+                &documents_ids,
+                query_vector.as_ref(),
+            )
+        })
+        .transpose()?;
+
+    let next_search_after = search_cursor_binding
+        .filter(|_| documents_ids.len() >= min(query.limit, max_total_hits))
+        .and_then(|(hash, now)| {
+            let cursor = next_search_cursor(&documents_ids, &document_scores)?;
+            Some(BoundSearchCursor { hash, now, cursor })
+        })
+        .map(|cursor| encrypt_search_cursor(index_scheduler, &cursor))
+        .transpose()?;
 
     let metadata = if include_metadata {
         let query_uid = Uuid::now_v7();
@@ -1864,6 +3107,7 @@ pub fn perform_search(
         limit,
         page,
         hits_per_page,
+        search_after: _,
         attributes_to_retrieve,
         attributes_to_crop,
         crop_length,
@@ -1872,23 +3116,35 @@ pub fn perform_search(
         highlight_pre_tag,
         highlight_post_tag,
         show_matches_position,
+        attributes_to_snippet,
+        snippet_count,
         filter: _,
         sort,
         distinct: _,
+        group_by: _,
         facets,
+        disjunctive_facets: _,
+        facet_aggregations,
+        geo_grid,
         matching_strategy: _,
+        query_syntax: _,
+        typo_tolerance: _,
+        use_synonyms: _,
         attributes_to_search_on: _,
+        attribute_weights: _,
         ranking_score_threshold: _,
         locales,
         hybrid: _,
         vector: _,
-        retrieve_vectors: _,
+        retrieve_vectors: retrieve_query_vector,
         media: _,
+        semantic_queries: _,
         personalize: _,
         use_network: _,
         show_ranking_score,
         show_ranking_score_details,
         show_performance_details: _,
+        explain: _,
     } = query;
 
     let format = AttributesFormat {
@@ -1902,19 +3158,30 @@ pub fn perform_search(
         highlight_pre_tag,
         highlight_post_tag,
         show_matches_position,
+        attributes_to_snippet,
+        snippet_count,
         sort,
         show_ranking_score,
         show_ranking_score_details,
         locales: locales.map(|l| l.iter().copied().map(Into::into).collect()),
     };
 
+    // the documents of the groups are formatted along with the hits
+    let groups_documents_ids_scores = groups
+        .iter()
+        .flatten()
+        .flat_map(|group| group.documents_ids.iter().copied().zip(group.document_scores.iter()));
     let mut documents = make_hits(
         index,
         &rtxn,
         &fields_ids_map,
         format,
         matching_words,
-        documents_ids.iter().copied().zip(document_scores.iter()),
+        documents_ids
+            .iter()
+            .copied()
+            .zip(document_scores.iter())
+            .chain(groups_documents_ids_scores),
         progress,
     )?;
 
@@ -1924,6 +3191,17 @@ pub fn perform_search(
         hydrate_documents(&mut documents, &foreign_keys, index_scheduler)?;
     }
 
+    let groups = groups.map(|groups| {
+        let mut groups_documents = documents.split_off(documents_ids.len()).into_iter();
+        groups
+            .into_iter()
+            .map(|RankedGroup { value, total_hits, documents_ids, document_scores: _ }| {
+                let hits = groups_documents.by_ref().take(documents_ids.len()).collect();
+                SearchGroup { value, total_hits, hits }
+            })
+            .collect()
+    });
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -1942,23 +3220,67 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let (facet_distribution, facet_stats) = facets
+    let facet_aggregations = facet_aggregations
+        .filter(|aggregations| !aggregations.is_empty())
+        .map(|aggregations| {
+            let _step = progress.update_progress_scoped(SearchStep::FacetDistribution);
+            compute_facet_aggregations(
+                &aggregations,
+                index,
+                &rtxn,
+                &fields_ids_map,
+                candidates.clone(),
+            )
+        })
+        .transpose()?;
+
+    let geo_grid = geo_grid
+        .map(|GeoGrid { precision }| {
+            let _step = progress.update_progress_scoped(SearchStep::FacetDistribution);
+            compute_geo_grid(precision, index, &rtxn, &fields_ids_map, candidates.clone())
+        })
+        .transpose()?;
+
+    let computed_facets = facets
         .map(move |facets| {
             let _step = progress.update_progress_scoped(SearchStep::FacetDistribution);
-            compute_facet_distribution_stats(&facets, index, &rtxn, &fields_ids_map, candidates)
+            compute_facet_distribution_stats(
+                &facets,
+                index,
+                &rtxn,
+                &fields_ids_map,
+                candidates,
+                search_filter.as_ref(),
+            )
         })
-        .transpose()?
-        .map(|ComputedFacets { distribution, stats }| (distribution, stats))
-        .unzip();
+        .transpose()?;
+
+    let (facet_distribution, facet_stats) = match (computed_facets, disjunctive_facets) {
+        (Some(mut computed_facets), Some(disjunctive_facets)) => {
+            // the disjunctive distribution of a facet takes precedence over its regular one
+            let patterns =
+                AttributePatterns::from(query.disjunctive_facets.clone().unwrap_or_default());
+            computed_facets.replace(&patterns, disjunctive_facets);
+            Some(computed_facets)
+        }
+        (computed_facets, disjunctive_facets) => computed_facets.or(disjunctive_facets),
+    }
+    .map(|ComputedFacets { distribution, stats }| (distribution, stats))
+    .unzip();
 
     let result = SearchResult {
         hits: documents,
+        groups,
         hits_info,
         query: q.unwrap_or_default(),
-        query_vector,
+        query_vector: query_vector.filter(|_| retrieve_query_vector),
         processing_time_ms: elapsed(before_search).as_millis(),
+        next_search_after,
         facet_distribution,
         facet_stats,
+        facet_aggregations,
+        geo_grid,
+        explain,
         degraded,
         used_negative_operator,
         semantic_hit_count,
@@ -1981,6 +3303,15 @@ pub struct ComputedFacets {
 }
 
 impl ComputedFacets {
+    /// Replaces the distribution and the stats of the facets matching the patterns with the ones
+    /// of `other`, a facet missing from `other` is removed.
+    fn replace(&mut self, patterns: &AttributePatterns, other: ComputedFacets) {
+        self.distribution.retain(|field, _| patterns.match_str(field) != PatternMatch::Match);
+        self.stats.retain(|field, _| patterns.match_str(field) != PatternMatch::Match);
+        self.distribution.extend(other.distribution);
+        self.stats.extend(other.stats);
+    }
+
     pub fn remove_hits(&mut self, hits: &[SearchHit]) {
         if hits.is_empty() {
             return;
@@ -2038,12 +3369,194 @@ pub enum Route {
     Similar,
 }
 
+/// What is needed to run the search again, with a relaxed filter or on a subset of the documents.
+struct SearchAgainContext<'a> {
+    index: &'a Index,
+    rtxn: &'a RoTxn<'a>,
+    fields_ids_map: &'a FieldsIdsMap,
+    index_uid: &'a str,
+    before_search: time::OffsetDateTime,
+    query: &'a SearchQuery,
+    filter: Option<&'a IndexFilter>,
+    search_kind: &'a SearchKind,
+    deadline: Deadline,
+    features: RoFeatures,
+    progress: &'a Progress,
+}
+
+/// Computes the distribution of each disjunctive facet against the candidates of the search
+/// ran with the clauses on this facet removed from the filter.
+///
+/// A keyword search is ran again for each facet with clauses in the filter, so that the distinct
+/// attribute and the ranking score threshold restrict its candidates as they restrict the ones of
+/// the search. The semantic and hybrid searches match all the documents, only their filter restricts
+/// the facet candidates: they can't be used with a distinct attribute or a ranking score threshold.
+fn compute_disjunctive_facets(
+    disjunctive_facets: &[String],
+    ctx: SearchAgainContext<'_>,
+    candidates: &roaring::RoaringBitmap,
+) -> Result<ComputedFacets, ResponseError> {
+    let SearchAgainContext {
+        index,
+        rtxn,
+        fields_ids_map,
+        index_uid,
+        before_search,
+        query,
+        filter,
+        search_kind,
+        deadline,
+        features,
+        progress,
+    } = ctx;
+
+    if !matches!(search_kind, SearchKind::KeywordOnly) {
+        let distinct = query.distinct.is_some()
+            || index.distinct_field(rtxn).map_err(milli::Error::from)?.is_some();
+        if distinct || query.ranking_score_threshold.is_some() {
+            return Err(ResponseError::from_msg(
+                "`disjunctiveFacets` cannot be used with a distinct attribute or a `rankingScoreThreshold` in a semantic or hybrid search.".to_string(),
+                Code::InvalidSearchDisjunctiveFacets,
+            ));
+        }
+    }
+
+    let mut computed_facets = ComputedFacets::default();
+    for facet in disjunctive_facets {
+        let relaxed_filter = filter.and_then(|filter| filter.without_field(facet));
+        let facet_candidates = if relaxed_filter.as_ref() == filter {
+            // the filter does not constrain this facet, the candidates of the search can be reused
+            candidates.clone()
+        } else {
+            match search_kind {
+                SearchKind::KeywordOnly => {
+                    let (search, _, _, _) = prepare_search(
+                        index,
+                        rtxn,
+                        fields_ids_map,
+                        index_uid,
+                        before_search,
+                        query,
+                        relaxed_filter.clone(),
+                        search_kind,
+                        deadline.clone(),
+                        features,
+                        progress,
+                    )?;
+                    search.execute_for_candidates(false)?
+                }
+                // no need to embed the query again
+                SearchKind::SemanticOnly { .. } | SearchKind::Hybrid { .. } => {
+                    filtered_universe(index, rtxn, fields_ids_map, &relaxed_filter, None, progress)?
+                }
+            }
+        };
+
+        let facet_patterns = AttributePatterns::from(vec![facet.clone()]);
+        let facet_computed = compute_facet_distribution_stats(
+            &facet_patterns,
+            index,
+            rtxn,
+            fields_ids_map,
+            facet_candidates,
+            relaxed_filter.as_ref(),
+        )?;
+        computed_facets.replace(&facet_patterns, facet_computed);
+    }
+
+    Ok(computed_facets)
+}
+
+/// The best documents of a group, before they are formatted.
+struct RankedGroup {
+    value: Value,
+    total_hits: u64,
+    documents_ids: Vec<DocumentId>,
+    document_scores: Vec<Vec<ScoreDetails>>,
+}
+
+/// Ranks the documents of the group of each hit, the hits being the best documents of their groups.
+///
+/// The documents of each group are ranked by a search restricted to them that ignores the
+/// distinct rule, which would otherwise only keep the best one, and stops once `hitsPerGroup`
+/// documents are ranked. The `totalHits` of a group are as exhaustive as the hits of the search.
+fn rank_groups(
+    group_by: &GroupBy,
+    ctx: SearchAgainContext<'_>,
+    hits: &[DocumentId],
+    query_vector: Option<&Vec<f32>>,
+) -> Result<Vec<RankedGroup>, ResponseError> {
+    let SearchAgainContext {
+        index,
+        rtxn,
+        fields_ids_map,
+        index_uid,
+        before_search,
+        query,
+        filter,
+        search_kind,
+        deadline,
+        features,
+        progress,
+    } = ctx;
+
+    // reuse the embedding of the query if the search computed it
+    let query_with_vector;
+    let query = match query_vector {
+        Some(vector) if query.vector.is_none() => {
+            query_with_vector = SearchQuery { vector: Some(vector.clone()), ..query.clone() };
+            &query_with_vector
+        }
+        _ => query,
+    };
+
+    let field_id = fields_ids_map.id(&group_by.attribute);
+    let mut groups = Vec::with_capacity(hits.len());
+    for &docid in hits {
+        let DistinctGroup { value, docids } = match field_id {
+            Some(field_id) => milli::distinct_group(index, rtxn, field_id, docid)?,
+            // no document has the attribute, each of them is alone in its group
+            None => DistinctGroup { value: Value::Null, docids: std::iter::once(docid).collect() },
+        };
+
+        let (mut search, _, _, _) = prepare_search(
+            index,
+            rtxn,
+            fields_ids_map,
+            index_uid,
+            before_search,
+            query,
+            filter.cloned(),
+            search_kind,
+            deadline.clone(),
+            features,
+            progress,
+        )?;
+        search.candidates(&docids).ignore_distinct(true).offset(0).limit(group_by.hits_per_group);
+
+        let (milli::SearchResult { candidates, documents_ids, document_scores, .. }, _) =
+            search_from_kind(search_kind.clone(), search)?;
+        groups.push(RankedGroup {
+            value,
+            total_hits: candidates.len(),
+            documents_ids,
+            document_scores,
+        });
+    }
+
+    Ok(groups)
+}
+
+/// Computes the distribution and stats of the facets among the candidates.
+///
+/// The `filter` of the search selects the node whose children are counted for the hierarchical facets.
 fn compute_facet_distribution_stats(
     facet_patterns: &AttributePatterns,
     index: &Index,
     rtxn: &RoTxn,
     fields_ids_map: &FieldsIdsMap,
     candidates: roaring::RoaringBitmap,
+    filter: Option<&IndexFilter>,
 ) -> Result<ComputedFacets, ResponseError> {
     let mut facet_distribution = index.facets_distribution(rtxn, fields_ids_map);
 
@@ -2110,6 +3623,15 @@ fn compute_facet_distribution_stats(
 
         Some((fname, sort_facet_values_by.get(fname)))
     });
+    let fields: Vec<_> = fields.collect();
+
+    if let Some(filter) = filter {
+        for (fname, _) in &fields {
+            if let Some(node) = filter.equality_value(fname) {
+                facet_distribution.hierarchy_node(*fname, node);
+            }
+        }
+    }
 
     facet_distribution.facets(fields);
 
@@ -2123,6 +3645,55 @@ fn compute_facet_distribution_stats(
     Ok(ComputedFacets { distribution, stats })
 }
 
+fn compute_facet_aggregations(
+    aggregations: &BTreeMap<String, FacetAggregation>,
+    index: &Index,
+    rtxn: &RoTxn,
+    fields_ids_map: &FieldsIdsMap,
+    candidates: roaring::RoaringBitmap,
+) -> Result<BTreeMap<String, Vec<FacetBucket>>, ResponseError> {
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let aggregations =
+        aggregations.iter().map(|(name, aggregation)| (name.clone(), aggregation.into())).collect();
+    let buckets = index
+        .facets_distribution(rtxn, fields_ids_map)
+        .max_values_per_facet(max_values_by_facet)
+        .candidates(candidates)
+        .compute_aggregations(&aggregations)?;
+
+    Ok(buckets
+        .into_iter()
+        .map(|(name, buckets)| (name, buckets.into_iter().map(FacetBucket::from).collect()))
+        .collect())
+}
+
+fn compute_geo_grid(
+    precision: usize,
+    index: &Index,
+    rtxn: &RoTxn,
+    fields_ids_map: &FieldsIdsMap,
+    candidates: roaring::RoaringBitmap,
+) -> Result<Vec<GeoGridBucket>, ResponseError> {
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let buckets = index
+        .facets_distribution(rtxn, fields_ids_map)
+        .max_values_per_facet(max_values_by_facet)
+        .candidates(candidates)
+        .compute_geo_grid(precision)?;
+
+    Ok(buckets.into_iter().map(GeoGridBucket::from).collect())
+}
+
 pub fn search_from_kind(
     search_kind: SearchKind,
     search: milli::Search<'_>,
@@ -2174,6 +3745,8 @@ struct AttributesFormat {
     highlight_pre_tag: String,
     highlight_post_tag: String,
     show_matches_position: bool,
+    attributes_to_snippet: Option<Vec<String>>,
+    snippet_count: usize,
     sort: Option<Vec<String>>,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
@@ -2214,6 +3787,8 @@ struct HitMaker<'a> {
     extra_ids: Vec<String>,
     formatter_builder: MatcherBuilder<'a>,
     formatted_options: BTreeMap<FieldId, FormatOptions>,
+    snippet_options: BTreeMap<FieldId, usize>,
+    snippet_count: usize,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     sort: Option<Vec<String>>,
@@ -2343,6 +3918,14 @@ impl<'a> HitMaker<'a> {
             &displayed_ids,
         );
 
+        let attr_to_snippet = format.attributes_to_snippet.unwrap_or_default();
+        let snippet_options = compute_snippet_options(
+            &attr_to_snippet,
+            format.crop_length,
+            fields_ids_map,
+            &displayed_ids,
+        );
+
         let attribute_state = AttributeState::from_criteria(index.criteria(rtxn)?);
 
         Ok(Self {
@@ -2356,6 +3939,8 @@ impl<'a> HitMaker<'a> {
             to_retrieve_ids,
             formatter_builder,
             formatted_options,
+            snippet_options,
+            snippet_count: format.snippet_count,
             show_ranking_score: format.show_ranking_score,
             show_ranking_score_details: format.show_ranking_score_details,
             show_matches_position: format.show_matches_position,
@@ -2457,6 +4042,28 @@ impl<'a> HitMaker<'a> {
             )?
         };
 
+        let snippets = if self.snippet_options.is_empty() {
+            None
+        } else {
+            let selectors: Vec<_> = self
+                .snippet_options
+                .keys()
+                .map(|&fid| self.fields_ids_map.name(fid).expect("Missing field name"))
+                .collect();
+
+            let document = make_document(obkv, self.fields_ids_map, &selectors)?;
+
+            Some(snippet_fields(
+                document,
+                self.fields_ids_map,
+                &self.formatter_builder,
+                &self.snippet_options,
+                self.snippet_count,
+                self.locales.as_deref(),
+                &localized_attributes,
+            ))
+        };
+
         if let Some(sort) = self.sort.as_ref() {
             insert_geo_distance(sort, &mut document);
         }
@@ -2472,6 +4079,7 @@ impl<'a> HitMaker<'a> {
             extra_document,
             formatted,
             matches_position,
+            snippets,
             ranking_score_details,
             ranking_score,
         };
@@ -2592,7 +4200,9 @@ pub fn perform_similar(
         offset,
         limit,
         filter,
-        embedder,
+        embedder: SimilarEmbedder(embedder),
+        // the embedder is only omitted in the keyword mode, see `validate_similar_query`
+        mode: _,
         attributes_to_retrieve,
         retrieve_vectors,
         show_ranking_score,
@@ -2603,14 +4213,18 @@ pub fn perform_similar(
 
     let retrieve_vectors = RetrieveVectors::new(retrieve_vectors);
 
-    let (embedder_name, embedder, quantized) = SearchKind::embedder(
-        index_scheduler,
-        index_uid.to_string(),
-        &index,
-        &embedder,
-        None,
-        Route::Similar,
-    )?;
+    let embedder = embedder
+        .map(|embedder| {
+            SearchKind::embedder(
+                index_scheduler,
+                index_uid.to_string(),
+                &index,
+                &embedder,
+                None,
+                Route::Similar,
+            )
+        })
+        .transpose()?;
 
     let docid_filter = search_rules.and_then(|search_rules| search_rules.filter);
     let docid_filter = docid_filter
@@ -2659,18 +4273,29 @@ pub fn perform_similar(
         ));
     }
 
-    let mut similar = milli::Similar::new(
-        internal_id,
-        offset,
-        limit,
-        &index,
-        &rtxn,
-        &fields_ids_map,
-        embedder_name,
-        embedder,
-        quantized,
-        progress,
-    );
+    let mut similar = match embedder {
+        Some((embedder_name, embedder, quantized)) => milli::Similar::new(
+            internal_id,
+            offset,
+            limit,
+            &index,
+            &rtxn,
+            &fields_ids_map,
+            embedder_name,
+            embedder,
+            quantized,
+            progress,
+        ),
+        None => milli::Similar::keyword(
+            internal_id,
+            offset,
+            limit,
+            &index,
+            &rtxn,
+            &fields_ids_map,
+            progress,
+        ),
+    };
 
     if let Some(filter) = candidates_filter {
         similar.filter(filter);
@@ -2688,6 +4313,7 @@ pub fn perform_similar(
         degraded: _,
         used_negative_operator: _,
         query_vector: _,
+        explanation: _,
     } = similar.execute().map_err(|err| match err {
         milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
             ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
@@ -2706,6 +4332,8 @@ pub fn perform_similar(
         highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
         highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
         show_matches_position: false,
+        attributes_to_snippet: None,
+        snippet_count: DEFAULT_SNIPPET_COUNT(),
         sort: None,
         show_ranking_score,
         show_ranking_score_details,
@@ -2861,14 +4489,7 @@ fn add_crop_to_formatted_options(
     displayed_ids: &BTreeSet<FieldId>,
 ) {
     for attr in attr_to_crop {
-        let mut split = attr.rsplitn(2, ':');
-        let (attr_name, attr_len) = match split.next().zip(split.next()) {
-            Some((len, name)) => {
-                let crop_len = len.parse::<usize>().unwrap_or(crop_length);
-                (name, crop_len)
-            }
-            None => (attr.as_str(), crop_length),
-        };
+        let (attr_name, attr_len) = split_attribute_crop_length(attr, crop_length);
 
         if attr_name == "*" {
             for id in displayed_ids {
@@ -2890,6 +4511,45 @@ fn add_crop_to_formatted_options(
     }
 }
 
+/// Splits an attribute in the `attribute:length` syntax, defaulting to the given crop length.
+fn split_attribute_crop_length(attr: &str, crop_length: usize) -> (&str, usize) {
+    let mut split = attr.rsplitn(2, ':');
+    match split.next().zip(split.next()) {
+        Some((len, name)) => {
+            let crop_len = len.parse::<usize>().unwrap_or(crop_length);
+            (name, crop_len)
+        }
+        None => (attr, crop_length),
+    }
+}
+
+fn compute_snippet_options(
+    attr_to_snippet: &[String],
+    crop_length: usize,
+    fields_ids_map: &FieldsIdsMap,
+    displayed_ids: &BTreeSet<FieldId>,
+) -> BTreeMap<FieldId, usize> {
+    let mut snippet_options = BTreeMap::new();
+
+    for attr in attr_to_snippet {
+        let (attr_name, attr_len) = split_attribute_crop_length(attr, crop_length);
+
+        if attr_name == "*" {
+            for id in displayed_ids {
+                snippet_options.insert(*id, attr_len);
+            }
+        }
+
+        if let Some(id) = fields_ids_map.id(attr_name) {
+            if displayed_ids.contains(&id) {
+                snippet_options.insert(id, attr_len);
+            }
+        }
+    }
+
+    snippet_options
+}
+
 fn add_non_formatted_ids_to_formatted_options(
     formatted_options: &mut BTreeMap<FieldId, FormatOptions>,
     to_retrieve_ids: &BTreeSet<FieldId>,
@@ -2976,6 +4636,59 @@ fn format_fields(
     Ok((matches_position, document))
 }
 
+fn snippet_fields(
+    mut document: Document,
+    field_ids_map: &FieldsIdsMap,
+    builder: &MatcherBuilder<'_>,
+    snippet_options: &BTreeMap<FieldId, usize>,
+    snippet_count: usize,
+    locales: Option<&[Language]>,
+    localized_attributes: &[LocalizedAttributesRule],
+) -> Snippets {
+    let mut snippets = BTreeMap::new();
+
+    let snippet_fields_options: Vec<_> = snippet_options
+        .iter()
+        .map(|(fid, crop_length)| (field_ids_map.name(*fid).unwrap(), *crop_length))
+        .collect();
+
+    let names = snippet_fields_options.iter().map(|(name, _)| *name);
+    permissive_json_pointer::map_leaf_values(&mut document, names, |key, _array_indices, value| {
+        // the most specific attribute decides of the crop length,
+        // eg. `doggo.name:5` takes precedence over `doggo:20` for `doggo.name`.
+        let Some(crop_length) = snippet_fields_options
+            .iter()
+            .filter(|(name, _)| milli::is_faceted_by(key, name))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, crop_length)| *crop_length)
+        else {
+            return;
+        };
+
+        let text = match value {
+            Value::String(s) => Cow::Borrowed(s.as_str()),
+            Value::Number(number) => Cow::Owned(number.to_string()),
+            _ => return,
+        };
+
+        // if no locales has been provided, we try to find the locales in the localized_attributes.
+        let locales = locales.or_else(|| {
+            localized_attributes
+                .iter()
+                .find(|rule| rule.match_str(key) == PatternMatch::Match)
+                .map(LocalizedAttributesRule::locales)
+        });
+
+        let mut matcher = builder.build(&text, locales);
+        let found = matcher.snippets(crop_length, snippet_count);
+        if !found.is_empty() {
+            snippets.entry(key.to_owned()).or_insert_with(Vec::new).extend(found);
+        }
+    });
+
+    snippets
+}
+
 fn format_value(
     value: Value,
     builder: &MatcherBuilder<'_>,
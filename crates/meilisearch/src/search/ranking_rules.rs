@@ -249,6 +249,7 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Decay(_) => canonical_criteria.push(criterion.clone()),
             }
         }
 
@@ -331,6 +332,7 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Decay(_) => canonical_criteria.push(criterion.clone()),
             }
         }
 
@@ -489,6 +491,7 @@ impl RankingRules {
                         canonical_criteria.push(criterion)
                     }
                 },
+                Criterion::Decay(_) => canonical_criteria.push(criterion),
             }
         }
 
@@ -619,7 +622,8 @@ fn canonicalize_sort(
                     }
                 }
             }
-            AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => match geo_sorted {
+            AscDesc::Asc(Member::Geo(_) | Member::GeoShape(_))
+            | AscDesc::Desc(Member::Geo(_) | Member::GeoShape(_)) => match geo_sorted {
                 Some(earlier_sort_index) => {
                     canonicalization_actions.push(CanonicalizationAction::RemovedDuplicate {
                         earlier_occurrence: RankingRuleSource::Sort {
@@ -718,6 +722,8 @@ impl RankingRuleSource {
                         }
                         AscDesc::Asc(Member::Geo(_)) => "_geo(..):asc".to_string(),
                         AscDesc::Desc(Member::Geo(_)) => "_geo(..):desc".to_string(),
+                        AscDesc::Asc(Member::GeoShape(_)) => "_geojson(..):asc".to_string(),
+                        AscDesc::Desc(Member::GeoShape(_)) => "_geojson(..):desc".to_string(),
                     },
                     None => "unknown".into(),
                 }
@@ -754,6 +760,8 @@ impl RankingRule {
             | Criterion::AttributeRank
             | Criterion::WordPosition
             | Criterion::Exactness => RankingRuleKind::Relevancy,
+            // decay rules contribute to the ranking score
+            Criterion::Decay(_) => RankingRuleKind::Relevancy,
             Criterion::Asc(s) if s == "_geo" => RankingRuleKind::AscendingGeoSort,
 
             Criterion::Asc(_) => RankingRuleKind::AscendingSort,
@@ -779,8 +787,10 @@ impl RankingRule {
         let kind = match asc_desc {
             AscDesc::Asc(Member::Field(_)) => RankingRuleKind::AscendingSort,
             AscDesc::Desc(Member::Field(_)) => RankingRuleKind::DescendingSort,
-            AscDesc::Asc(Member::Geo(_)) => RankingRuleKind::AscendingGeoSort,
-            AscDesc::Desc(Member::Geo(_)) => RankingRuleKind::DescendingGeoSort,
+            AscDesc::Asc(Member::Geo(_) | Member::GeoShape(_)) => RankingRuleKind::AscendingGeoSort,
+            AscDesc::Desc(Member::Geo(_) | Member::GeoShape(_)) => {
+                RankingRuleKind::DescendingGeoSort
+            }
         };
         Self {
             source: RankingRuleSource::Sort {
@@ -71,6 +71,7 @@ pub static AUTHORIZATIONS: Lazy<AuthorizationMap> = Lazy::new(|| {
         ("DELETE",  "/tasks", Allow) =>                                           str_hashmap!{&["tasks.delete"] => Allow, &["tasks.*"] => Allow, &["*"] => Allow},
         ("GET",     "/tasks?indexUid=products", Allow) =>                         str_hashmap!{&["tasks.get"] => Allow, &["tasks.*"] => Allow, &["*"] => Allow},
         ("GET",     "/tasks/0", Allow) =>                                         str_hashmap!{&["tasks.get"] => Allow, &["tasks.*"] => Allow, &["*"] => Allow},
+        ("GET",     "/tasks/processing", Allow) =>                                str_hashmap!{&["tasks.get"] => Allow, &["tasks.*"] => Allow, &["*"] => Allow},
         ("POST",    "/tasks/compact", Deny) =>                                    str_hashmap!{&["tasks.compact"] => Deny, &["tasks.*"] => Allow, &["*"] => Allow},
         ("PATCH",   "/indexes/products/", Allow) =>                               str_hashmap!{&["indexes.update"] => Allow, &["indexes.*"] => Allow, &["*"] => Allow},
         ("GET",     "/indexes/products/", Allow) =>                               str_hashmap!{&["indexes.get"] => Allow, &["indexes.*"] => Allow, &["*"] => Allow},
@@ -205,6 +205,16 @@ impl Server<Owned> {
         self.service.patch(url, value).await
     }
 
+    pub async fn get_webhook_deliveries(&self, uuid: impl AsRef<str>) -> (Value, StatusCode) {
+        let url = format!("/webhooks/{}/deliveries", uuid.as_ref());
+        self.service.get(url).await
+    }
+
+    pub async fn replay_webhook_deliveries(&self, uuid: impl AsRef<str>) -> (Value, StatusCode) {
+        let url = format!("/webhooks/{}/deliveries/replay", uuid.as_ref());
+        self.service.post(url, json!(null)).await
+    }
+
     pub async fn create_dynamic_search_rule(
         &self,
         uid: impl AsRef<str>,
@@ -470,6 +480,10 @@ impl<State> Server<State> {
         self.service.post("/swap-indexes", value).await
     }
 
+    pub async fn create_transaction(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/transactions", value).await
+    }
+
     pub async fn cancel_tasks(&self, value: &str) -> (Value, StatusCode) {
         self.service.post(format!("/tasks/cancel?{}", value), json!(null)).await
     }
@@ -482,6 +496,22 @@ impl<State> Server<State> {
         self.service.post("/tasks/compact", json!(null)).await
     }
 
+    pub async fn pause_processing(&self, value: &str) -> (Value, StatusCode) {
+        self.service.post(format!("/tasks/pause?{}", value), json!(null)).await
+    }
+
+    pub async fn resume_processing(&self) -> (Value, StatusCode) {
+        self.service.post("/tasks/resume", json!(null)).await
+    }
+
+    pub async fn processing_state(&self) -> (Value, StatusCode) {
+        self.service.get("/tasks/processing").await
+    }
+
+    pub async fn update_task_retention_policy(&self, value: Value) -> (Value, StatusCode) {
+        self.service.patch("/tasks/retention-policy", value).await
+    }
+
     pub async fn wait_task(&self, update_id: impl super::IntoTaskUid) -> Value {
         let update_id = update_id.uid();
 
@@ -1,5 +1,6 @@
 use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::time::Duration;
 
 use actix_web::body::MessageBody;
 use actix_web::dev::ServiceResponse;
@@ -218,6 +219,59 @@ impl Service {
         (response, status_code)
     }
 
+    /// Send a test get request to a route streaming server-sent events and read its first
+    /// `count` events, as their name and data. No event is read if the request fails.
+    pub async fn get_events(
+        &self,
+        url: impl AsRef<str>,
+        count: usize,
+    ) -> (Vec<(String, Value)>, StatusCode) {
+        let app = self.init_web_app().await;
+
+        let mut req = test::TestRequest::get().uri(url.as_ref());
+        if let Some(api_key) = &self.api_key {
+            req = req.insert_header(("Authorization", ["Bearer ", api_key].concat()));
+        }
+        let res = test::call_service(&app, req.to_request()).await;
+        let status_code = res.status();
+        if !status_code.is_success() {
+            return (Vec::new(), status_code);
+        }
+
+        let mut body = std::pin::pin!(res.into_body());
+        let mut buffer = String::new();
+        let mut events = Vec::new();
+        while events.len() < count {
+            let chunk = tokio::time::timeout(
+                Duration::from_secs(60),
+                std::future::poll_fn(|cx| body.as_mut().poll_next(cx)),
+            )
+            .await
+            .expect("Timeout waiting for an event");
+            let Some(Ok(chunk)) = chunk else { panic!("The event stream was interrupted") };
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+
+            // the events are separated by an empty line, the other blocks are keep-alives
+            while let Some(end) = buffer.find("\n\n") {
+                let block: String = buffer.drain(..end + 2).collect();
+                let mut name = String::from("message");
+                let mut data = None;
+                for line in block.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        name = value.trim().to_string();
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data = Some(serde_json::from_str(value.trim()).unwrap());
+                    }
+                }
+                if let Some(data) = data {
+                    events.push((name, data));
+                }
+            }
+        }
+        events.truncate(count);
+        (events, status_code)
+    }
+
     fn encode(&self, req: TestRequest, body: Value, encoder: Encoder) -> TestRequest {
         let bytes = serde_json::to_string(&body).expect("Failed to serialize test data to json");
         let encoded_body = encoder.encode(bytes);
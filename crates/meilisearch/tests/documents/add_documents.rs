@@ -2955,3 +2955,39 @@ async fn batch_several_documents_addition() {
     assert_eq!(code, 200, "failed with `{}`", response);
     assert_eq!(response["results"].as_array().unwrap().len(), 120);
 }
+
+#[actix_rt::test]
+async fn add_documents_with_a_reused_idempotency_key() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let key = Uuid::new_v4().to_string();
+    let headers = vec![("content-type", "application/json"), ("Idempotency-Key", key.as_str())];
+
+    let (task, code) =
+        index.raw_add_documents(r#"[{ "id": 1, "doggo": "bob" }]"#, headers.clone(), "").await;
+    snapshot!(code, @"202 Accepted");
+
+    // a retry returns the original task
+    let (retried, code) =
+        index.raw_add_documents(r#"[{ "id": 1, "doggo": "bob" }]"#, headers.clone(), "").await;
+    snapshot!(code, @"202 Accepted");
+    assert_eq!(retried["taskUid"], task["taskUid"], "{retried}");
+
+    // the same key with other documents is rejected
+    let (response, code) =
+        index.raw_add_documents(r#"[{ "id": 1, "doggo": "bobby" }]"#, headers, "").await;
+    snapshot!(code, @"422 Unprocessable Entity");
+    snapshot!(response["code"], @r###""idempotency_key_mismatch""###);
+    snapshot!(response["type"], @r###""invalid_request""###);
+
+    server.wait_task(task.uid()).await.succeeded();
+    let (response, _) = index.get_all_documents_raw("").await;
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "doggo": "bob"
+      }
+    ]
+    "###);
+}
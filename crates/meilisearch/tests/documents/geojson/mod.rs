@@ -419,3 +419,200 @@ async fn bug_5904() {
     let (response, _code) = index.add_documents(geojson, Some("id")).await;
     server.wait_task(response.uid()).await.succeeded();
 }
+
+#[actix_rt::test]
+async fn geo_shape_relations_and_sort() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, _status_code) =
+        index.update_settings(json!({"filterableAttributes": ["_geojson"]})).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        {
+            "id": "square",
+            "_geojson": { "type": "Polygon", "coordinates": [[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]]] },
+        },
+        {
+            "id": "inner",
+            "_geojson": { "type": "Point", "coordinates": [5, 5] },
+        },
+        {
+            "id": "line",
+            "_geojson": { "type": "LineString", "coordinates": [[-5, 5], [15, 5]] },
+        },
+        {
+            "id": "far",
+            "_geojson": { "type": "Point", "coordinates": [50, 50] },
+        },
+        {
+            "id": "missing",
+        },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, Some("id")).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let around = r#"{"type":"Polygon","coordinates":[[[-1,-1],[11,-1],[11,11],[-1,11],[-1,-1]]]}"#;
+
+    let (response, code) = index
+        .search_post(json!({ "filter": format!("_geoShape(intersects, '{around}')"), "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "square"
+      },
+      {
+        "id": "inner"
+      },
+      {
+        "id": "line"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "filter": format!("_geoShape(within, '{around}')"), "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "square"
+      },
+      {
+        "id": "inner"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "filter": format!("_geoShape(disjoint, '{around}')"), "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "far"
+      }
+    ]
+    "###);
+
+    // the inner point is in the bounding box of the triangle, but not in the triangle
+    let triangle = r#"{"type":"Polygon","coordinates":[[[0,0],[10,0],[10,8],[0,0]]]}"#;
+    let (response, code) = index
+        .search_post(json!({ "filter": format!("_geoShape(intersects, '{triangle}')"), "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "square"
+      },
+      {
+        "id": "line"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "filter": format!("_geoShape(disjoint, '{triangle}')"), "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "inner"
+      },
+      {
+        "id": "far"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "filter": r#"_geoShape(contains, '{"type":"Point","coordinates":[2,2]}')"#, "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "square"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "filter": "_geoShape(intersects, '{\"type\":\"Nope\"}')" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_filter""###);
+
+    // the line is 5 degrees of longitude away, the east edge of the square 10 and the inner point 15
+    let (response, code) = index
+        .search_post(
+            json!({ "sort": ["_geoShapePoint(5, 20):asc"], "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "line"
+      },
+      {
+        "id": "square"
+      },
+      {
+        "id": "inner"
+      },
+      {
+        "id": "far"
+      },
+      {
+        "id": "missing"
+      }
+    ]
+    "###);
+
+    // the shapes are fetched around the antipode of the point when sorting by descending distance
+    let (response, code) = index
+        .search_post(
+            json!({ "sort": ["_geoShapePoint(5, 20):desc"], "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "far"
+      },
+      {
+        "id": "inner"
+      },
+      {
+        "id": "square"
+      },
+      {
+        "id": "line"
+      },
+      {
+        "id": "missing"
+      }
+    ]
+    "###);
+
+    // a point inside the square is at a distance of zero from it
+    let (response, code) = index
+        .search_post(json!({ "sort": ["_geoShapePoint(2, 2):asc"], "attributesToRetrieve": ["id"], "limit": 1 }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "square"
+      }
+    ]
+    "###);
+}
@@ -16,6 +16,7 @@ mod snapshot;
 mod stats;
 mod swap_indexes;
 mod tasks;
+mod transactions;
 mod upgrade;
 mod vector;
 
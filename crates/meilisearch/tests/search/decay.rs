@@ -0,0 +1,66 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_decay_ranking_rule() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "cheap phone", "price": 50 },
+        { "id": 2, "title": "expensive phone", "price": 1000 },
+        { "id": 3, "title": "phone", "price": 200 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) =
+        index.update_settings(json!({ "rankingRules": ["words", "price:gauss(200, 100)"] })).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["rankingRules"], json!(["words", "price:gauss(200, 100)"]));
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "phone",
+            "attributesToRetrieve": ["id"],
+            "showRankingScoreDetails": true,
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    let ids: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect();
+    assert_eq!(ids, [3, 1, 2]);
+    assert_eq!(
+        response["hits"][0]["_rankingScoreDetails"]["price:gauss(200, 100)"],
+        json!({ "order": 1, "score": 1.0, "weight": 1.0 })
+    );
+
+    // the weight of the rule is kept in the settings
+    let (task, code) = index
+        .update_settings(json!({ "rankingRules": ["words", "price:gauss(200, 100, 0.5)"] }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["rankingRules"], json!(["words", "price:gauss(200, 100, 0.5)"]));
+
+    for rule in [
+        "price:gauss(200, -1)",
+        "price:gauss(200, 0)",
+        "price:gauss(200)",
+        "price:gauss(200, 100, 0)",
+        "price:gauss(200, 100, 1, 2)",
+        "price:linear(soon, 100)",
+        "price:log(-1)",
+        ":gauss(200, 100)",
+    ] {
+        let (response, code) = index.update_settings(json!({ "rankingRules": [rule] })).await;
+        assert_eq!(code, 400, "{rule}: {response}");
+        assert_eq!(response["code"], json!("invalid_settings_ranking_rules"), "{rule}");
+    }
+}
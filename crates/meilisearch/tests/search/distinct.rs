@@ -304,3 +304,155 @@ async fn distinct_at_search_time() {
     snapshot!(response["totalPages"], @"1");
     snapshot!(response["totalHits"], @"3");
 }
+
+#[actix_rt::test]
+async fn search_group_by() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "family": "shoes", "price": 30 },
+        { "id": 2, "family": "shoes", "price": 10 },
+        { "id": 3, "family": "shoes", "price": 20 },
+        { "id": 4, "family": "shirts", "price": 15 },
+        { "id": 5, "family": "shirts", "price": 25 },
+        { "id": 6, "family": "hats", "price": 5 },
+        { "id": 7, "price": 50 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .update_settings(
+            json!({ "filterableAttributes": ["family", "price"], "sortableAttributes": ["price"] }),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "groupBy": { "attribute": "family", "hitsPerGroup": 2 },
+            "sort": ["price:asc"],
+            "limit": 2,
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 6 }, { "id": 2 }]));
+    assert_eq!(
+        response["groups"],
+        json!([
+            { "value": "hats", "totalHits": 1, "hits": [{ "id": 6 }] },
+            { "value": "shoes", "totalHits": 3, "hits": [{ "id": 2 }, { "id": 3 }] },
+        ])
+    );
+
+    // the pagination applies to the groups
+    let (response, code) = index
+        .search_post(json!({
+            "groupBy": { "attribute": "family", "hitsPerGroup": 2 },
+            "filter": "price < 40",
+            "sort": ["price:asc"],
+            "offset": 2,
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(
+        response["groups"],
+        json!([{ "value": "shirts", "totalHits": 2, "hits": [{ "id": 4 }, { "id": 5 }] }])
+    );
+
+    // documents without value are each in their own group
+    let (response, code) = index
+        .search_post(json!({
+            "groupBy": { "attribute": "family" },
+            "filter": "price > 40",
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(
+        response["groups"],
+        json!([{ "value": null, "totalHits": 1, "hits": [{ "id": 7 }] }])
+    );
+
+    // the groups are still listed without any of their documents
+    let (response, code) = index
+        .search_post(json!({
+            "groupBy": { "attribute": "family", "hitsPerGroup": 0 },
+            "sort": ["price:asc"],
+            "limit": 2,
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(
+        response["groups"],
+        json!([
+            { "value": "hats", "totalHits": 1, "hits": [] },
+            { "value": "shoes", "totalHits": 3, "hits": [] },
+        ])
+    );
+
+    let (response, code) =
+        index.search_post(json!({ "groupBy": { "attribute": "family" }, "offset": 10 })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["groups"], json!([]));
+
+    let (response, code) = index
+        .search_post(json!({ "groupBy": { "attribute": "family" }, "distinct": "family" }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_group_by"));
+
+    let (response, code) = index.search_post(json!({ "groupBy": { "hitsPerGroup": 2 } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_group_by"));
+
+    let (response, code) = index
+        .search_post(json!({ "groupBy": { "attribute": "family", "hitsPerGroup": -1 } }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_group_by"));
+}
+
+#[actix_rt::test]
+async fn search_group_by_embeds_the_query_once() {
+    let (mock, setting) = crate::vector::rest::create_mock().await;
+    let server = crate::vector::get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (task, code) = index
+        .update_settings(
+            json!({ "embedders": { "rest": setting }, "filterableAttributes": ["family"] }),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let documents = json!([
+        { "id": 1, "name": "kefir", "family": "dogs" },
+        { "id": 2, "name": "intel", "family": "dogs" },
+        { "id": 3, "name": "echo", "family": "cats" },
+        { "id": 4, "name": "iko", "family": "birds" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let received_requests = mock.received_requests().await.unwrap().len();
+    let (response, code) = index
+        .search_post(json!({
+            "semanticQueries": [{ "q": "intel" }, { "q": "kefir", "weight": 0.5 }],
+            "hybrid": { "embedder": "rest", "semanticRatio": 1.0 },
+            "groupBy": { "attribute": "family", "hitsPerGroup": 2 },
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["groups"].as_array().unwrap().len(), 3, "{response}");
+    // each semantic query is embedded by the search, the groups reuse the query vector
+    let embedded = mock.received_requests().await.unwrap().len() - received_requests;
+    assert_eq!(embedded, 2, "{response}");
+}
@@ -389,6 +389,80 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_disjunctive_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"disjunctiveFacets": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.disjunctiveFacets`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_disjunctive_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_disjunctive_facets"
+    }
+    "###);
+    // Can't make the `disjunctive_facets` fail with a get search since it'll accept anything as an array of strings.
+}
+
+#[actix_rt::test]
+async fn search_bad_explain() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"explain": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.explain`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_explain",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_explain"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?explain=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `explain`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_explain",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_explain"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_use_synonyms() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"useSynonyms": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.useSynonyms`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_use_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_use_synonyms"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?useSynonyms=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `useSynonyms`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_use_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_use_synonyms"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_threshold() {
     let server = Server::new_shared();
@@ -0,0 +1,59 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_explain() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "text": "quick fox" },
+        { "id": 2, "text": "quack fox" },
+        { "id": 3, "text": "quick brown" },
+        { "id": 4, "text": "nothing here" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .update_settings(
+            json!({ "searchableAttributes": ["text"], "rankingRules": ["words", "typo"] }),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "q": "quick fox", "explain": true })).await;
+    assert_eq!(code, 200, "{response}");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    assert_eq!(ids, [&json!(1), &json!(2), &json!(3)]);
+
+    let explain = &response["explain"];
+    assert_eq!(explain["initialCandidates"], json!(3));
+    assert_eq!(explain["rankingRules"][0]["name"], json!("words"));
+    assert_eq!(explain["rankingRules"][0]["bucketSizes"], json!([2, 1]));
+    assert_eq!(explain["rankingRules"][1]["name"], json!("typo"));
+    assert_eq!(explain["rankingRules"][1]["bucketSizes"], json!([1, 1]));
+    assert_eq!(explain["queryTerms"][0]["original"], json!("quick"));
+    assert_eq!(explain["queryTerms"][0]["oneTypo"], json!(["quack"]));
+    assert_eq!(
+        explain["hits"],
+        json!([
+            { "buckets": [{ "rankingRule": "words", "rank": 0 }, { "rankingRule": "typo", "rank": 0 }] },
+            { "buckets": [{ "rankingRule": "words", "rank": 0 }, { "rankingRule": "typo", "rank": 1 }] },
+            { "buckets": [{ "rankingRule": "words", "rank": 1 }] },
+        ])
+    );
+
+    let (response, code) = index.search_post(json!({ "q": "quick fox" })).await;
+    assert_eq!(code, 200, "{response}");
+    assert!(response.get("explain").is_none(), "{response}");
+
+    let (response, code) = index.search_post(json!({ "explain": true })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["explain"]["queryTerms"], json!([]));
+    assert_eq!(
+        response["explain"]["hits"].as_array().unwrap().len(),
+        response["hits"].as_array().unwrap().len()
+    );
+}
@@ -1285,3 +1285,321 @@ async fn issue_6335() {
     )
     .await;
 }
+
+#[actix_rt::test]
+async fn search_disjunctive_facet_distribution() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "color": "red", "size": "S" },
+        { "id": 2, "color": "red", "size": "M" },
+        { "id": 3, "color": "blue", "size": "M" },
+        { "id": 4, "color": "green", "size": "L" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) =
+        index.update_settings_filterable_attributes(json!(["id", "color", "size"])).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "filter": "color = red AND size = M",
+                "facets": ["color", "size"],
+                "disjunctiveFacets": ["color"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+                let dist = response["facetDistribution"].as_object().unwrap();
+                // the counts of `color` ignore the `color = red` clause
+                assert_eq!(dist["color"], json!({ "blue": 1, "red": 1 }));
+                // the counts of `size` are computed against the whole filter
+                assert_eq!(dist["size"], json!({ "M": 1 }));
+            },
+        )
+        .await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": [["color = red", "color = green"], "size = M"],
+            "disjunctiveFacets": ["color", "size"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    let dist = response["facetDistribution"].as_object().unwrap();
+    assert_eq!(dist["color"], json!({ "blue": 1, "red": 1 }));
+    assert_eq!(dist["size"], json!({ "L": 1, "M": 1, "S": 1 }));
+
+    // without a clause on the facet, its disjunctive counts are the regular ones
+    let (response, code) =
+        index.search_post(json!({ "filter": "size = M", "disjunctiveFacets": ["color"] })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["facetDistribution"]["color"], json!({ "blue": 1, "red": 1 }));
+
+    // the distinct attribute applies to the documents matching the relaxed filter
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "id > 1 AND size = M",
+            "distinct": "color",
+            "page": 1,
+            "disjunctiveFacets": ["size"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["facetDistribution"]["size"], json!({ "L": 1, "M": 2 }));
+
+    // the stats of the facet are replaced by the disjunctive ones
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "id > 2",
+            "facets": ["id"],
+            "disjunctiveFacets": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["facetStats"]["id"], json!({ "min": 1.0, "max": 4.0 }));
+
+    let (response, code) = index.search_post(json!({ "disjunctiveFacets": [] })).await;
+    assert_eq!(code, 200, "{response}");
+    assert!(response.get("facetDistribution").is_none(), "{response}");
+
+    let (response, code) = index.search_post(json!({ "disjunctiveFacets": ["price"] })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facets"));
+}
+
+#[actix_rt::test]
+async fn search_facet_aggregations() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "price": 5, "released_at": 1_000 },
+        { "id": 2, "price": 12.5, "released_at": 2_500 },
+        { "id": 3, "price": 30, "released_at": 2_900 },
+        { "id": 4, "price": [45, 80], "released_at": 4_200 },
+        { "id": 5, "price": "free" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) =
+        index.update_settings_filterable_attributes(json!(["price", "released_at"])).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetAggregations": {
+                "price": { "ranges": [{ "to": 10 }, { "from": 10, "to": 50 }, { "from": 50 }] },
+                "released_at": { "interval": 1000 },
+            },
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(json_string!(response["facetAggregations"]), @r###"
+    {
+      "price": [
+        {
+          "to": 10.0,
+          "count": 1
+        },
+        {
+          "from": 10.0,
+          "to": 50.0,
+          "count": 3
+        },
+        {
+          "from": 50.0,
+          "count": 1
+        }
+      ],
+      "released_at": [
+        {
+          "from": 1000.0,
+          "to": 2000.0,
+          "count": 1
+        },
+        {
+          "from": 2000.0,
+          "to": 3000.0,
+          "count": 2
+        },
+        {
+          "from": 3000.0,
+          "to": 4000.0,
+          "count": 0
+        },
+        {
+          "from": 4000.0,
+          "to": 5000.0,
+          "count": 1
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "price < 40",
+            "facetAggregations": { "released_at": { "interval": 1000 } },
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    let buckets = response["facetAggregations"]["released_at"].as_array().unwrap();
+    assert_eq!(buckets.iter().map(|bucket| &bucket["count"]).collect::<Vec<_>>(), [1, 2]);
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetAggregations": { "price": { "ranges": [{ "to": 10 }], "interval": 10 } },
+        }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facet_aggregations"));
+
+    let (response, code) =
+        index.search_post(json!({ "facetAggregations": { "price": { "interval": 0 } } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facet_aggregations"));
+
+    let (response, code) =
+        index.search_post(json!({ "facetAggregations": { "price": { "interval": -10 } } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facet_aggregations"));
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetAggregations": { "price": { "ranges": [{ "from": 50, "to": 10 }] } },
+        }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facet_aggregations"));
+
+    let (response, code) = index.search_post(json!({ "facetAggregations": { "price": {} } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facet_aggregations"));
+
+    let (response, code) =
+        index.search_post(json!({ "facetAggregations": { "price": { "ranges": [] } } })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["facetAggregations"], json!({ "price": [] }));
+
+    let (response, code) =
+        index.search_post(json!({ "facetAggregations": { "id": { "interval": 10 } } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facets"));
+
+    let (task, code) =
+        index.update_settings(json!({ "faceting": { "maxValuesPerFacet": 2 } })).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({ "facetAggregations": { "released_at": { "interval": 1000 } } }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facet_aggregations"));
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetAggregations": {
+                "price": { "ranges": [{ "to": 10 }, { "from": 10, "to": 50 }, { "from": 50 }] },
+            },
+        }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_facet_aggregations"));
+}
+
+#[actix_rt::test]
+async fn search_hierarchical_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "category": "Electronics > Phones > Android" },
+        { "id": 2, "category": "Electronics > Phones > iOS" },
+        { "id": 3, "category": "Electronics > Phone cases" },
+        { "id": 4, "category": ["Books > Novels", "Electronics > Laptops"] },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .update_settings_filterable_attributes(json!([{
+            "attributePatterns": ["category"],
+            "hierarchySeparator": " > ",
+        }]))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(json!({ "facets": ["category"] }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(
+                response["facetDistribution"]["category"],
+                json!({ "Books": 1, "Electronics": 4 })
+            );
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "filter": "category = Electronics", "facets": ["category"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                assert_eq!(response["hits"].as_array().unwrap().len(), 4);
+                assert_eq!(
+                    response["facetDistribution"]["category"],
+                    json!({
+                        "Electronics > Laptops": 1,
+                        "Electronics > Phone cases": 1,
+                        "Electronics > Phones": 2,
+                    })
+                );
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "filter": "category = \"Electronics > Phones\"", "facets": ["category"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+                assert_eq!(
+                    response["facetDistribution"]["category"],
+                    json!({ "Electronics > Phones > Android": 1, "Electronics > Phones > iOS": 1 })
+                );
+            },
+        )
+        .await;
+
+    // a leaf has no children to count
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "category = \"Electronics > Phones > iOS\"",
+            "facets": ["category"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+    assert_eq!(response["facetDistribution"]["category"], json!({}));
+
+    for separator in [" ", ""] {
+        let (response, code) = index
+            .update_settings_filterable_attributes(json!([{
+                "attributePatterns": ["category"],
+                "hierarchySeparator": separator,
+            }]))
+            .await;
+        assert_eq!(code, 400, "{separator:?}: {response}");
+        assert_eq!(response["code"], json!("invalid_settings_filterable_attributes"));
+    }
+}
@@ -618,3 +618,100 @@ async fn test_cjk_highlight() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn snippets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        {
+            "id": 0,
+            "text": "split the world void void void void void void void void void void world",
+        },
+    ]);
+    let (response, _) = index.add_documents(documents, None).await;
+    server.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "q": "split the world",
+                "attributesToRetrieve": ["id"],
+                "attributesToSnippet": ["text"],
+                "cropLength": 3,
+                "snippetCount": 2
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                allow_duplicates! {
+                  assert_json_snapshot!(response["hits"][0], @r#"
+                  {
+                    "id": 0,
+                    "_snippets": {
+                      "text": [
+                        {
+                          "text": "<em>split</em> <em>the</em> <em>world</em>…",
+                          "start": 0,
+                          "length": 15,
+                          "score": 1.0
+                        },
+                        {
+                          "text": "…void void <em>world</em>",
+                          "start": 56,
+                          "length": 15,
+                          "score": 0.3333333333333333
+                        }
+                      ]
+                    }
+                  }
+                  "#);
+                }
+            },
+        )
+        .await;
+
+    // the crop length can be overridden per attribute and the best passage is returned alone.
+    index
+        .search(
+            json!({
+                "q": "split the world",
+                "attributesToRetrieve": ["id"],
+                "attributesToSnippet": ["text:3"],
+                "snippetCount": 1
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(
+                    response["hits"][0]["_snippets"]["text"],
+                    json!([{
+                        "text": "<em>split</em> <em>the</em> <em>world</em>…",
+                        "start": 0,
+                        "length": 15,
+                        "score": 1.0
+                    }])
+                );
+            },
+        )
+        .await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "split the world", "attributesToSnippet": [] })).await;
+    assert_eq!(code, 200, "{response}");
+    assert!(response["hits"][0].get("_snippets").is_none(), "{response}");
+
+    let (response, code) =
+        index.search_post(json!({ "q": "split the world", "attributesToSnippet": "text" })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_attributes_to_snippet"));
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "split the world",
+            "attributesToSnippet": ["text"],
+            "snippetCount": -1,
+        }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_snippet_count"));
+}
@@ -2,7 +2,7 @@ use meili_snap::{json_string, snapshot};
 use meilisearch_types::milli::constants::RESERVED_GEO_FIELD_NAME;
 
 use super::test_settings_documents_indexing_swapping_and_search;
-use crate::common::shared_index_with_geo_documents;
+use crate::common::{shared_index_with_geo_documents, Server};
 use crate::json;
 
 #[actix_rt::test]
@@ -317,3 +317,74 @@ async fn geo_sort_with_words() {
     )
     .await;
 }
+
+#[actix_rt::test]
+async fn search_geo_grid() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "city": "paris", "_geo": { "lat": 48.8566, "lng": 2.3522 } },
+        { "id": 2, "city": "paris", "_geo": { "lat": 48.8606, "lng": 2.3376 } },
+        { "id": 3, "city": "lyon", "_geo": { "lat": 45.7640, "lng": 4.8357 } },
+        { "id": 4, "city": "sydney", "_geo": { "lat": -33.8688, "lng": 151.2093 } },
+        { "id": 5, "city": "nowhere" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index.update_settings_filterable_attributes(json!(["_geo", "city"])).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "geoGrid": { "precision": 3 } })).await;
+    assert_eq!(code, 200, "{response}");
+    let cells = response["geoGrid"].as_array().unwrap();
+    assert_eq!(
+        cells.iter().map(|cell| (&cell["cell"], &cell["count"])).collect::<Vec<_>>(),
+        [(&json!("u09"), &json!(2)), (&json!("r3g"), &json!(1)), (&json!("u05"), &json!(1))]
+    );
+    snapshot!(json_string!(cells[2]["centroid"]), @r###"
+    {
+      "lat": 45.764,
+      "lng": 4.8357
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "filter": "city != paris", "geoGrid": { "precision": 1 } }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    let cells = response["geoGrid"].as_array().unwrap();
+    assert_eq!(
+        cells.iter().map(|cell| (&cell["cell"], &cell["count"])).collect::<Vec<_>>(),
+        [(&json!("r"), &json!(1)), (&json!("u"), &json!(1))]
+    );
+
+    let (response, code) = index
+        .search_post(json!({ "filter": "city = nowhere", "geoGrid": { "precision": 5 } }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["geoGrid"], json!([]));
+
+    let (response, code) = index.search_post(json!({ "geoGrid": { "precision": 13 } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_geo_grid"));
+
+    let (response, code) = index.search_post(json!({ "geoGrid": { "precision": 0 } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_geo_grid"));
+
+    let (response, code) = index.search_post(json!({ "geoGrid": {} })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_geo_grid"));
+
+    let (task, code) =
+        index.update_settings(json!({ "faceting": { "maxValuesPerFacet": 2 } })).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "geoGrid": { "precision": 3 } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_geo_grid"));
+}
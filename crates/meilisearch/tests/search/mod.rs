@@ -1,9 +1,11 @@
 // This module contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod decay;
 mod distinct;
 mod document_join;
 mod errors;
+mod explain;
 mod facet_search;
 mod filters;
 mod formatted;
@@ -16,8 +18,10 @@ mod metadata;
 mod multi;
 mod pagination;
 mod performance_details;
+mod query_syntax;
 mod restrict_searchable;
 mod search_queue;
+mod typo_tolerance;
 
 use meili_snap::{json_string, snapshot};
 
@@ -195,3 +195,269 @@ async fn test_issue_5274() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn search_after() {
+    let index = shared_index_with_documents().await;
+
+    let mut ids = Vec::new();
+    let mut search_after = json!("");
+    loop {
+        let (response, code) = index
+            .search_post(json!({
+                "sort": ["title:asc"],
+                "limit": 2,
+                "searchAfter": search_after,
+                "attributesToRetrieve": ["id"],
+            }))
+            .await;
+        assert_eq!(code, 200, "{response}");
+        ids.extend(response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()));
+        // the last page is not full and has no cursor
+        if response["nextSearchAfter"].is_null() {
+            break;
+        }
+        search_after = response["nextSearchAfter"].clone();
+    }
+    assert_eq!(ids, ["299537", "522681", "450465", "166428", "287947"]);
+
+    // a full last page has a cursor leading to an empty page
+    let query = json!({ "sort": ["title:asc"], "limit": 5, "searchAfter": "" });
+    let (response, code) = index.search_post(query.clone()).await;
+    assert_eq!(code, 200, "{response}");
+    assert!(response["nextSearchAfter"].is_string(), "{response}");
+    let mut next_page = query;
+    next_page["searchAfter"] = response["nextSearchAfter"].clone();
+    let (response, code) = index.search_post(next_page).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([]));
+    assert!(response.get("nextSearchAfter").is_none(), "{response}");
+
+    let (response, code) = index.search_post(json!({ "searchAfter": "", "limit": 0 })).await;
+    assert_eq!(code, 200, "{response}");
+    assert!(response.get("nextSearchAfter").is_none(), "{response}");
+
+    for parameters in [json!({ "offset": 2 }), json!({ "page": 1 }), json!({ "hitsPerPage": 2 })] {
+        let mut query = json!({ "searchAfter": "" });
+        query.as_object_mut().unwrap().extend(parameters.as_object().unwrap().clone());
+        let (response, code) = index.search_post(query).await;
+        assert_eq!(code, 400, "{parameters}: {response}");
+        assert_eq!(response["code"], json!("invalid_search_search_after"), "{parameters}");
+    }
+
+    // `e30` is `{}` in base64
+    for search_after in [json!("not a cursor"), json!("e30"), json!(42)] {
+        let (response, code) = index.search_post(json!({ "searchAfter": search_after })).await;
+        assert_eq!(code, 400, "{search_after}: {response}");
+        assert_eq!(response["code"], json!("invalid_search_search_after"), "{search_after}");
+    }
+}
+
+#[actix_rt::test]
+async fn search_after_every_page_size() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "red cat", "price": 30 },
+        { "id": 2, "title": "red dog", "price": 10 },
+        { "id": 3, "title": "red cat", "price": 20 },
+        { "id": 4, "title": "blue cat", "price": 20 },
+        { "id": 5, "title": "red cat", "price": 5 },
+        { "id": 6, "title": "cat", "price": 20 },
+        { "id": 7, "title": "red", "price": 10 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .update_settings(json!({
+            "sortableAttributes": ["price"],
+            "rankingRules": ["words", "sort", "typo", "exactness"],
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let query = json!({ "q": "red cat", "sort": ["price:desc"], "attributesToRetrieve": ["id"] });
+    let (response, code) = index.search_post(query.clone()).await;
+    assert_eq!(code, 200, "{response}");
+    let expected: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect();
+
+    // the cursor ends up in the buckets of every ranking rule
+    for limit in 1..=expected.len() {
+        let mut ids = Vec::new();
+        let mut search_after = json!("");
+        loop {
+            let mut query = query.clone();
+            query["limit"] = json!(limit);
+            query["searchAfter"] = search_after;
+            let (response, code) = index.search_post(query).await;
+            assert_eq!(code, 200, "{response}");
+            ids.extend(response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()));
+            if response["nextSearchAfter"].is_null() {
+                break;
+            }
+            search_after = response["nextSearchAfter"].clone();
+        }
+        assert_eq!(ids, expected, "limit: {limit}");
+    }
+}
+
+#[actix_rt::test]
+async fn search_after_is_bound_to_the_search() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "red cat", "price": 30 },
+        { "id": 2, "title": "red dog", "price": 10 },
+        { "id": 3, "title": "blue cat", "price": 20 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .update_settings(json!({
+            "sortableAttributes": ["price"],
+            "filterableAttributes": ["price"],
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let query = json!({ "q": "red", "sort": ["price:asc"], "limit": 1, "searchAfter": "" });
+    let (response, code) = index.search_post(query.clone()).await;
+    assert_eq!(code, 200, "{response}");
+    let search_after = response["nextSearchAfter"].clone();
+    assert!(search_after.is_string(), "{response}");
+
+    // the same search can be continued, even when only the returned attributes change
+    let mut same_search = query.clone();
+    same_search["searchAfter"] = search_after.clone();
+    same_search["attributesToRetrieve"] = json!(["id"]);
+    let (response, code) = index.search_post(same_search).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 1 }]));
+
+    for (parameter, value) in [
+        ("q", json!("cat")),
+        ("sort", json!(["price:desc"])),
+        ("filter", json!("price > 5")),
+        ("matchingStrategy", json!("all")),
+        ("rankingScoreThreshold", json!(0.5)),
+    ] {
+        let mut other_search = query.clone();
+        other_search["searchAfter"] = search_after.clone();
+        other_search[parameter] = value;
+        let (response, code) = index.search_post(other_search).await;
+        assert_eq!(code, 400, "{parameter}: {response}");
+        assert_eq!(response["code"], json!("invalid_search_search_after"), "{parameter}");
+    }
+
+    // nor with other ranking rules
+    let (task, code) =
+        index.update_settings(json!({ "rankingRules": ["sort", "words", "typo"] })).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let mut same_search = query.clone();
+    same_search["searchAfter"] = search_after;
+    let (response, code) = index.search_post(same_search).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_search_after"));
+}
+
+#[actix_rt::test]
+async fn search_after_hides_the_sort_values() {
+    use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+    use base64::Engine as _;
+
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "secret": "swordfish" },
+        { "id": 2, "secret": "hunter2" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .update_settings(json!({ "sortableAttributes": ["secret"], "displayedAttributes": ["id"] }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let query = json!({ "sort": ["secret:asc"], "limit": 1, "searchAfter": "" });
+    let (response, code) = index.search_post(query.clone()).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 2 }]));
+    let search_after = response["nextSearchAfter"].as_str().unwrap().to_string();
+
+    // the sort value of the last hit is not displayed and must not be readable from the cursor
+    let bytes = BASE64_URL_SAFE_NO_PAD.decode(&search_after).unwrap();
+    assert!(!bytes.windows(b"hunter2".len()).any(|window| window == b"hunter2"));
+    assert!(!bytes.windows(b"secret".len()).any(|window| window == b"secret"));
+
+    let mut next_page = query.clone();
+    next_page["searchAfter"] = json!(search_after);
+    let (response, code) = index.search_post(next_page).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 1 }]));
+
+    // a tampered cursor is rejected
+    let mut tampered = bytes;
+    *tampered.last_mut().unwrap() ^= 1;
+    let mut next_page = query;
+    next_page["searchAfter"] = json!(BASE64_URL_SAFE_NO_PAD.encode(tampered));
+    let (response, code) = index.search_post(next_page).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_search_after"));
+}
+
+#[actix_rt::test]
+async fn search_after_is_shared_by_the_instances_of_a_master_key() {
+    async fn index_documents(server: &Server) {
+        let index = server.index("search_after");
+        let documents = json!([
+            { "id": 1, "price": 30 },
+            { "id": 2, "price": 10 },
+            { "id": 3, "price": 20 },
+        ]);
+        let (task, code) = index.add_documents(documents, None).await;
+        assert_eq!(code, 202, "{task}");
+        server.wait_task(task.uid()).await.succeeded();
+        let (task, code) = index.update_settings(json!({ "sortableAttributes": ["price"] })).await;
+        assert_eq!(code, 202, "{task}");
+        server.wait_task(task.uid()).await.succeeded();
+    }
+
+    let mut first = Server::new_auth().await;
+    first.use_api_key("MASTER_KEY");
+    index_documents(&first).await;
+    let mut second = Server::new_auth().await;
+    second.use_api_key("MASTER_KEY");
+    index_documents(&second).await;
+
+    let query = json!({ "sort": ["price:asc"], "limit": 1, "searchAfter": "" });
+    let (response, code) = first.index("search_after").search_post(query.clone()).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 2, "price": 10 }]));
+
+    // the cursor can be used by another instance with the same master key, such as a replica
+    let mut next_page = query.clone();
+    next_page["searchAfter"] = response["nextSearchAfter"].clone();
+    let (response, code) = second.index("search_after").search_post(next_page).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 3, "price": 20 }]));
+
+    // but not by an instance without a master key, its cursors are encrypted with its own secret
+    let third = Server::new().await;
+    index_documents(&third).await;
+    let mut next_page = query;
+    next_page["searchAfter"] = response["nextSearchAfter"].clone();
+    let (response, code) = third.index("search_after").search_post(next_page).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_search_after"));
+}
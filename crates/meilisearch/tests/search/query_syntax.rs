@@ -0,0 +1,81 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_advanced_query_syntax() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "the dark knight", "overview": "batman fights the joker" },
+        { "id": 2, "title": "batman begins", "overview": "bruce wayne becomes batman" },
+        { "id": 3, "title": "the joker", "overview": "a villain story in gotham" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) =
+        index.update_settings(json!({ "searchableAttributes": ["title", "overview"] })).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    // the simple syntax searches for the `title` word
+    let (response, code) = index.search_post(json!({ "q": "title:batman" })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([]));
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "title:batman",
+            "querySyntax": "advanced",
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 2 }]));
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "+(gotham OR wayne) -title:batman",
+            "querySyntax": "advanced",
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 3 }]));
+
+    let (response, code) =
+        index.search_get("?q=title%3Abatman&querySyntax=advanced&attributesToRetrieve=id").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 2 }]));
+
+    // a required term matching nothing excludes every document
+    let (response, code) =
+        index.search_post(json!({ "q": "+nolan batman", "querySyntax": "advanced" })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([]));
+
+    // the unbalanced parentheses are closed
+    let (response, code) = index
+        .search_post(json!({
+            "q": "(batman OR wayne",
+            "querySyntax": "advanced",
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{response}");
+
+    let (response, code) =
+        index.search_post(json!({ "q": "director:nolan", "querySyntax": "advanced" })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_q"));
+
+    let (response, code) = index.search_post(json!({ "querySyntax": "lucene" })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_query_syntax"));
+
+    let (response, code) = index.search_get("?q=batman&querySyntax=lucene").await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_query_syntax"));
+}
@@ -857,3 +857,71 @@ async fn search_on_empty_index_with_searchable_attributes() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn search_attribute_weights() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "the tiger", "overview": "a movie about a cat" },
+        { "id": 2, "title": "the cat", "overview": "a movie about a tiger" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) =
+        index.update_settings(json!({ "searchableAttributes": ["title", "overview"] })).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "q": "cat", "attributesToRetrieve": ["id"] })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 2 }, { "id": 1 }]));
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "cat",
+            "attributeWeights": { "overview": 2, "title": 1 },
+            "attributesToRetrieve": ["id"],
+            "showRankingScoreDetails": true,
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"][0]["id"], json!(1));
+    assert_eq!(response["hits"][1]["id"], json!(2));
+    assert_eq!(
+        response["hits"][0]["_rankingScoreDetails"]["attribute"]["attributeWeights"],
+        json!({ "title": 1, "overview": 2 })
+    );
+
+    // the attributes missing from the weights are still searched
+    let (response, code) = index
+        .search_post(json!({
+            "q": "cat",
+            "attributeWeights": { "title": 1 },
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 2 }, { "id": 1 }]));
+
+    let (response, code) = index
+        .search_post(json!({ "q": "cat", "attributeWeights": {}, "attributesToRetrieve": ["id"] }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{response}");
+
+    let (response, code) =
+        index.search_post(json!({ "q": "cat", "attributeWeights": { "id": 1 } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_attribute_weights"));
+
+    for weight in [json!(-1), json!(70_000), json!(1.5), json!("high")] {
+        let (response, code) =
+            index.search_post(json!({ "q": "cat", "attributeWeights": { "title": weight } })).await;
+        assert_eq!(code, 400, "{weight}: {response}");
+        assert_eq!(response["code"], json!("invalid_search_attribute_weights"), "{weight}");
+    }
+}
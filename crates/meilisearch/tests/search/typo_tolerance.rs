@@ -0,0 +1,79 @@
+use crate::common::{Server, Value};
+use crate::json;
+
+#[actix_rt::test]
+async fn search_typo_tolerance_and_synonyms_overrides() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "text": "quick fox" },
+        { "id": 2, "text": "quack fox" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index.update_settings(json!({ "synonyms": { "fast": ["quick"] } })).await;
+    assert_eq!(code, 202, "{task}");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let hit_ids = |response: &Value| {
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect::<Vec<_>>()
+    };
+
+    let (response, code) = index.search_post(json!({ "q": "quack" })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), [json!(2), json!(1)]);
+
+    let (response, code) =
+        index.search_post(json!({ "q": "quack", "typoTolerance": { "enabled": false } })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), [json!(2)]);
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "quack", "typoTolerance": { "minWordSizeForTypos": { "oneTypo": 6 } } }),
+        )
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), [json!(2)]);
+
+    // an empty override keeps the settings of the index
+    let (response, code) = index.search_post(json!({ "q": "quack", "typoTolerance": {} })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), [json!(2), json!(1)]);
+
+    let (response, code) = index.search_post(json!({ "q": "fast" })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), [json!(1)]);
+
+    let (response, code) = index.search_post(json!({ "q": "fast", "useSynonyms": false })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), Vec::<Value>::new());
+
+    let (response, code) = index.search_get("?q=fast&useSynonyms=false").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), Vec::<Value>::new());
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "quack",
+            "typoTolerance": { "minWordSizeForTypos": { "oneTypo": 8, "twoTypos": 5 } },
+        }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_typo_tolerance"));
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "quack", "typoTolerance": { "minWordSizeForTypos": { "oneTypo": 256 } } }),
+        )
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_typo_tolerance"));
+
+    let (response, code) =
+        index.search_post(json!({ "q": "quack", "typoTolerance": { "enabled": "no" } })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_typo_tolerance"));
+}
@@ -886,3 +886,31 @@ async fn similar_bad_embedder() {
     assert_eq!(response, expected_response);
     assert_eq!(code, 400);
 }
+
+#[actix_rt::test]
+async fn similar_bad_mode() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.similar_post(json!({"id": 287947, "mode": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.mode`: expected one of `semantic`, `keyword`",
+      "code": "invalid_similar_mode",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_mode"
+    }
+    "###);
+
+    let (response, code) = index.similar_get("?id=287947&mode=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` for parameter `mode`: expected one of `semantic`, `keyword`",
+      "code": "invalid_similar_mode",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_mode"
+    }
+    "###);
+}
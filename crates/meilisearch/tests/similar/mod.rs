@@ -767,3 +767,180 @@ async fn performance_details() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn keyword_similar() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .update_settings(json!({
+            "searchableAttributes": ["title"],
+            "filterableAttributes": ["genre"],
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "title": "The Dark Knight: Batman returns to Gotham", "genre": "action" },
+        { "id": 2, "title": "Batman begins in Gotham city", "genre": "action" },
+        { "id": 3, "title": "A romantic comedy in Paris", "genre": "romance" },
+        { "id": 4, "title": "Gotham city police story", "genre": "crime" },
+        { "id": 5, "title": "Batman and Robin", "genre": "action" },
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(value.uid()).await.succeeded();
+
+    // no embedder is configured, the similar documents share words with the target document,
+    // `batman` and `gotham` are as discriminative and the documents sharing one of them tie
+    let (response, code) = index
+        .similar_post(json!({
+            "id": 1,
+            "mode": "keyword",
+            "attributesToRetrieve": ["id"],
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].as_u64()).collect();
+    assert_eq!(ids, [Some(2), Some(4), Some(5)], "{response}");
+    let scores: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["_rankingScore"].as_f64().unwrap())
+        .collect();
+    assert!(scores[0] > scores[1], "{response}");
+    assert_eq!(scores[1], scores[2], "{response}");
+    snapshot!(response["estimatedTotalHits"], @"3");
+
+    let (response, code) = index
+        .similar_post(json!({
+            "id": 1,
+            "mode": "keyword",
+            "attributesToRetrieve": ["id"],
+            "filter": "genre = action AND id != 2",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 5
+      }
+    ]
+    "###);
+
+    // the limit and the ranking score threshold are applied while ranking the documents
+    let (response, code) = index
+        .similar_post(
+            json!({ "id": 1, "mode": "keyword", "attributesToRetrieve": ["id"], "limit": 1 }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      }
+    ]
+    "###);
+    let (response, code) = index
+        .similar_post(json!({
+            "id": 1,
+            "mode": "keyword",
+            "attributesToRetrieve": ["id"],
+            "rankingScoreThreshold": (scores[0] + scores[1]) / 2.0,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.similar_get("?id=1&mode=keyword&attributesToRetrieve=id&limit=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      }
+    ]
+    "###);
+
+    // the keyword mode is explicit and does not use an embedder
+    let (response, code) =
+        index.similar_post(json!({ "id": 1, "mode": "keyword", "embedder": "default" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.embedder`: the `keyword` mode does not use an embedder.",
+      "code": "invalid_similar_embedder",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_embedder"
+    }
+    "###);
+    let (response, code) = index.similar_get("?id=1&mode=keyword&embedder=default").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `embedder`: the `keyword` mode does not use an embedder.",
+      "code": "invalid_similar_embedder",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_embedder"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn keyword_similar_sums_the_weights_of_the_keywords() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "title": "wizard dragon castle" },
+        { "id": 2, "title": "wizard tale" },
+        { "id": 3, "title": "dragon castle" },
+        { "id": 4, "title": "dragon ship" },
+        { "id": 5, "title": "castle moat" },
+        { "id": 6, "title": "ocean" },
+        { "id": 7, "title": "desert" },
+        { "id": 8, "title": "forest" },
+        { "id": 9, "title": "river" },
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(value.uid()).await.succeeded();
+
+    // `wizard` is the most discriminative keyword, but the weights of `dragon` and `castle`
+    // add up to more than its weight and the document lacking `wizard` is the most similar
+    let (response, code) = index
+        .similar_post(json!({
+            "id": 1,
+            "mode": "keyword",
+            "attributesToRetrieve": ["id"],
+            "showRankingScoreDetails": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].as_u64()).collect();
+    assert_eq!(ids, [Some(3), Some(2), Some(4), Some(5)], "{response}");
+    let keywords = &response["hits"][0]["_rankingScoreDetails"]["keywords"];
+    snapshot!(keywords["matchingKeywords"], @"2");
+    snapshot!(keywords["maxMatchingKeywords"], @"3");
+    snapshot!(response["estimatedTotalHits"], @"4");
+}
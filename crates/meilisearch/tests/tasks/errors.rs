@@ -1,6 +1,7 @@
 use meili_snap::*;
 
 use crate::common::Server;
+use crate::json;
 
 #[actix_rt::test]
 async fn task_bad_uids() {
@@ -555,3 +556,32 @@ async fn task_bad_before_finished_at() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn task_retention_policy_out_of_range() {
+    let server = Server::new_shared();
+
+    let (response, code) =
+        server.update_task_retention_policy(json!({ "succeededTasksDays": 4294967295u32 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value for `succeededTasksDays`: expected at most 36500 days, but found 4294967295.",
+      "code": "invalid_task_retention_succeeded_tasks_days",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_task_retention_succeeded_tasks_days"
+    }
+    "###);
+
+    let (response, code) =
+        server.update_task_retention_policy(json!({ "failedTasksDays": 36501 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value for `failedTasksDays`: expected at most 36500 days, but found 36501.",
+      "code": "invalid_task_retention_failed_tasks_days",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_task_retention_failed_tasks_days"
+    }
+    "###);
+}
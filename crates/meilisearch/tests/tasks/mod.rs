@@ -1,5 +1,6 @@
 mod deletion;
 mod errors;
+mod pause;
 mod webhook;
 
 use meili_snap::{json_string, snapshot};
@@ -1165,3 +1166,148 @@ async fn export_foreign_filter() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn delayed_task() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let documents = r#"[{ "id": 1 }]"#;
+
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json"), ("Meili-Task-Run-At", "tomorrow")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_run_at""###);
+
+    // a date in the past doesn't delay the task
+    let (task, code) = index
+        .raw_add_documents(
+            documents,
+            vec![
+                ("Content-Type", "application/json"),
+                ("Meili-Task-Run-At", "2020-01-01T00:00:00Z"),
+            ],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let run_at = (OffsetDateTime::now_utc() + time::Duration::hours(1)).format(&Rfc3339).unwrap();
+    let (delayed, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json"), ("Meili-Task-Run-At", run_at.as_str())],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    assert!(delayed["runAt"].is_string(), "{delayed}");
+    let (later, code) = index.add_documents(json!([{ "id": 2 }]), None).await;
+    snapshot!(code, @"202 Accepted");
+
+    // the delayed task doesn't block the tasks enqueued after it
+    server.wait_task(later.uid()).await.succeeded();
+    let other_index = server.unique_index();
+    let (task, _) = other_index.add_documents(json!([{ "id": 1 }]), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _) = server.get_task(delayed.uid()).await;
+    assert_eq!(task["status"], json!("enqueued"), "{task}");
+    assert_eq!(task["runAt"], delayed["runAt"], "{task}");
+
+    let (tasks, code) = server
+        .tasks_filter(&format!("indexUids={}&afterRunAt=2020-01-01T00:00:00Z", index.uid))
+        .await;
+    snapshot!(code, @"200 OK");
+    let uids: Vec<_> =
+        tasks["results"].as_array().unwrap().iter().map(|task| task["uid"].as_u64()).collect();
+    assert_eq!(uids, [Some(delayed.uid())], "{tasks}");
+
+    let (task, code) = server.cancel_tasks(&format!("uids={}", delayed.uid())).await;
+    snapshot!(code, @"200 OK");
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _) = server.get_task(delayed.uid()).await;
+    assert_eq!(task["status"], json!("canceled"), "{task}");
+}
+
+#[actix_rt::test]
+async fn stream_tasks() {
+    let server = Server::new_shared();
+
+    let (response, code) = server.service.get("/tasks/stream?statuses=unknown").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_statuses""###);
+
+    // the stream starts with the tasks matching the query
+    let index = server.unique_index();
+    let (task, _) = index.add_documents(json!([{ "id": 1 }]), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (events, code) =
+        server.service.get_events(format!("/tasks/stream?uids={}", task.uid()), 1).await;
+    snapshot!(code, @"200 OK");
+    let (name, data) = &events[0];
+    assert_eq!(name, "task");
+    assert_eq!(data["uid"].as_u64(), Some(task.uid()), "{data}");
+    assert_eq!(data["status"], json!("succeeded"), "{data}");
+
+    // then sends the tasks matching the query once they change status
+    let (task, _) = index.add_documents(json!([{ "id": 2 }]), None).await;
+    let (events, code) = server
+        .service
+        .get_events(format!("/tasks/stream?uids={}&statuses=succeeded,failed", task.uid()), 1)
+        .await;
+    snapshot!(code, @"200 OK");
+    let (name, data) = &events[0];
+    assert_eq!(name, "task");
+    assert_eq!(data["uid"].as_u64(), Some(task.uid()), "{data}");
+    assert_eq!(data["status"], json!("succeeded"), "{data}");
+}
+
+#[actix_rt::test]
+async fn tasks_are_processed_by_priority() {
+    let server = Server::new_shared();
+
+    let (response, code) = server
+        .unique_index()
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json"), ("Meili-Task-Priority", "urgent")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_priority""###);
+
+    // the tasks are delayed to the same date so that they are all enqueued when the
+    // scheduler picks the next index, the oldest task has the lowest priority.
+    let run_at = (OffsetDateTime::now_utc() + time::Duration::seconds(5)).format(&Rfc3339).unwrap();
+    let mut tasks = Vec::new();
+    for priority in ["low", "normal", "high"] {
+        let index = server.unique_index();
+        let (task, code) = index
+            .raw_add_documents(
+                r#"[{ "id": 1 }]"#,
+                vec![
+                    ("Content-Type", "application/json"),
+                    ("Meili-Task-Priority", priority),
+                    ("Meili-Task-Run-At", run_at.as_str()),
+                ],
+                "",
+            )
+            .await;
+        snapshot!(code, @"202 Accepted");
+        tasks.push(task.uid());
+    }
+
+    let mut batches = Vec::new();
+    for uid in tasks {
+        let task = server.wait_task(uid).await.succeeded();
+        batches.push(task["batchUid"].as_u64().unwrap());
+    }
+    let (low, normal, high) = (batches[0], batches[1], batches[2]);
+    assert!(high < normal && normal < low, "{batches:?}");
+}
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+/// Polls the task until it isn't processing anymore and returns its status.
+async fn status_once_not_processing(server: &Server, uid: u64) -> serde_json::Value {
+    for _ in 0..400 {
+        let (task, code) = server.get_task(uid).await;
+        assert_eq!(code, 200, "{task}");
+        if task["status"] != json!("processing") {
+            return task["status"].clone();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("the task {uid} is still processing");
+}
+
+#[actix_rt::test]
+async fn pause_and_resume_processing() {
+    let server = Server::new().await;
+    let index = server.unique_index();
+
+    let (response, code) = server.pause_processing("").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["paused"], json!(true), "{response}");
+    let paused_at = response["pausedAt"].clone();
+    assert!(paused_at.is_string(), "{response}");
+
+    // pausing again keeps the original date
+    let (response, code) = server.pause_processing("").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["pausedAt"], paused_at, "{response}");
+
+    // the paused state is visible in the processing state, the stats and the list of tasks
+    let (response, code) = server.processing_state().await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response, json!({ "paused": true, "pausedAt": paused_at }), "{response}");
+    let (stats, code) = server.stats().await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(stats["pausedAt"], paused_at, "{stats}");
+    let (tasks, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(tasks["paused"], json!(true), "{tasks}");
+    assert_eq!(tasks["pausedAt"], paused_at, "{tasks}");
+
+    // the tasks are still enqueued, but not processed
+    let (task, code) = index.add_documents(json!([{ "id": 1 }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let (response, _) = server.get_task(task.uid()).await;
+    assert_eq!(response["status"], json!("enqueued"), "{response}");
+
+    let (response, code) = server.resume_processing().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "paused": false
+    }
+    "###);
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = server.processing_state().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "paused": false
+    }
+    "###);
+    let (stats, code) = server.stats().await;
+    snapshot!(code, @"200 OK");
+    assert!(stats.get("pausedAt").is_none(), "{stats}");
+    let (tasks, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    assert!(tasks.get("paused").is_none(), "{tasks}");
+    assert!(tasks.get("pausedAt").is_none(), "{tasks}");
+
+    // resuming a running scheduler is a no-op
+    let (_, code) = server.resume_processing().await;
+    snapshot!(code, @"200 OK");
+}
+
+#[actix_rt::test]
+async fn pause_processing_aborts_the_processing_batch() {
+    let server = Server::new().await;
+    let index = server.unique_index();
+
+    let (response, code) = server.pause_processing("abortProcessing=maybe").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_abort_processing""###);
+    let (stats, _) = server.stats().await;
+    assert!(stats.get("pausedAt").is_none(), "{stats}");
+
+    let documents: Vec<_> =
+        (0..10_000).map(|id| serde_json::json!({ "id": id, "text": "hello" })).collect();
+    let (task, code) = index.add_documents(json!(documents), None).await;
+    snapshot!(code, @"202 Accepted");
+
+    let (response, code) = server.pause_processing("abortProcessing=true").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["paused"], json!(true), "{response}");
+
+    // an aborted task is enqueued again instead of failing or being canceled
+    let status = status_once_not_processing(&server, task.uid()).await;
+    assert!(status == json!("enqueued") || status == json!("succeeded"), "{status}");
+
+    let (_, code) = server.resume_processing().await;
+    snapshot!(code, @"200 OK");
+    server.wait_task(task.uid()).await.succeeded();
+    let (response, _) = index.get_all_documents_raw("?limit=0").await;
+    assert_eq!(response["total"], json!(10_000), "{response}");
+}
+
+#[actix_rt::test]
+async fn pause_processing_requires_all_the_indexes() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let mut keys = Vec::new();
+    for (actions, indexes) in
+        [(json!(["tasks.get"]), json!(["*"])), (json!(["tasks.*"]), json!(["products"]))]
+    {
+        let (response, code) = server
+            .add_api_key(json!({ "actions": actions, "indexes": indexes, "expiresAt": null }))
+            .await;
+        assert_eq!(code, 201, "{response}");
+        keys.push(response["key"].as_str().unwrap().to_string());
+    }
+
+    for key in keys {
+        server.use_api_key(&key);
+        let (response, code) = server.pause_processing("").await;
+        assert_eq!(code, 403, "{response}");
+        assert_eq!(response["code"], json!("invalid_api_key"), "{response}");
+        let (response, code) = server.resume_processing().await;
+        assert_eq!(code, 403, "{response}");
+        assert_eq!(response["code"], json!("invalid_api_key"), "{response}");
+    }
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["tasks.*"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    assert_eq!(code, 201, "{response}");
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    let (response, code) = server.pause_processing("").await;
+    assert_eq!(code, 200, "{response}");
+    let (response, code) = server.resume_processing().await;
+    assert_eq!(code, 200, "{response}");
+}
@@ -29,6 +29,13 @@ async fn forward_body(
     assert_eq!(headers.get("transfer-encoding").unwrap(), "chunked");
     assert_eq!(headers.get("accept-encoding").unwrap(), "gzip");
     assert_eq!(headers.get("content-encoding").unwrap(), "gzip");
+    if let Some(signature) = headers.get("x-meilisearch-signature") {
+        let signature = signature.to_str().unwrap();
+        let signature = signature.strip_prefix("sha256=").unwrap();
+        assert_eq!(signature.len(), 64, "{signature}");
+        let timestamp = headers.get("x-meilisearch-timestamp").unwrap().to_str().unwrap();
+        timestamp.parse::<i64>().unwrap();
+    }
 
     let body = body.to_vec();
     sender.send(body).unwrap();
@@ -164,6 +171,158 @@ async fn single_receives_data() {
     server_handle.abort();
 }
 
+#[actix_web::test]
+async fn filters_and_secret() {
+    let WebhookHandle { server_handle, url, mut receiver } = create_webhook_server().await;
+
+    let server = Server::new().await;
+
+    let (value, code) = server
+        .create_webhook(json!({
+            "url": url,
+            "filters": { "types": ["documentAdditionOrUpdate"], "statuses": ["*"], "indexUids": ["tamo*"] },
+            "secret": "a-very-long-signing-secret"
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    snapshot!(json_string!(value, { ".uuid" => "[uuid]", ".url" => "[ignored]" }), @r#"
+    {
+      "uuid": "[uuid]",
+      "isEditable": true,
+      "url": "[ignored]",
+      "headers": {},
+      "filters": {
+        "types": [
+          "documentAdditionOrUpdate"
+        ],
+        "indexUids": [
+          "tamo*"
+        ]
+      },
+      "secret": "a-vXXXXX..."
+    }
+    "#);
+    let uuid = value["uuid"].as_str().unwrap().to_string();
+
+    // neither the settings update nor the other index are sent
+    let (task, _status) =
+        server.index("tamo").update_settings(json!({ "searchableAttributes": ["doggo"] })).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _status) =
+        server.index("other").add_documents(json!({ "id": 1, "doggo": "bone" }), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _status) =
+        server.index("tamo").add_documents(json!({ "id": 1, "doggo": "bone" }), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let payload = receiver.recv().await.unwrap();
+    let payload = String::from_utf8(payload).unwrap();
+    let tasks: Vec<serde_json::Value> =
+        payload.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(tasks.len(), 1, "{payload}");
+    assert_eq!(tasks[0]["indexUid"], json!("tamo"));
+    assert_eq!(tasks[0]["type"], json!("documentAdditionOrUpdate"));
+
+    // removing the filters and the secret
+    let (value, code) =
+        server.patch_webhook(&uuid, json!({ "filters": null, "secret": null })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(value, { ".uuid" => "[uuid]", ".url" => "[ignored]" }), @r#"
+    {
+      "uuid": "[uuid]",
+      "isEditable": true,
+      "url": "[ignored]",
+      "headers": {}
+    }
+    "#);
+
+    let (value, code) =
+        server.patch_webhook(&uuid, json!({ "filters": { "types": ["doggo"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(value, @r#"
+    {
+      "message": "Invalid value at `.filters.types[0]`: `doggo` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentEdition`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`, `export`, `upgradeDatabase`, `indexCompaction`, `networkTopologyChange`, `dsrUpdate`, `dsrClear`.",
+      "code": "invalid_webhook_filters",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_webhook_filters"
+    }
+    "#);
+
+    let (value, code) = server.patch_webhook(&uuid, json!({ "secret": "" })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(value["code"], json!("invalid_webhook_secret"), "{value}");
+
+    server_handle.abort();
+}
+
+#[actix_web::test]
+async fn deliveries_status_and_replay() {
+    let WebhookHandle { server_handle, url, mut receiver } = create_webhook_server().await;
+
+    let server = Server::new().await;
+
+    let (value, code) = server.create_webhook(json!({ "url": url })).await;
+    snapshot!(code, @"201 Created");
+    let uuid = value["uuid"].as_str().unwrap().to_string();
+
+    let (value, code) = server.get_webhook_deliveries(&uuid).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(value, @r#"
+    {
+      "pending": 0,
+      "failed": 0,
+      "lastSuccessAt": null,
+      "lastErrorAt": null,
+      "lastError": null
+    }
+    "#);
+
+    let index = server.index("tamo");
+    let (task, _status) = index.add_documents(json!({ "id": 1, "doggo": "bone" }), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    receiver.recv().await.unwrap();
+
+    // the success is recorded right after the webhook answered
+    let mut value = json!(null);
+    for _ in 0..50 {
+        let (response, code) = server.get_webhook_deliveries(&uuid).await;
+        snapshot!(code, @"200 OK");
+        value = response;
+        if !value["lastSuccessAt"].is_null() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    snapshot!(json_string!(value, { ".lastSuccessAt" => "[date]" }), @r#"
+    {
+      "pending": 0,
+      "failed": 0,
+      "lastSuccessAt": "[date]",
+      "lastErrorAt": null,
+      "lastError": null
+    }
+    "#);
+
+    let (value, code) = server.replay_webhook_deliveries(&uuid).await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(value, @r#"
+    {
+      "replayed": 0
+    }
+    "#);
+
+    let unknown = Uuid::new_v4();
+    let (value, code) = server.get_webhook_deliveries(unknown.to_string()).await;
+    snapshot!(code, @"404 Not Found");
+    assert_eq!(value["code"], json!("webhook_not_found"), "{value}");
+
+    let (value, code) = server.replay_webhook_deliveries(unknown.to_string()).await;
+    snapshot!(code, @"404 Not Found");
+    assert_eq!(value["code"], json!("webhook_not_found"), "{value}");
+
+    server_handle.abort();
+}
+
 #[actix_web::test]
 async fn multiple_receive_data() {
     let WebhookHandle { server_handle: handle1, url: url1, receiver: mut receiver1 } =
@@ -591,7 +750,7 @@ async fn forbidden_fields() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(value, @r#"
     {
-      "message": "Immutable field `uuid`: expected one of `url`, `headers`",
+      "message": "Immutable field `uuid`: expected one of `url`, `headers`, `filters`, `secret`",
       "code": "immutable_webhook_uuid",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_webhook_uuid"
@@ -609,7 +768,7 @@ async fn forbidden_fields() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(value, @r#"
     {
-      "message": "Immutable field `isEditable`: expected one of `url`, `headers`",
+      "message": "Immutable field `isEditable`: expected one of `url`, `headers`, `filters`, `secret`",
       "code": "immutable_webhook_is_editable",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_webhook_is_editable"
@@ -629,7 +788,7 @@ async fn forbidden_fields() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(value, @r#"
     {
-      "message": "Immutable field `uuid`: expected one of `url`, `headers`",
+      "message": "Immutable field `uuid`: expected one of `url`, `headers`, `filters`, `secret`",
       "code": "immutable_webhook_uuid",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_webhook_uuid"
@@ -649,7 +808,7 @@ async fn forbidden_fields() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(value, { ".uuid" => "[uuid]" }), @r#"
     {
-      "message": "Immutable field `isEditable`: expected one of `url`, `headers`",
+      "message": "Immutable field `isEditable`: expected one of `url`, `headers`, `filters`, `secret`",
       "code": "immutable_webhook_is_editable",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_webhook_is_editable"
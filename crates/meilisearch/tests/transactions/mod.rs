@@ -0,0 +1,226 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn transaction_commits_operations_on_several_indexes() {
+    let server = Server::new_shared();
+    let movies = server.unique_index();
+    let directors = server.unique_index();
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": &movies.uid, "documents": [{ "id": 1, "title": "Carol" }], "primaryKey": "id" },
+            { "type": "updateSettings", "indexUid": &directors.uid, "settings": { "filterableAttributes": ["name"] } },
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let transaction_uid = response[0]["taskUid"].clone();
+    assert_eq!(response[0]["transactionUid"], transaction_uid);
+    assert_eq!(response[1]["transactionUid"], transaction_uid);
+
+    let first = server.wait_task(response[0].uid()).await.succeeded();
+    let second = server.wait_task(response[1].uid()).await.succeeded();
+    assert_eq!(first["batchUid"], second["batchUid"]);
+
+    let (document, code) = movies.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "title": "Carol"
+    }
+    "###);
+    let (settings, _) = directors.settings().await;
+    snapshot!(json_string!(settings["filterableAttributes"]), @r###"
+    [
+      "name"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn transaction_operations_on_the_same_index_see_each_other() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": &index.uid, "documents": [{ "id": 1 }, { "id": 2 }], "primaryKey": "id" },
+            { "type": "deleteDocuments", "indexUid": &index.uid, "documentIds": [1] },
+            { "type": "updateDocuments", "indexUid": &index.uid, "documents": [{ "id": 2, "title": "Carol" }] },
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    for task in response.as_array().unwrap() {
+        server.wait_task(task["taskUid"].as_u64().unwrap()).await.succeeded();
+    }
+
+    let (_, code) = index.get_document(1, None).await;
+    snapshot!(code, @"404 Not Found");
+    let (document, _) = index.get_document(2, None).await;
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 2,
+      "title": "Carol"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn failing_transaction_is_rolled_back() {
+    let server = Server::new_shared();
+    let movies = server.unique_index();
+    let directors = server.unique_index();
+    let (task, _) = directors.add_documents(json!([{ "id": 1 }]), Some("id")).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": &movies.uid, "documents": [{ "id": 1 }], "primaryKey": "id" },
+            { "type": "addDocuments", "indexUid": &directors.uid, "documents": [{ "uid": 2 }], "primaryKey": "uid" },
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let first = server.wait_task(response[0].uid()).await.failed();
+    server.wait_task(response[1].uid()).await.failed();
+    snapshot!(json_string!(first["error"]["code"]), @r###""transaction_aborted""###);
+
+    let (_, code) = movies.get_document(1, None).await;
+    snapshot!(code, @"404 Not Found");
+}
+
+#[actix_rt::test]
+async fn error_empty_transaction() {
+    let server = Server::new_shared();
+
+    let (response, code) = server.create_transaction(json!([])).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "A transaction must contain at least one operation.",
+      "code": "invalid_transaction_operations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_transaction_operations"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_operation_missing_its_field() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = server
+        .create_transaction(json!([{ "type": "deleteDocuments", "indexUid": &index.uid }]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The operation at position 0 is missing the `documentIds` field required by its type.",
+      "code": "invalid_transaction_operations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_transaction_operations"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_operations_that_cannot_be_merged() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let uid = index.uid.as_str();
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": uid, "documents": [{ "id": 1 }] },
+            { "type": "updateSettings", "indexUid": uid, "settings": { "filterableAttributes": ["id"] } },
+        ]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(
+        response["message"],
+        format!("The operation at position 1 cannot be applied along with the previous operations on the index `{uid}`: the settings updates cannot be mixed with document operations.")
+    );
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "updateDocuments", "indexUid": uid, "documents": [{ "id": 1 }] },
+            { "type": "deleteDocumentsByFilter", "indexUid": uid, "filter": "id = 1" },
+        ]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(
+        response["message"],
+        format!("The operation at position 1 cannot be applied along with the previous operations on the index `{uid}`: the deletions by filter must come before the document additions and updates.")
+    );
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": uid, "documents": [{ "id": 1 }], "primaryKey": "id" },
+            { "type": "addDocuments", "indexUid": uid, "documents": [{ "uid": 1 }], "primaryKey": "uid" },
+        ]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(
+        response["message"],
+        format!("The operation at position 1 cannot be applied along with the previous operations on the index `{uid}`: all the operations of an index must use the same primary key.")
+    );
+    snapshot!(json_string!(response["code"]), @r###""invalid_transaction_operations""###);
+}
+
+#[actix_rt::test]
+async fn transaction_requires_the_actions_of_its_operations() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["documents.*"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    assert_eq!(code, 201, "{response}");
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": "movies", "documents": [{ "id": 1 }], "primaryKey": "id" },
+            { "type": "deleteDocuments", "indexUid": "directors", "documentIds": [1] },
+        ]))
+        .await;
+    assert_eq!(code, 202, "{response}");
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": "movies", "documents": [{ "id": 1 }], "primaryKey": "id" },
+            { "type": "updateSettings", "indexUid": "directors", "settings": { "filterableAttributes": ["name"] } },
+        ]))
+        .await;
+    assert_eq!(code, 403, "{response}");
+    assert_eq!(response["code"], json!("invalid_api_key"), "{response}");
+
+    // the action is checked on the index of each operation
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(
+            json!({ "actions": ["documents.add"], "indexes": ["movies"], "expiresAt": null }),
+        )
+        .await;
+    assert_eq!(code, 201, "{response}");
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    let (response, code) = server
+        .create_transaction(json!([
+            { "type": "addDocuments", "indexUid": "movies", "documents": [{ "id": 2 }], "primaryKey": "id" },
+        ]))
+        .await;
+    assert_eq!(code, 202, "{response}");
+
+    for operation in [
+        json!({ "type": "addDocuments", "indexUid": "directors", "documents": [{ "id": 2 }] }),
+        json!({ "type": "deleteDocuments", "indexUid": "movies", "documentIds": [2] }),
+    ] {
+        let (response, code) = server.create_transaction(json!([operation])).await;
+        assert_eq!(code, 403, "{operation}: {response}");
+        assert_eq!(response["code"], json!("invalid_api_key"), "{operation}");
+    }
+}
@@ -13,7 +13,7 @@ use meili_snap::{json_string, snapshot};
 use meilisearch::option::MaxThreads;
 
 use crate::common::index::Index;
-use crate::common::{default_settings, GetAllDocumentsOptions, Server};
+use crate::common::{default_settings, GetAllDocumentsOptions, Server, Value};
 use crate::json;
 
 pub async fn get_server_vector() -> Server {
@@ -932,3 +932,113 @@ async fn eq_with_sort() {
     }
     "#);
 }
+
+#[actix_rt::test]
+async fn search_with_weighted_semantic_queries() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 2,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "_vectors": { "manual": [1, 0] }},
+      {"id": 1, "_vectors": { "manual": [0, 1] }},
+      {"id": 2, "_vectors": { "manual": [1, -1] }},
+      {"id": 3, "_vectors": { "manual": [1, 1] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(value.uid()).await.succeeded();
+
+    let hit_ids = |response: &Value| {
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect::<Vec<_>>()
+    };
+
+    // like the first document but not like the second one
+    let (response, code) = index
+        .search_post(json!({
+            "semanticQueries": [
+                { "vector": [1, 0] },
+                { "vector": [0, 1], "weight": -1 },
+            ],
+            "hybrid": { "embedder": "manual", "semanticRatio": 1.0 },
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response), [json!(2), json!(0), json!(3), json!(1)]);
+
+    // the weights decide of the importance of each vector, whatever their length
+    let (response, code) = index
+        .search_post(json!({
+            "semanticQueries": [
+                { "vector": [10, 0], "weight": 0.5 },
+                { "vector": [0, 0.1], "weight": 2 },
+            ],
+            "hybrid": { "embedder": "manual", "semanticRatio": 1.0 },
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(hit_ids(&response)[0], json!(1));
+
+    for (query, error_code) in [
+        (json!([{ "vector": [1, 0], "q": "doggo" }]), "invalid_search_semantic_queries"),
+        (json!([{ "weight": 1 }]), "invalid_search_semantic_queries"),
+        (json!([{ "vector": [1, 0], "weight": 0 }]), "invalid_search_semantic_queries"),
+        (
+            json!([{ "vector": [1, 0] }, { "vector": [2, 0], "weight": -1 }]),
+            "invalid_search_semantic_queries",
+        ),
+        (json!([{ "vector": [1, 0, 0] }]), "invalid_vector_dimensions"),
+    ] {
+        let (response, code) = index
+            .search_post(json!({
+                "semanticQueries": query,
+                "hybrid": { "embedder": "manual", "semanticRatio": 1.0 },
+            }))
+            .await;
+        assert_eq!(code, 400, "{response}");
+        assert_eq!(response["code"], json!(error_code), "{response}");
+    }
+
+    let (response, code) = index
+        .search_post(json!({
+            "semanticQueries": [],
+            "hybrid": { "embedder": "manual", "semanticRatio": 1.0 },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.semanticQueries`: the value of `semanticQueries` is invalid, expected at least one query.",
+      "code": "invalid_search_semantic_queries",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_semantic_queries"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "semanticQueries": [{ "vector": [1, 0] }],
+            "vector": [1, 0],
+            "hybrid": { "embedder": "manual" },
+        }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_search_semantic_queries"));
+
+    let (response, code) =
+        index.search_post(json!({ "semanticQueries": [{ "vector": [1, 0] }] })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("missing_search_hybrid"));
+}
@@ -39,6 +39,9 @@ impl From<AscDescError> for CriterionError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoShapePoint") => {
+                CriterionError::ReservedNameForSort { name: "_geoShapePoint".to_string() }
+            }
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoRadius") => {
                 CriterionError::ReservedNameForFilter { name: "_geoRadius".to_string() }
             }
@@ -54,36 +57,47 @@ impl From<AscDescError> for CriterionError {
 pub enum Member {
     Field(String),
     Geo([f64; 2]),
+    /// Sorts by the distance between the point and the nearest edge of the `_geojson` shapes.
+    GeoShape([f64; 2]),
+}
+
+/// Parses the `latitude, longitude` arguments of a geo sort expression.
+fn parse_geo_point_arguments(text: &str, arguments: &str) -> Result<[f64; 2], AscDescError> {
+    let (lat, lng) = arguments
+        .split_once(',')
+        .ok_or_else(|| AscDescError::ReservedKeyword { name: text.to_string() })
+        .and_then(|(lat, lng)| {
+            lat.trim()
+                .parse()
+                .and_then(|lat| lng.trim().parse().map(|lng| (lat, lng)))
+                .map_err(|_| AscDescError::ReservedKeyword { name: text.to_string() })
+        })?;
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(BadGeoError::Lat(lat))?;
+    } else if !(-180.0..=180.0).contains(&lng) {
+        return Err(BadGeoError::Lng(lng))?;
+    }
+    Ok([lat, lng])
 }
 
 impl FromStr for Member {
     type Err = AscDescError;
 
     fn from_str(text: &str) -> Result<Member, Self::Err> {
+        if let Some(point) =
+            text.strip_prefix("_geoShapePoint(").and_then(|text| text.strip_suffix(')'))
+        {
+            return parse_geo_point_arguments(text, point).map(Member::GeoShape);
+        }
         match text.strip_prefix("_geoPoint(").and_then(|text| text.strip_suffix(')')) {
-            Some(point) => {
-                let (lat, lng) = point
-                    .split_once(',')
-                    .ok_or_else(|| AscDescError::ReservedKeyword { name: text.to_string() })
-                    .and_then(|(lat, lng)| {
-                        lat.trim()
-                            .parse()
-                            .and_then(|lat| lng.trim().parse().map(|lng| (lat, lng)))
-                            .map_err(|_| AscDescError::ReservedKeyword { name: text.to_string() })
-                    })?;
-                if !(-90.0..=90.0).contains(&lat) {
-                    return Err(BadGeoError::Lat(lat))?;
-                } else if !(-180.0..=180.0).contains(&lng) {
-                    return Err(BadGeoError::Lng(lng))?;
-                }
-                Ok(Member::Geo([lat, lng]))
-            }
+            Some(point) => parse_geo_point_arguments(text, point).map(Member::Geo),
             None => {
                 if is_reserved_keyword(text)
                     || text.starts_with("_geoRadius(")
                     || text.starts_with("_geoBoundingBox(")
                     || text.starts_with("_geo(")
                     || text.starts_with("_geoDistance(")
+                    || text.starts_with("_geoShape(")
                 {
                     return Err(AscDescError::ReservedKeyword { name: text.to_string() })?;
                 }
@@ -98,6 +112,7 @@ impl fmt::Display for Member {
         match self {
             Member::Field(name) => f.write_str(name),
             Member::Geo([lat, lng]) => write!(f, "_geoPoint({}, {})", lat, lng),
+            Member::GeoShape([lat, lng]) => write!(f, "_geoShapePoint({}, {})", lat, lng),
         }
     }
 }
@@ -106,14 +121,14 @@ impl Member {
     pub fn field(&self) -> Option<&str> {
         match self {
             Member::Field(field) => Some(field),
-            Member::Geo(_) => None,
+            Member::Geo(_) | Member::GeoShape(_) => None,
         }
     }
 
     pub fn geo_point(&self) -> Option<&[f64; 2]> {
         match self {
             Member::Geo(point) => Some(point),
-            Member::Field(_) => None,
+            Member::Field(_) | Member::GeoShape(_) => None,
         }
     }
 }
@@ -230,6 +245,8 @@ mod tests {
             ("_geoPoint(-90, -180.0000000000):asc", Asc(Geo([-90., -180.]))),
             ("_geoPoint(42.0002, 59.895):desc", Desc(Geo([42.0002, 59.895]))),
             ("_geoPoint(42., 59.):desc", Desc(Geo([42., 59.]))),
+            ("_geoShapePoint(42, 59):asc", Asc(GeoShape([42., 59.]))),
+            ("_geoShapePoint(-90, 180.0):desc", Desc(GeoShape([-90., 180.]))),
             ("truc(12, 13):desc", Desc(Field(S("truc(12, 13)")))),
         ];
 
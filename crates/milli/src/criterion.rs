@@ -1,9 +1,11 @@
 use std::fmt;
 use std::str::FromStr;
 
-use serde::{Deserialize, Serialize};
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
+use crate::error::is_reserved_keyword;
 use crate::{AscDesc, Member};
 
 #[derive(Error, Debug)]
@@ -22,6 +24,13 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error(
+        "`{name}` ranking rule is invalid. Decay ranking rules are written `field:linear(origin, scale)`, \
+`field:exp(origin, scale)`, `field:gauss(origin, scale)` or `field:log`, where the origin is a number \
+or `now` and the scale is a positive number. An optional positive weight can be given as the last \
+parameter, e.g. `field:gauss(origin, scale, weight)` or `field:log(weight)`."
+    )]
+    InvalidDecay { name: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -55,6 +64,191 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Blends the relevancy of the documents with a score computed from the numeric value
+    /// of the field specified.
+    Decay(DecayRule),
+}
+
+/// A ranking rule scoring the documents with a function of a numeric field,
+/// e.g. `release_date:gauss(now, 2592000)` or `popularity:log(0.5)`.
+///
+/// The score is between 0 and 1. Instead of ranking the documents after the previous ranking
+/// rules, it is blended with their relevancy: the ranking score of a document is
+/// `(relevancy + Σ weight × score) / (1 + Σ weight)` over the decay rules of the index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecayRule {
+    pub field: String,
+    pub function: DecayFunction,
+    /// How much the score counts compared to the relevancy, `1` by default.
+    pub weight: OrderedFloat<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayFunction {
+    /// Decreases linearly from 1 at the origin to 0 at twice the scale from the origin.
+    Linear { origin: DecayOrigin, scale: OrderedFloat<f64> },
+    /// Halves every `scale` away from the origin.
+    Exp { origin: DecayOrigin, scale: OrderedFloat<f64> },
+    /// Bell curve centered on the origin, equal to 0.5 at `scale` from the origin.
+    Gauss { origin: DecayOrigin, scale: OrderedFloat<f64> },
+    /// Logarithmic boost of the value, the greater the better.
+    Log,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayOrigin {
+    /// The current Unix timestamp, in seconds.
+    Now,
+    Value(OrderedFloat<f64>),
+}
+
+impl DecayFunction {
+    /// Returns the score, between 0 and 1, of the `value`.
+    ///
+    /// `now` is the value of the `now` origin, `max` is the greatest value of the field
+    /// and is only used by the log boost.
+    pub fn score(&self, value: f64, now: f64, max: f64) -> f64 {
+        let distance = |origin: &DecayOrigin, scale: &OrderedFloat<f64>| {
+            let origin = match origin {
+                DecayOrigin::Now => now,
+                DecayOrigin::Value(origin) => origin.0,
+            };
+            (value - origin).abs() / scale.0
+        };
+
+        let score = match self {
+            DecayFunction::Linear { origin, scale } => 1.0 - distance(origin, scale) / 2.0,
+            DecayFunction::Exp { origin, scale } => 0.5f64.powf(distance(origin, scale)),
+            DecayFunction::Gauss { origin, scale } => 0.5f64.powf(distance(origin, scale).powi(2)),
+            DecayFunction::Log if max <= 0.0 => 0.0,
+            DecayFunction::Log => value.max(0.0).ln_1p() / max.ln_1p(),
+        };
+
+        if score.is_nan() {
+            0.0
+        } else {
+            score.clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl FromStr for DecayRule {
+    type Err = CriterionError;
+
+    fn from_str(text: &str) -> Result<DecayRule, Self::Err> {
+        let invalid = || CriterionError::InvalidDecay { name: text.to_string() };
+        let (field, function) = text.rsplit_once(':').ok_or_else(invalid)?;
+        if field.is_empty() {
+            return Err(invalid());
+        }
+        if is_reserved_keyword(field) {
+            return Err(CriterionError::ReservedName { name: field.to_string() });
+        }
+
+        let parse_weight = |weight: &str| match weight.trim().parse::<f64>() {
+            Ok(weight) if weight.is_finite() && weight > 0.0 => Ok(OrderedFloat(weight)),
+            _ => Err(invalid()),
+        };
+
+        let (function, weight) = if function == "log" {
+            (DecayFunction::Log, DecayRule::DEFAULT_WEIGHT)
+        } else {
+            let (name, parameters) = function
+                .strip_suffix(')')
+                .and_then(|function| function.split_once('('))
+                .ok_or_else(invalid)?;
+            if name == "log" {
+                return Ok(DecayRule {
+                    field: field.to_string(),
+                    function: DecayFunction::Log,
+                    weight: parse_weight(parameters)?,
+                });
+            }
+            let mut parameters = parameters.split(',');
+            let (Some(origin), Some(scale)) = (parameters.next(), parameters.next()) else {
+                return Err(invalid());
+            };
+            let weight = match (parameters.next(), parameters.next()) {
+                (None, _) => DecayRule::DEFAULT_WEIGHT,
+                (Some(weight), None) => parse_weight(weight)?,
+                (Some(_), Some(_)) => return Err(invalid()),
+            };
+            let origin = match origin.trim() {
+                "now" => DecayOrigin::Now,
+                origin => match origin.parse::<f64>() {
+                    Ok(origin) if origin.is_finite() => DecayOrigin::Value(OrderedFloat(origin)),
+                    _ => return Err(invalid()),
+                },
+            };
+            let scale = match scale.trim().parse::<f64>() {
+                Ok(scale) if scale.is_finite() && scale > 0.0 => OrderedFloat(scale),
+                _ => return Err(invalid()),
+            };
+            let function = match name {
+                "linear" => DecayFunction::Linear { origin, scale },
+                "exp" => DecayFunction::Exp { origin, scale },
+                "gauss" => DecayFunction::Gauss { origin, scale },
+                _ => return Err(invalid()),
+            };
+            (function, weight)
+        };
+
+        Ok(DecayRule { field: field.to_string(), function, weight })
+    }
+}
+
+impl DecayRule {
+    pub const DEFAULT_WEIGHT: OrderedFloat<f64> = OrderedFloat(1.0);
+
+    /// Returns `true` if the text looks like a decay ranking rule, valid or not.
+    fn is_decay(text: &str) -> bool {
+        let Some((_, function)) = text.rsplit_once(':') else { return false };
+        function == "log"
+            || ["linear(", "exp(", "gauss(", "log("].iter().any(|name| function.starts_with(name))
+    }
+}
+
+impl fmt::Display for DecayOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayOrigin::Now => f.write_str("now"),
+            DecayOrigin::Value(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl fmt::Display for DecayRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DecayRule { field, function, weight } = self;
+        let (name, origin, scale) = match function {
+            DecayFunction::Linear { origin, scale } => ("linear", origin, scale),
+            DecayFunction::Exp { origin, scale } => ("exp", origin, scale),
+            DecayFunction::Gauss { origin, scale } => ("gauss", origin, scale),
+            DecayFunction::Log if *weight == DecayRule::DEFAULT_WEIGHT => {
+                return write!(f, "{field}:log")
+            }
+            DecayFunction::Log => return write!(f, "{field}:log({weight})"),
+        };
+        if *weight == DecayRule::DEFAULT_WEIGHT {
+            write!(f, "{field}:{name}({origin}, {scale})")
+        } else {
+            write!(f, "{field}:{name}({origin}, {scale}, {weight})")
+        }
+    }
+}
+
+/// Decay rules are stored in their textual form, like in the settings.
+impl Serialize for DecayRule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DecayRule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
 }
 
 /// How is the attribute ranking rule defined.
@@ -89,6 +283,7 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Decay(DecayRule { field, .. }) => Some(field),
             _otherwise => None,
         }
     }
@@ -107,12 +302,16 @@ impl FromStr for Criterion {
             "wordPosition" => Ok(Criterion::WordPosition),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            text if DecayRule::is_decay(text) => Ok(Criterion::Decay(text.parse()?)),
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
                 AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
                     Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
                 }
+                AscDesc::Asc(Member::GeoShape(_)) | AscDesc::Desc(Member::GeoShape(_)) => {
+                    Err(CriterionError::ReservedNameForSort { name: "_geoShapePoint".to_string() })?
+                }
             },
         }
     }
@@ -145,6 +344,7 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Decay(rule) => write!(f, "{rule}"),
         }
     }
 }
@@ -175,6 +375,66 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            (
+                "release_date:gauss(now, 86400)",
+                Criterion::Decay(DecayRule {
+                    field: S("release_date"),
+                    function: DecayFunction::Gauss {
+                        origin: DecayOrigin::Now,
+                        scale: OrderedFloat(86400.0),
+                    },
+                    weight: DecayRule::DEFAULT_WEIGHT,
+                }),
+            ),
+            (
+                "price:linear(10, 2.5)",
+                Criterion::Decay(DecayRule {
+                    field: S("price"),
+                    function: DecayFunction::Linear {
+                        origin: DecayOrigin::Value(OrderedFloat(10.0)),
+                        scale: OrderedFloat(2.5),
+                    },
+                    weight: DecayRule::DEFAULT_WEIGHT,
+                }),
+            ),
+            (
+                "distance:exp(-3,1)",
+                Criterion::Decay(DecayRule {
+                    field: S("distance"),
+                    function: DecayFunction::Exp {
+                        origin: DecayOrigin::Value(OrderedFloat(-3.0)),
+                        scale: OrderedFloat(1.0),
+                    },
+                    weight: DecayRule::DEFAULT_WEIGHT,
+                }),
+            ),
+            (
+                "popularity:log",
+                Criterion::Decay(DecayRule {
+                    field: S("popularity"),
+                    function: DecayFunction::Log,
+                    weight: DecayRule::DEFAULT_WEIGHT,
+                }),
+            ),
+            (
+                "popularity:log(0.5)",
+                Criterion::Decay(DecayRule {
+                    field: S("popularity"),
+                    function: DecayFunction::Log,
+                    weight: OrderedFloat(0.5),
+                }),
+            ),
+            (
+                "release_date:exp(now, 60, 3)",
+                Criterion::Decay(DecayRule {
+                    field: S("release_date"),
+                    function: DecayFunction::Exp {
+                        origin: DecayOrigin::Now,
+                        scale: OrderedFloat(60.0),
+                    },
+                    weight: OrderedFloat(3.0),
+                }),
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -210,6 +470,15 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("price:gauss(now)", InvalidDecay { name: S("price:gauss(now)") }),
+            ("price:gauss(now, 0)", InvalidDecay { name: S("price:gauss(now, 0)") }),
+            ("price:exp(yesterday, 1)", InvalidDecay { name: S("price:exp(yesterday, 1)") }),
+            ("price:linear(1, 2", InvalidDecay { name: S("price:linear(1, 2") }),
+            (":log", InvalidDecay { name: S(":log") }),
+            ("price:log(0)", InvalidDecay { name: S("price:log(0)") }),
+            ("price:gauss(now, 1, -2)", InvalidDecay { name: S("price:gauss(now, 1, -2)") }),
+            ("price:gauss(now, 1, 2, 3)", InvalidDecay { name: S("price:gauss(now, 1, 2, 3)") }),
+            ("_geo:log", ReservedName { name: S(RESERVED_GEO_FIELD_NAME) }),
         ];
 
         for (input, expected) in invalid_criteria {
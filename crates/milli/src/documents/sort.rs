@@ -11,7 +11,7 @@ use crate::documents::GeoSortParameter;
 use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec};
 use crate::heed_codec::BytesRefCodec;
 use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
-use crate::{is_faceted, AscDesc, DocumentId, FieldsIdsMap, Member, UserError};
+use crate::{is_faceted, AscDesc, DocumentId, FieldsIdsMap, Member, SortError, UserError};
 
 #[derive(Debug, Clone, Copy)]
 enum AscDescId {
@@ -427,6 +427,11 @@ pub fn recursive_sort<'ctx>(
             AscDesc::Desc(Member::Field(field)) => (Some((field, false)), None),
             AscDesc::Asc(Member::Geo(target_point)) => (None, Some((target_point, true))),
             AscDesc::Desc(Member::Geo(target_point)) => (None, Some((target_point, false))),
+            // sorting by the distance to the shapes is only supported by the search
+            AscDesc::Asc(Member::GeoShape(_)) | AscDesc::Desc(Member::GeoShape(_)) => {
+                return Err(SortError::ReservedName { name: "_geoShapePoint".to_string() }
+                    .into_document_error())
+            }
         };
         if let Some((field, ascending)) = field {
             if is_faceted(field, &sortable_fields) {
@@ -624,7 +624,8 @@ impl<'a> DynamicSearchRulesView<'a> {
                 ConstraintConditionKind::VectorExists { .. }
                 | ConstraintConditionKind::GeoLowerThan { .. }
                 | ConstraintConditionKind::GeoBoundingBox { .. }
-                | ConstraintConditionKind::GeoPolygon { .. } => return Ok(Default::default()),
+                | ConstraintConditionKind::GeoPolygon { .. }
+                | ConstraintConditionKind::GeoShape { .. } => return Ok(Default::default()),
             };
             if polarity {
                 // exclude rules that were evaluated to 0
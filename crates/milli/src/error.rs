@@ -229,6 +229,12 @@ and can not be more than 511 bytes.", .document_id.to_string()
         }
     )]
     InvalidDocumentSortableAttribute { field: String, sortable_fields: BTreeSet<String> },
+    #[error("The search cursor does not match the ranking rules of the index.")]
+    InvalidSearchCursor,
+    #[error("The aggregation of `{field}` has {buckets} buckets, more than the {max} allowed by the `maxValuesPerFacet` setting. Use fewer ranges, a greater interval or a narrower filter.")]
+    TooManyFacetBuckets { field: String, buckets: usize, max: usize },
+    #[error("The geo grid of precision {precision} has {cells} cells, more than the {max} allowed by the `maxValuesPerFacet` setting. Use a lower precision or a narrower filter.")]
+    TooManyGeoGridCells { precision: usize, cells: usize, max: usize },
     #[error("Attribute `{}` is not filterable and thus, cannot be used as distinct attribute. {}",
         .field,
         match (.valid_patterns.is_empty(), .matching_rule_index) {
@@ -287,6 +293,16 @@ and can not be more than 511 bytes.", .document_id.to_string()
         valid_fields: BTreeSet<String>,
         hidden_fields: bool,
     },
+    #[error("Attribute `{}` used in the query is not searchable. Available searchable attributes are: `{}{}`.",
+        .field,
+        .valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+        .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+    )]
+    InvalidSearchQueryAttribute {
+        field: String,
+        valid_fields: BTreeSet<String>,
+        hidden_fields: bool,
+    },
     #[error("An LMDB environment is already opened")]
     EnvAlreadyOpened,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
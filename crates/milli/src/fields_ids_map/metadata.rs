@@ -316,6 +316,8 @@ impl MetadataBuilder {
             .filter_map(|criterion| match criterion {
                 Criterion::Asc(field) => Some((field, FieldSortOrder::Asc)),
                 Criterion::Desc(field) => Some((field, FieldSortOrder::Desc)),
+                // the documents with the greatest scores come first
+                Criterion::Decay(rule) => Some((rule.field, FieldSortOrder::Desc)),
                 _otherwise => None,
             })
             .collect();
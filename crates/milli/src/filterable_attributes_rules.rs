@@ -1,6 +1,7 @@
 use std::collections::{BTreeSet, HashSet};
+use std::convert::Infallible;
 
-use deserr::{DeserializeError, Deserr, ValuePointerRef};
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -68,6 +69,14 @@ impl FilterableAttributesRule {
         matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEOJSON_FIELD_NAME)
     }
 
+    /// Get the separator of the hierarchical values of the rule, if any.
+    pub fn hierarchy_separator(&self) -> Option<&str> {
+        match self {
+            FilterableAttributesRule::Field(_) => None,
+            FilterableAttributesRule::Pattern(patterns) => patterns.hierarchy_separator(),
+        }
+    }
+
     /// Get the features of the rule.
     pub fn features(&self) -> FilterableAttributesFeatures {
         match self {
@@ -84,7 +93,7 @@ impl FilterableAttributesRule {
 /// allowed on matched attributes.
 #[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Deserr, ToSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
-#[deserr(rename_all = camelCase, deny_unknown_fields)]
+#[deserr(rename_all = camelCase, deny_unknown_fields, validate = validate_hierarchy_separator -> __Deserr_E)]
 pub struct FilterableAttributesPatterns {
     /// Patterns to match attribute names. Use `*` as a wildcard to match any
     /// characters. For example, `["price_*", "stock"]` matches `price_usd`,
@@ -97,6 +106,33 @@ pub struct FilterableAttributesPatterns {
     #[serde(default)]
     #[deserr(default)]
     pub features: FilterableAttributesFeatures,
+    /// Makes the matching attributes hierarchical: their values are paths
+    /// of nodes joined by this separator, for example
+    /// `"Electronics > Phones > Android"` with `" > "`. Filtering with `=`
+    /// on a node also matches its descendants, and the facet distribution
+    /// counts the children of the node selected by the filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub hierarchy_separator: Option<String>,
+}
+
+fn validate_hierarchy_separator<E: DeserializeError>(
+    patterns: FilterableAttributesPatterns,
+    location: ValuePointerRef,
+) -> Result<FilterableAttributesPatterns, E> {
+    if patterns.hierarchy_separator.as_deref().is_some_and(|separator| separator.trim().is_empty())
+    {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: "`hierarchySeparator` must contain at least one non-whitespace character"
+                    .to_string(),
+            },
+            location.push_key("hierarchySeparator"),
+        )));
+    }
+
+    Ok(patterns)
 }
 
 impl FilterableAttributesPatterns {
@@ -111,6 +147,10 @@ impl FilterableAttributesPatterns {
     pub fn features(&self) -> FilterableAttributesFeatures {
         self.features
     }
+
+    pub fn hierarchy_separator(&self) -> Option<&str> {
+        self.hierarchy_separator.as_deref()
+    }
 }
 
 /// Controls which filtering and faceting operations are enabled for matching
@@ -366,6 +406,21 @@ pub fn matching_features(
     None
 }
 
+/// Match a field against a set of filterable attributes rules.
+///
+/// This function will return the hierarchy separator of the rule matching
+/// the given field name, `None` if the rule is not hierarchical or if no
+/// rule matches.
+pub fn matching_hierarchy_separator<'rules>(
+    field_name: &str,
+    filterable_attributes: &'rules [FilterableAttributesRule],
+) -> Option<&'rules str> {
+    filterable_attributes
+        .iter()
+        .find(|rule| rule.match_str(field_name) == PatternMatch::Match)
+        .and_then(|rule| rule.hierarchy_separator())
+}
+
 /// Match a field against a set of filterable, facet searchable fields,
 /// distinct field, sortable fields, and asc_desc fields.
 pub fn match_faceted_field(
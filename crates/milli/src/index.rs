@@ -91,6 +91,7 @@ pub mod main_key {
     pub const DISABLED_TYPOS_TERMS: &str = "disabled_typos_terms";
     pub const CHAT: &str = "chat";
     pub const VECTOR_STORE_BACKEND: &str = "vector_store_backend";
+    pub const LAST_TRANSACTION_UID: &str = "last-transaction-uid";
 }
 
 pub mod db_name {
@@ -509,6 +510,18 @@ impl Index {
         self.main.remap_types::<Str, VersionCodec>().get(rtxn, main_key::VERSION_KEY)
     }
 
+    /* last transaction uid */
+
+    /// Writes the uid of the last transaction committed on this index.
+    pub fn put_last_transaction_uid(&self, wtxn: &mut RwTxn<'_>, uid: u32) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU32>().put(wtxn, main_key::LAST_TRANSACTION_UID, &uid)
+    }
+
+    /// Returns the uid of the last transaction committed on this index, if any.
+    pub fn last_transaction_uid(&self, rtxn: &RoTxn<'_>) -> heed::Result<Option<u32>> {
+        self.main.remap_types::<Str, BEU32>().get(rtxn, main_key::LAST_TRANSACTION_UID)
+    }
+
     /* vector store */
     /// Writes the vector store
     pub(crate) fn put_vector_store(
@@ -1127,6 +1140,7 @@ impl Index {
             .into_iter()
             .filter_map(|criterion| match criterion {
                 Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Decay(rule) => Some(rule.field),
                 _otherwise => None,
             })
             .collect();
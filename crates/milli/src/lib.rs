@@ -58,8 +58,9 @@ pub use grenad::CompressionType;
 pub use must_stop_processing::MustStopProcessing;
 use permissive_json_pointer::contained_in;
 pub use search::new::{
-    execute_search, filtered_universe, DefaultSearchLogger, SearchContext, SearchLogger,
-    VisualSearchLogger,
+    distinct_group, execute_search, filtered_universe, DefaultSearchLogger, DistinctGroup,
+    ExplainLogger, QueryTermExplanation, RankingRuleExplanation, SearchContext, SearchExplanation,
+    SearchLogger, VisualSearchLogger,
 };
 use serde::de::DeserializeSeed as _;
 use serde_json::Value;
@@ -69,7 +70,10 @@ pub use {arroy, cellulite, charabia as tokenizer, hannoy, heed, rhai};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
 pub use self::attribute_patterns::{AttributePatterns, PatternMatch};
-pub use self::criterion::{default_criteria, AttributeState, Criterion, CriterionError};
+pub use self::criterion::{
+    default_criteria, AttributeState, Criterion, CriterionError, DecayFunction, DecayOrigin,
+    DecayRule,
+};
 pub use self::error::{
     Error, FaultSource, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -96,9 +100,11 @@ pub use self::search::facet::{FacetValueHit, SearchForFacetValues, SHARD_FIELD};
 pub use self::search::similar::Similar;
 pub use self::search::steps::{FederatingResultsStep, SearchStep, TotalProcessingTimeStep};
 pub use self::search::{
-    merge_positioned_hits_into_page, serialize_index_filter_to_filter_string, FacetDistribution,
-    Filter, FormatOptions, IndexFilter, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    PinDoc, Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    merge_positioned_hits_into_page, serialize_index_filter_to_filter_string, FacetAggregation,
+    FacetBucket, FacetDistribution, Filter, FormatOptions, GeoGridBucket, IndexFilter, MatchBounds,
+    MatcherBuilder, MatchingWords, OrderBy, PinDoc, QuerySyntax, Search, SearchCursor,
+    SearchResult, SemanticSearch, Snippet, TermsMatchingStrategy, TypoToleranceOverride,
+    DEFAULT_VALUES_PER_FACET, MAX_GEOHASH_PRECISION,
 };
 pub use self::update::{
     ChannelCongestion, FragmentDiff, InnerIndexSettings, InnerIndexSettingsDiff, SettingsDelta,
@@ -443,6 +449,12 @@ pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
 
+/// Normalize the separator of hierarchical facet values the same way as the values themselves,
+/// without trimming it as its surrounding whitespaces are part of the values.
+pub fn normalize_hierarchy_separator(separator: &str) -> String {
+    CompatibilityDecompositionNormalizer.normalize_str(separator).to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -1,22 +1,27 @@
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-use crate::{criterion::AttributeState, distance_between_two_points};
+use crate::criterion::{AttributeState, DecayRule};
+use crate::{distance_between_two_points, Weight};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ScoreDetails {
     Words(Words),
     Typo(Typo),
     Proximity(Rank),
-    Fid(Rank),
+    Fid(Fid),
     Position(Rank),
     ExactAttribute(ExactAttribute),
     ExactWords(ExactWords),
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Decay(Decay),
+    Keywords(Keywords),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -107,18 +112,43 @@ impl ScoreDetails {
         self.rank().map(Rank::local_score)
     }
 
+    /// Compares the buckets, returned by the same ranking rule, that these details describe.
+    ///
+    /// `Greater` means that the bucket of `self` is ranked before the one of `other`.
+    /// Returns `None` if the details do not come from the same ranking rule.
+    pub fn cmp_bucket(&self, other: &Self) -> Option<Ordering> {
+        if std::mem::discriminant(self) != std::mem::discriminant(other) {
+            return None;
+        }
+
+        match (self, other) {
+            (ScoreDetails::Sort(left), ScoreDetails::Sort(right)) => left.partial_cmp(right),
+            (ScoreDetails::GeoSort(left), ScoreDetails::GeoSort(right)) => left.partial_cmp(right),
+            (ScoreDetails::Vector(left), ScoreDetails::Vector(right)) => {
+                left.similarity.partial_cmp(&right.similarity)
+            }
+            (ScoreDetails::Keywords(left), ScoreDetails::Keywords(right)) => {
+                left.similarity.partial_cmp(&right.similarity)
+            }
+            (ScoreDetails::Pin { .. }, _) => None,
+            (left, right) => left.local_score()?.partial_cmp(&right.local_score()?),
+        }
+    }
+
     pub fn rank(&self) -> Option<Rank> {
         match self {
             ScoreDetails::Words(details) => Some(details.rank()),
             ScoreDetails::Typo(details) => Some(details.rank()),
             ScoreDetails::Proximity(details) => Some(*details),
-            ScoreDetails::Fid(details) => Some(*details),
+            ScoreDetails::Fid(details) => Some(details.rank),
             ScoreDetails::Position(details) => Some(*details),
             ScoreDetails::ExactAttribute(details) => Some(details.rank()),
             ScoreDetails::ExactWords(details) => Some(details.rank()),
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Decay(_) => None,
+            ScoreDetails::Keywords(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
             ScoreDetails::Pin { .. } => None,
         }
@@ -127,32 +157,35 @@ impl ScoreDetails {
     /// Calculate the global score of the details.
     ///
     /// It is computed from the ranks of the ranking rules, excluding the sort/geo sort rules.
-    /// If the details contain a semantic score (ScoreDetails::Vector), it is used instead of the ranking score.
+    /// If the details contain a semantic score (ScoreDetails::Vector or ScoreDetails::Keywords), it is used instead of the ranking score.
+    /// The resulting relevancy is then blended with the scores of the decay rules, see [`DecayBlend`].
     ///
     /// note: this function expects a maximum of one semantic score, otherwise only the last one will be used.
     pub fn global_score<'a>(details: impl Iterator<Item = &'a Self> + 'a) -> f64 {
         // Filter out only the ranking scores (Rank values) and exclude sort/geo sort.
         // Pin is a placement directive, not a score — it is filtered out upfront.
         let mut semantic_score = None;
-        let ranking_ranks =
-            details.filter_map(ScoreDetails::rank_or_value).filter_map(|rank_or_value| {
-                match rank_or_value {
-                    RankOrValue::Rank(rank) => Some(rank),
-                    RankOrValue::Score(score) => {
-                        semantic_score = Some(score);
-                        None
-                    }
-                    RankOrValue::Sort(_) => None,
-                    RankOrValue::GeoSort(_) => None,
+        let mut decay = DecayBlend::default();
+        let ranking_ranks = details
+            .inspect(|details| decay.add(details))
+            .filter_map(ScoreDetails::rank_or_value)
+            .filter_map(|rank_or_value| match rank_or_value {
+                RankOrValue::Rank(rank) => Some(rank),
+                RankOrValue::Score(score) => {
+                    semantic_score = Some(score);
+                    None
                 }
+                RankOrValue::Sort(_) => None,
+                RankOrValue::GeoSort(_) => None,
             });
 
         let ranking_score = Rank::global_score(ranking_ranks);
 
         // If we have semantic score, use it, otherwise use ranking score
-        semantic_score.unwrap_or(ranking_score)
+        decay.blend(semantic_score.unwrap_or(ranking_score))
     }
 
+    /// The decay rules are only part of the global score, see [`Self::global_score`].
     pub fn score_values<'a>(
         details: impl Iterator<Item = &'a Self> + 'a,
     ) -> impl Iterator<Item = ScoreValue<'a>> + 'a {
@@ -174,6 +207,7 @@ impl ScoreDetails {
             })
     }
 
+    /// The decay rules are only part of the global score, see [`Self::global_score`].
     pub fn weighted_score_values<'a>(
         details: impl Iterator<Item = &'a Self> + 'a,
         weight: f64,
@@ -203,7 +237,7 @@ impl ScoreDetails {
             ScoreDetails::Words(w) => Some(RankOrValue::Rank(w.rank())),
             ScoreDetails::Typo(t) => Some(RankOrValue::Rank(t.rank())),
             ScoreDetails::Proximity(p) => Some(RankOrValue::Rank(*p)),
-            ScoreDetails::Fid(f) => Some(RankOrValue::Rank(*f)),
+            ScoreDetails::Fid(f) => Some(RankOrValue::Rank(f.rank)),
             ScoreDetails::Position(p) => Some(RankOrValue::Rank(*p)),
             ScoreDetails::ExactAttribute(e) => Some(RankOrValue::Rank(e.rank())),
             ScoreDetails::ExactWords(e) => Some(RankOrValue::Rank(e.rank())),
@@ -212,10 +246,13 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => Some(RankOrValue::Score(
                 vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64),
             )),
+            ScoreDetails::Keywords(keywords) => Some(RankOrValue::Score(keywords.similarity)),
             ScoreDetails::Skipped => Some(RankOrValue::Rank(Rank { rank: 0, max_rank: 1 })),
             // Pin is filtered out before reaching rank_or_value() — see global_score(),
             // score_values(), and weighted_score_values().
             ScoreDetails::Pin { .. } => None,
+            // The decay scores are only blended with the global score, see DecayBlend.
+            ScoreDetails::Decay(_) => None,
         }
     }
 
@@ -261,25 +298,30 @@ impl ScoreDetails {
                     order += 1;
                 }
                 ScoreDetails::Fid(fid) => {
-                    match attribute_state {
+                    let (name, mut fid_json) = match attribute_state {
                         AttributeState::Unified => {
                             // copy the rank for future use in Position.
-                            fid_details = Some(*fid);
+                            fid_details = Some(fid.rank);
                             // In this case, fid is a virtual rule always followed by the "position" rule
-                            let fid_details = serde_json::json!({
+                            let fid_json = serde_json::json!({
                                 "order": order,
-                                "attributeRankingOrderScore": fid.local_score(),
+                                "attributeRankingOrderScore": fid.rank.local_score(),
                             });
-                            details_map.insert("attribute".into(), fid_details);
+                            ("attribute", fid_json)
                         }
                         AttributeState::Separated => {
-                            let fid_details = serde_json::json!({
+                            let fid_json = serde_json::json!({
                                 "order": order,
-                                "score": fid.local_score(),
+                                "score": fid.rank.local_score(),
                             });
-                            details_map.insert("attributeRank".into(), fid_details);
+                            ("attributeRank", fid_json)
                         }
+                    };
+                    if let Some(attribute_weights) = &fid.attribute_weights {
+                        fid_json["attributeWeights"] =
+                            serde_json::json!(attribute_weights.as_ref());
                     }
+                    details_map.insert(name.into(), fid_json);
 
                     order += 1;
                 }
@@ -400,6 +442,30 @@ impl ScoreDetails {
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Decay(details) => {
+                    let rule = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        details.rule.to_string()
+                    };
+                    let decay_details = serde_json::json!({
+                        "order": order,
+                        "score": details.score,
+                        "weight": details.rule.weight.0,
+                    });
+                    details_map.insert(rule, decay_details);
+                    order += 1;
+                }
+                ScoreDetails::Keywords(keywords) => {
+                    let details = serde_json::json!({
+                        "order": order,
+                        "matchingKeywords": keywords.matching_keywords,
+                        "maxMatchingKeywords": keywords.max_matching_keywords,
+                        "similarity": keywords.similarity,
+                    });
+                    details_map.insert("keywords".into(), details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -437,7 +503,7 @@ pub enum ScoringStrategy {
     Detailed,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Words {
     pub matching_words: u32,
     pub max_matching_words: u32,
@@ -457,7 +523,7 @@ impl Words {
 ///
 /// In exactness, the number of matching words can actually be 0 with a non-zero score,
 /// if no words from the query appear exactly in the document.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct ExactWords {
     pub matching_words: u32,
     pub max_matching_words: u32,
@@ -479,7 +545,7 @@ impl ExactWords {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Typo {
     pub typo_count: u32,
     pub max_typo_count: u32,
@@ -508,7 +574,18 @@ impl Typo {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// The details of the `attribute` ranking rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fid {
+    pub rank: Rank,
+    /// The weights of the searchable attributes given at search time, which ranked the documents.
+    ///
+    /// They are the same for all the documents of the search, so they are not part of the cursors.
+    #[serde(skip)]
+    pub attribute_weights: Option<Arc<BTreeMap<String, Weight>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Rank {
     /// The ordinal rank, such that `max_rank` is the first rank, and 0 is the last rank.
     ///
@@ -546,7 +623,7 @@ impl Rank {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ExactAttribute {
     ExactMatch,
@@ -565,7 +642,7 @@ impl ExactAttribute {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Sort {
     pub field_name: String,
     pub ascending: bool,
@@ -628,7 +705,7 @@ impl PartialOrd for Sort {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct GeoSort {
     pub target_point: [f64; 2],
     pub ascending: bool,
@@ -657,11 +734,54 @@ impl PartialOrd for GeoSort {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Decay {
+    pub rule: DecayRule,
+    pub redacted: bool,
+    /// The score of the document, between 0 and 1.
+    pub score: f64,
+}
+
+/// Blends a relevancy score with the scores of the decay rules:
+/// `(relevancy + Σ weight × score) / (1 + Σ weight)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DecayBlend {
+    weighted_scores: f64,
+    weights: f64,
+}
+
+impl DecayBlend {
+    fn add(&mut self, details: &ScoreDetails) {
+        if let ScoreDetails::Decay(Decay { rule, score, .. }) = details {
+            self.weighted_scores += rule.weight.0 * score;
+            self.weights += rule.weight.0;
+        }
+    }
+
+    /// The greatest blend of the `relevancy` with decay rules of these `weights`.
+    pub fn max_blend(relevancy: f64, weights: f64) -> f64 {
+        DecayBlend { weighted_scores: weights, weights }.blend(relevancy)
+    }
+
+    pub fn blend(&self, relevancy: f64) -> f64 {
+        (relevancy + self.weighted_scores) / (1.0 + self.weights)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct Vector {
     pub similarity: Option<f32>,
 }
 
+/// Similarity of a document with the keywords of a source document, see [`crate::Similar::keyword`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keywords {
+    pub matching_keywords: u32,
+    pub max_matching_keywords: u32,
+    /// The sum of the weights of the matching keywords divided by the sum of all the weights.
+    pub similarity: f64,
+}
+
 impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
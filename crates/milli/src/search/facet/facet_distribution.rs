@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
-use std::ops::ControlFlow;
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
 use heed::types::Bytes;
@@ -10,23 +10,36 @@ use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
 use crate::attribute_patterns::match_field_legacy;
+use crate::constants::{RESERVED_GEO_LAT_FIELD_NAME, RESERVED_GEO_LNG_FIELD_NAME};
+use crate::documents::geo_sort::geo_value;
+use crate::documents::GeoSortStrategy;
 use crate::facet::FacetType;
-use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::filterable_attributes_rules::{
+    filtered_matching_patterns, matching_features, matching_hierarchy_separator,
+};
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
 use crate::{
-    Error, FieldId, FieldsIdsMap, FilterableAttributesRule, Index, PatternMatch, Result, UserError,
+    normalize_facet, normalize_hierarchy_separator, DocumentId, Error, FieldId, FieldsIdsMap,
+    FilterableAttributesRule, Index, PatternMatch, Result, UserError, RESERVED_GEO_FIELD_NAME,
 };
 
 /// The default number of values by facets that will
 /// be fetched from the key-value store.
 pub const DEFAULT_VALUES_PER_FACET: usize = 100;
 
+/// The maximum precision of a geohash, cells are about 3.7cm wide at this precision.
+pub const MAX_GEOHASH_PRECISION: usize = 12;
+
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
 /// Threshold on the number of candidates that will make
 /// the system to choose between one algorithm or another.
 const CANDIDATES_THRESHOLD: u64 = 3000;
@@ -50,8 +63,36 @@ impl Display for OrderBy {
     }
 }
 
+/// How the numeric values of a facet should be grouped into buckets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FacetAggregation {
+    /// User-defined ranges, each one given as `[from, to)`, an unset bound being unbounded.
+    Ranges(Vec<(Option<f64>, Option<f64>)>),
+    /// Consecutive `[from, from + interval)` buckets covering the values of the candidates.
+    Histogram { interval: f64 },
+}
+
+/// The number of candidates whose facet values are within `[from, to)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetBucket {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+    pub count: u64,
+}
+
+/// The number of candidates whose `_geo` point is in a geohash cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoGridBucket {
+    /// The geohash of the cell.
+    pub cell: String,
+    pub count: u64,
+    /// The `[lat, lng]` mean of the points of the cell.
+    pub centroid: [f64; 2],
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
+    hierarchy_nodes: HashMap<String, String>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
@@ -68,6 +109,7 @@ impl<'a> FacetDistribution<'a> {
     ) -> FacetDistribution<'a> {
         FacetDistribution {
             facets: None,
+            hierarchy_nodes: HashMap::new(),
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
@@ -90,6 +132,17 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Selects the node of a hierarchical facet whose children are counted,
+    /// the roots of the hierarchy are counted otherwise.
+    pub fn hierarchy_node(
+        &mut self,
+        field: impl Into<String>,
+        node: impl Into<String>,
+    ) -> &mut Self {
+        self.hierarchy_nodes.insert(field.into(), node.into());
+        self
+    }
+
     pub fn max_values_per_facet(&mut self, max: usize) -> &mut Self {
         self.max_values_per_facet = max;
         self
@@ -295,6 +348,84 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Counts the documents below each child of the selected `node` of a hierarchical facet,
+    /// the values being paths of nodes joined by the `separator`.
+    fn facet_hierarchy_values(
+        &self,
+        field_id: FieldId,
+        separator: &str,
+        node: Option<&str>,
+        order_by: OrderBy,
+    ) -> heed::Result<IndexMap<String, u64>> {
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let normalized_separator = normalize_hierarchy_separator(separator);
+        let prefix = match node.map(normalize_facet) {
+            Some(node) if !node.is_empty() => format!("{node}{normalized_separator}"),
+            _ => String::new(),
+        };
+        let depth = prefix.matches(normalized_separator.as_str()).count() + 1;
+
+        // the children by normalized path, with their original path and the documents below them
+        let mut children: BTreeMap<String, (String, RoaringBitmap)> = BTreeMap::new();
+        let base = FacetGroupKey { field_id, level: 0, left_bound: prefix.as_str() };
+        for result in self.index.facet_id_string_docids.prefix_iter(self.rtxn, &base)? {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            let docids = bitmap & candidates;
+            let Some(any_docid) = docids.min() else { continue };
+
+            let descendant = &left_bound[prefix.len()..];
+            let child_len = descendant.find(&normalized_separator).unwrap_or(descendant.len());
+            if child_len == 0 {
+                continue;
+            }
+
+            let child = &left_bound[..prefix.len() + child_len];
+            match children.get_mut(child) {
+                Some((_, child_docids)) => *child_docids |= docids,
+                None => {
+                    let original = self.original_hierarchy_node(
+                        field_id, any_docid, left_bound, separator, depth,
+                    )?;
+                    let original = original.unwrap_or_else(|| child.to_string());
+                    children.insert(child.to_string(), (original, docids));
+                }
+            }
+        }
+
+        let mut distribution: Vec<_> =
+            children.into_values().map(|(original, docids)| (original, docids.len())).collect();
+        if order_by == OrderBy::Count {
+            distribution.sort_by(|(_, left), (_, right)| right.cmp(left));
+        }
+
+        Ok(distribution.into_iter().take(self.max_values_per_facet).collect())
+    }
+
+    /// Returns the first `depth` nodes of the original path of a facet value of a document.
+    fn original_hierarchy_node(
+        &self,
+        field_id: FieldId,
+        docid: DocumentId,
+        normalized_value: &str,
+        separator: &str,
+        depth: usize,
+    ) -> heed::Result<Option<String>> {
+        let key: (FieldId, _, &str) = (field_id, docid, normalized_value);
+        let original = self.index.field_id_docid_facet_strings.get(self.rtxn, &key)?;
+
+        Ok(original.map(|original| {
+            original.trim().split(separator).take(depth).collect::<Vec<_>>().join(separator)
+        }))
+    }
+
     pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
         let candidates = if let Some(candidates) = self.candidates.clone() {
             candidates
@@ -336,6 +467,188 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Computes the requested buckets of each facet, a document with several values being
+    /// counted once in every bucket containing one of them.
+    ///
+    /// The histograms start at the multiple of the interval right below the smallest value,
+    /// an aggregation with more than `max_values_per_facet` ranges or buckets is rejected.
+    pub fn compute_aggregations(
+        &self,
+        aggregations: &BTreeMap<String, FacetAggregation>,
+    ) -> Result<BTreeMap<String, Vec<FacetBucket>>> {
+        let candidates = if let Some(candidates) = self.candidates.clone() {
+            candidates
+        } else {
+            return Ok(Default::default());
+        };
+
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        self.check_filterable_fields(
+            aggregations.keys().map(String::as_str),
+            &filterable_attributes_rules,
+        )?;
+
+        let mut buckets = BTreeMap::new();
+        for (name, aggregation) in aggregations {
+            let fid = self.fields_ids_map.id(name);
+            let facet_buckets = match aggregation {
+                FacetAggregation::Ranges(ranges) => {
+                    self.check_number_of_buckets(name, ranges.len())?;
+                    ranges
+                        .iter()
+                        .map(|&(from, to)| self.facet_bucket(fid, from, to, &candidates))
+                        .collect::<Result<_>>()?
+                }
+                FacetAggregation::Histogram { interval } => {
+                    self.facet_histogram(name, fid, *interval, &candidates)?
+                }
+            };
+            buckets.insert(name.to_string(), facet_buckets);
+        }
+
+        Ok(buckets)
+    }
+
+    fn check_number_of_buckets(&self, name: &str, number_of_buckets: usize) -> Result<()> {
+        if number_of_buckets > self.max_values_per_facet {
+            return Err(UserError::TooManyFacetBuckets {
+                field: name.to_string(),
+                buckets: number_of_buckets,
+                max: self.max_values_per_facet,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn facet_histogram(
+        &self,
+        name: &str,
+        fid: Option<FieldId>,
+        interval: f64,
+        candidates: &RoaringBitmap,
+    ) -> Result<Vec<FacetBucket>> {
+        let Some(fid) = fid else { return Ok(Vec::new()) };
+        if !interval.is_finite() || interval <= 0.0 {
+            return Ok(Vec::new());
+        }
+
+        let min =
+            crate::search::facet::facet_min_value(self.index, self.rtxn, fid, candidates.clone())?;
+        let max =
+            crate::search::facet::facet_max_value(self.index, self.rtxn, fid, candidates.clone())?;
+        let (Some(min), Some(max)) = (min, max) else { return Ok(Vec::new()) };
+
+        let start = (min / interval).floor() * interval;
+        let number_of_buckets = (((max - start) / interval).floor() as usize).saturating_add(1);
+        self.check_number_of_buckets(name, number_of_buckets)?;
+        (0..number_of_buckets)
+            .map(|i| {
+                let from = start + i as f64 * interval;
+                self.facet_bucket(Some(fid), Some(from), Some(from + interval), candidates)
+            })
+            .collect()
+    }
+
+    /// Counts the candidates per geohash cell of the given precision.
+    ///
+    /// Like the geo sort, the points of a few candidates are read one by one from the facet
+    /// databases while the points of many candidates are read from the `_geo` rtree.
+    ///
+    /// The most populated cells are returned first, a grid with more than `max_values_per_facet`
+    /// cells is rejected.
+    pub fn compute_geo_grid(&self, precision: usize) -> Result<Vec<GeoGridBucket>> {
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates,
+            None => return Ok(Vec::new()),
+        };
+
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        self.check_filterable_fields([RESERVED_GEO_FIELD_NAME], &filterable_attributes_rules)?;
+
+        let geo_candidates = self.index.geo_faceted_documents_ids(self.rtxn)? & candidates;
+        if geo_candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+        let precision = precision.clamp(1, MAX_GEOHASH_PRECISION);
+
+        // cell -> (count, sum of the latitudes, sum of the longitudes)
+        let mut cells = HashMap::<String, (u64, f64, f64)>::new();
+        let mut add_point = |[lat, lng]: [f64; 2]| {
+            let (count, lat_sum, lng_sum) =
+                cells.entry(geohash([lat, lng], precision)).or_default();
+            *count += 1;
+            *lat_sum += lat;
+            *lng_sum += lng;
+        };
+
+        if GeoSortStrategy::default().use_rtree(geo_candidates.len() as usize) {
+            let Some(rtree) = self.index.geo_rtree(self.rtxn)? else { return Ok(Vec::new()) };
+            for point in rtree.iter().filter(|point| geo_candidates.contains(point.data.0)) {
+                add_point(point.data.1);
+            }
+        } else {
+            let lat = self
+                .fields_ids_map
+                .id(RESERVED_GEO_LAT_FIELD_NAME)
+                .expect("geo candidates but no fid for lat");
+            let lng = self
+                .fields_ids_map
+                .id(RESERVED_GEO_LNG_FIELD_NAME)
+                .expect("geo candidates but no fid for lng");
+            for docid in &geo_candidates {
+                add_point(geo_value(docid, lat, lng, self.index, self.rtxn)?);
+            }
+        }
+
+        let mut buckets: Vec<_> = cells
+            .into_iter()
+            .map(|(cell, (count, lat_sum, lng_sum))| GeoGridBucket {
+                cell,
+                count,
+                centroid: [lat_sum / count as f64, lng_sum / count as f64],
+            })
+            .collect();
+        if buckets.len() > self.max_values_per_facet {
+            return Err(UserError::TooManyGeoGridCells {
+                precision,
+                cells: buckets.len(),
+                max: self.max_values_per_facet,
+            }
+            .into());
+        }
+        buckets.sort_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.cell.cmp(&b.cell)));
+
+        Ok(buckets)
+    }
+
+    /// Counts the candidates within the bounds by walking the facet level trees.
+    fn facet_bucket(
+        &self,
+        fid: Option<FieldId>,
+        from: Option<f64>,
+        to: Option<f64>,
+        candidates: &RoaringBitmap,
+    ) -> Result<FacetBucket> {
+        let mut docids = RoaringBitmap::new();
+        let is_empty_range = from.zip(to).is_some_and(|(from, to)| from >= to);
+        if let Some(fid) = fid.filter(|_| !is_empty_range) {
+            let left = from.map_or(Bound::Unbounded, Bound::Included);
+            let right = to.map_or(Bound::Unbounded, Bound::Excluded);
+            find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                self.rtxn,
+                self.index.facet_id_f64_docids,
+                fid,
+                &left,
+                &right,
+                Some(candidates),
+                &mut docids,
+            )?;
+        }
+
+        Ok(FacetBucket { from, to, count: docids.len() })
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
         self.check_faceted_fields(&filterable_attributes_rules)?;
@@ -348,7 +661,14 @@ impl<'a> FacetDistribution<'a> {
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let values = match matching_hierarchy_separator(name, &filterable_attributes_rules)
+                {
+                    Some(separator) => {
+                        let node = self.hierarchy_nodes.get(name).map(String::as_str);
+                        self.facet_hierarchy_values(fid, separator, node, order_by)?
+                    }
+                    None => self.facet_values(fid, order_by)?,
+                };
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -384,27 +704,41 @@ impl<'a> FacetDistribution<'a> {
         filterable_attributes_rules: &[FilterableAttributesRule],
     ) -> Result<()> {
         if let Some(facets) = &self.facets {
-            for field in facets.keys() {
-                let matched_rule = matching_features(field, filterable_attributes_rules);
-                let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
-
-                if !is_filterable {
-                    let valid_patterns =
-                        filtered_matching_patterns(filterable_attributes_rules, &|features| {
-                            features.is_filterable()
-                        })
-                        .into_iter()
-                        .map(String::from)
-                        .collect();
-
-                    return Err(Error::UserError(UserError::InvalidFacetsDistribution {
-                        invalid_facet_pattern: field.to_string(),
-                        valid_patterns,
-                        // If the field matched a rule but that rule doesn't enable filtering,
-                        // store the rule index for better error messages
-                        matching_rule_index: matched_rule.map(|(rule_index, _)| rule_index),
-                    }));
-                }
+            self.check_filterable_fields(
+                facets.keys().map(String::as_str),
+                filterable_attributes_rules,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if the fields are valid filterable fields.
+    fn check_filterable_fields<'f>(
+        &self,
+        fields: impl IntoIterator<Item = &'f str>,
+        filterable_attributes_rules: &[FilterableAttributesRule],
+    ) -> Result<()> {
+        for field in fields {
+            let matched_rule = matching_features(field, filterable_attributes_rules);
+            let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
+
+            if !is_filterable {
+                let valid_patterns =
+                    filtered_matching_patterns(filterable_attributes_rules, &|features| {
+                        features.is_filterable()
+                    })
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+
+                return Err(Error::UserError(UserError::InvalidFacetsDistribution {
+                    invalid_facet_pattern: field.to_string(),
+                    valid_patterns,
+                    // If the field matched a rule but that rule doesn't enable filtering,
+                    // store the rule index for better error messages
+                    matching_rule_index: matched_rule.map(|(rule_index, _)| rule_index),
+                }));
             }
         }
 
@@ -412,10 +746,45 @@ impl<'a> FacetDistribution<'a> {
     }
 }
 
+/// Encodes a `[lat, lng]` point into the geohash of the given precision,
+/// by alternately halving the longitude and latitude intervals.
+fn geohash([lat, lng]: [f64; 2], precision: usize) -> String {
+    let mut lat_interval = (-90.0, 90.0);
+    let mut lng_interval = (-180.0, 180.0);
+    let mut hash = String::with_capacity(precision);
+    let mut is_lng_bit = true;
+    let mut bits = 0;
+    let mut index = 0;
+
+    while hash.len() < precision {
+        let (interval, value) =
+            if is_lng_bit { (&mut lng_interval, lng) } else { (&mut lat_interval, lat) };
+        let mid = (interval.0 + interval.1) / 2.0;
+        index <<= 1;
+        if value >= mid {
+            index |= 1;
+            interval.0 = mid;
+        } else {
+            interval.1 = mid;
+        }
+        is_lng_bit = !is_lng_bit;
+
+        bits += 1;
+        if bits == 5 {
+            hash.push(GEOHASH_ALPHABET[index] as char);
+            bits = 0;
+            index = 0;
+        }
+    }
+
+    hash
+}
+
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let FacetDistribution {
             facets,
+            hierarchy_nodes,
             candidates,
             max_values_per_facet,
             default_order_by,
@@ -426,6 +795,7 @@ impl fmt::Debug for FacetDistribution<'_> {
 
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
+            .field("hierarchy_nodes", hierarchy_nodes)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
@@ -435,13 +805,17 @@ impl fmt::Debug for FacetDistribution<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::iter;
 
     use big_s::S;
 
     use crate::documents::mmap_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, FilterableAttributesRule, OrderBy};
+    use crate::{
+        milli_snap, FacetAggregation, FacetDistribution, FilterableAttributesFeatures,
+        FilterableAttributesPatterns, FilterableAttributesRule, OrderBy,
+    };
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -832,6 +1206,158 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_aggregations() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("colour"))])
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({
+                "id": i,
+                "colour": i,
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+        let aggregations = BTreeMap::from([(
+            S("colour"),
+            FacetAggregation::Ranges(vec![
+                (None, Some(100.0)),
+                (Some(100.0), Some(250.0)),
+                (Some(990.0), None),
+                (Some(500.0), Some(400.0)),
+            ]),
+        )]);
+
+        let map = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .compute_aggregations(&aggregations)
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "no_candidates", @"{}");
+
+        let map = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates((0..1000).collect())
+            .compute_aggregations(&aggregations)
+            .unwrap();
+        let counts: Vec<_> = map["colour"].iter().map(|bucket| bucket.count).collect();
+
+        assert_eq!(counts, [100, 150, 10, 0]);
+
+        let aggregations =
+            BTreeMap::from([(S("colour"), FacetAggregation::Histogram { interval: 250.0 })]);
+
+        let map = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates((217..777).collect())
+            .compute_aggregations(&aggregations)
+            .unwrap();
+        let buckets: Vec<_> =
+            map["colour"].iter().map(|bucket| (bucket.from, bucket.to, bucket.count)).collect();
+
+        milli_snap!(format!("{buckets:?}"), "candidates_217_777", @"[(Some(0.0), Some(250.0), 33), (Some(250.0), Some(500.0), 250), (Some(500.0), Some(750.0), 250), (Some(750.0), Some(1000.0), 27)]");
+
+        let error = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates((0..1000).collect())
+            .max_values_per_facet(2)
+            .compute_aggregations(&aggregations)
+            .unwrap_err();
+
+        milli_snap!(error.to_string(), @"The aggregation of `colour` has 4 buckets, more than the 2 allowed by the `maxValuesPerFacet` setting. Use fewer ranges, a greater interval or a narrower filter.");
+
+        let aggregations = BTreeMap::from([(
+            S("colour"),
+            FacetAggregation::Ranges(vec![(None, Some(100.0)), (Some(100.0), None), (None, None)]),
+        )]);
+
+        let error = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates((0..1000).collect())
+            .max_values_per_facet(2)
+            .compute_aggregations(&aggregations)
+            .unwrap_err();
+
+        milli_snap!(error.to_string(), @"The aggregation of `colour` has 3 buckets, more than the 2 allowed by the `maxValuesPerFacet` setting. Use fewer ranges, a greater interval or a narrower filter.");
+
+        let aggregations =
+            BTreeMap::from([(S("id"), FacetAggregation::Histogram { interval: 250.0 })]);
+
+        FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates((0..1000).collect())
+            .compute_aggregations(&aggregations)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn facet_hierarchy_distribution() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Pattern(
+                    FilterableAttributesPatterns {
+                        attribute_patterns: vec![S("category")].into(),
+                        features: FilterableAttributesFeatures::default(),
+                        hierarchy_separator: Some(S(" > ")),
+                    },
+                )])
+            })
+            .unwrap();
+
+        let documents = mmap_from_objects(
+            [
+                serde_json::json!({ "id": 0, "category": "Electronics" }),
+                serde_json::json!({ "id": 1, "category": "Electronics > Phones" }),
+                serde_json::json!({ "id": 2, "category": "Electronics > Phones > Android" }),
+                serde_json::json!({ "id": 3, "category": "Electronics > Phones > iOS" }),
+                serde_json::json!({ "id": 4, "category": "Electronics > Laptops" }),
+                serde_json::json!({ "id": 5, "category": ["Books > Novels", "Electronics > Phones > Android"] }),
+            ]
+            .into_iter()
+            .map(|document| document.as_object().unwrap().clone()),
+        );
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+        let map = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .facets(iter::once(("category", OrderBy::default())))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "roots", @r###"{"category": {"Books": 1, "Electronics": 6}}"###);
+
+        let map = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .facets(iter::once(("category", OrderBy::Count)))
+            .hierarchy_node("category", "electronics")
+            .candidates((1..6).collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "electronics_children", @r###"{"category": {"Electronics > Phones": 4, "Electronics > Laptops": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .facets(iter::once(("category", OrderBy::default())))
+            .hierarchy_node("category", "Electronics > Phones")
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "phones_children", @r###"{"category": {"Electronics > Phones > Android": 2, "Electronics > Phones > iOS": 1}}"###);
+    }
+
     #[test]
     fn facet_mixed_values() {
         let index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -890,4 +1416,61 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn geohash() {
+        assert_eq!(super::geohash([57.64911, 10.40744], 11), "u4pruydqqvj");
+        assert_eq!(super::geohash([48.8566, 2.3522], 5), "u09tv");
+        assert_eq!(super::geohash([-33.8688, 151.2093], 1), "r");
+    }
+
+    #[test]
+    fn geo_grid() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("_geo"))])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "_geo": { "lat": 48.8566, "lng": 2.3522 } },
+            { "id": 1, "_geo": { "lat": 48.8606, "lng": 2.3376 } },
+            { "id": 2, "_geo": { "lat": 45.7640, "lng": 4.8357 } },
+            { "id": 3, "_geo": { "lat": -33.8688, "lng": 151.2093 } },
+            { "id": 4 }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+        let buckets = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates((0..5).collect())
+            .compute_geo_grid(1)
+            .unwrap();
+        let counts: Vec<_> = buckets.iter().map(|b| (b.cell.as_str(), b.count)).collect();
+
+        assert_eq!(counts, [("u", 3), ("r", 1)]);
+
+        let buckets = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates([1, 2, 3].into_iter().collect())
+            .max_values_per_facet(3)
+            .compute_geo_grid(4)
+            .unwrap();
+        let counts: Vec<_> = buckets.iter().map(|b| (b.cell.as_str(), b.count)).collect();
+
+        assert_eq!(counts, [("r3gx", 1), ("u05k", 1), ("u09t", 1)]);
+        assert_eq!(buckets[0].centroid, [-33.8688, 151.2093]);
+
+        let error = FacetDistribution::new(&txn, &index, &fields_ids_map)
+            .candidates([1, 2, 3].into_iter().collect())
+            .max_values_per_facet(2)
+            .compute_geo_grid(4)
+            .unwrap_err();
+
+        milli_snap!(error.to_string(), @"The geo grid of precision 4 has 3 cells, more than the 2 allowed by the `maxValuesPerFacet` setting. Use a lower precision or a narrower filter.");
+    }
 }
@@ -3,7 +3,7 @@ use std::fmt::{Debug, Write as FmtWrite};
 use std::ops::Bound::{self, Excluded, Included};
 
 pub use filter_parser::Condition;
-use filter_parser::{IndexFilterCondition, TokenLike, VectorFilter};
+use filter_parser::{GeoShapeRelation, IndexFilterCondition, TokenLike, VectorFilter};
 use heed::types::LazyDecode;
 use heed::BytesEncode;
 use memchr::memmem::Finder;
@@ -22,12 +22,17 @@ use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds
 use crate::search::facet::filter::{FilterError, MAX_FILTER_DEPTH};
 use crate::search::facet::value_bounds::{evaluate_equal, ValueBounds};
 use crate::search::facet::BadGeoError;
+use crate::search::geo_shape::Shape;
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, FieldId, FieldsIdsMap,
-    FilterableAttributesFeatures, FilterableAttributesRule, Index, InternalError, Result,
-    SerializationError, SHARD_FIELD,
+    distance_between_two_points, lat_lng_to_xyz, normalize_hierarchy_separator, FieldId,
+    FieldsIdsMap, FilterableAttributesFeatures, FilterableAttributesRule, Index, InternalError,
+    Result, SerializationError, SHARD_FIELD,
 };
 
+/// The margin around the bounding box of a `_geoShape` filter, in degrees, so that the
+/// approximations of cellulite don't miss the shapes touching its edges.
+const GEO_SHAPE_PREFILTER_MARGIN: f64 = 1e-3;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct IndexFilter {
     pub condition: IndexFilterCondition,
@@ -72,6 +77,22 @@ impl IndexFilter {
         self.inner_evaluate(rtxn, index, fields_ids_map, &filterable_attributes_rules, None)
     }
 
+    /// Returns this filter without the clauses on `field`, `None` if the filter only targets `field`.
+    ///
+    /// This is used to compute disjunctive facet distributions, where the counts of a facet
+    /// must ignore the values selected for this same facet.
+    pub fn without_field(&self, field: &str) -> Option<IndexFilter> {
+        self.condition.without_field(field).map(IndexFilter::from)
+    }
+
+    /// Returns the value `field` must be equal to for this filter to match, if any.
+    ///
+    /// This is used to find the node selected in a hierarchical facet.
+    pub fn equality_value(&self, field: &str) -> Option<&str> {
+        self.condition.equality_value(field)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn evaluate_operator(
         rtxn: &heed::RoTxn<'_>,
         index: &Index,
@@ -80,6 +101,7 @@ impl IndexFilter {
         operator: &Condition,
         features: &FilterableAttributesFeatures,
         rule_index: usize,
+        hierarchy_separator: Option<&str>,
     ) -> Result<RoaringBitmap> {
         let numbers_db = index.facet_id_f64_docids;
         let strings_db = index.facet_id_string_docids;
@@ -156,11 +178,29 @@ impl IndexFilter {
             ValueBounds::FieldIsNull => index.null_faceted_documents_ids(rtxn, field_id)?,
             ValueBounds::FieldExists => index.exists_faceted_documents_ids(rtxn, field_id)?,
             ValueBounds::Equal { normalized, number } => {
+                let descendants = Self::evaluate_descendants(
+                    rtxn,
+                    index,
+                    field_id,
+                    &normalized,
+                    hierarchy_separator,
+                    universe_hint,
+                )?;
                 evaluate_equal(rtxn, field_id, numbers_db, strings_db, normalized, number)?
+                    | descendants
             }
             ValueBounds::NotEqual { normalized, number } => {
+                let descendants = Self::evaluate_descendants(
+                    rtxn,
+                    index,
+                    field_id,
+                    &normalized,
+                    hierarchy_separator,
+                    None,
+                )?;
                 index.documents_ids(rtxn)?
                     - evaluate_equal(rtxn, field_id, numbers_db, strings_db, normalized, number)?
+                    - descendants
             }
             ValueBounds::Contains { normalized } => {
                 let finder = Finder::new(&normalized);
@@ -197,58 +237,84 @@ impl IndexFilter {
                 docids
             }
             ValueBounds::StartsWith { normalized } => {
-                // The idea here is that "STARTS WITH baba" is the same as "baba <= value < babb".
-                // We just incremented the last letter to find the upper bound.
-                // The upper bound may not be valid utf8, but lmdb doesn't care as it works over bytes.
-                let mut value2 = normalized.as_bytes().to_owned();
+                Self::evaluate_starts_with(rtxn, index, field_id, &normalized, universe_hint)?
+            }
+        })
+    }
 
-                let last = match value2.last_mut() {
-                    Some(last) => last,
-                    None => {
-                        // The prefix is empty, so all documents that have the field will match.
-                        return index
-                            .exists_faceted_documents_ids(rtxn, field_id)
-                            .map_err(|e| e.into());
-                    }
-                };
+    /// Returns the documents with a value starting with `prefix`.
+    fn evaluate_starts_with(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_id: FieldId,
+        prefix: &str,
+        universe_hint: Option<&RoaringBitmap>,
+    ) -> Result<RoaringBitmap> {
+        // The idea here is that "STARTS WITH baba" is the same as "baba <= value < babb".
+        // We just incremented the last letter to find the upper bound.
+        // The upper bound may not be valid utf8, but lmdb doesn't care as it works over bytes.
+        let mut value2 = prefix.as_bytes().to_owned();
 
-                if *last == u8::MAX {
-                    // u8::MAX is a forbidden UTF-8 byte, we're guaranteed it cannot be sent through a filter to meilisearch, but just in case, we're going to return something
-                    tracing::warn!(
-                        "Found non utf-8 character in filter. That shouldn't be possible"
-                    );
-                    return Ok(RoaringBitmap::new());
-                }
-                *last += 1;
+        let last = match value2.last_mut() {
+            Some(last) => last,
+            None => {
+                // The prefix is empty, so all documents that have the field will match.
+                return index.exists_faceted_documents_ids(rtxn, field_id).map_err(|e| e.into());
+            }
+        };
 
-                // This is very similar to `heed::Bytes` but its `EItem` is `&[u8]` instead of `[u8]`
-                struct BytesRef;
-                impl<'a> BytesEncode<'a> for BytesRef {
-                    type EItem = &'a [u8];
+        if *last == u8::MAX {
+            // u8::MAX is a forbidden UTF-8 byte, we're guaranteed it cannot be sent through a filter to meilisearch, but just in case, we're going to return something
+            tracing::warn!("Found non utf-8 character in filter. That shouldn't be possible");
+            return Ok(RoaringBitmap::new());
+        }
+        *last += 1;
 
-                    fn bytes_encode(
-                        item: &'a Self::EItem,
-                    ) -> std::result::Result<Cow<'a, [u8]>, heed::BoxedError> {
-                        Ok(Cow::Borrowed(item))
-                    }
-                }
+        // This is very similar to `heed::Bytes` but its `EItem` is `&[u8]` instead of `[u8]`
+        struct BytesRef;
+        impl<'a> BytesEncode<'a> for BytesRef {
+            type EItem = &'a [u8];
 
-                let mut docids = RoaringBitmap::new();
-                let bytes_db =
-                    index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRef>>();
-                find_docids_of_facet_within_bounds::<BytesRef>(
-                    rtxn,
-                    bytes_db,
-                    field_id,
-                    &Included(normalized.as_bytes()),
-                    &Excluded(value2.as_slice()),
-                    universe_hint,
-                    &mut docids,
-                )?;
+            fn bytes_encode(
+                item: &'a Self::EItem,
+            ) -> std::result::Result<Cow<'a, [u8]>, heed::BoxedError> {
+                Ok(Cow::Borrowed(item))
+            }
+        }
 
-                docids
+        let mut docids = RoaringBitmap::new();
+        let bytes_db =
+            index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRef>>();
+        find_docids_of_facet_within_bounds::<BytesRef>(
+            rtxn,
+            bytes_db,
+            field_id,
+            &Included(prefix.as_bytes()),
+            &Excluded(value2.as_slice()),
+            universe_hint,
+            &mut docids,
+        )?;
+
+        Ok(docids)
+    }
+
+    /// Returns the documents with a value below the `node` of a hierarchical field,
+    /// nothing if the field is not hierarchical.
+    fn evaluate_descendants(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_id: FieldId,
+        node: &str,
+        hierarchy_separator: Option<&str>,
+        universe_hint: Option<&RoaringBitmap>,
+    ) -> Result<RoaringBitmap> {
+        match hierarchy_separator {
+            Some(separator) if !node.is_empty() => {
+                let prefix = format!("{node}{}", normalize_hierarchy_separator(separator));
+                Self::evaluate_starts_with(rtxn, index, field_id, &prefix, universe_hint)
             }
-        })
+            _ => Ok(RoaringBitmap::new()),
+        }
     }
 
     fn evaluate_shard_operator(
@@ -373,6 +439,8 @@ impl IndexFilter {
                 else {
                     return Ok(RoaringBitmap::new());
                 };
+                let hierarchy_separator =
+                    filterable_attribute_rules[rule_index].hierarchy_separator();
 
                 els.iter()
                     .map(|el| Condition::Equal(el.clone().into()))
@@ -385,6 +453,7 @@ impl IndexFilter {
                             &op,
                             &features,
                             rule_index,
+                            hierarchy_separator,
                         )
                     })
                     .union()
@@ -402,6 +471,8 @@ impl IndexFilter {
                 else {
                     return Ok(RoaringBitmap::new());
                 };
+                let hierarchy_separator =
+                    filterable_attribute_rules[rule_index].hierarchy_separator();
 
                 Self::evaluate_operator(
                     rtxn,
@@ -411,6 +482,7 @@ impl IndexFilter {
                     op,
                     &features,
                     rule_index,
+                    hierarchy_separator,
                 )
             }
             IndexFilterCondition::Or(subfilters) => subfilters
@@ -725,6 +797,64 @@ impl IndexFilter {
 
                 let result = roaring::RoaringBitmap::from_iter(result); // TODO: Remove once we update roaring in meilisearch
 
+                Ok(result)
+            }
+            IndexFilterCondition::GeoShape { relation, shape } => {
+                if !index.is_geojson_filtering_enabled(rtxn)? {
+                    return Err(shape.to_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEOJSON_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?;
+                }
+
+                let filter_shape = Shape::from_geojson(shape.fragment()).map_err(|error| {
+                    shape.to_external_error(BadGeoError::InvalidGeoShape(error.to_string()))
+                })?;
+
+                let Some(geojson_fid) = field_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) else {
+                    return Ok(RoaringBitmap::new());
+                };
+
+                let candidates = match universe_hint {
+                    Some(universe) => universe.clone(),
+                    None => index.documents_ids(rtxn)?,
+                };
+
+                // Cellulite gives the documents whose shape intersects the bounding box of the
+                // filter shape, the only ones that can intersect it. The relation is then checked
+                // against the exact shape of these documents, the cells only approximate them.
+                let hits = match filter_shape.bounding_polygon(GEO_SHAPE_PREFILTER_MARGIN) {
+                    Some(polygon) => {
+                        let hits = index.cellulite.in_shape(rtxn, &polygon)?;
+                        // TODO: Remove once we update roaring in meilisearch
+                        roaring::RoaringBitmap::from_iter(hits) & &candidates
+                    }
+                    None => RoaringBitmap::new(),
+                };
+                let mut result = RoaringBitmap::new();
+                for docid in &hits {
+                    let Some(document_shape) =
+                        Shape::from_document(index, rtxn, geojson_fid, docid)?
+                    else {
+                        continue;
+                    };
+                    if document_shape.relates_to(&filter_shape, *relation) {
+                        result.insert(docid);
+                    }
+                }
+
+                if *relation == GeoShapeRelation::Disjoint {
+                    // the other documents are disjoint from the filter shape, if they have a shape
+                    for docid in candidates - hits {
+                        if index.document(rtxn, docid)?.get(geojson_fid).is_some() {
+                            result.insert(docid);
+                        }
+                    }
+                }
+
                 Ok(result)
             }
         }
@@ -862,6 +992,9 @@ fn serialize_index_filter_condition(
             }
             write!(f, ")")?;
         }
+        IndexFilterCondition::GeoShape { relation, shape } => {
+            write!(f, "_geoShape({relation}, {})", shape.escaped_fragment())?;
+        }
     }
 
     Ok(())
@@ -31,6 +31,7 @@ pub enum BadGeoError {
     Lng(f64),
     InvalidResolution(usize),
     BoundingBoxTopIsBelowBottom(f64, f64),
+    InvalidGeoShape(String),
 }
 
 impl std::error::Error for BadGeoError {}
@@ -41,6 +42,9 @@ impl Display for BadGeoError {
             Self::BoundingBoxTopIsBelowBottom(top, bottom) => {
                 write!(f, "The top latitude `{top}` is below the bottom latitude `{bottom}`.")
             }
+            Self::InvalidGeoShape(error) => {
+                write!(f, "The `_geoShape` filter expects a valid GeoJSON geometry: {error}.")
+            }
             Self::InvalidResolution(resolution) => write!(
                 f,
                 "Invalid resolution `{resolution}`. Resolution must be between 3 and 1000."
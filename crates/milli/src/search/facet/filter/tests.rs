@@ -43,6 +43,9 @@ fn condition_to_index_condition(filter: FilterCondition) -> IndexFilterCondition
             IndexFilterCondition::GeoBoundingBox { top_right_point, bottom_left_point }
         }
         FilterCondition::GeoPolygon { points } => IndexFilterCondition::GeoPolygon { points },
+        FilterCondition::GeoShape { relation, shape } => {
+            IndexFilterCondition::GeoShape { relation, shape }
+        }
         FilterCondition::Foreign { .. } => {
             unreachable!("Foreign filters are not supported in index conditions")
         }
@@ -729,3 +732,110 @@ fn test_serialize_index_filter_to_filter_string() {
     let _ = Filter::from_str(&serialized).unwrap().unwrap();
     insta::assert_snapshot!(serialized, @"_geoPolygon([1, 2], [3, 4], [5, 6])");
 }
+
+#[test]
+fn index_filter_without_field() {
+    use crate::search::facet::filter::index_filter::serialize_index_filter_to_filter_string;
+
+    let without_field = |filter: &str, field: &str| {
+        let filter = IndexFilter::from(Filter::from_str(filter).unwrap().unwrap());
+        filter
+            .without_field(field)
+            .map(|filter| serialize_index_filter_to_filter_string(&filter).unwrap())
+    };
+
+    // The clauses on the field are removed from the conjunction
+    let relaxed =
+        without_field("(color = red OR color = blue) AND size = M AND price > 10", "color");
+    insta::assert_snapshot!(relaxed.unwrap(), @r###"("size" = "M") AND ("price" > "10")"###);
+
+    // A single remaining clause is unwrapped
+    let relaxed = without_field("color IN [red, blue] AND size = M", "color");
+    insta::assert_snapshot!(relaxed.unwrap(), @r###""size" = "M""###);
+
+    // Nothing remains when the filter only targets the field
+    assert_eq!(without_field("color = red OR NOT color = blue", "color"), None);
+
+    // A disjunction mixing several fields is kept untouched
+    let relaxed = without_field("color = red OR size = M", "color");
+    insta::assert_snapshot!(relaxed.unwrap(), @r###"("color" = "red") OR ("size" = "M")"###);
+
+    // Geo clauses never target a facet
+    let relaxed = without_field("color = red AND _geoRadius(1.1, 2.2, 3.3)", "color");
+    insta::assert_snapshot!(relaxed.unwrap(), @"_geoRadius(1.1, 2.2, 3.3)");
+
+    // A filter not using the field is unchanged
+    let relaxed = without_field("size = M", "color");
+    insta::assert_snapshot!(relaxed.unwrap(), @r###""size" = "M""###);
+}
+
+#[test]
+fn index_filter_equality_value() {
+    let equality_value = |filter: &str, field: &str| {
+        let filter = IndexFilter::from(Filter::from_str(filter).unwrap().unwrap());
+        filter.equality_value(field).map(String::from)
+    };
+
+    assert_eq!(equality_value("color = red", "color").as_deref(), Some("red"));
+    assert_eq!(equality_value("color IN [red]", "color").as_deref(), Some("red"));
+    assert_eq!(equality_value("color IN [red, blue]", "color"), None);
+    assert_eq!(equality_value("color != red", "color"), None);
+    assert_eq!(equality_value("color = red OR size = M", "color"), None);
+    assert_eq!(equality_value("size = M", "color"), None);
+
+    // The longest value is the deepest node of a hierarchy
+    let value = equality_value("cat = \"a > b\" AND size = M AND cat = a", "cat");
+    assert_eq!(value.as_deref(), Some("a > b"));
+}
+
+#[test]
+fn filter_hierarchical_values() {
+    use crate::{FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns};
+
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_filterable_fields(vec![FilterableAttributesRule::Pattern(
+                FilterableAttributesPatterns {
+                    attribute_patterns: vec![S("category")].into(),
+                    features: FilterableAttributesFeatures {
+                        facet_search: false,
+                        filter: FilterFeatures::default(),
+                    },
+                    hierarchy_separator: Some(S(" > ")),
+                },
+            )]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "category": "Electronics" },
+            { "id": 1, "category": "Electronics > Phones" },
+            { "id": 2, "category": "Electronics > Phones > Android" },
+            { "id": 3, "category": "Electronics > Phone cases" },
+            { "id": 4, "category": ["Books", "Electronics > Laptops"] },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+    let evaluate = |filter: &str| {
+        let filter = Filter::from_str(filter).unwrap().unwrap();
+        IndexFilter::from(filter).evaluate(&rtxn, &index, &fields_ids_map).unwrap()
+    };
+
+    assert_eq!(evaluate("category = electronics"), RoaringBitmap::from_iter([0, 1, 2, 3, 4]));
+    // a node only matches its own descendants, not the siblings sharing a prefix
+    assert_eq!(evaluate("category = \"Electronics > Phones\""), RoaringBitmap::from_iter([1, 2]));
+    assert_eq!(
+        evaluate("category IN [\"Electronics > Phones > Android\", Books]"),
+        RoaringBitmap::from_iter([2, 4])
+    );
+    assert_eq!(
+        evaluate("category != \"Electronics > Phones\""),
+        RoaringBitmap::from_iter([0, 3, 4])
+    );
+    assert_eq!(evaluate("category = Electro"), RoaringBitmap::new());
+}
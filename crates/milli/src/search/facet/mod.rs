@@ -4,7 +4,10 @@ use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{
+    FacetAggregation, FacetBucket, FacetDistribution, GeoGridBucket, OrderBy,
+    DEFAULT_VALUES_PER_FACET, MAX_GEOHASH_PRECISION,
+};
 pub use self::filter::{
     serialize_index_filter_to_filter_string, value_bounds, BadGeoError, Filter, IndexFilter,
     SHARD_FIELD,
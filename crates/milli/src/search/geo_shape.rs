@@ -0,0 +1,343 @@
+//! Spatial relations and distances between GeoJSON geometries.
+//!
+//! The geometries are handled on a plane where `x` is the longitude and `y` the latitude,
+//! which is precise enough as long as the shapes don't cross the antimeridian.
+
+use std::str::FromStr;
+
+use filter_parser::GeoShapeRelation;
+use geo_types::{Coord, Geometry, LineString, Polygon, Rect};
+use geojson::GeoJson;
+use heed::RoTxn;
+
+use crate::{distance_between_two_points, DocumentId, FieldId, Index, Result};
+
+/// Tolerance used to decide whether three points are aligned.
+const COLLINEAR_EPSILON: f64 = 1e-12;
+
+/// A geometry split into the points, segments and polygons it is made of.
+#[derive(Debug, Default)]
+pub struct Shape {
+    points: Vec<Coord>,
+    /// The segments of the lines and of the rings of the polygons.
+    segments: Vec<[Coord; 2]>,
+    polygons: Vec<Polygon>,
+    /// The bottom left and top right corners of the bounding box of the shape.
+    bounding_box: Option<[Coord; 2]>,
+}
+
+impl Shape {
+    pub fn new(geometry: &Geometry) -> Self {
+        let mut shape = Shape::default();
+        shape.push_geometry(geometry);
+        shape.bounding_box = shape.vertices().fold(None, |bounding_box, coord| {
+            let [min, max] = bounding_box.unwrap_or([coord, coord]);
+            Some([
+                Coord { x: min.x.min(coord.x), y: min.y.min(coord.y) },
+                Coord { x: max.x.max(coord.x), y: max.y.max(coord.y) },
+            ])
+        });
+        shape
+    }
+
+    /// Parses a GeoJSON geometry, feature or feature collection.
+    pub fn from_geojson(geojson: &str) -> std::result::Result<Self, geojson::Error> {
+        let geojson = GeoJson::from_str(geojson)?;
+        let geometry = Geometry::try_from(geojson)?;
+        Ok(Self::new(&geometry))
+    }
+
+    /// Returns the shape stored in the `_geojson` field of a document, if any.
+    ///
+    /// The GeoJSON was validated when the document was indexed, a document that can't be
+    /// parsed anymore is considered as not having a shape.
+    pub fn from_document(
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        geojson_fid: FieldId,
+        docid: DocumentId,
+    ) -> Result<Option<Self>> {
+        let document = index.document(rtxn, docid)?;
+        let Some(geojson) = document.get(geojson_fid) else { return Ok(None) };
+        let shape = std::str::from_utf8(geojson).ok().and_then(|geojson| {
+            Self::from_geojson(geojson).ok().filter(|shape| shape.bounding_box.is_some())
+        });
+        Ok(shape)
+    }
+
+    /// Returns the bounding box of the shape grown by `margin` degrees on every side, within the
+    /// valid coordinates, or `None` if the shape is empty.
+    pub fn bounding_polygon(&self, margin: f64) -> Option<Polygon> {
+        let [min, max] = self.bounding_box?;
+        let min = Coord { x: (min.x - margin).max(-180.0), y: (min.y - margin).max(-90.0) };
+        let max = Coord { x: (max.x + margin).min(180.0), y: (max.y + margin).min(90.0) };
+        Some(Rect::new(min, max).to_polygon())
+    }
+
+    /// Whether this shape, the one of a document, is in the given relation with the `other` shape.
+    pub fn relates_to(&self, other: &Shape, relation: GeoShapeRelation) -> bool {
+        match relation {
+            GeoShapeRelation::Intersects => self.intersects(other),
+            GeoShapeRelation::Within => other.contains(self),
+            GeoShapeRelation::Contains => self.contains(other),
+            GeoShapeRelation::Disjoint => !self.intersects(other),
+        }
+    }
+
+    /// Returns the point of the shape that is the closest to the `[lat, lng]` target.
+    ///
+    /// The target itself is returned when it lies inside one of the polygons of the shape,
+    /// otherwise the closest point is on one of the edges of the shape.
+    pub fn nearest_point(&self, target: [f64; 2]) -> Option<[f64; 2]> {
+        let [lat, lng] = target;
+        let origin = Coord { x: lng, y: lat };
+        if self.polygons.iter().any(|polygon| in_polygon(origin, polygon)) {
+            return Some(target);
+        }
+
+        // We look for the closest point of each segment on a plane centered on the target
+        // where a degree of longitude is shrunk to its length at the latitude of the target.
+        let scale = lat.to_radians().cos().max(f64::EPSILON);
+        let project = |coord: Coord| Coord { x: (coord.x - lng) * scale, y: coord.y - lat };
+        let unproject = |coord: Coord| Coord { x: coord.x / scale + lng, y: coord.y + lat };
+
+        let closest_on_segments = self.segments.iter().map(|&[start, end]| {
+            let (start, end) = (project(start), project(end));
+            let direction = Coord { x: end.x - start.x, y: end.y - start.y };
+            let length = direction.x * direction.x + direction.y * direction.y;
+            let t = if length == 0.0 {
+                0.0
+            } else {
+                (-(start.x * direction.x + start.y * direction.y) / length).clamp(0.0, 1.0)
+            };
+            unproject(Coord { x: start.x + t * direction.x, y: start.y + t * direction.y })
+        });
+
+        self.points
+            .iter()
+            .copied()
+            .chain(closest_on_segments)
+            .map(|coord| {
+                let point = [coord.y, coord.x];
+                (distance_between_two_points(&target, &point), point)
+            })
+            .min_by(|(left, _), (right, _)| left.total_cmp(right))
+            .map(|(_, point)| point)
+    }
+
+    fn push_geometry(&mut self, geometry: &Geometry) {
+        match geometry {
+            Geometry::Point(point) => self.points.push(point.0),
+            Geometry::MultiPoint(points) => {
+                self.points.extend(points.0.iter().map(|point| point.0))
+            }
+            Geometry::Line(line) => self.segments.push([line.start, line.end]),
+            Geometry::LineString(line) => self.push_line(line),
+            Geometry::MultiLineString(lines) => {
+                lines.0.iter().for_each(|line| self.push_line(line))
+            }
+            Geometry::Polygon(polygon) => self.push_polygon(polygon.clone()),
+            Geometry::MultiPolygon(polygons) => {
+                polygons.0.iter().for_each(|polygon| self.push_polygon(polygon.clone()))
+            }
+            Geometry::Rect(rect) => self.push_polygon(rect.to_polygon()),
+            Geometry::Triangle(triangle) => self.push_polygon(triangle.to_polygon()),
+            Geometry::GeometryCollection(collection) => {
+                collection.0.iter().for_each(|geometry| self.push_geometry(geometry))
+            }
+        }
+    }
+
+    fn push_line(&mut self, line: &LineString) {
+        match line.0.as_slice() {
+            [] => (),
+            [point] => self.points.push(*point),
+            _ => self.segments.extend(line.lines().map(|line| [line.start, line.end])),
+        }
+    }
+
+    fn push_polygon(&mut self, polygon: Polygon) {
+        self.push_line(polygon.exterior());
+        polygon.interiors().iter().for_each(|ring| self.push_line(ring));
+        self.polygons.push(polygon);
+    }
+
+    fn vertices(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.points.iter().copied().chain(self.segments.iter().flatten().copied())
+    }
+
+    /// Whether the point is on or inside the shape.
+    fn covers_point(&self, point: Coord) -> bool {
+        self.points.contains(&point)
+            || self.segments.iter().any(|&segment| on_segment(point, segment))
+            || self.polygons.iter().any(|polygon| in_polygon(point, polygon))
+    }
+
+    fn intersects(&self, other: &Shape) -> bool {
+        let (Some([min, max]), Some([other_min, other_max])) =
+            (self.bounding_box, other.bounding_box)
+        else {
+            return false;
+        };
+        if max.x < other_min.x || other_max.x < min.x || max.y < other_min.y || other_max.y < min.y
+        {
+            return false;
+        }
+
+        // Either one shape has a vertex on or inside the other, or their edges are crossing.
+        self.vertices().any(|vertex| other.covers_point(vertex))
+            || other.vertices().any(|vertex| self.covers_point(vertex))
+            || self.segments.iter().any(|&segment| {
+                other.segments.iter().any(|&other| segments_intersect(segment, other))
+            })
+    }
+
+    fn contains(&self, other: &Shape) -> bool {
+        let (Some([min, max]), Some([other_min, other_max])) =
+            (self.bounding_box, other.bounding_box)
+        else {
+            return false;
+        };
+        if other_min.x < min.x || max.x < other_max.x || other_min.y < min.y || max.y < other_max.y
+        {
+            return false;
+        }
+
+        // All the vertices of the other shape must be covered, and its edges can't leave this
+        // shape: they can't cross the edges of this shape, and their middle must be covered too
+        // to catch the edges that leave a concave polygon through one of its vertices.
+        other.vertices().all(|vertex| self.covers_point(vertex))
+            && other.segments.iter().all(|&[start, end]| {
+                let middle = Coord { x: (start.x + end.x) / 2.0, y: (start.y + end.y) / 2.0 };
+                self.covers_point(middle)
+                    && !self.segments.iter().any(|&own| segments_cross([start, end], own))
+            })
+    }
+}
+
+/// The cross product of `origin -> a` and `origin -> b`, positive when `b` is on the left of `origin -> a`.
+fn cross(origin: Coord, a: Coord, b: Coord) -> f64 {
+    (a.x - origin.x) * (b.y - origin.y) - (a.y - origin.y) * (b.x - origin.x)
+}
+
+fn on_segment(point: Coord, [start, end]: [Coord; 2]) -> bool {
+    cross(start, end, point).abs() <= COLLINEAR_EPSILON
+        && start.x.min(end.x) <= point.x
+        && point.x <= start.x.max(end.x)
+        && start.y.min(end.y) <= point.y
+        && point.y <= start.y.max(end.y)
+}
+
+/// Whether the segments cross each other at a single point that is not one of their ends.
+fn segments_cross([a, b]: [Coord; 2], [c, d]: [Coord; 2]) -> bool {
+    let opposite = |left: f64, right: f64| {
+        (left > COLLINEAR_EPSILON && right < -COLLINEAR_EPSILON)
+            || (left < -COLLINEAR_EPSILON && right > COLLINEAR_EPSILON)
+    };
+    opposite(cross(c, d, a), cross(c, d, b)) && opposite(cross(a, b, c), cross(a, b, d))
+}
+
+fn segments_intersect(segment: [Coord; 2], other: [Coord; 2]) -> bool {
+    segments_cross(segment, other)
+        || on_segment(segment[0], other)
+        || on_segment(segment[1], other)
+        || on_segment(other[0], segment)
+        || on_segment(other[1], segment)
+}
+
+/// Whether the point is on the ring or inside of it, using the even-odd rule.
+fn in_ring(point: Coord, ring: &LineString) -> bool {
+    let mut inside = false;
+    for line in ring.lines() {
+        let (start, end) = (line.start, line.end);
+        if on_segment(point, [start, end]) {
+            return true;
+        }
+        if (start.y > point.y) != (end.y > point.y) {
+            let x = start.x + (point.y - start.y) * (end.x - start.x) / (end.y - start.y);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Whether the point is on the boundary of the polygon or inside of it.
+fn in_polygon(point: Coord, polygon: &Polygon) -> bool {
+    in_ring(point, polygon.exterior())
+        && !polygon.interiors().iter().any(|hole| {
+            in_ring(point, hole)
+                && !hole.lines().any(|line| on_segment(point, [line.start, line.end]))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn shape(geojson: &str) -> Shape {
+        Shape::from_geojson(geojson).unwrap()
+    }
+
+    #[test]
+    fn relations() {
+        let square =
+            shape(r#"{"type":"Polygon","coordinates":[[[0,0],[10,0],[10,10],[0,10],[0,0]]]}"#);
+        let inner = shape(r#"{"type":"Polygon","coordinates":[[[2,2],[4,2],[4,4],[2,4],[2,2]]]}"#);
+        let overlapping =
+            shape(r#"{"type":"Polygon","coordinates":[[[8,8],[12,8],[12,12],[8,12],[8,8]]]}"#);
+        let far = shape(r#"{"type":"Point","coordinates":[20,20]}"#);
+        let crossing_line = shape(r#"{"type":"LineString","coordinates":[[-5,5],[15,5]]}"#);
+
+        assert!(inner.relates_to(&square, GeoShapeRelation::Intersects));
+        assert!(inner.relates_to(&square, GeoShapeRelation::Within));
+        assert!(!inner.relates_to(&square, GeoShapeRelation::Contains));
+        assert!(square.relates_to(&inner, GeoShapeRelation::Contains));
+
+        assert!(overlapping.relates_to(&square, GeoShapeRelation::Intersects));
+        assert!(!overlapping.relates_to(&square, GeoShapeRelation::Within));
+        assert!(!overlapping.relates_to(&square, GeoShapeRelation::Disjoint));
+
+        assert!(crossing_line.relates_to(&square, GeoShapeRelation::Intersects));
+        assert!(!crossing_line.relates_to(&square, GeoShapeRelation::Within));
+        assert!(!crossing_line.relates_to(&inner, GeoShapeRelation::Intersects));
+
+        assert!(far.relates_to(&square, GeoShapeRelation::Disjoint));
+        assert!(!far.relates_to(&square, GeoShapeRelation::Intersects));
+    }
+
+    #[test]
+    fn holes() {
+        let donut = shape(
+            r#"{"type":"Polygon","coordinates":[
+                [[0,0],[10,0],[10,10],[0,10],[0,0]],
+                [[3,3],[7,3],[7,7],[3,7],[3,3]]
+            ]}"#,
+        );
+        let in_the_hole = shape(r#"{"type":"Point","coordinates":[5,5]}"#);
+        let in_the_dough = shape(r#"{"type":"Point","coordinates":[1,1]}"#);
+        let across_the_hole = shape(r#"{"type":"LineString","coordinates":[[1,5],[9,5]]}"#);
+
+        assert!(in_the_hole.relates_to(&donut, GeoShapeRelation::Disjoint));
+        assert!(in_the_dough.relates_to(&donut, GeoShapeRelation::Within));
+        assert!(across_the_hole.relates_to(&donut, GeoShapeRelation::Intersects));
+        assert!(!across_the_hole.relates_to(&donut, GeoShapeRelation::Within));
+    }
+
+    #[test]
+    fn nearest_point() {
+        let square = shape(r#"{"type":"Polygon","coordinates":[[[0,0],[1,0],[1,1],[0,1],[0,0]]]}"#);
+
+        // inside the polygon the target is its own nearest point
+        assert_eq!(square.nearest_point([0.5, 0.5]), Some([0.5, 0.5]));
+        // east of the polygon the nearest point is on its east edge
+        let [lat, lng] = square.nearest_point([0.5, 2.0]).unwrap();
+        assert!((lat - 0.5).abs() < 1e-9 && (lng - 1.0).abs() < 1e-9);
+        // north east of the polygon the nearest point is its corner
+        let [lat, lng] = square.nearest_point([3.0, 3.0]).unwrap();
+        assert!((lat - 1.0).abs() < 1e-9 && (lng - 1.0).abs() < 1e-9);
+
+        assert_eq!(Shape::default().nearest_point([0.0, 0.0]), None);
+    }
+}
@@ -10,7 +10,7 @@ use crate::search::steps::SearchStep;
 use crate::search::SemanticSearch;
 use crate::vector::{Embedding, SearchQuery};
 use crate::{
-    merge_positioned_hits_into_page, FieldsIdsMap, Index, MatchingWords, PinDoc, Result, Search,
+    merge_positioned_hits_into_page, FieldId, Index, MatchingWords, PinDoc, Result, Search,
     SearchResult,
 };
 
@@ -104,10 +104,9 @@ impl ScoreWithRatioResult {
         mut keyword_results: Self,
         from: usize,
         length: usize,
-        distinct: Option<&str>,
+        distinct_fid: Option<FieldId>,
         index: &Index,
         rtxn: &RoTxn<'_>,
-        fields_ids_map: &FieldsIdsMap,
     ) -> Result<(SearchResult, u32)> {
         // Pinned documents carry ScoreDetails::Pin, which is a placement directive, not a score.
         // We extract them before the score-based merge, merge organic results normally, then
@@ -149,7 +148,6 @@ impl ScoreWithRatioResult {
             vector_results.document_scores.len() + keyword_results.document_scores.len(),
         );
 
-        let distinct_fid = distinct_fid(distinct, index, rtxn, fields_ids_map)?;
         // Seed excluded_documents with pinned docids so they don't appear as organic results
         // (they'll be re-injected at their target positions after the merge).
         let mut excluded_documents = pinned_doc_ids.clone();
@@ -229,6 +227,8 @@ impl ScoreWithRatioResult {
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
                 query_vector: vector_results.query_vector,
+                // the semantic ranking is fully described by the similarity of the score details
+                explanation: keyword_results.explanation,
             },
             semantic_hit_count,
         ))
@@ -272,8 +272,10 @@ impl Search<'_> {
             sort_criteria: self.sort_criteria.clone(),
             distinct: self.distinct.clone(),
             searchable_attributes: self.searchable_attributes,
+            attribute_weights: self.attribute_weights,
             geo_param: self.geo_param,
             terms_matching_strategy: self.terms_matching_strategy,
+            query_syntax: self.query_syntax,
             scoring_strategy: ScoringStrategy::Detailed,
             words_limit: self.words_limit,
             retrieve_vectors: self.retrieve_vectors,
@@ -291,6 +293,12 @@ impl Search<'_> {
             progress: self.progress,
             dynamic_search_rules: self.dynamic_search_rules,
             candidates: self.candidates,
+            ignore_distinct: self.ignore_distinct,
+            search_after: self.search_after.clone(),
+            decay_now: self.decay_now,
+            explain: self.explain,
+            typo_tolerance: self.typo_tolerance,
+            ignore_synonyms: self.ignore_synonyms,
         };
 
         let semantic = search.semantic.take();
@@ -351,15 +359,25 @@ impl Search<'_> {
         let keyword_results = ScoreWithRatioResult::new(keyword_results, 1.0 - semantic_ratio);
         let vector_results = ScoreWithRatioResult::new(vector_results, semantic_ratio);
 
+        let distinct_fid = if search.ignore_distinct {
+            None
+        } else {
+            distinct_fid(
+                search.distinct.as_deref(),
+                search.index,
+                search.rtxn,
+                search.fields_ids_map,
+            )?
+        };
+
         let (merge_results, semantic_hit_count) = ScoreWithRatioResult::merge(
             vector_results,
             keyword_results,
             self.offset,
             self.limit,
-            search.distinct.as_deref(),
+            distinct_fid,
             search.index,
             search.rtxn,
-            search.fields_ids_map,
         )?;
         assert!(merge_results.documents_ids.len() <= self.limit);
         Ok((merge_results, Some(semantic_hit_count)))
@@ -397,6 +415,7 @@ fn return_keyword_results(
         degraded,
         used_negative_operator,
         query_vector,
+        explanation,
     }: SearchResult,
 ) -> (SearchResult, Option<u32>) {
     let (documents_ids, document_scores) = if offset >= documents_ids.len() ||
@@ -424,6 +443,7 @@ fn return_keyword_results(
             degraded,
             used_negative_operator,
             query_vector,
+            explanation,
         },
         Some(0),
     )
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
@@ -5,27 +6,30 @@ use charabia::Language;
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 pub use self::facet::{
-    serialize_index_filter_to_filter_string, FacetDistribution, Filter, IndexFilter, OrderBy,
-    DEFAULT_VALUES_PER_FACET,
+    serialize_index_filter_to_filter_string, FacetAggregation, FacetBucket, FacetDistribution,
+    Filter, GeoGridBucket, IndexFilter, OrderBy, DEFAULT_VALUES_PER_FACET, MAX_GEOHASH_PRECISION,
 };
-pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
+pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, Snippet};
 use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats};
 use crate::documents::GeoSortParameter;
 use crate::dynamic_search_rules::{DsrFuel, DynamicSearchRules};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::index::MatchingStrategy;
 use crate::progress::Progress;
-use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::score_details::{ScoreDetails, ScoringStrategy, Sort};
 use crate::search::new::{
-    extract_tokens, resolve_negative_phrases, resolve_negative_words, ExtractedTokens, QueryGraph,
+    extract_tokens, resolve_negative_phrases, resolve_negative_words, ExtractedTokens,
+    PlaceholderQuery, QueryGraph,
 };
 use crate::vector::{Embedder, Embedding};
 use crate::{
     execute_search, filtered_universe, AscDesc, Deadline, DefaultSearchLogger, DocumentId, Error,
-    FieldsIdsMap, Index, Position, Result, SearchContext, SearchStep, UserError,
+    ExplainLogger, FieldsIdsMap, Index, Position, Result, SearchContext, SearchExplanation,
+    SearchLogger, SearchStep, UserError, Weight,
 };
 
 // Building these factories is not free.
@@ -35,6 +39,7 @@ static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 
 pub mod facet;
 mod fst_utils;
+pub(crate) mod geo_shape;
 pub mod hybrid;
 pub mod new;
 pub mod similar;
@@ -55,6 +60,43 @@ pub struct PinDoc {
     pub doc_id: DocumentId,
 }
 
+/// Position of a document in the results of a search, to return the documents ranked after it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchCursor {
+    /// The score details of the document, one per ranking rule, identifying its buckets.
+    pub scores: Vec<ScoreDetails>,
+    /// The id of the document, the documents of its last bucket are ranked by id.
+    pub docid: DocumentId,
+}
+
+impl SearchCursor {
+    /// Keeps only what is needed to find the buckets of the document back, the names of the
+    /// sorted fields are known from the sort criteria of the search.
+    pub fn new(scores: &[ScoreDetails], docid: DocumentId) -> Self {
+        let scores = scores
+            .iter()
+            .cloned()
+            .map(|details| match details {
+                ScoreDetails::Sort(sort) => {
+                    ScoreDetails::Sort(Sort { field_name: String::new(), ..sort })
+                }
+                details => details,
+            })
+            .collect();
+        Self { scores, docid }
+    }
+}
+
+/// Typo tolerance given at search time, replacing the typo tolerance settings of the index.
+///
+/// The fields left to `None` keep the value of the settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypoToleranceOverride {
+    pub enabled: Option<bool>,
+    pub min_word_len_one_typo: Option<u8>,
+    pub min_word_len_two_typos: Option<u8>,
+}
+
 pub struct Search<'a> {
     query: Option<String>,
     // this should be linked to the String in the query
@@ -64,8 +106,10 @@ pub struct Search<'a> {
     sort_criteria: Option<Vec<AscDesc>>,
     distinct: Option<String>,
     searchable_attributes: Option<&'a [String]>,
+    attribute_weights: Option<&'a BTreeMap<String, Weight>>,
     geo_param: GeoSortParameter,
     terms_matching_strategy: TermsMatchingStrategy,
+    query_syntax: QuerySyntax,
     scoring_strategy: ScoringStrategy,
     words_limit: usize,
     retrieve_vectors: bool,
@@ -83,6 +127,12 @@ pub struct Search<'a> {
     progress: &'a Progress,
     dynamic_search_rules: Option<(&'a DynamicSearchRules, DsrFuel)>,
     candidates: Option<&'a RoaringBitmap>,
+    ignore_distinct: bool,
+    search_after: Option<SearchCursor>,
+    decay_now: Option<OffsetDateTime>,
+    explain: bool,
+    typo_tolerance: TypoToleranceOverride,
+    ignore_synonyms: bool,
 }
 
 impl<'a> Search<'a> {
@@ -102,8 +152,10 @@ impl<'a> Search<'a> {
             sort_criteria: None,
             distinct: None,
             searchable_attributes: None,
+            attribute_weights: None,
             geo_param: GeoSortParameter::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
+            query_syntax: QuerySyntax::default(),
             scoring_strategy: Default::default(),
             retrieve_vectors: false,
             exhaustive_number_hits: false,
@@ -121,6 +173,12 @@ impl<'a> Search<'a> {
             progress,
             dynamic_search_rules: None,
             candidates: None,
+            ignore_distinct: false,
+            search_after: None,
+            decay_now: None,
+            explain: false,
+            typo_tolerance: TypoToleranceOverride::default(),
+            ignore_synonyms: false,
         }
     }
 
@@ -166,11 +224,25 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Replace the weights of the searchable attributes for the `attribute` and `attributeRank` ranking rules.
+    ///
+    /// The greater the weight, the more important the attribute, the searchable attributes missing from the map have a weight of 0.
+    pub fn attribute_weights(&mut self, weights: &'a BTreeMap<String, Weight>) -> &mut Search<'a> {
+        self.attribute_weights = Some(weights);
+        self
+    }
+
     pub fn terms_matching_strategy(&mut self, value: TermsMatchingStrategy) -> &mut Search<'a> {
         self.terms_matching_strategy = value;
         self
     }
 
+    /// Choose how the query is parsed, see [`QuerySyntax`].
+    pub fn query_syntax(&mut self, value: QuerySyntax) -> &mut Search<'a> {
+        self.query_syntax = value;
+        self
+    }
+
     pub fn scoring_strategy(&mut self, value: ScoringStrategy) -> &mut Search<'a> {
         self.scoring_strategy = value;
         self
@@ -247,6 +319,50 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Do not apply the distinct rule, not even the one of the distinct attribute of the index.
+    ///
+    /// Useful to rank the documents sharing the same distinct value.
+    pub fn ignore_distinct(&mut self, ignore_distinct: bool) -> &mut Search<'a> {
+        self.ignore_distinct = ignore_distinct;
+        self
+    }
+
+    /// Override the typo tolerance settings of the index for this search only.
+    pub fn typo_tolerance(&mut self, typo_tolerance: TypoToleranceOverride) -> &mut Search<'a> {
+        self.typo_tolerance = typo_tolerance;
+        self
+    }
+
+    /// Do not derive the query terms into their synonyms, ignoring the synonyms of the index.
+    pub fn ignore_synonyms(&mut self, ignore_synonyms: bool) -> &mut Search<'a> {
+        self.ignore_synonyms = ignore_synonyms;
+        self
+    }
+
+    /// Only return the documents ranked after the one described by the cursor.
+    ///
+    /// The scores of the cursor must come from a search using [`ScoringStrategy::Detailed`],
+    /// and the offset is applied on the documents after the cursor.
+    pub fn search_after(&mut self, cursor: SearchCursor) -> &mut Search<'a> {
+        self.search_after = Some(cursor);
+        self
+    }
+
+    /// The time from which the decay ranking rules score the documents, the time of the search
+    /// by default.
+    ///
+    /// Freezing it keeps the decay scores of the documents stable while paginating.
+    pub fn decay_now(&mut self, now: OffsetDateTime) -> &mut Search<'a> {
+        self.decay_now = Some(now);
+        self
+    }
+
+    /// Explain how the ranking rules split the candidates into buckets, see [`SearchExplanation`].
+    pub fn explain(&mut self, explain: bool) -> &mut Search<'a> {
+        self.explain = explain;
+        self
+    }
+
     pub fn index_uid(&self) -> &'a str {
         self.index_uid
     }
@@ -290,6 +406,17 @@ impl<'a> Search<'a> {
             ctx.attributes_to_search_on(searchable_attributes)?;
         }
 
+        if let Some(attribute_weights) = self.attribute_weights {
+            ctx.attribute_weights(attribute_weights)?;
+        }
+
+        ctx.ignore_distinct = self.ignore_distinct;
+        ctx.typo_tolerance = self.typo_tolerance;
+        ctx.ignore_synonyms = self.ignore_synonyms;
+        if let Some(decay_now) = self.decay_now {
+            ctx.decay_now = decay_now;
+        }
+
         if let Some(distinct) = &self.distinct {
             let filterable_fields = ctx.index.filterable_attributes_rules(ctx.txn)?;
             // check if the distinct field is in the filterable fields
@@ -330,6 +457,20 @@ impl<'a> Search<'a> {
         let (query_terms, pins, used_negative_operator) =
             self.build_located_query_terms(&mut ctx, self.filter.as_ref(), &mut universe)?;
 
+        let mut default_placeholder_logger = DefaultSearchLogger;
+        let mut default_query_graph_logger = DefaultSearchLogger;
+        let mut explain_loggers =
+            self.explain.then(|| (ExplainLogger::default(), ExplainLogger::default()));
+        let (placeholder_search_logger, query_graph_logger): (
+            &mut dyn SearchLogger<PlaceholderQuery>,
+            &mut dyn SearchLogger<QueryGraph>,
+        ) = match &mut explain_loggers {
+            Some((placeholder_logger, query_graph_logger)) => {
+                (placeholder_logger, query_graph_logger)
+            }
+            None => (&mut default_placeholder_logger, &mut default_query_graph_logger),
+        };
+
         let mut query_vector = None;
         let PartialSearchResult {
             located_query_terms,
@@ -363,10 +504,12 @@ impl<'a> Search<'a> {
                     embedder_name,
                     embedder,
                     *quantized,
+                    placeholder_search_logger,
                     self.deadline.clone(),
                     self.ranking_score_threshold,
                     self.progress,
                     pins,
+                    self.search_after.as_ref(),
                 )?
             }
             _ => execute_search(
@@ -382,12 +525,13 @@ impl<'a> Search<'a> {
                 self.geo_param,
                 self.offset,
                 self.limit,
-                &mut DefaultSearchLogger,
-                &mut DefaultSearchLogger,
+                placeholder_search_logger,
+                query_graph_logger,
                 self.deadline.clone(),
                 self.ranking_score_threshold,
                 self.progress,
                 pins,
+                self.search_after.as_ref(),
             )?,
         };
 
@@ -397,6 +541,15 @@ impl<'a> Search<'a> {
             tracing::debug!("Vector store stats: total_time={total_time:.02?}, total_queries={total_queries}, total_results={total_results}");
         }
 
+        // only one of the loggers was used, depending on the kind of search
+        let explanation =
+            explain_loggers.map(
+                |(placeholder_logger, query_graph_logger)| match located_query_terms.as_deref() {
+                    Some(terms) => query_graph_logger.into_explanation(&ctx, Some(terms)),
+                    None => placeholder_logger.into_explanation(&ctx, None),
+                },
+            );
+
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
             Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
@@ -411,6 +564,7 @@ impl<'a> Search<'a> {
             degraded,
             used_negative_operator,
             query_vector,
+            explanation,
         })
     }
 
@@ -428,13 +582,33 @@ impl<'a> Search<'a> {
             if let Some(query) = self.query.as_deref().filter(|q| !q.trim().is_empty()) {
                 let _step = self.progress.update_progress_scoped(SearchStep::TokenizeQuery);
 
-                let ExtractedTokens { query_terms, graph, negative_words, negative_phrases } =
-                    extract_tokens(ctx, query, Some(self.words_limit), self.locales.as_ref())?;
+                let ExtractedTokens {
+                    query_terms,
+                    graph,
+                    negative_words,
+                    negative_phrases,
+                    excluded_docids,
+                    required_docids,
+                } = extract_tokens(
+                    ctx,
+                    query,
+                    self.query_syntax,
+                    Some(self.words_limit),
+                    self.locales.as_ref(),
+                )?;
 
-                used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
+                used_negative_operator = !negative_words.is_empty()
+                    || !negative_phrases.is_empty()
+                    || excluded_docids.is_some();
 
                 ignored |= resolve_negative_words(ctx, Some(&*universe), &negative_words)?;
                 ignored |= resolve_negative_phrases(ctx, &negative_phrases)?;
+                if let Some(excluded_docids) = excluded_docids {
+                    ignored |= excluded_docids;
+                }
+                if let Some(required_docids) = required_docids {
+                    *universe &= required_docids;
+                }
 
                 if query_terms.is_empty() {
                     // Do a placeholder search instead
@@ -476,8 +650,10 @@ impl fmt::Debug for Search<'_> {
             sort_criteria,
             distinct,
             searchable_attributes,
+            attribute_weights,
             geo_param: _,
             terms_matching_strategy,
+            query_syntax,
             scoring_strategy,
             words_limit,
             retrieve_vectors,
@@ -495,6 +671,12 @@ impl fmt::Debug for Search<'_> {
             candidates,
             progress: _,
             dynamic_search_rules: _,
+            ignore_distinct,
+            search_after,
+            decay_now,
+            explain,
+            typo_tolerance,
+            ignore_synonyms,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -505,7 +687,9 @@ impl fmt::Debug for Search<'_> {
             .field("sort_criteria", sort_criteria)
             .field("distinct", distinct)
             .field("searchable_attributes", searchable_attributes)
+            .field("attribute_weights", attribute_weights)
             .field("terms_matching_strategy", terms_matching_strategy)
+            .field("query_syntax", query_syntax)
             .field("scoring_strategy", scoring_strategy)
             .field("retrieve_vectors", retrieve_vectors)
             .field("exhaustive_number_hits", exhaustive_number_hits)
@@ -519,6 +703,12 @@ impl fmt::Debug for Search<'_> {
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
             .field("candidates", candidates)
+            .field("ignore_distinct", ignore_distinct)
+            .field("search_after", search_after)
+            .field("decay_now", decay_now)
+            .field("explain", explain)
+            .field("typo_tolerance", typo_tolerance)
+            .field("ignore_synonyms", ignore_synonyms)
             .finish()
     }
 }
@@ -532,6 +722,19 @@ pub struct SearchResult {
     pub degraded: bool,
     pub used_negative_operator: bool,
     pub query_vector: Option<Embedding>,
+    /// The explanation of the ranking, when requested with [`Search::explain`].
+    pub explanation: Option<SearchExplanation>,
+}
+
+/// How the query string is parsed.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuerySyntax {
+    /// Words, `"phrases"` and `-` negation.
+    #[default]
+    Simple,
+    /// The simple syntax extended with `field:term` scopes, `OR` alternatives,
+    /// `(...)` groups and `+` required terms.
+    Advanced,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -1,13 +1,16 @@
+use std::cmp::Ordering;
+
 use roaring::RoaringBitmap;
 
+use super::decay::Decay;
 use super::logger::SearchLogger;
-use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
+use super::ranking_rules::{BoxRankingRule, RankingRuleId, RankingRuleQueryTrait};
 use super::SearchContext;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::{
     apply_distinct_rule, distinct_fid, distinct_single_docid, DistinctOutput,
 };
-use crate::{merge_positioned_hits_into_page, Deadline, PinDoc, Result};
+use crate::{merge_positioned_hits_into_page, Deadline, PinDoc, Result, SearchCursor, UserError};
 
 pub struct BucketSortOutput {
     pub docids: Vec<u32>,
@@ -35,32 +38,160 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     exhaustive_number_hits: bool,
     max_total_hits: Option<usize>,
     pins: Vec<PinDoc>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<BucketSortOutput> {
     logger.initial_query(query);
     logger.ranking_rules(&ranking_rules);
     logger.initial_universe(universe);
 
-    let distinct_fid = distinct_fid(distinct, ctx.index, ctx.txn, ctx.fields_ids_map)?;
+    // Pins are positioned relative to the first page, they make no sense after a cursor.
+    let pins = if search_after.is_some() { Vec::new() } else { pins };
+
+    let Some(mut decay) = Decay::new(ctx, universe, from.saturating_add(length), search_after)?
+    else {
+        // When pins are present we need the organic prefix up to the end of the
+        // requested page. Injecting the surviving pins into that prefix and slicing
+        // afterwards preserves the target positions and naturally "pumps" pins
+        // forward when there are fewer organic results than the requested limit.
+        let (ranked_from, ranked_length) =
+            if pins.is_empty() { (from, length) } else { (0, from.saturating_add(length)) };
+
+        let output = rank_buckets(
+            ctx,
+            ranking_rules,
+            query,
+            distinct,
+            universe,
+            ranked_from,
+            ranked_length,
+            scoring_strategy,
+            logger,
+            deadline,
+            ranking_score_threshold,
+            exhaustive_number_hits,
+            max_total_hits,
+            search_after,
+            None,
+        )?;
+        return Ok(inject_pins(pins, from, length, output));
+    };
+
+    // Without the ranking rules that need a query, all the documents have a relevancy of 1
+    // and the best documents only depend on the decay rules.
+    let same_relevancy = ranking_rules.iter().all(|rule| {
+        matches!(
+            rule.id(),
+            RankingRuleId::Sort
+                | RankingRuleId::GeoSort
+                | RankingRuleId::Asc(_)
+                | RankingRuleId::Desc(_)
+        )
+    });
+    let has_distinct = !ctx.ignore_distinct
+        && distinct_fid(distinct, ctx.index, ctx.txn, ctx.fields_ids_map)?.is_some();
+    let best_candidates = if same_relevancy && !has_distinct {
+        decay.best_candidates(ctx, universe, ranking_score_threshold)?
+    } else {
+        None
+    };
+
+    // Otherwise, at most `max_scored` documents are ranked.
+    let (ranked_universe, ranked_length) = match &best_candidates {
+        Some(candidates) => (&candidates.ranked, usize::MAX),
+        None => (universe, decay.max_scored()),
+    };
+
+    // The blended scores don't follow the order of the buckets: the documents are ranked
+    // from the first one with their detailed scores, then sorted by their blended score.
+    let BucketSortOutput { docids, scores, mut all_candidates, mut degraded } = rank_buckets(
+        ctx,
+        ranking_rules,
+        query,
+        distinct,
+        ranked_universe,
+        0,
+        ranked_length,
+        ScoringStrategy::Detailed,
+        logger,
+        deadline,
+        ranking_score_threshold,
+        exhaustive_number_hits,
+        max_total_hits,
+        None,
+        Some(&mut decay),
+    )?;
+    // Only the documents left out of the ranking degrade the search.
+    degraded |= decay.truncated()
+        || (docids.len() >= ranked_length && ranked_universe.len() > ranked_length as u64);
+    if let Some(candidates) = best_candidates {
+        all_candidates = candidates.all_candidates;
+    }
+    let (docids, scores) =
+        decay.rank(ctx, docids, scores, ranking_score_threshold, &mut all_candidates)?;
 
-    // When pins are present we need the organic prefix up to the end of the
-    // requested page. Injecting the surviving pins into that prefix and slicing
-    // afterwards preserves the target positions and naturally "pumps" pins
-    // forward when there are fewer organic results than the requested limit.
     let (ranked_from, ranked_length) =
         if pins.is_empty() { (from, length) } else { (0, from.saturating_add(length)) };
+    let output = BucketSortOutput {
+        docids: docids.into_iter().skip(ranked_from).take(ranked_length).collect(),
+        scores: scores.into_iter().skip(ranked_from).take(ranked_length).collect(),
+        all_candidates,
+        degraded,
+    };
+    Ok(inject_pins(pins, from, length, output))
+}
+
+/// Ranks the documents of the universe with the ranking rules, from `ranked_from` and up to
+/// `ranked_length` documents.
+///
+/// With decay rules, the documents are scored along the way and the ranking stops as soon as
+/// the remaining documents can't reach the blended scores of the requested ones.
+#[allow(clippy::too_many_arguments)]
+fn rank_buckets<'ctx, Q: RankingRuleQueryTrait>(
+    ctx: &mut SearchContext<'ctx>,
+    mut ranking_rules: Vec<BoxRankingRule<'ctx, Q>>,
+    query: &Q,
+    distinct: Option<&str>,
+    universe: &RoaringBitmap,
+    ranked_from: usize,
+    ranked_length: usize,
+    scoring_strategy: ScoringStrategy,
+    logger: &mut dyn SearchLogger<Q>,
+    deadline: Deadline,
+    ranking_score_threshold: Option<f64>,
+    exhaustive_number_hits: bool,
+    max_total_hits: Option<usize>,
+    search_after: Option<&SearchCursor>,
+    mut decay: Option<&mut Decay>,
+) -> Result<BucketSortOutput> {
+    let distinct_fid = if ctx.ignore_distinct {
+        None
+    } else {
+        distinct_fid(distinct, ctx.index, ctx.txn, ctx.fields_ids_map)?
+    };
+
+    // The cursor is compared to every bucket down to its own, they must not be merged when small.
+    let scoring_strategy =
+        if search_after.is_some() { ScoringStrategy::Detailed } else { scoring_strategy };
+
+    // Without any bucket to compare to, the documents after the cursor are simply the ones with a greater id.
+    let universe_after_cursor;
+    let universe = match search_after {
+        Some(cursor) if ranking_rules.is_empty() || cursor.scores.is_empty() => {
+            let mut universe = universe.clone();
+            universe.remove_range(..=cursor.docid);
+            universe_after_cursor = universe;
+            &universe_after_cursor
+        }
+        _ => universe,
+    };
 
     if universe.len() < ranked_from as u64 {
-        return Ok(inject_pins(
-            pins,
-            from,
-            length,
-            BucketSortOutput {
-                docids: vec![],
-                scores: vec![],
-                all_candidates: universe.clone(),
-                degraded: false,
-            },
-        ));
+        return Ok(BucketSortOutput {
+            docids: vec![],
+            scores: vec![],
+            all_candidates: universe.clone(),
+            degraded: false,
+        });
     }
     if ranking_rules.is_empty() {
         if let Some(distinct_fid) = distinct_fid {
@@ -89,30 +220,20 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 results.clear();
             }
 
-            return Ok(inject_pins(
-                pins,
-                from,
-                length,
-                BucketSortOutput {
-                    scores: vec![Default::default(); results.len()],
-                    docids: results,
-                    all_candidates,
-                    degraded: false,
-                },
-            ));
+            return Ok(BucketSortOutput {
+                scores: vec![Default::default(); results.len()],
+                docids: results,
+                all_candidates,
+                degraded: false,
+            });
         } else {
             let docids: Vec<u32> = universe.iter().skip(ranked_from).take(ranked_length).collect();
-            return Ok(inject_pins(
-                pins,
-                from,
-                length,
-                BucketSortOutput {
-                    scores: vec![Default::default(); docids.len()],
-                    docids,
-                    all_candidates: universe.clone(),
-                    degraded: false,
-                },
-            ));
+            return Ok(BucketSortOutput {
+                scores: vec![Default::default(); docids.len()],
+                docids,
+                all_candidates: universe.clone(),
+                degraded: false,
+            });
         };
     }
 
@@ -128,6 +249,10 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         vec![RoaringBitmap::default(); ranking_rules_len];
     ranking_rule_universes[0].clone_from(universe);
     let mut cur_ranking_rule_index = 0;
+    // The ranking rule index at which we are still looking for the bucket of the cursor.
+    // When set, it is always the current ranking rule index: its universe only contains
+    // documents that are equal to the cursor for all the previous ranking rules.
+    let mut cursor_depth = search_after.filter(|cursor| !cursor.scores.is_empty()).map(|_| 0);
 
     /// Finish iterating over the current ranking rule, yielding
     /// control to the parent (or finishing the search if not possible).
@@ -147,6 +272,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             );
             ranking_rule_universes[cur_ranking_rule_index].clear();
             ranking_rules[cur_ranking_rule_index].end_iteration(ctx, logger);
+            // the remaining buckets of the parent are all ranked after the cursor
+            cursor_depth = None;
             if cur_ranking_rule_index == 0 {
                 break;
             } else {
@@ -162,9 +289,12 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     let mut valid_docids = vec![];
     let mut valid_scores = vec![];
     let mut cur_offset = 0usize;
+    // Whether the documents ranked so far contain the best blended scores.
+    let mut decay_complete = false;
 
     macro_rules! maybe_add_to_results {
         ($candidates:expr) => {
+            let previous_len = valid_docids.len();
             maybe_add_to_results(
                 ctx,
                 ranked_from,
@@ -181,6 +311,13 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 &ranking_rule_scores,
                 $candidates,
             )?;
+            if let Some(decay) = decay.as_deref_mut() {
+                decay_complete = decay.add_documents(
+                    ctx,
+                    &valid_docids[previous_len..],
+                    &valid_scores[previous_len..],
+                )?;
+            }
         };
     }
 
@@ -190,7 +327,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             _ => ranked_length,
         };
 
-    while valid_docids.len() < max_len_to_evaluate {
+    while valid_docids.len() < max_len_to_evaluate && !decay_complete {
         // The universe for this bucket is zero, so we don't need to sort
         // anything, just go back to the parent ranking rule.
         if ranking_rule_universes[cur_ranking_rule_index].is_empty()
@@ -212,15 +349,26 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 )? {
                     std::task::Poll::Ready(bucket) => break bucket,
                     std::task::Poll::Pending => {
-                        let bucket =
+                        let mut bucket =
                             std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
+                        // The documents of the universe of the cursor can't be ranked anymore, they are
+                        // considered equal to the cursor and only the ones with a greater id are kept.
+                        if let Some(cursor) =
+                            search_after.filter(|_| cursor_depth == Some(cur_ranking_rule_index))
+                        {
+                            bucket.remove_range(..=cursor.docid);
+                        }
+                        // the remaining buckets of the parents are all ranked after the cursor
+                        cursor_depth = None;
                         ranking_rule_scores.push(ScoreDetails::Skipped);
 
                         // remove candidates from the universe without adding them to result if their score is below the threshold
                         let is_below_threshold =
                             ranking_score_threshold.is_some_and(|ranking_score_threshold| {
-                                let current_score =
-                                    ScoreDetails::global_score(ranking_rule_scores.iter());
+                                let current_score = max_blended_score(
+                                    decay.as_deref(),
+                                    ScoreDetails::global_score(ranking_rule_scores.iter()),
+                                );
                                 current_score < ranking_score_threshold
                             });
 
@@ -234,17 +382,12 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                         ranking_rule_scores.pop();
 
                         if cur_ranking_rule_index == 0 {
-                            return Ok(inject_pins(
-                                pins,
-                                from,
-                                length,
-                                BucketSortOutput {
-                                    scores: valid_scores,
-                                    docids: valid_docids,
-                                    all_candidates,
-                                    degraded: true,
-                                },
-                            ));
+                            return Ok(BucketSortOutput {
+                                scores: valid_scores,
+                                docids: valid_docids,
+                                all_candidates,
+                                degraded: true,
+                            });
                         }
 
                         // This is a copy/paste/adapted of the ugly back!() macro
@@ -275,6 +418,44 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             };
             next_bucket
         };
+        let mut next_bucket = next_bucket;
+
+        // Skip the buckets ranked before the cursor and only keep the documents after it in its bucket.
+        let mut descends_to_cursor = false;
+        if let (Some(depth), Some(cursor)) = (cursor_depth, search_after) {
+            debug_assert_eq!(depth, cur_ranking_rule_index);
+            let ordering = match &cursor.scores[depth] {
+                // the cursor comes from a degraded search, its bucket was not ranked any further
+                ScoreDetails::Skipped => Some(Ordering::Equal),
+                score => next_bucket.score.cmp_bucket(score),
+            };
+            // the cursor was made with other ranking rules
+            let Some(ordering) = ordering else {
+                return Err(UserError::InvalidSearchCursor.into());
+            };
+            match ordering {
+                Ordering::Greater => {
+                    logger.skip_bucket_ranking_rule(
+                        cur_ranking_rule_index,
+                        ranking_rules[cur_ranking_rule_index].as_ref(),
+                        &next_bucket.candidates,
+                    );
+                    ranking_rule_universes[cur_ranking_rule_index] -= &next_bucket.candidates;
+                    continue;
+                }
+                Ordering::Less => cursor_depth = None,
+                Ordering::Equal => {
+                    if depth + 1 >= cursor.scores.len() || depth == ranking_rules_len - 1 {
+                        ranking_rule_universes[cur_ranking_rule_index] -= &next_bucket.candidates;
+                        next_bucket.candidates.remove_range(..=cursor.docid);
+                        cursor_depth = None;
+                    } else {
+                        // the cursor depth is only updated once we descend into the bucket
+                        descends_to_cursor = true;
+                    }
+                }
+            }
+        }
 
         ranking_rule_scores.push(next_bucket.score);
 
@@ -291,7 +472,10 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
         // remove candidates from the universe without adding them to result if their score is below the threshold
         let is_below_threshold = ranking_score_threshold.is_some_and(|ranking_score_threshold| {
-            let current_score = ScoreDetails::global_score(ranking_rule_scores.iter());
+            let current_score = max_blended_score(
+                decay.as_deref(),
+                ScoreDetails::global_score(ranking_rule_scores.iter()),
+            );
             current_score < ranking_score_threshold
         });
 
@@ -299,7 +483,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
         if cur_ranking_rule_index == ranking_rules_len - 1
             || (scoring_strategy == ScoringStrategy::Skip && next_bucket.candidates.len() <= 1)
-            || cur_offset + (next_bucket.candidates.len() as usize) < ranked_from
+            || (!descends_to_cursor
+                && cur_offset + (next_bucket.candidates.len() as usize) < ranked_from)
             || is_below_threshold
         {
             if is_below_threshold {
@@ -308,11 +493,20 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             } else {
                 maybe_add_to_results!(next_bucket.candidates);
             }
+            if descends_to_cursor {
+                // the bucket of the cursor is below the threshold and dropped,
+                // the following buckets are all ranked after the cursor
+                debug_assert!(is_below_threshold);
+                cursor_depth = None;
+            }
             ranking_rule_scores.pop();
             continue;
         }
 
         cur_ranking_rule_index += 1;
+        if descends_to_cursor {
+            cursor_depth = Some(cur_ranking_rule_index);
+        }
         ranking_rule_universes[cur_ranking_rule_index].clone_from(&next_bucket.candidates);
         logger.start_iteration_ranking_rule(
             cur_ranking_rule_index,
@@ -329,17 +523,17 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         )?;
     }
 
-    Ok(inject_pins(
-        pins,
-        from,
-        length,
-        BucketSortOutput {
-            docids: valid_docids,
-            scores: valid_scores,
-            all_candidates,
-            degraded: false,
-        },
-    ))
+    Ok(BucketSortOutput {
+        docids: valid_docids,
+        scores: valid_scores,
+        all_candidates,
+        degraded: false,
+    })
+}
+
+/// The greatest ranking score a document of this relevancy can reach once blended with the decay rules.
+fn max_blended_score(decay: Option<&Decay>, relevancy: f64) -> f64 {
+    decay.map_or(relevancy, |decay| decay.max_blended_score(relevancy))
 }
 
 /// Inject all surviving pins into the organic prefix, then slice the requested
@@ -0,0 +1,427 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::iter::Peekable;
+use std::ops::Bound;
+
+use heed::BytesDecode;
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+
+use super::distinct::facet_number_values;
+use super::SearchContext;
+use crate::criterion::{DecayFunction, DecayOrigin, DecayRule};
+use crate::heed_codec::facet::{FacetGroupKey, OrderedF64Codec};
+use crate::score_details::{self, DecayBlend, ScoreDetails};
+use crate::search::facet::facet_max_value;
+use crate::{Criterion, FieldId, Result, SearchCursor};
+
+/// The maximum number of documents scored by the decay rules during a search,
+/// unless more documents are requested.
+pub(super) const MAX_SCORED_DOCUMENTS: usize = 10_000;
+
+/// Blends the relevancy of the ranked documents with the scores computed by the decay rules of
+/// the index from the numeric values of a field, see [`DecayBlend`].
+///
+/// The decay rules are not part of the ranking rules of the bucket sort. The documents are
+/// ranked by relevancy and scored by the decay rules bucket by bucket, until none of the
+/// remaining documents can reach the blended score of the requested documents.
+/// The documents are then sorted by their blended score.
+///
+/// When all the documents have the same relevancy, the best documents are found by reading
+/// the values of the fields from the best scored ones instead, see [`Decay::best_candidates`].
+/// At most [`MAX_SCORED_DOCUMENTS`] documents are scored, the search is degraded past it.
+pub struct Decay {
+    rules: Vec<ScoredDecayRule>,
+    now: f64,
+    /// The sum of the weights of the rules.
+    weights: f64,
+    /// The number of documents to rank after the cursor.
+    needed: usize,
+    /// The number of documents that can be scored before the search is degraded.
+    max_scored: usize,
+    /// The number of documents of the universe, the search is not degraded once all are scored.
+    universe_len: u64,
+    /// Whether the ranking stopped before finding the best blended scores.
+    truncated: bool,
+    /// The blended score and id of the document of the cursor.
+    cursor: Option<(f64, u32)>,
+    /// The blended scores of the best documents after the cursor, the worst one on top.
+    best: BinaryHeap<Reverse<OrderedFloat<f64>>>,
+    /// The details of the decay rules of the scored documents.
+    details: HashMap<u32, Vec<ScoreDetails>>,
+}
+
+struct ScoredDecayRule {
+    rule: DecayRule,
+    field_id: Option<FieldId>,
+    must_redact: bool,
+    /// The greatest value of the field in the universe, only used by the log boost.
+    max: f64,
+}
+
+impl Decay {
+    /// Returns `None` if the index has no decay rules.
+    pub fn new(
+        ctx: &SearchContext<'_>,
+        universe: &RoaringBitmap,
+        needed: usize,
+        search_after: Option<&SearchCursor>,
+    ) -> Result<Option<Self>> {
+        let displayed_fields = ctx.index.displayed_fields(ctx.txn)?;
+        let mut rules = Vec::new();
+        for criterion in ctx.index.criteria(ctx.txn)? {
+            let Criterion::Decay(rule) = criterion else { continue };
+            let field_id = ctx.fields_ids_map.id(&rule.field);
+            let must_redact = displayed_fields
+                .as_ref()
+                .is_some_and(|displayed_fields| !displayed_fields.contains(&rule.field.as_str()));
+            let max = match (rule.function, field_id) {
+                (DecayFunction::Log, Some(field_id)) => {
+                    facet_max_value(ctx.index, ctx.txn, field_id, universe.clone())?.unwrap_or(0.0)
+                }
+                _ => 0.0,
+            };
+            rules.push(ScoredDecayRule { rule, field_id, must_redact, max });
+        }
+
+        if rules.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            weights: rules.iter().map(|rule| rule.rule.weight.0).sum(),
+            rules,
+            now: ctx.decay_now.unix_timestamp() as f64,
+            needed,
+            max_scored: needed.max(MAX_SCORED_DOCUMENTS),
+            universe_len: universe.len(),
+            truncated: false,
+            cursor: search_after
+                .map(|cursor| (ScoreDetails::global_score(cursor.scores.iter()), cursor.docid)),
+            best: BinaryHeap::new(),
+            details: HashMap::new(),
+        }))
+    }
+
+    /// The greatest blended score of a document with at most this relevancy.
+    pub fn max_blended_score(&self, relevancy: f64) -> f64 {
+        DecayBlend::max_blend(relevancy, self.weights)
+    }
+
+    /// The number of documents that can be ranked and scored before the search is degraded.
+    pub fn max_scored(&self) -> usize {
+        self.max_scored
+    }
+
+    /// Whether the ranking stopped after [`Self::max_scored`] documents, before finding
+    /// the best blended scores.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Scores the documents ranked so far by the bucket sort, with their ranking rule details.
+    ///
+    /// Returns `true` once the documents ranked after them by the bucket sort can't be part of
+    /// the requested documents, or once too many documents were scored.
+    pub fn add_documents(
+        &mut self,
+        ctx: &SearchContext<'_>,
+        docids: &[u32],
+        scores: &[Vec<ScoreDetails>],
+    ) -> Result<bool> {
+        for (docid, scores) in docids.iter().zip(scores) {
+            if self.details.contains_key(docid) {
+                continue;
+            }
+            let details = self.score_details(ctx, *docid)?;
+            let blended = ScoreDetails::global_score(scores.iter().chain(&details));
+            self.details.insert(*docid, details);
+
+            if self.is_after_cursor(blended, *docid) {
+                self.best.push(Reverse(OrderedFloat(blended)));
+                if self.best.len() > self.needed {
+                    self.best.pop();
+                }
+            }
+        }
+
+        let Some(last_scores) = scores.last() else { return Ok(false) };
+        if self.needed == 0 {
+            return Ok(true);
+        }
+        // The global score ignores the sort rules, it only decreases along the buckets without them.
+        let max_relevancy = if last_scores
+            .iter()
+            .any(|details| matches!(details, ScoreDetails::Sort(_) | ScoreDetails::GeoSort(_)))
+        {
+            1.0
+        } else {
+            ScoreDetails::global_score(last_scores.iter())
+        };
+
+        let complete = self.best.len() >= self.needed
+            && self
+                .best
+                .peek()
+                .is_some_and(|Reverse(worst)| worst.0 > self.max_blended_score(max_relevancy));
+        if !complete && self.details.len() >= self.max_scored {
+            self.truncated = (self.details.len() as u64) < self.universe_len;
+            return Ok(true);
+        }
+        Ok(complete)
+    }
+
+    /// Finds the documents with the best blended scores when all the documents of the universe
+    /// have the same relevancy of 1, e.g. in a placeholder search.
+    ///
+    /// The values of the fields of the rules are read from the facet databases from the best
+    /// scored one, i.e. around the origin of the rule. The documents are scored as they are
+    /// read, until none of the documents left can beat the requested ones.
+    ///
+    /// Returns `None` if more than [`MAX_SCORED_DOCUMENTS`] documents must be scored.
+    pub fn best_candidates(
+        &self,
+        ctx: &SearchContext<'_>,
+        universe: &RoaringBitmap,
+        ranking_score_threshold: Option<f64>,
+    ) -> Result<Option<DecayCandidates>> {
+        let mut values = Vec::with_capacity(self.rules.len());
+        for rule in &self.rules {
+            values.push(match rule.field_id {
+                Some(field_id) => Some(ScoreOrderedValues::new(ctx, field_id, rule, self.now)?),
+                None => None,
+            });
+        }
+
+        let mut scored = RoaringBitmap::new();
+        let mut above_threshold = RoaringBitmap::new();
+        // The blended scores and ids of the best documents after the cursor, the worst one on top.
+        let mut best = BinaryHeap::new();
+        let mut exhausted = false;
+        loop {
+            // The documents that were not scored yet have, for each rule, either a value
+            // that was not read yet or no value at all and a score of 0.
+            let mut weighted_scores = 0.0;
+            let mut next_rule = None;
+            for (index, (rule, rule_values)) in self.rules.iter().zip(&mut values).enumerate() {
+                let Some(rule_values) = rule_values else { continue };
+                let Some(score) = rule_values.next_score(rule, self.now)? else { continue };
+                let weighted_score = rule.rule.weight.0 * score;
+                weighted_scores += weighted_score;
+                if next_rule.is_none_or(|(_, greatest)| weighted_score > greatest) {
+                    next_rule = Some((index, weighted_score));
+                }
+            }
+            let max_unscored = (1.0 + weighted_scores) / (1.0 + self.weights);
+
+            let requested_found = best.len() >= self.needed
+                && best.peek().is_none_or(|Reverse((worst, _))| worst.0 > max_unscored);
+            let threshold_reached =
+                ranking_score_threshold.is_none_or(|threshold| max_unscored < threshold);
+            if requested_found && threshold_reached {
+                break;
+            }
+            let Some((index, _)) = next_rule else {
+                exhausted = true;
+                break;
+            };
+
+            let Some(rule_values) = &mut values[index] else { continue };
+            let Some(docids) = rule_values.next(&self.rules[index], self.now)? else { continue };
+            let docids = (docids & universe) - &scored;
+            scored |= &docids;
+            if scored.len() > self.max_scored as u64 {
+                return Ok(None);
+            }
+            for docid in docids {
+                let details = self.score_details(ctx, docid)?;
+                let blended = ScoreDetails::global_score(details.iter());
+                if ranking_score_threshold.is_none_or(|threshold| blended >= threshold) {
+                    above_threshold.insert(docid);
+                }
+                if self.is_after_cursor(blended, docid) {
+                    best.push(Reverse((OrderedFloat(blended), Reverse(docid))));
+                    if best.len() > self.needed {
+                        best.pop();
+                    }
+                }
+            }
+        }
+
+        let mut ranked: RoaringBitmap =
+            best.into_iter().map(|Reverse((_, Reverse(docid)))| docid).collect();
+        let mut all_candidates = match ranking_score_threshold {
+            Some(_) => above_threshold,
+            None => universe.clone(),
+        };
+        // The documents left don't have any value and share the lowest blended score,
+        // they are ranked by id.
+        if exhausted {
+            let unscored = universe - &scored;
+            let blended = 1.0 / (1.0 + self.weights);
+            if ranking_score_threshold.is_none_or(|threshold| blended >= threshold) {
+                ranked.extend(
+                    unscored
+                        .iter()
+                        .filter(|docid| self.is_after_cursor(blended, *docid))
+                        .take(self.needed),
+                );
+                all_candidates |= unscored;
+            }
+        }
+
+        Ok(Some(DecayCandidates { ranked, all_candidates }))
+    }
+
+    /// Sorts the ranked documents by their blended score, ties being sorted by id.
+    ///
+    /// The documents before the cursor or below the ranking score threshold are removed.
+    pub fn rank(
+        mut self,
+        ctx: &SearchContext<'_>,
+        docids: Vec<u32>,
+        scores: Vec<Vec<ScoreDetails>>,
+        ranking_score_threshold: Option<f64>,
+        all_candidates: &mut RoaringBitmap,
+    ) -> Result<(Vec<u32>, Vec<Vec<ScoreDetails>>)> {
+        self.add_documents(ctx, &docids, &scores)?;
+
+        let mut hits = Vec::with_capacity(docids.len());
+        for (docid, mut scores) in docids.into_iter().zip(scores) {
+            scores.extend(self.details.remove(&docid).unwrap_or_default());
+            let blended = ScoreDetails::global_score(scores.iter());
+            if ranking_score_threshold.is_some_and(|threshold| blended < threshold) {
+                all_candidates.remove(docid);
+                continue;
+            }
+            if self.is_after_cursor(blended, docid) {
+                hits.push((Reverse(OrderedFloat(blended)), docid, scores));
+            }
+        }
+        hits.sort_unstable_by_key(|(blended, docid, _)| (*blended, *docid));
+
+        Ok(hits.into_iter().map(|(_, docid, scores)| (docid, scores)).unzip())
+    }
+
+    fn is_after_cursor(&self, blended: f64, docid: u32) -> bool {
+        match self.cursor {
+            Some((cursor_blended, cursor_docid)) => {
+                blended < cursor_blended || (blended == cursor_blended && docid > cursor_docid)
+            }
+            None => true,
+        }
+    }
+
+    /// Returns the details of the decay rules for the document.
+    ///
+    /// A document with several values for a field is scored with its best value,
+    /// a document without numeric value scores 0.
+    fn score_details(&self, ctx: &SearchContext<'_>, docid: u32) -> Result<Vec<ScoreDetails>> {
+        let mut details = Vec::with_capacity(self.rules.len());
+        for ScoredDecayRule { rule, field_id, must_redact, max } in &self.rules {
+            let mut score = 0.0f64;
+            if let Some(field_id) = field_id {
+                for item in facet_number_values(docid, *field_id, ctx.index, ctx.txn)? {
+                    let ((_, _, value), _) = item?;
+                    let value =
+                        OrderedF64Codec::bytes_decode(value).map_err(heed::Error::Decoding)?;
+                    score = score.max(rule.function.score(value, self.now, *max));
+                }
+            }
+            details.push(ScoreDetails::Decay(score_details::Decay {
+                rule: rule.clone(),
+                redacted: *must_redact,
+                score,
+            }));
+        }
+        Ok(details)
+    }
+}
+
+/// The documents to rank when all the documents have the same relevancy,
+/// see [`Decay::best_candidates`].
+pub struct DecayCandidates {
+    /// The documents that can be part of the requested ones.
+    pub ranked: RoaringBitmap,
+    /// The documents of the universe that are not below the ranking score threshold.
+    pub all_candidates: RoaringBitmap,
+}
+
+type FacetValues<'t> = Peekable<Box<dyn Iterator<Item = heed::Result<(f64, RoaringBitmap)>> + 't>>;
+
+/// The values of the field of a decay rule in the facet database, from the best scored one.
+struct ScoreOrderedValues<'t> {
+    /// The values lower than the origin, in descending order.
+    below: FacetValues<'t>,
+    /// The values greater than or equal to the origin, in ascending order.
+    above: FacetValues<'t>,
+}
+
+impl<'t> ScoreOrderedValues<'t> {
+    fn new(
+        ctx: &SearchContext<'t>,
+        field_id: FieldId,
+        rule: &ScoredDecayRule,
+        now: f64,
+    ) -> Result<Self> {
+        let origin = match &rule.rule.function {
+            DecayFunction::Linear { origin, .. }
+            | DecayFunction::Exp { origin, .. }
+            | DecayFunction::Gauss { origin, .. } => match origin {
+                DecayOrigin::Now => now,
+                DecayOrigin::Value(origin) => origin.0,
+            },
+            // the log boost increases along with the value
+            DecayFunction::Log => f64::MAX,
+        };
+
+        let key = |left_bound| FacetGroupKey { field_id, level: 0, left_bound };
+        let db = ctx.index.facet_id_f64_docids;
+        let below = db
+            .rev_range(ctx.txn, &(Bound::Included(key(f64::MIN)), Bound::Excluded(key(origin))))?
+            .map(|result| result.map(|(key, value)| (key.left_bound, value.bitmap)));
+        let above = db
+            .range(ctx.txn, &(Bound::Included(key(origin)), Bound::Included(key(f64::MAX))))?
+            .map(|result| result.map(|(key, value)| (key.left_bound, value.bitmap)));
+
+        let below: Box<dyn Iterator<Item = _> + 't> = Box::new(below);
+        let above: Box<dyn Iterator<Item = _> + 't> = Box::new(above);
+        Ok(Self { below: below.peekable(), above: above.peekable() })
+    }
+
+    /// The score of the next value, without reading it.
+    fn next_score(&mut self, rule: &ScoredDecayRule, now: f64) -> Result<Option<f64>> {
+        let below = peek_value(&mut self.below)?;
+        let above = peek_value(&mut self.above)?;
+        let score = |value| rule.rule.function.score(value, now, rule.max);
+        Ok(match (below.map(score), above.map(score)) {
+            (Some(below), Some(above)) => Some(below.max(above)),
+            (below, above) => below.or(above),
+        })
+    }
+
+    /// The documents with the next best scored value.
+    fn next(&mut self, rule: &ScoredDecayRule, now: f64) -> Result<Option<RoaringBitmap>> {
+        let below = peek_value(&mut self.below)?;
+        let above = peek_value(&mut self.above)?;
+        let score = |value| rule.rule.function.score(value, now, rule.max);
+        let values = match (below, above) {
+            (Some(below), Some(above)) if score(below) > score(above) => &mut self.below,
+            (_, Some(_)) => &mut self.above,
+            (Some(_), None) => &mut self.below,
+            (None, None) => return Ok(None),
+        };
+        Ok(values.next().transpose()?.map(|(_, docids)| docids))
+    }
+}
+
+fn peek_value(values: &mut FacetValues<'_>) -> Result<Option<f64>> {
+    match values.peek() {
+        Some(Ok((value, _))) => Ok(Some(*value)),
+        Some(Err(_)) => match values.next() {
+            Some(Err(error)) => Err(error.into()),
+            _ => unreachable!("the peeked value is an error"),
+        },
+        None => Ok(None),
+    }
+}
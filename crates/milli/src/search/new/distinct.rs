@@ -1,12 +1,12 @@
 use heed::types::{Bytes, Str, Unit};
-use heed::{Database, RoPrefix, RoTxn};
+use heed::{BytesDecode, Database, RoPrefix, RoTxn};
 use roaring::RoaringBitmap;
 
 const FID_SIZE: usize = 2;
 const DOCID_SIZE: usize = 4;
 
 use crate::heed_codec::facet::{
-    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetCodec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetCodec, OrderedF64Codec,
 };
 use crate::heed_codec::BytesRefCodec;
 use crate::{FieldId, FieldsIdsMap, Index, Result, SearchContext};
@@ -69,6 +69,44 @@ pub fn distinct_single_docid(
     Ok(())
 }
 
+/// The documents sharing a distinct value with a given document.
+pub struct DistinctGroup {
+    /// The first value of the document for the distinct field, `null` if it has none.
+    pub value: serde_json::Value,
+    /// The document itself and all the documents containing one of its values for the distinct field.
+    pub docids: RoaringBitmap,
+}
+
+/// Return the [`DistinctGroup`] that the given document is the representative of
+/// when applying the distinct rule on the given field.
+pub fn distinct_group(
+    index: &Index,
+    txn: &RoTxn<'_>,
+    field_id: u16,
+    docid: u32,
+) -> Result<DistinctGroup> {
+    let mut value = None;
+    if let Some(item) = facet_string_values(docid, field_id, index, txn)?.next() {
+        let (_, original) = item?;
+        value = Some(serde_json::Value::from(original));
+    }
+    if value.is_none() {
+        if let Some(item) = facet_number_values(docid, field_id, index, txn)?.next() {
+            let ((_, _, facet_value), _) = item?;
+            value = OrderedF64Codec::bytes_decode(facet_value)
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(serde_json::Value::Number);
+        }
+    }
+
+    let mut docids = RoaringBitmap::new();
+    distinct_single_docid(index, txn, field_id, docid, &mut docids)?;
+    docids.insert(docid);
+
+    Ok(DistinctGroup { value: value.unwrap_or_default(), docids })
+}
+
 /// Return all the docids containing the given value in the given field
 fn facet_value_docids(
     database: Database<FacetGroupKeyCodec<BytesRefCodec>, FacetGroupValueCodec>,
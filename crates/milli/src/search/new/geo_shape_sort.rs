@@ -0,0 +1,273 @@
+use std::collections::VecDeque;
+
+use geo_types::{Coord, Rect};
+use roaring::RoaringBitmap;
+
+use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use crate::constants::RESERVED_GEOJSON_FIELD_NAME;
+use crate::documents::GeoSortParameter;
+use crate::score_details::{self, ScoreDetails};
+use crate::search::geo_shape::Shape;
+use crate::search::new::ranking_rules::RankingRuleId;
+use crate::{distance_between_two_points, Deadline, Result, SearchContext, SearchLogger};
+
+/// The half-size, in degrees, of the first box around the point in which the shapes are looked for.
+const INITIAL_RING_SIZE: f64 = 0.01;
+/// The factor by which the box grows each time the shapes it contains are exhausted.
+const RING_GROWTH: f64 = 4.0;
+/// The margin around the box, in degrees, so that the approximations of cellulite don't miss the
+/// shapes touching its edges.
+const RING_MARGIN: f64 = 1e-3;
+
+/// Sorts the documents by the distance between a point and the nearest edge of their `_geojson` shape.
+///
+/// A document whose shape contains the point is at a distance of zero. The shapes are fetched
+/// lazily from cellulite in growing boxes around the point, or around its antipode when sorting
+/// by descending distance, and a document is returned once no shape outside the box can come
+/// before it. The documents without a shape are returned last.
+pub struct GeoShapeSort<Q: RankingRuleQueryTrait> {
+    query: Option<Q>,
+
+    ascending: bool,
+    point: [f64; 2],
+
+    /// The documents of the universe that were not fetched yet.
+    remaining: RoaringBitmap,
+    /// The half-size of the last box, in degrees, `None` before the first one.
+    ring_size: Option<f64>,
+    /// The minimum distance from the center of the boxes to a shape that is not fetched yet.
+    safe_distance: f64,
+    /// Whether all the shapes of the universe are fetched.
+    exhausted: bool,
+
+    /// The fetched documents with a shape, their nearest point and its distance, by ascending distance.
+    sorted_docids: VecDeque<(u32, [f64; 2], f64)>,
+
+    // Limit the number of docs in a single bucket to avoid unexpectedly large overhead
+    max_bucket_size: u64,
+    // Distances less than distance_error_margin apart are treated as equal
+    distance_error_margin: f64,
+}
+
+impl<Q: RankingRuleQueryTrait> GeoShapeSort<Q> {
+    pub fn new(parameter: GeoSortParameter, point: [f64; 2], ascending: bool) -> Self {
+        let GeoSortParameter { strategy: _, max_bucket_size, distance_error_margin } = parameter;
+        Self {
+            query: None,
+            ascending,
+            point,
+            remaining: RoaringBitmap::new(),
+            ring_size: None,
+            safe_distance: 0.0,
+            exhausted: false,
+            sorted_docids: VecDeque::new(),
+            max_bucket_size,
+            distance_error_margin,
+        }
+    }
+
+    /// The point around which the boxes grow: the farthest documents are the ones nearest to
+    /// the antipode of the point.
+    fn center(&self) -> [f64; 2] {
+        if self.ascending {
+            self.point
+        } else {
+            let [lat, lng] = self.point;
+            [-lat, if lng > 0.0 { lng - 180.0 } else { lng + 180.0 }]
+        }
+    }
+
+    /// Whether no shape that is not fetched yet can come before the document at this distance.
+    fn is_safe(&self, distance: f64) -> bool {
+        if self.exhausted {
+            true
+        } else if self.ascending {
+            distance <= self.safe_distance
+        } else {
+            distance >= half_circumference() - self.safe_distance
+        }
+    }
+
+    fn next_docid(&mut self, ctx: &mut SearchContext) -> Result<Option<(u32, [f64; 2], f64)>> {
+        loop {
+            let next =
+                if self.ascending { self.sorted_docids.front() } else { self.sorted_docids.back() };
+            match next.map(|&(_, _, distance)| distance) {
+                Some(distance) if self.is_safe(distance) => break,
+                _ if self.exhausted => break,
+                _ => self.fetch_next_ring(ctx)?,
+            }
+        }
+
+        if self.ascending {
+            Ok(self.sorted_docids.pop_front())
+        } else {
+            Ok(self.sorted_docids.pop_back())
+        }
+    }
+
+    fn put_back(&mut self, entry: (u32, [f64; 2], f64)) {
+        if self.ascending {
+            self.sorted_docids.push_front(entry)
+        } else {
+            self.sorted_docids.push_back(entry)
+        }
+    }
+
+    /// Fetches the shapes of the next box around the center, or of all the remaining documents
+    /// once the box would wrap around the antimeridian or cellulite doesn't store the shapes.
+    fn fetch_next_ring(&mut self, ctx: &mut SearchContext) -> Result<()> {
+        let Some(geojson_fid) = ctx.fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) else {
+            self.exhausted = true;
+            return Ok(());
+        };
+
+        let ring_size = match self.ring_size {
+            Some(ring_size) => ring_size * RING_GROWTH,
+            None => INITIAL_RING_SIZE,
+        };
+        self.ring_size = Some(ring_size);
+
+        let [lat, lng] = self.center();
+        let candidates = if ring_size < 90.0
+            && lng - ring_size >= -180.0
+            && lng + ring_size <= 180.0
+            && ctx.index.is_geojson_filtering_enabled(ctx.txn)?
+        {
+            let min = Coord {
+                x: lng - ring_size - RING_MARGIN,
+                y: (lat - ring_size - RING_MARGIN).max(-90.0),
+            };
+            let max = Coord {
+                x: lng + ring_size + RING_MARGIN,
+                y: (lat + ring_size + RING_MARGIN).min(90.0),
+            };
+            let hits = ctx.index.cellulite.in_shape(ctx.txn, &Rect::new(min, max).to_polygon())?;
+            // TODO: Remove once we update roaring in meilisearch
+            let candidates = RoaringBitmap::from_iter(hits) & &self.remaining;
+            self.safe_distance = ring_safe_distance(lat, ring_size);
+            candidates
+        } else {
+            self.exhausted = true;
+            self.remaining.clone()
+        };
+        self.remaining -= &candidates;
+
+        let mut fetched = Vec::new();
+        for docid in &candidates {
+            let Some(shape) = Shape::from_document(ctx.index, ctx.txn, geojson_fid, docid)? else {
+                continue;
+            };
+            if let Some(nearest) = shape.nearest_point(self.point) {
+                let distance = distance_between_two_points(&self.point, &nearest);
+                fetched.push((docid, nearest, distance));
+            }
+        }
+        if !fetched.is_empty() {
+            self.sorted_docids.extend(fetched);
+            self.sorted_docids
+                .make_contiguous()
+                .sort_by(|(_, _, left), (_, _, right)| left.total_cmp(right));
+        }
+
+        Ok(())
+    }
+}
+
+/// Half the circumference of the earth in meters, the largest distance between two points.
+fn half_circumference() -> f64 {
+    distance_between_two_points(&[0.0, 0.0], &[0.0, 180.0])
+}
+
+/// The minimum distance in meters from the center of a box of the given half-size in degrees,
+/// that doesn't wrap around the antimeridian, to a point outside of it.
+///
+/// It is the distance to the great circles of the east and west edges, which is never larger
+/// than the distance to the north and south edges.
+fn ring_safe_distance(lat: f64, ring_size: f64) -> f64 {
+    let angle = (lat.to_radians().cos() * ring_size.to_radians().sin()).asin();
+    distance_between_two_points(&[0.0, 0.0], &[angle.to_degrees(), 0.0])
+}
+
+impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoShapeSort<Q> {
+    fn id(&self) -> RankingRuleId {
+        RankingRuleId::GeoSort
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::geo_shape_sort")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+        query: &Q,
+        _deadline: &Deadline,
+    ) -> Result<()> {
+        assert!(self.query.is_none());
+
+        self.query = Some(query.clone());
+        self.remaining = universe.clone();
+        self.ring_size = None;
+        self.safe_distance = 0.0;
+        self.exhausted = false;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::geo_shape_sort")]
+    fn next_bucket(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+        _deadline: &Deadline,
+    ) -> Result<Option<RankingRuleOutput<Q>>> {
+        let query = self.query.as_ref().unwrap().clone();
+        self.remaining &= universe;
+
+        let mut bucket = RoaringBitmap::new();
+        let mut bucket_point = None;
+        while let Some((docid, point, distance)) = self.next_docid(ctx)? {
+            if !universe.contains(docid) {
+                continue;
+            }
+            match bucket_point {
+                Some((_, bucket_distance)) => {
+                    if (distance - bucket_distance).abs() > self.distance_error_margin {
+                        // different distance, the document belongs to the next bucket
+                        self.put_back((docid, point, distance));
+                        break;
+                    }
+                }
+                None => bucket_point = Some((point, distance)),
+            }
+            bucket.insert(docid);
+            if bucket.len() == self.max_bucket_size {
+                break;
+            }
+        }
+
+        // once all the shapes are returned, the documents without a shape make up the last bucket
+        let (candidates, value) = match bucket_point {
+            Some((point, _)) => (bucket, Some(point)),
+            None => (universe.clone(), None),
+        };
+
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates,
+            score: ScoreDetails::GeoSort(score_details::GeoSort {
+                target_point: self.point,
+                ascending: self.ascending,
+                value,
+            }),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::geo_shape_sort")]
+    fn end_iteration(&mut self, _ctx: &mut SearchContext<'ctx>, _logger: &mut dyn SearchLogger<Q>) {
+        self.query = None;
+        self.remaining.clear();
+        self.sorted_docids.clear();
+    }
+}
@@ -15,3 +15,6 @@ pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
 ///
 /// This limit is meant to gracefully handle the case where a word would have very long phrases as synonyms.
 pub const MAX_SYNONYM_WORD_COUNT: usize = 100;
+
+/// Maximum number of nested groups in a query using the advanced query syntax.
+pub const MAX_QUERY_GROUP_DEPTH: usize = 16;
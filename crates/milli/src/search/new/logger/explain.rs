@@ -0,0 +1,187 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use roaring::RoaringBitmap;
+
+use crate::search::new::query_term::LocatedQueryTerm;
+use crate::search::new::ranking_rules::BoxRankingRule;
+use crate::search::new::{RankingRule, RankingRuleQueryTrait, SearchContext, SearchLogger};
+use crate::DocumentId;
+
+/// The maximum number of bucket sizes kept for each ranking rule.
+const MAX_EXPLAINED_BUCKETS: usize = 100;
+
+/// How the ranking rules of a search split the candidates into buckets.
+#[derive(Debug, Clone, Default)]
+pub struct SearchExplanation {
+    /// The number of candidates given to the first ranking rule.
+    pub initial_candidates: u64,
+    pub ranking_rules: Vec<RankingRuleExplanation>,
+    /// The derivations of the query terms, empty for a placeholder or a vector search.
+    pub query_terms: Vec<QueryTermExplanation>,
+    /// For each returned document, the rank of its bucket in the iteration of each ranking rule.
+    ///
+    /// The ranks stop at the ranking rule that returned the document, and are `None`
+    /// when the document was returned with the rest of the candidates of a ranking rule.
+    pub documents: HashMap<DocumentId, Vec<Option<u64>>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RankingRuleExplanation {
+    pub name: String,
+    /// The number of times the ranking rule sorted a bucket of its parent.
+    pub iterations: u64,
+    /// The number of candidates the ranking rule had to sort, over all its iterations.
+    pub candidates: u64,
+    /// The number of buckets returned by the ranking rule.
+    pub buckets: u64,
+    /// The sizes of the first buckets returned by the ranking rule, in order.
+    pub bucket_sizes: Vec<u64>,
+    /// The number of candidates skipped because they are ranked before the offset.
+    pub skipped: u64,
+}
+
+/// The words and phrases a query term was derived into during the search.
+#[derive(Debug, Clone)]
+pub struct QueryTermExplanation {
+    pub original: String,
+    pub positions: RangeInclusive<u16>,
+    pub is_prefix: bool,
+    pub exact: Option<String>,
+    /// The words of the index starting with the term, when it is a prefix.
+    pub prefix_of: Vec<String>,
+    pub synonyms: Vec<String>,
+    pub split_words: Option<String>,
+    pub one_typo: Vec<String>,
+    pub two_typos: Vec<String>,
+}
+
+/// A [`SearchLogger`] building a [`SearchExplanation`] of the bucket sort.
+#[derive(Default)]
+pub struct ExplainLogger {
+    initial_candidates: u64,
+    ranking_rules: Vec<RankingRuleExplanation>,
+    /// The number of buckets returned by each ranking rule in its current iteration.
+    iteration_buckets: Vec<u64>,
+    /// The rank of the bucket currently sorted by each ranking rule in its iteration.
+    current_buckets: Vec<Option<u64>>,
+    /// The deepest ranking rule currently iterating.
+    current_ranking_rule: usize,
+    documents: HashMap<DocumentId, Vec<Option<u64>>>,
+}
+
+impl ExplainLogger {
+    pub fn into_explanation(
+        self,
+        ctx: &SearchContext<'_>,
+        located_query_terms: Option<&[LocatedQueryTerm]>,
+    ) -> SearchExplanation {
+        let ExplainLogger {
+            initial_candidates,
+            ranking_rules,
+            iteration_buckets: _,
+            current_buckets: _,
+            current_ranking_rule: _,
+            documents,
+        } = self;
+
+        let query_terms = located_query_terms
+            .unwrap_or_default()
+            .iter()
+            .map(|located_term| {
+                ctx.term_interner
+                    .get(located_term.value)
+                    .explain(ctx, located_term.positions.clone())
+            })
+            .collect();
+
+        SearchExplanation { initial_candidates, ranking_rules, query_terms, documents }
+    }
+}
+
+impl<Q: RankingRuleQueryTrait> SearchLogger<Q> for ExplainLogger {
+    fn initial_query(&mut self, _query: &Q) {}
+
+    fn initial_universe(&mut self, universe: &RoaringBitmap) {
+        self.initial_candidates = universe.len();
+    }
+
+    fn query_for_initial_universe(&mut self, _query: &Q) {}
+
+    fn ranking_rules(&mut self, rr: &[BoxRankingRule<'_, Q>]) {
+        self.ranking_rules = rr
+            .iter()
+            .map(|rr| RankingRuleExplanation { name: rr.id().to_string(), ..Default::default() })
+            .collect();
+        self.iteration_buckets = vec![0; rr.len()];
+        self.current_buckets = vec![None; rr.len()];
+    }
+
+    fn start_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        _query: &Q,
+        universe: &RoaringBitmap,
+    ) {
+        let explanation = &mut self.ranking_rules[ranking_rule_idx];
+        explanation.iterations += 1;
+        explanation.candidates += universe.len();
+        self.iteration_buckets[ranking_rule_idx] = 0;
+        self.current_buckets[ranking_rule_idx] = None;
+        self.current_ranking_rule = ranking_rule_idx;
+    }
+
+    fn next_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        _universe: &RoaringBitmap,
+        candidates: &RoaringBitmap,
+    ) {
+        let explanation = &mut self.ranking_rules[ranking_rule_idx];
+        explanation.buckets += 1;
+        if explanation.bucket_sizes.len() < MAX_EXPLAINED_BUCKETS {
+            explanation.bucket_sizes.push(candidates.len());
+        }
+        let rank = self.iteration_buckets[ranking_rule_idx];
+        self.iteration_buckets[ranking_rule_idx] += 1;
+        self.current_buckets[ranking_rule_idx] = Some(rank);
+        self.current_ranking_rule = ranking_rule_idx;
+    }
+
+    fn skip_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        candidates: &RoaringBitmap,
+    ) {
+        self.ranking_rules[ranking_rule_idx].skipped += candidates.len();
+    }
+
+    fn end_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        _universe: &RoaringBitmap,
+    ) {
+        self.current_buckets[ranking_rule_idx] = None;
+        // the bucket of the parent has been entirely sorted
+        if let Some(parent) = ranking_rule_idx.checked_sub(1) {
+            self.current_buckets[parent] = None;
+        }
+        self.current_ranking_rule = ranking_rule_idx.saturating_sub(1);
+    }
+
+    fn add_to_results(&mut self, docids: &[u32]) {
+        let buckets = self.current_buckets[..=self.current_ranking_rule].to_vec();
+        for &docid in docids {
+            self.documents.insert(docid, buckets.clone());
+        }
+        // the remaining candidates of the ranking rule are not part of this bucket
+        self.current_buckets[self.current_ranking_rule] = None;
+    }
+
+    fn log_internal_state(&mut self, _rr: &dyn Any) {}
+}
@@ -137,3 +137,35 @@ pub fn find_best_match_interval(matches: &[Match], crop_size: usize) -> [&Match;
         |MatchIntervalWithScore { interval: [first, last], .. }| [&matches[first], &matches[last]],
     )
 }
+
+/// Returns the first and last match of every interval fitting in the crop window along with its score,
+/// the best intervals first.
+///
+/// An interval starts at each match and ends at the furthest match that keeps it within `crop_size` words,
+/// intervals with the same score are kept in the order they appear in the text.
+pub fn find_match_intervals_by_score(
+    matches: &[Match],
+    crop_size: usize,
+) -> Vec<([&Match; 2], [i16; 3])> {
+    let mut intervals = Vec::with_capacity(matches.len());
+    let mut interval_last = 0;
+
+    for (interval_first, first_match) in matches.iter().enumerate() {
+        let interval_first_match_first_word_pos = first_match.get_first_word_pos();
+        interval_last = interval_last.max(interval_first);
+
+        // extend the interval as long as the next match fits in the crop window.
+        while let Some(next_match) = matches.get(interval_last + 1) {
+            if next_match.get_last_word_pos() - interval_first_match_first_word_pos >= crop_size {
+                break;
+            }
+            interval_last += 1;
+        }
+
+        let score = get_interval_score(&matches[interval_first..=interval_last]);
+        intervals.push(([first_match, &matches[interval_last]], score));
+    }
+
+    intervals.sort_by(|(_, left), (_, right)| right.cmp(left));
+    intervals
+}
@@ -68,6 +68,13 @@ impl MatchingWords {
         }
     }
 
+    /// Returns the number of words written by the end user that can be matched.
+    pub fn query_word_count(&self) -> usize {
+        let phrases_ends = self.phrases.iter().map(|phrase| *phrase.positions.end());
+        let words_ends = self.words.iter().map(|words| *words.positions.end());
+        phrases_ends.chain(words_ends).max().map_or(0, |end| end as usize + 1)
+    }
+
     /// Returns an iterator over terms that match or partially match the given token.
     pub fn match_token<'a, 'b>(&'a self, token: &'b Token<'b>) -> MatchesIter<'a, 'b> {
         MatchesIter { matching_words: self, phrases: Box::new(self.phrases.iter()), token }
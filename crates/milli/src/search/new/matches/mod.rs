@@ -120,6 +120,19 @@ pub struct MatchBounds {
     pub indices: Option<Vec<usize>>,
 }
 
+/// A highlighted passage of an attribute value, cropped around some of the matching terms.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct Snippet {
+    /// The highlighted and cropped text of the passage.
+    pub text: String,
+    /// The byte offset where the passage begins within the attribute value.
+    pub start: usize,
+    /// The length in bytes of the passage within the attribute value.
+    pub length: usize,
+    /// The proportion of the query words found in the passage, from 0.0 to 1.0.
+    pub score: f64,
+}
+
 /// Structure used to analyze a string, compute words that match,
 /// and format the source string, returning a highlighted and cropped
 /// sub-string.
@@ -266,16 +279,20 @@ impl<'t> Matcher<'t, '_, '_, '_> {
     }
 
     /// Returns the bounds in byte index of the crop window.
-    fn crop_bounds(&self, tokens: &[Token<'_>], matches: &[Match], crop_size: usize) -> [usize; 2] {
+    ///
+    /// The crop window is grown around the given matches interval, or from the start of the text if there is none.
+    fn crop_bounds(
+        &self,
+        tokens: &[Token<'_>],
+        interval: Option<[&Match; 2]>,
+        crop_size: usize,
+    ) -> [usize; 2] {
         let (
             mut remaining_words,
             is_iterating_forward,
             before_tokens_starting_index,
             after_tokens_starting_index,
-        ) = if !matches.is_empty() {
-            let [matches_first, matches_last] =
-                best_match_interval::find_best_match_interval(matches, crop_size);
-
+        ) = if let Some([matches_first, matches_last]) = interval {
             let matches_size =
                 matches_last.get_last_word_pos() - matches_first.get_first_word_pos() + 1;
 
@@ -413,93 +430,150 @@ impl<'t> Matcher<'t, '_, '_, '_> {
             match &self.matches {
                 Some((tokens, matches)) => {
                     // If the text has to be cropped, crop around the best interval.
-                    let [crop_byte_start, crop_byte_end] = match format_options.crop {
+                    let crop_bounds = match format_options.crop {
                         Some(crop_size) if crop_size > 0 => {
-                            self.crop_bounds(tokens, matches, crop_size)
+                            let interval = (!matches.is_empty()).then(|| {
+                                best_match_interval::find_best_match_interval(matches, crop_size)
+                            });
+                            self.crop_bounds(tokens, interval, crop_size)
                         }
                         _ => [0, self.text.len()],
                     };
 
-                    let mut formatted = Vec::new();
+                    self.format_window(tokens, matches, crop_bounds, format_options.highlight)
+                }
+                None => self.compute_matches().format(format_options),
+            }
+        }
+    }
 
-                    // push crop marker if it's not the start of the text.
-                    if crop_byte_start > 0 && !self.crop_marker.is_empty() {
-                        formatted.push(self.crop_marker);
-                    }
+    /// Returns up to `count` non-overlapping passages of the text, highlighted and cropped
+    /// around the best matches intervals, ordered by their position in the text.
+    ///
+    /// No passage is returned when nothing matches the query.
+    pub fn snippets(&mut self, crop_size: usize, count: usize) -> Vec<Snippet> {
+        match &self.matches {
+            Some((tokens, matches)) => {
+                if matches.is_empty() || crop_size == 0 || count == 0 {
+                    return Vec::new();
+                }
 
-                    let mut byte_index = crop_byte_start;
+                let query_word_count = self.matching_words.query_word_count().max(1);
 
-                    if format_options.highlight {
-                        // insert highlight markers around matches.
-                        for m in matches {
-                            let [m_byte_start, m_byte_end] = match m.position {
-                                MatchPosition::Word { token_position, .. } => {
-                                    let token = &tokens[token_position];
-                                    [&token.byte_start, &token.byte_end]
-                                }
-                                MatchPosition::Phrase { token_positions: [ftp, ltp], .. } => {
-                                    [&tokens[ftp].byte_start, &tokens[ltp].byte_end]
-                                }
-                            };
+                let mut windows: Vec<([usize; 2], f64)> = Vec::with_capacity(count);
+                for (interval, [uniq_score, ..]) in
+                    best_match_interval::find_match_intervals_by_score(matches, crop_size)
+                {
+                    let [start, end] = self.crop_bounds(tokens, Some(interval), crop_size);
+                    // skip the passages sharing some text with a better one.
+                    if windows.iter().any(|([s, e], _)| start < *e && *s < end) {
+                        continue;
+                    }
 
-                            // skip matches out of the crop window
-                            if *m_byte_end < crop_byte_start || *m_byte_start > crop_byte_end {
-                                continue;
-                            }
+                    let score = (uniq_score as f64 / query_word_count as f64).min(1.0);
+                    windows.push(([start, end], score));
+                    if windows.len() == count {
+                        break;
+                    }
+                }
 
-                            // adjust start and end to the crop window size
-                            let [m_byte_start, m_byte_end] = [
-                                max(m_byte_start, &crop_byte_start),
-                                min(m_byte_end, &crop_byte_end),
-                            ];
+                windows.sort_unstable_by_key(|([start, _], _)| *start);
+                windows
+                    .into_iter()
+                    .map(|([start, end], score)| Snippet {
+                        text: self.format_window(tokens, matches, [start, end], true).into_owned(),
+                        start,
+                        length: end - start,
+                        score,
+                    })
+                    .collect()
+            }
+            None => self.compute_matches().snippets(crop_size, count),
+        }
+    }
 
-                            // push text that is positioned before our matches
-                            if byte_index < *m_byte_start {
-                                formatted.push(&self.text[byte_index..*m_byte_start]);
-                            }
+    /// Returns the text between the given crop bounds, with crop markers and optionally highlighted matches.
+    fn format_window(
+        &self,
+        tokens: &[Token<'t>],
+        matches: &[Match],
+        [crop_byte_start, crop_byte_end]: [usize; 2],
+        highlight: bool,
+    ) -> Cow<'t, str> {
+        let mut formatted = Vec::new();
+
+        // push crop marker if it's not the start of the text.
+        if crop_byte_start > 0 && !self.crop_marker.is_empty() {
+            formatted.push(self.crop_marker);
+        }
 
-                            formatted.push(self.highlight_prefix);
+        let mut byte_index = crop_byte_start;
 
-                            // TODO: This is additional work done, charabia::token::Token byte_len
-                            // should already get us the original byte length, however, that doesn't work as
-                            // it's supposed to, investigate why
-                            let highlight_byte_index = self.text[*m_byte_start..]
-                                .char_indices()
-                                .nth(m.char_count)
-                                .map_or(*m_byte_end, |(i, _)| min(i + *m_byte_start, *m_byte_end));
-                            formatted.push(&self.text[*m_byte_start..highlight_byte_index]);
+        if highlight {
+            // insert highlight markers around matches.
+            for m in matches {
+                let [m_byte_start, m_byte_end] = match m.position {
+                    MatchPosition::Word { token_position, .. } => {
+                        let token = &tokens[token_position];
+                        [&token.byte_start, &token.byte_end]
+                    }
+                    MatchPosition::Phrase { token_positions: [ftp, ltp], .. } => {
+                        [&tokens[ftp].byte_start, &tokens[ltp].byte_end]
+                    }
+                };
 
-                            formatted.push(self.highlight_suffix);
+                // skip matches out of the crop window
+                if *m_byte_end < crop_byte_start || *m_byte_start > crop_byte_end {
+                    continue;
+                }
 
-                            // if it's a prefix highlight, we put the end of the word after the highlight marker.
-                            if highlight_byte_index < *m_byte_end {
-                                formatted.push(&self.text[highlight_byte_index..*m_byte_end]);
-                            }
+                // adjust start and end to the crop window size
+                let [m_byte_start, m_byte_end] =
+                    [max(m_byte_start, &crop_byte_start), min(m_byte_end, &crop_byte_end)];
 
-                            byte_index = *m_byte_end;
-                        }
-                    }
+                // push text that is positioned before our matches
+                if byte_index < *m_byte_start {
+                    formatted.push(&self.text[byte_index..*m_byte_start]);
+                }
 
-                    // push the rest of the text between last match and the end of crop.
-                    if byte_index < crop_byte_end {
-                        formatted.push(&self.text[byte_index..crop_byte_end]);
-                    }
+                formatted.push(self.highlight_prefix);
 
-                    // push crop marker if it's not the end of the text.
-                    if crop_byte_end < self.text.len() && !self.crop_marker.is_empty() {
-                        formatted.push(self.crop_marker);
-                    }
+                // TODO: This is additional work done, charabia::token::Token byte_len
+                // should already get us the original byte length, however, that doesn't work as
+                // it's supposed to, investigate why
+                let highlight_byte_index = self.text[*m_byte_start..]
+                    .char_indices()
+                    .nth(m.char_count)
+                    .map_or(*m_byte_end, |(i, _)| min(i + *m_byte_start, *m_byte_end));
+                formatted.push(&self.text[*m_byte_start..highlight_byte_index]);
 
-                    if formatted.len() == 1 {
-                        // avoid concatenating if there is already 1 slice.
-                        Cow::Borrowed(&self.text[crop_byte_start..crop_byte_end])
-                    } else {
-                        Cow::Owned(formatted.concat())
-                    }
+                formatted.push(self.highlight_suffix);
+
+                // if it's a prefix highlight, we put the end of the word after the highlight marker.
+                if highlight_byte_index < *m_byte_end {
+                    formatted.push(&self.text[highlight_byte_index..*m_byte_end]);
                 }
-                None => self.compute_matches().format(format_options),
+
+                byte_index = *m_byte_end;
             }
         }
+
+        // push the rest of the text between last match and the end of crop.
+        if byte_index < crop_byte_end {
+            formatted.push(&self.text[byte_index..crop_byte_end]);
+        }
+
+        // push crop marker if it's not the end of the text.
+        if crop_byte_end < self.text.len() && !self.crop_marker.is_empty() {
+            formatted.push(self.crop_marker);
+        }
+
+        if formatted.len() == 1 {
+            // avoid concatenating if there is already 1 slice.
+            Cow::Borrowed(&self.text[crop_byte_start..crop_byte_end])
+        } else {
+            Cow::Owned(formatted.concat())
+        }
     }
 }
 
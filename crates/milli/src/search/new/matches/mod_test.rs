@@ -55,6 +55,7 @@ impl<'a> MatcherBuilder<'a> {
             None,
             &progress,
             vec![],
+            None,
         )
         .unwrap();
 
@@ -425,6 +426,44 @@ fn format_highlight_crop_phrase_query() {
     );
 }
 
+#[test]
+fn snippets() {
+    let temp_index = temp_index_with_documents();
+    let rtxn = temp_index.read_txn().unwrap();
+    let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "split the world");
+
+    // Text without any match.
+    let text = "A quick brown fox can not jump 32 feet, right? Brr, it is cold!";
+    let mut matcher = builder.build(text, None);
+    assert!(matcher.snippets(3, 2).is_empty());
+
+    // Text containing matches far from each other.
+    let text = "split the world void void void void void void void void void void world";
+    let mut matcher = builder.build(text, None);
+    let snippets = matcher.snippets(3, 2);
+    assert_eq!(snippets.len(), 2);
+    // the best passage contains all the query words, the second one the last query word.
+    insta::assert_snapshot!(snippets[0].text, @"<em>split</em> <em>the</em> <em>world</em>…");
+    assert_eq!((snippets[0].start, snippets[0].length), (0, 15));
+    assert_eq!(snippets[0].score, 1.0);
+    insta::assert_snapshot!(snippets[1].text, @"…void void <em>world</em>");
+    assert_eq!((snippets[1].start, snippets[1].length), (56, 15));
+    assert!(snippets[1].score < snippets[0].score);
+
+    // only the best passage is returned when a single one is requested.
+    let mut matcher = builder.build(text, None);
+    let snippets = matcher.snippets(3, 1);
+    assert_eq!(snippets.len(), 1);
+    assert_eq!(snippets[0].start, 0);
+
+    // passages sharing some text are not returned twice.
+    let text = "void void split the world void void.";
+    let mut matcher = builder.build(text, None);
+    let snippets = matcher.snippets(10, 3);
+    assert_eq!(snippets.len(), 1);
+    insta::assert_snapshot!(snippets[0].text, @"void void <em>split</em> <em>the</em> <em>world</em> void void.");
+}
+
 #[test]
 fn smaller_crop_size() {
     //! testing: https://github.com/meilisearch/specifications/pull/120#discussion_r836536295
@@ -1,6 +1,8 @@
 mod bucket_sort;
 mod db_cache;
+mod decay;
 mod distinct;
+pub(crate) mod geo_shape_sort;
 pub(crate) mod geo_sort;
 mod graph_based_ranking_rule;
 mod interner;
@@ -21,32 +23,38 @@ mod vector_sort;
 #[cfg(test)]
 mod tests;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::AddAssign;
+use std::sync::Arc;
 use std::time::Duration;
 
 use bucket_sort::{bucket_sort, BucketSortOutput};
 use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
-pub use distinct::{distinct_fid, distinct_single_docid};
+pub use distinct::{distinct_fid, distinct_group, distinct_single_docid, DistinctGroup};
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
 use interner::{DedupInterner, Interner};
+pub use logger::explain::{
+    ExplainLogger, QueryTermExplanation, RankingRuleExplanation, SearchExplanation,
+};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 pub use query_graph::{QueryGraph, QueryNode};
-use query_term::{located_query_terms_from_tokens, Phrase, QueryTerm};
-pub use query_term::{ExtractedTokens, LocatedQueryTerm};
-use ranking_rules::{
-    BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
+use query_term::{
+    located_query_terms_from_advanced_query, located_query_terms_from_tokens, Phrase, QueryTerm,
 };
+pub use query_term::{ExtractedTokens, LocatedQueryTerm};
+pub(crate) use ranking_rules::PlaceholderQuery;
+use ranking_rules::{BoxRankingRule, RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
 use sort::Sort;
 use time::OffsetDateTime;
 
 pub(crate) use self::distinct::{facet_string_values, facet_values_prefix_key};
+use self::geo_shape_sort::GeoShapeSort;
 use self::geo_sort::GeoSort;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
@@ -63,8 +71,9 @@ use crate::search::new::distinct::apply_distinct_rule;
 use crate::search::steps::SearchStep;
 use crate::vector::Embedder;
 use crate::{
-    AscDesc, Deadline, DocumentId, FieldId, FieldsIdsMap, Index, Member, PinDoc, Result,
-    TermsMatchingStrategy, UserError, Weight,
+    is_faceted_by, AscDesc, Deadline, DocumentId, FieldId, FieldidsWeightsMap, FieldsIdsMap, Index,
+    Member, PinDoc, QuerySyntax, Result, SearchCursor, TermsMatchingStrategy,
+    TypoToleranceOverride, UserError, Weight,
 };
 
 /// Cache for synonyms to avoid repeated database access
@@ -89,6 +98,16 @@ pub struct SearchContext<'ctx> {
     pub restricted_fids: Option<RestrictedFids>,
     pub prefix_search: PrefixSearch,
     pub vector_store_stats: Option<VectorStoreStats>,
+    /// Whether the distinct rule must be skipped, even if the index has a distinct attribute.
+    pub ignore_distinct: bool,
+    /// Weights of the searchable attributes given at search time, replacing the ones of the settings.
+    pub attribute_weights: Option<AttributeWeights>,
+    /// Typo tolerance given at search time, replacing the one of the settings.
+    pub typo_tolerance: TypoToleranceOverride,
+    /// Whether the synonyms of the index must be ignored when deriving the query terms.
+    pub ignore_synonyms: bool,
+    /// The time from which the decay ranking rules score the documents.
+    pub decay_now: OffsetDateTime,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -129,6 +148,11 @@ impl<'ctx> SearchContext<'ctx> {
             restricted_fids: None,
             prefix_search,
             vector_store_stats: None,
+            ignore_distinct: false,
+            attribute_weights: None,
+            typo_tolerance: TypoToleranceOverride::default(),
+            ignore_synonyms: false,
+            decay_now: before_search,
         })
     }
 
@@ -221,6 +245,50 @@ impl<'ctx> SearchContext<'ctx> {
 
         Ok(())
     }
+
+    /// Replace the weights of the searchable attributes, given from the most to the least important,
+    /// by the ones of the `boosts` where the greater boost is the most important attribute.
+    ///
+    /// The boost of an attribute applies to its nested fields, and the searchable attributes without boost have a boost of 0.
+    pub fn attribute_weights(&mut self, boosts: &BTreeMap<String, Weight>) -> Result<()> {
+        let searchable_fields_weights =
+            self.index.searchable_fields_and_weights(self.txn, self.fields_ids_map)?;
+        let max_boost = boosts.values().copied().max().unwrap_or_default();
+
+        // the most precise attribute wins, i.e. `a.b` over `a` for the field `a.b.c`
+        let boost_of = |name: &str| {
+            boosts
+                .iter()
+                .filter(|(attribute, _)| is_faceted_by(name, attribute))
+                .max_by_key(|(attribute, _)| attribute.len())
+                .map_or(0, |(_, boost)| *boost)
+        };
+
+        let mut weights = FieldidsWeightsMap::default();
+        for (name, fid, _weight) in searchable_fields_weights {
+            weights.insert(fid, max_boost - boost_of(&name));
+        }
+
+        let boosts = match self.index.user_defined_searchable_fields(self.txn)? {
+            Some(searchable) => {
+                searchable.into_iter().map(|name| (name.to_string(), boost_of(name))).collect()
+            }
+            None => boosts.clone(),
+        };
+
+        self.attribute_weights =
+            Some(AttributeWeights { weights, max_weight: max_boost, boosts: Arc::new(boosts) });
+        Ok(())
+    }
+}
+
+/// Weights of the searchable attributes, the lower the weight, the more important the attribute.
+#[derive(Debug, Default)]
+pub struct AttributeWeights {
+    pub weights: FieldidsWeightsMap,
+    pub max_weight: Weight,
+    /// The boosts of the searchable attributes, reported in the ranking score details.
+    pub boosts: Arc<BTreeMap<String, Weight>>,
 }
 
 #[derive(Debug, Default)]
@@ -410,6 +478,8 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                     false,
                 )?));
             }
+            // The decay rules are blended with the relevancy by the bucket sort
+            crate::Criterion::Decay(_) => continue,
         }
     }
     Ok(ranking_rules)
@@ -500,6 +570,8 @@ fn get_ranking_rules_for_vector<'ctx>(
                     false,
                 )?));
             }
+            // The decay rules are blended with the relevancy by the bucket sort
+            crate::Criterion::Decay(_) => continue,
         }
     }
 
@@ -643,6 +715,8 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                     false,
                 )?));
             }
+            // The decay rules are blended with the relevancy by the bucket sort
+            crate::Criterion::Decay(_) => continue,
         }
     }
     Ok(ranking_rules)
@@ -710,6 +784,18 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     false,
                 )?));
             }
+            AscDesc::Asc(Member::GeoShape(point)) => {
+                if *geo_sorted {
+                    continue;
+                }
+                ranking_rules.push(Box::new(GeoShapeSort::new(geo_param, point, true)));
+            }
+            AscDesc::Desc(Member::GeoShape(point)) => {
+                if *geo_sorted {
+                    continue;
+                }
+                ranking_rules.push(Box::new(GeoShapeSort::new(geo_param, point, false)));
+            }
         };
     }
     Ok(())
@@ -756,10 +842,12 @@ pub fn execute_vector_search(
     embedder_name: &str,
     embedder: &Embedder,
     quantized: bool,
+    placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery>,
     deadline: Deadline,
     ranking_score_threshold: Option<f64>,
     progress: &Progress,
     pins: Vec<PinDoc>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
@@ -776,10 +864,6 @@ pub fn execute_vector_search(
         quantized,
     )?;
 
-    let mut placeholder_search_logger = logger::DefaultSearchLogger;
-    let placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery> =
-        &mut placeholder_search_logger;
-
     let _step = progress.update_progress_scoped(SearchStep::SemanticRanking);
     let BucketSortOutput { docids, scores, all_candidates, degraded } = bucket_sort(
         ctx,
@@ -796,6 +880,7 @@ pub fn execute_vector_search(
         exhaustive_number_hits,
         max_total_hits,
         pins,
+        search_after,
     )?;
 
     Ok(PartialSearchResult {
@@ -828,6 +913,7 @@ pub fn execute_search(
     ranking_score_threshold: Option<f64>,
     progress: &Progress,
     pins: Vec<PinDoc>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
@@ -866,6 +952,7 @@ pub fn execute_search(
             exhaustive_number_hits,
             max_total_hits,
             pins,
+            search_after,
         )?
     } else {
         let ranking_rules =
@@ -886,6 +973,7 @@ pub fn execute_search(
             exhaustive_number_hits,
             max_total_hits,
             pins,
+            search_after,
         )?
     };
 
@@ -893,7 +981,7 @@ pub fn execute_search(
 
     // The candidates is the universe unless the exhaustive number of hits
     // is requested and a distinct attribute is set.
-    if exhaustive_number_hits {
+    if exhaustive_number_hits && !ctx.ignore_distinct {
         let distinct_field = match distinct.as_deref() {
             Some(distinct) => Some(distinct),
             None => ctx.index.distinct_field(ctx.txn)?,
@@ -918,6 +1006,7 @@ pub fn execute_search(
 pub fn extract_tokens(
     ctx: &mut SearchContext<'_>,
     query: &str,
+    syntax: QuerySyntax,
     words_limit: Option<usize>,
     locales: Option<&Vec<Language>>,
 ) -> Result<ExtractedTokens> {
@@ -987,12 +1076,19 @@ pub fn extract_tokens(
     let tokenizer = tokbuilder.build();
     drop(entered);
 
-    let span = tracing::trace_span!(target: "search::tokens", "tokenize");
-    let entered = span.enter();
-    let tokens = tokenizer.tokenize(query);
-    drop(entered);
+    match syntax {
+        QuerySyntax::Simple => {
+            let span = tracing::trace_span!(target: "search::tokens", "tokenize");
+            let entered = span.enter();
+            let tokens = tokenizer.tokenize(query);
+            drop(entered);
 
-    located_query_terms_from_tokens(ctx, &tokenizer, tokens, words_limit)
+            located_query_terms_from_tokens(ctx, &tokenizer, tokens, words_limit)
+        }
+        QuerySyntax::Advanced => {
+            located_query_terms_from_advanced_query(ctx, &tokenizer, query, words_limit)
+        }
+    }
 }
 
 pub(crate) fn check_sort_criteria(
@@ -186,6 +186,34 @@ impl QueryGraph {
         Ok((graph, new_located_query_terms))
     }
 
+    /// Build the query graph from the data of its nodes and the edges between them.
+    ///
+    /// The first two nodes must be the start and end nodes.
+    pub fn from_nodes_and_edges(
+        nodes_data: Vec<QueryNodeData>,
+        edges: &[(u16, u16)],
+    ) -> QueryGraph {
+        let mut nodes = FixedSizeInterner::new(
+            nodes_data.len() as u16,
+            QueryNode {
+                data: QueryNodeData::Deleted,
+                predecessors: SmallBitmap::new(nodes_data.len() as u16),
+                successors: SmallBitmap::new(nodes_data.len() as u16),
+            },
+        );
+        for (node_idx, node_data) in nodes_data.into_iter().enumerate() {
+            let node = nodes.get_mut(Interned::from_raw(node_idx as u16));
+            node.data = node_data;
+        }
+        for &(from, to) in edges {
+            let (from, to) = (Interned::from_raw(from), Interned::from_raw(to));
+            nodes.get_mut(from).successors.insert(to);
+            nodes.get_mut(to).predecessors.insert(from);
+        }
+
+        QueryGraph { root_node: Interned::from_raw(0), end_node: Interned::from_raw(1), nodes }
+    }
+
     /// Remove the given nodes, connecting all their predecessors to all their successors.
     pub fn remove_nodes_keep_edges(&mut self, nodes: &[Interned<QueryNode>]) {
         for &node_id in nodes {
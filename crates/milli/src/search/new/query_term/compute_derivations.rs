@@ -219,11 +219,12 @@ pub fn partially_initialized_term_from_word(
     }
 
     let mut synonym_word_count = 0;
-    let synonyms = ctx
-        .index
-        .synonyms
-        .remap_key_type::<SynonymsKeyCodec<&str>>()
-        .get(ctx.txn, &[word])?
+    let synonyms = if ctx.ignore_synonyms {
+        None
+    } else {
+        ctx.index.synonyms.remap_key_type::<SynonymsKeyCodec<&str>>().get(ctx.txn, &[word])?
+    };
+    let synonyms = synonyms
         .map_or(Vec::<Vec<_>>::new(), |synonyms| synonyms.synonyms(tokenizer))
         .into_iter()
         .take(limits::MAX_SYNONYM_PHRASE_COUNT)
@@ -1,5 +1,6 @@
 mod compute_derivations;
 mod ntypo_subset;
+mod parse_advanced_query;
 mod parse_query;
 mod phrase;
 
@@ -9,12 +10,14 @@ use std::ops::RangeInclusive;
 
 use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
+pub use parse_advanced_query::located_query_terms_from_advanced_query;
 pub use parse_query::{
     located_query_terms_from_tokens, make_ngram, number_of_typos_allowed, ExtractedTokens,
 };
 pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
+use super::logger::explain::QueryTermExplanation;
 use super::{limits, SearchContext, Word};
 use crate::Result;
 
@@ -507,4 +510,41 @@ impl QueryTerm {
 
         (words.into_iter().collect(), phrases.into_iter().collect())
     }
+
+    /// Describes the derivations of the term computed so far, by kind.
+    pub fn explain(
+        &self,
+        ctx: &SearchContext<'_>,
+        positions: RangeInclusive<u16>,
+    ) -> QueryTermExplanation {
+        let word = |word: &Interned<String>| ctx.word_interner.get(*word).clone();
+
+        let ZeroTypoTerm { phrase: _, exact, prefix_of, synonyms, use_prefix_db: _ } =
+            &self.zero_typo;
+
+        let (split_words, one_typo) = match &self.one_typo {
+            Lazy::Init(OneTypoTerm { split_words, one_typo }) => (
+                split_words.map(|split_words| split_words.description(ctx)),
+                one_typo.iter().map(word).collect(),
+            ),
+            Lazy::Uninit => (None, Vec::new()),
+        };
+
+        let two_typos = match &self.two_typo {
+            Lazy::Init(TwoTypoTerm { two_typos }) => two_typos.iter().map(word).collect(),
+            Lazy::Uninit => Vec::new(),
+        };
+
+        QueryTermExplanation {
+            original: self.original_word(ctx),
+            positions,
+            is_prefix: self.is_prefix,
+            exact: exact.as_ref().map(word),
+            prefix_of: prefix_of.iter().map(word).collect(),
+            synonyms: synonyms.iter().map(|synonym| synonym.description(ctx)).collect(),
+            split_words,
+            one_typo,
+            two_typos,
+        }
+    }
 }
@@ -0,0 +1,680 @@
+//! Parsing of the advanced query syntax.
+//!
+//! On top of the words, `"phrases"` and `-` exclusions of the simple syntax, it supports:
+//! - `field:word`, `field:"a phrase"` and `field:(...)` to only match terms within a searchable field,
+//! - `a OR b` to match any of the alternatives,
+//! - `(...)` to group terms,
+//! - `+word` to make terms mandatory whatever the terms matching strategy.
+//!
+//! The `+` and `-` operators apply to the whole alternation that follows them, i.e. `-a OR b`
+//! excludes the documents containing either `a` or `b`. Field-scoped terms are always mandatory,
+//! and an exclusion nested in a group applies to the whole query.
+//!
+//! The alternations, groups and required terms are compiled into the [`QueryGraph`], while the
+//! field scopes and the exclusions are resolved into sets of documents. Unlike the simple syntax,
+//! no n-grams are derived from consecutive words.
+
+use std::iter::Peekable;
+
+use charabia::{TokenKind, Tokenizer};
+use roaring::RoaringBitmap;
+
+use super::compute_derivations::partially_initialized_term_from_word;
+use super::parse_query::PhraseBuilder;
+use super::{
+    number_of_typos_allowed, ExtractedTokens, LocatedQueryTerm, LocatedQueryTermSubset,
+    QueryTermSubset,
+};
+use crate::search::new::limits::{MAX_QUERY_GROUP_DEPTH, MAX_TOKEN_COUNT};
+use crate::search::new::query_graph::{QueryGraph, QueryNodeData};
+use crate::search::new::resolve_query_graph::compute_query_term_subset_docids_within_field_id;
+use crate::search::new::{SearchContext, Word};
+use crate::{is_faceted_by, FieldId, Result, UserError};
+
+/// Convert a search query written with the advanced syntax into a query graph.
+#[tracing::instrument(level = "trace", skip_all, target = "search::query")]
+pub fn located_query_terms_from_advanced_query(
+    ctx: &mut SearchContext<'_>,
+    tokenizer: &Tokenizer<'_>,
+    query: &str,
+    words_limit: Option<usize>,
+) -> Result<ExtractedTokens> {
+    let clauses = parse(query);
+
+    let mut compiler = QueryCompiler {
+        nbr_typos: number_of_typos_allowed(ctx)?,
+        allow_prefix_search: ctx.is_prefix_search_allowed(),
+        // the term ids of the query graph are stored in a u8
+        words_limit: words_limit.unwrap_or(usize::MAX).min(u8::MAX as usize),
+        nodes_data: vec![QueryNodeData::Start, QueryNodeData::End],
+        edges: Vec::new(),
+        query_terms: Vec::new(),
+        next_term_id: 0,
+        next_position: 0,
+        excluded_docids: None,
+    };
+
+    let (exits, required_docids) =
+        compiler.compile_sequence(ctx, tokenizer, &clauses, false, vec![START_NODE])?;
+    compiler.edges.extend(exits.into_iter().map(|exit| (exit, END_NODE)));
+
+    let graph = QueryGraph::from_nodes_and_edges(compiler.nodes_data, &compiler.edges);
+
+    Ok(ExtractedTokens {
+        query_terms: compiler.query_terms,
+        graph,
+        negative_words: Vec::new(),
+        negative_phrases: Vec::new(),
+        excluded_docids: compiler.excluded_docids,
+        required_docids,
+    })
+}
+
+const START_NODE: u16 = 0;
+const END_NODE: u16 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Lexeme {
+    Words(String),
+    /// Words of which the last one is searched as a prefix.
+    PrefixWords(String),
+    Phrase(String),
+    Field(String),
+    Required,
+    Excluded,
+    Or,
+    OpenGroup,
+    CloseGroup,
+}
+
+/// Split the query into the lexemes of the advanced syntax.
+fn lex(query: &str) -> Vec<Lexeme> {
+    let mut lexemes = Vec::new();
+    let mut chars = query.chars().peekable();
+    // `+` and `-` are only operators at the start of a clause
+    let mut at_clause_start = true;
+
+    while let Some(c) = chars.next() {
+        if lexemes.len() >= MAX_TOKEN_COUNT {
+            return lexemes;
+        }
+
+        match c {
+            c if c.is_whitespace() => at_clause_start = true,
+            '(' => {
+                lexemes.push(Lexeme::OpenGroup);
+                at_clause_start = true;
+            }
+            ')' => {
+                lexemes.push(Lexeme::CloseGroup);
+                at_clause_start = true;
+            }
+            // If a quote is never closed, we consider all of the end of the query as a phrase.
+            '"' => {
+                let phrase = chars.by_ref().take_while(|&c| c != '"').collect();
+                lexemes.push(Lexeme::Phrase(phrase));
+                at_clause_start = false;
+            }
+            '+' | '-' if at_clause_start && chars.peek().is_some_and(|c| !c.is_whitespace()) => {
+                lexemes.push(if c == '+' { Lexeme::Required } else { Lexeme::Excluded });
+                at_clause_start = false;
+            }
+            c => {
+                let mut text = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | ')' | '"') {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+
+                match text.split_once(':') {
+                    _ if text == "OR" => lexemes.push(Lexeme::Or),
+                    Some((field, words)) if !field.is_empty() => {
+                        lexemes.push(Lexeme::Field(field.to_string()));
+                        if !words.is_empty() {
+                            lexemes.push(Lexeme::Words(words.to_string()));
+                        }
+                    }
+                    _ => lexemes.push(Lexeme::Words(text)),
+                }
+                at_clause_start = false;
+            }
+        }
+    }
+
+    // like in the simple syntax, the last word is a prefix unless the query ends with a separator
+    if !query.ends_with(char::is_whitespace) {
+        if let Some(Lexeme::Words(text)) = lexemes.pop_if(|l| matches!(l, Lexeme::Words(_))) {
+            lexemes.push(Lexeme::PrefixWords(text));
+        }
+    }
+
+    lexemes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// The terms can be removed by the terms matching strategy.
+    Optional,
+    /// The terms cannot be removed by the terms matching strategy.
+    Required,
+    /// The documents matching the terms are removed from the results.
+    Excluded,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Clause {
+    operator: Operator,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Words { text: String, field: Option<String>, prefix: bool },
+    Phrase { text: String, field: Option<String> },
+    Group(Vec<Clause>),
+    Or(Vec<Expr>),
+}
+
+type Lexemes = Peekable<std::vec::IntoIter<Lexeme>>;
+
+/// Parse the query into clauses.
+///
+/// The parsing never fails: unbalanced parentheses are closed or ignored, as well as
+/// the operators that are not followed by any term.
+fn parse(query: &str) -> Vec<Clause> {
+    let mut lexemes = lex(query).into_iter().peekable();
+    parse_sequence(&mut lexemes, 0, None)
+}
+
+fn parse_sequence(lexemes: &mut Lexemes, depth: usize, field: Option<&str>) -> Vec<Clause> {
+    let mut clauses = Vec::new();
+    loop {
+        let operator = match lexemes.peek() {
+            None => break,
+            Some(Lexeme::CloseGroup) if depth > 0 => break,
+            Some(Lexeme::CloseGroup) => {
+                lexemes.next();
+                continue;
+            }
+            Some(Lexeme::Required) => {
+                lexemes.next();
+                Operator::Required
+            }
+            Some(Lexeme::Excluded) => {
+                lexemes.next();
+                Operator::Excluded
+            }
+            Some(_) => Operator::Optional,
+        };
+
+        if let Some(expr) = parse_alternation(lexemes, depth, field) {
+            clauses.push(Clause { operator, expr });
+        }
+    }
+    clauses
+}
+
+fn parse_alternation(lexemes: &mut Lexemes, depth: usize, field: Option<&str>) -> Option<Expr> {
+    let mut alternatives: Vec<_> = parse_primary(lexemes, depth, field).into_iter().collect();
+    while lexemes.next_if_eq(&Lexeme::Or).is_some() {
+        alternatives.extend(parse_primary(lexemes, depth, field));
+    }
+
+    match alternatives.len() {
+        0 | 1 => alternatives.pop(),
+        _ => Some(Expr::Or(alternatives)),
+    }
+}
+
+fn parse_primary(lexemes: &mut Lexemes, depth: usize, field: Option<&str>) -> Option<Expr> {
+    // the closing parenthesis is consumed by the group
+    if lexemes.peek() == Some(&Lexeme::CloseGroup) {
+        return None;
+    }
+
+    let field_name = field.map(String::from);
+    match lexemes.next()? {
+        Lexeme::Words(text) => Some(Expr::Words { text, field: field_name, prefix: false }),
+        Lexeme::PrefixWords(text) => Some(Expr::Words { text, field: field_name, prefix: true }),
+        Lexeme::Phrase(text) => Some(Expr::Phrase { text, field: field_name }),
+        Lexeme::Field(field) => parse_primary(lexemes, depth, Some(&field)),
+        Lexeme::OpenGroup if depth < MAX_QUERY_GROUP_DEPTH => {
+            let clauses = parse_sequence(lexemes, depth + 1, field);
+            lexemes.next_if_eq(&Lexeme::CloseGroup);
+            Some(Expr::Group(clauses))
+        }
+        // operators are only allowed at the start of a clause
+        Lexeme::Required | Lexeme::Excluded => parse_primary(lexemes, depth, field),
+        Lexeme::OpenGroup | Lexeme::Or | Lexeme::CloseGroup => None,
+    }
+}
+
+/// Compiles the parsed clauses into the nodes and edges of a query graph.
+struct QueryCompiler<F> {
+    nbr_typos: F,
+    allow_prefix_search: bool,
+    words_limit: usize,
+    nodes_data: Vec<QueryNodeData>,
+    edges: Vec<(u16, u16)>,
+    query_terms: Vec<LocatedQueryTerm>,
+    next_term_id: u8,
+    next_position: u16,
+    excluded_docids: Option<RoaringBitmap>,
+}
+
+impl<F: Fn(&str) -> u8> QueryCompiler<F> {
+    /// Add the nodes of the clauses after the `entry` nodes, returning the nodes ending the
+    /// clauses and the documents satisfying their field scopes, if any.
+    fn compile_sequence(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        tokenizer: &Tokenizer<'_>,
+        clauses: &[Clause],
+        mandatory: bool,
+        entry: Vec<u16>,
+    ) -> Result<(Vec<u16>, Option<RoaringBitmap>)> {
+        let mut exits = entry;
+        let mut required_docids = None;
+        for Clause { operator, expr } in clauses {
+            let mandatory = match operator {
+                Operator::Optional => mandatory,
+                Operator::Required => true,
+                Operator::Excluded => {
+                    let docids = resolve_excluded_docids(ctx, tokenizer, expr)?;
+                    *self.excluded_docids.get_or_insert_with(RoaringBitmap::new) |= docids;
+                    continue;
+                }
+            };
+            let (clause_exits, docids) =
+                self.compile_expr(ctx, tokenizer, expr, mandatory, exits)?;
+            exits = clause_exits;
+            if let Some(docids) = docids {
+                intersect(&mut required_docids, docids);
+            }
+        }
+        Ok((exits, required_docids))
+    }
+
+    fn compile_expr(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        tokenizer: &Tokenizer<'_>,
+        expr: &Expr,
+        mandatory: bool,
+        entry: Vec<u16>,
+    ) -> Result<(Vec<u16>, Option<RoaringBitmap>)> {
+        match expr {
+            Expr::Words { text, field, prefix } => {
+                let fids =
+                    field.as_deref().map(|field| searchable_field_ids(ctx, field)).transpose()?;
+                let terms = self.words_terms(ctx, tokenizer, text, *prefix)?;
+                self.compile_terms(ctx, terms, mandatory, fids, entry)
+            }
+            Expr::Phrase { text, field } => {
+                let fids =
+                    field.as_deref().map(|field| searchable_field_ids(ctx, field)).transpose()?;
+                let terms = if self.query_terms.len() < self.words_limit {
+                    build_phrase(ctx, tokenizer, text, &mut self.next_position)
+                        .into_iter()
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                self.compile_terms(ctx, terms, mandatory, fids, entry)
+            }
+            Expr::Group(clauses) => {
+                self.compile_sequence(ctx, tokenizer, clauses, mandatory, entry)
+            }
+            Expr::Or(alternatives) => {
+                // the alternatives start at the same term id, such that the terms matching
+                // strategy removes them together.
+                let (first_term_id, first_position) = (self.next_term_id, self.next_position);
+                let (mut last_term_id, mut last_position) = (first_term_id, first_position);
+                let mut exits = Vec::new();
+                let mut required_docids = Some(RoaringBitmap::new());
+                for alternative in alternatives {
+                    self.next_term_id = first_term_id;
+                    self.next_position = first_position;
+                    let (alternative_exits, docids) =
+                        self.compile_expr(ctx, tokenizer, alternative, mandatory, entry.clone())?;
+                    // an alternative without any term would make the whole alternation optional
+                    if self.next_term_id == first_term_id {
+                        continue;
+                    }
+                    exits.extend(alternative_exits);
+                    required_docids = match (required_docids, docids) {
+                        (Some(required_docids), Some(docids)) => Some(required_docids | docids),
+                        _ => None,
+                    };
+                    last_term_id = last_term_id.max(self.next_term_id);
+                    last_position = last_position.max(self.next_position);
+                }
+                self.next_term_id = last_term_id;
+                self.next_position = last_position;
+
+                if exits.is_empty() {
+                    Ok((entry, None))
+                } else {
+                    Ok((exits, required_docids))
+                }
+            }
+        }
+    }
+
+    /// Add a node for each of the terms, one after the other.
+    fn compile_terms(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        terms: Vec<LocatedQueryTerm>,
+        mandatory: bool,
+        fids: Option<Vec<FieldId>>,
+        entry: Vec<u16>,
+    ) -> Result<(Vec<u16>, Option<RoaringBitmap>)> {
+        let mut exits = entry;
+        let mut required_docids = None;
+        for term in terms {
+            let mut term_subset = QueryTermSubset::full(term.value);
+            if mandatory || fids.is_some() {
+                term_subset.make_mandatory();
+            }
+
+            if let Some(fids) = &fids {
+                let mut docids = RoaringBitmap::new();
+                for &fid in fids {
+                    docids |= compute_query_term_subset_docids_within_field_id(
+                        ctx,
+                        None,
+                        &term_subset,
+                        fid,
+                    )?;
+                }
+                intersect(&mut required_docids, docids);
+            }
+
+            let term_id = self.next_term_id;
+            self.next_term_id = self.next_term_id.saturating_add(1);
+            let node = self.nodes_data.len() as u16;
+            self.nodes_data.push(QueryNodeData::Term(LocatedQueryTermSubset {
+                term_subset,
+                positions: term.positions.clone(),
+                term_ids: term_id..=term_id,
+            }));
+            self.edges.extend(exits.iter().map(|&exit| (exit, node)));
+            exits = vec![node];
+            self.query_terms.push(term);
+        }
+        Ok((exits, required_docids))
+    }
+
+    fn words_terms(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        tokenizer: &Tokenizer<'_>,
+        text: &str,
+        prefix: bool,
+    ) -> Result<Vec<LocatedQueryTerm>> {
+        let tokens: Vec<_> = tokenizer
+            .tokenize(text)
+            .filter(|token| !token.lemma().is_empty())
+            .filter(|token| matches!(token.kind, TokenKind::Word | TokenKind::StopWord))
+            .collect();
+
+        let mut terms = Vec::new();
+        for (i, token) in tokens.iter().enumerate() {
+            let position = self.next_position;
+            self.next_position = self.next_position.saturating_add(1);
+
+            // like in the simple syntax, the stop words are only searched as the last prefix
+            let is_last = prefix && i + 1 == tokens.len();
+            if matches!(token.kind, TokenKind::StopWord) && !is_last {
+                continue;
+            }
+            if self.query_terms.len() + terms.len() >= self.words_limit {
+                break;
+            }
+
+            let word = token.lemma();
+            let term = partially_initialized_term_from_word(
+                ctx,
+                tokenizer,
+                word,
+                (self.nbr_typos)(word),
+                is_last && self.allow_prefix_search,
+                false,
+            )?;
+            terms.push(LocatedQueryTerm {
+                value: ctx.term_interner.push(term),
+                positions: position..=position,
+            });
+        }
+        Ok(terms)
+    }
+}
+
+fn build_phrase(
+    ctx: &mut SearchContext<'_>,
+    tokenizer: &Tokenizer<'_>,
+    text: &str,
+    position: &mut u16,
+) -> Option<LocatedQueryTerm> {
+    let mut phrase = PhraseBuilder::empty();
+    for token in tokenizer.tokenize(text) {
+        if !token.lemma().is_empty() && matches!(token.kind, TokenKind::Word | TokenKind::StopWord)
+        {
+            phrase.push_word(ctx, &token, *position);
+            *position = position.saturating_add(1);
+        }
+    }
+    phrase.build(ctx)
+}
+
+/// Return the documents matching an excluded expression.
+///
+/// Like the exclusions of the simple syntax, the words are matched without typos nor prefixes.
+fn resolve_excluded_docids(
+    ctx: &mut SearchContext<'_>,
+    tokenizer: &Tokenizer<'_>,
+    expr: &Expr,
+) -> Result<RoaringBitmap> {
+    match expr {
+        Expr::Words { text, field, prefix: _ } => {
+            let fids =
+                field.as_deref().map(|field| searchable_field_ids(ctx, field)).transpose()?;
+            let mut docids = None;
+            for token in tokenizer.tokenize(text) {
+                if token.lemma().is_empty() || !matches!(token.kind, TokenKind::Word) {
+                    continue;
+                }
+                let word = ctx.word_interner.insert(token.lemma().to_string());
+                let word_docids = match &fids {
+                    Some(fids) => {
+                        let mut docids = RoaringBitmap::new();
+                        for &fid in fids {
+                            docids |=
+                                ctx.get_db_word_fid_docids(None, word, fid)?.unwrap_or_default();
+                        }
+                        docids
+                    }
+                    None => ctx.word_docids(None, Word::Original(word))?.unwrap_or_default(),
+                };
+                intersect(&mut docids, word_docids);
+            }
+            Ok(docids.unwrap_or_default())
+        }
+        Expr::Phrase { text, field } => {
+            let fids =
+                field.as_deref().map(|field| searchable_field_ids(ctx, field)).transpose()?;
+            let Some(phrase) = build_phrase(ctx, tokenizer, text, &mut 0)
+                .and_then(|term| ctx.term_interner.get(term.value).original_phrase())
+            else {
+                return Ok(RoaringBitmap::new());
+            };
+
+            let docids = ctx.get_phrase_docids(phrase)?.clone();
+            match fids {
+                // There may be false positives when resolving a phrase, so we're not
+                // guaranteed that all of its words are within a single fid.
+                Some(fids) => {
+                    let Some(word) = phrase.words(ctx).into_iter().flatten().next() else {
+                        return Ok(RoaringBitmap::new());
+                    };
+                    let mut field_docids = RoaringBitmap::new();
+                    for fid in fids {
+                        field_docids |=
+                            ctx.get_db_word_fid_docids(None, word, fid)?.unwrap_or_default();
+                    }
+                    Ok(docids & field_docids)
+                }
+                None => Ok(docids),
+            }
+        }
+        Expr::Group(clauses) => {
+            let mut docids = None;
+            let mut excluded_docids = RoaringBitmap::new();
+            for Clause { operator, expr } in clauses {
+                let clause_docids = resolve_excluded_docids(ctx, tokenizer, expr)?;
+                match operator {
+                    Operator::Optional | Operator::Required => {
+                        intersect(&mut docids, clause_docids)
+                    }
+                    Operator::Excluded => excluded_docids |= clause_docids,
+                }
+            }
+            Ok(docids.unwrap_or_default() - excluded_docids)
+        }
+        Expr::Or(alternatives) => {
+            let mut docids = RoaringBitmap::new();
+            for alternative in alternatives {
+                docids |= resolve_excluded_docids(ctx, tokenizer, alternative)?;
+            }
+            Ok(docids)
+        }
+    }
+}
+
+/// Return the ids of the searchable fields targeted by a field-scoped term, including the nested ones.
+fn searchable_field_ids(ctx: &SearchContext<'_>, field: &str) -> Result<Vec<FieldId>> {
+    let searchable_fields_weights =
+        ctx.index.searchable_fields_and_weights(ctx.txn, ctx.fields_ids_map)?;
+    let fids: Vec<_> = searchable_fields_weights
+        .iter()
+        .filter(|(name, _, _)| is_faceted_by(name, field))
+        .map(|(_, fid, _)| *fid)
+        .collect();
+
+    if fids.is_empty() {
+        // the field is searchable but no document contains it yet
+        let is_searchable = match ctx.index.user_defined_searchable_fields(ctx.txn)? {
+            Some(user_defined) => user_defined.iter().any(|name| is_faceted_by(field, name)),
+            None => true,
+        };
+        if !is_searchable {
+            let (valid_fields, hidden_fields) = ctx.index.remove_hidden_fields(
+                ctx.txn,
+                searchable_fields_weights.iter().map(|(name, _, _)| name),
+            )?;
+            return Err(UserError::InvalidSearchQueryAttribute {
+                field: field.to_string(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+    }
+
+    Ok(fids)
+}
+
+fn intersect(docids: &mut Option<RoaringBitmap>, other: RoaringBitmap) {
+    match docids {
+        Some(docids) => *docids &= other,
+        None => *docids = Some(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Expr {
+        Expr::Words { text: text.to_string(), field: None, prefix: false }
+    }
+
+    fn optional(expr: Expr) -> Clause {
+        Clause { operator: Operator::Optional, expr }
+    }
+
+    #[test]
+    fn parse_simple_syntax() {
+        assert_eq!(
+            parse("hello \"big world\" -bye"),
+            vec![
+                optional(words("hello")),
+                optional(Expr::Phrase { text: "big world".to_string(), field: None }),
+                Clause {
+                    operator: Operator::Excluded,
+                    expr: Expr::Words { text: "bye".to_string(), field: None, prefix: true },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_operators() {
+        assert_eq!(
+            parse("+(title:batman OR \"dark knight\") -robin "),
+            vec![
+                Clause {
+                    operator: Operator::Required,
+                    expr: Expr::Group(vec![optional(Expr::Or(vec![
+                        Expr::Words {
+                            text: "batman".to_string(),
+                            field: Some("title".to_string()),
+                            prefix: false
+                        },
+                        Expr::Phrase { text: "dark knight".to_string(), field: None },
+                    ]))]),
+                },
+                Clause { operator: Operator::Excluded, expr: words("robin") },
+            ]
+        );
+
+        assert_eq!(
+            parse("overview:(hero OR villain) a-b"),
+            vec![
+                optional(Expr::Group(vec![optional(Expr::Or(vec![
+                    Expr::Words {
+                        text: "hero".to_string(),
+                        field: Some("overview".to_string()),
+                        prefix: false
+                    },
+                    Expr::Words {
+                        text: "villain".to_string(),
+                        field: Some("overview".to_string()),
+                        prefix: false
+                    },
+                ]))])),
+                optional(Expr::Words { text: "a-b".to_string(), field: None, prefix: true }),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_unbalanced_query() {
+        assert_eq!(
+            parse(") OR (a OR (b"),
+            vec![optional(Expr::Group(vec![optional(Expr::Or(vec![
+                words("a"),
+                Expr::Group(vec![optional(Expr::Words {
+                    text: "b".to_string(),
+                    field: None,
+                    prefix: true
+                })]),
+            ]))]))]
+        );
+        assert_eq!(parse("OR title:"), vec![]);
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::BTreeSet;
 
 use charabia::normalizer::NormalizedTokenIter;
 use charabia::{SeparatorKind, TokenKind, Tokenizer};
+use roaring::RoaringBitmap;
 
 use super::compute_derivations::partially_initialized_term_from_word;
 use super::{LocatedQueryTerm, ZeroTypoTerm};
@@ -21,6 +22,10 @@ pub struct ExtractedTokens {
     pub negative_words: Vec<Word>,
     /// The phrases that must not appear in the results.
     pub negative_phrases: Vec<LocatedQueryTerm>,
+    /// The documents matching the excluded clauses of the advanced query syntax, if any.
+    pub excluded_docids: Option<RoaringBitmap>,
+    /// The documents satisfying the field-scoped terms of the advanced query syntax, if any.
+    pub required_docids: Option<RoaringBitmap>,
 }
 
 /// Convert the tokenised search query into a list of located query terms.
@@ -58,7 +63,14 @@ pub fn located_query_terms_from_tokens(
         if query_terms.len() >= parts_limit {
             let (graph, query_terms) = QueryGraph::from_query(ctx, tokenizer, &query_terms)?;
 
-            return Ok(ExtractedTokens { query_terms, graph, negative_words, negative_phrases });
+            return Ok(ExtractedTokens {
+                query_terms,
+                graph,
+                negative_words,
+                negative_phrases,
+                excluded_docids: None,
+                required_docids: None,
+            });
         }
 
         match token.kind {
@@ -198,15 +210,32 @@ pub fn located_query_terms_from_tokens(
 
     let (graph, query_terms) = QueryGraph::from_query(ctx, tokenizer, &query_terms)?;
 
-    Ok(ExtractedTokens { query_terms, graph, negative_words, negative_phrases })
+    Ok(ExtractedTokens {
+        query_terms,
+        graph,
+        negative_words,
+        negative_phrases,
+        excluded_docids: None,
+        required_docids: None,
+    })
 }
 
 pub fn number_of_typos_allowed<'ctx>(
     ctx: &SearchContext<'ctx>,
 ) -> Result<impl Fn(&str) -> u8 + 'ctx> {
-    let authorize_typos = ctx.index.authorize_typos(ctx.txn)?;
-    let min_len_one_typo = ctx.index.min_word_len_one_typo(ctx.txn)?;
-    let min_len_two_typos = ctx.index.min_word_len_two_typos(ctx.txn)?;
+    let typo_tolerance = ctx.typo_tolerance;
+    let authorize_typos = match typo_tolerance.enabled {
+        Some(enabled) => enabled,
+        None => ctx.index.authorize_typos(ctx.txn)?,
+    };
+    let min_len_one_typo = match typo_tolerance.min_word_len_one_typo {
+        Some(min_len) => min_len,
+        None => ctx.index.min_word_len_one_typo(ctx.txn)?,
+    };
+    let min_len_two_typos = match typo_tolerance.min_word_len_two_typos {
+        Some(min_len) => min_len,
+        None => ctx.index.min_word_len_two_typos(ctx.txn)?,
+    };
 
     let exact_words = ctx.index.exact_words(ctx.txn)?;
 
@@ -275,7 +304,9 @@ pub fn make_ngram(
     )?;
 
     // Now add the synonyms
-    if let Some(synonyms) = ctx.index.synonyms.get(ctx.txn, &words)? {
+    let synonyms =
+        if ctx.ignore_synonyms { None } else { ctx.index.synonyms.get(ctx.txn, &words)? };
+    if let Some(synonyms) = synonyms {
         for synonym in synonyms.synonyms(tokenizer) {
             let words =
                 synonym.into_iter().map(|w| Some(ctx.word_interner.insert(w.to_owned()))).collect();
@@ -299,14 +330,14 @@ pub fn make_ngram(
     Ok(Some(term))
 }
 
-struct PhraseBuilder {
+pub(super) struct PhraseBuilder {
     words: Vec<Option<crate::search::new::Interned<String>>>,
     start: u16,
     end: u16,
 }
 
 impl PhraseBuilder {
-    fn empty() -> Self {
+    pub(super) fn empty() -> Self {
         Self { words: Default::default(), start: u16::MAX, end: u16::MAX }
     }
 
@@ -315,7 +346,7 @@ impl PhraseBuilder {
     }
 
     // precondition: token has kind Word or StopWord
-    fn push_word(
+    pub(super) fn push_word(
         &mut self,
         ctx: &mut SearchContext<'_>,
         token: &charabia::Token<'_>,
@@ -334,7 +365,7 @@ impl PhraseBuilder {
         }
     }
 
-    fn build(self, ctx: &mut SearchContext<'_>) -> Option<LocatedQueryTerm> {
+    pub(super) fn build(self, ctx: &mut SearchContext<'_>) -> Option<LocatedQueryTerm> {
         if self.is_empty() {
             return None;
         }
@@ -86,7 +86,7 @@ impl RankingRuleGraphTrait for ExactnessGraph {
     }
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::exactness")]
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
+    fn rank_to_score(_ctx: &SearchContext<'_>, rank: Rank) -> ScoreDetails {
         ScoreDetails::ExactWords(score_details::ExactWords::from_rank(rank))
     }
 }
@@ -2,7 +2,7 @@ use fxhash::FxHashSet;
 use roaring::RoaringBitmap;
 
 use super::{ComputedCondition, RankingRuleGraphTrait};
-use crate::score_details::{Rank, ScoreDetails};
+use crate::score_details::{self, Rank, ScoreDetails};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::search::new::resolve_query_graph::compute_query_term_subset_docids_within_field_id;
@@ -75,7 +75,14 @@ impl RankingRuleGraphTrait for FidGraph {
             all_fields.extend(fields);
         }
 
-        let weights_map = ctx.index.fieldids_weights_map(ctx.txn, ctx.fields_ids_map)?;
+        let index_weights_map;
+        let weights_map = match &ctx.attribute_weights {
+            Some(attribute_weights) => &attribute_weights.weights,
+            None => {
+                index_weights_map = ctx.index.fieldids_weights_map(ctx.txn, ctx.fields_ids_map)?;
+                &index_weights_map
+            }
+        };
 
         let mut edges = vec![];
         for fid in all_fields.iter().copied() {
@@ -103,7 +110,10 @@ impl RankingRuleGraphTrait for FidGraph {
         }
 
         // always lookup the max_fid if we don't already and add an artificial condition for max scoring
-        let max_weight = ctx.index.max_searchable_attribute_weight(ctx.txn)?;
+        let max_weight = match &ctx.attribute_weights {
+            Some(attribute_weights) => Some(attribute_weights.max_weight),
+            None => ctx.index.max_searchable_attribute_weight(ctx.txn)?,
+        };
 
         if let Some(max_weight) = max_weight {
             if current_max_weight < max_weight {
@@ -121,7 +131,9 @@ impl RankingRuleGraphTrait for FidGraph {
     }
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::fid")]
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
-        ScoreDetails::Fid(rank)
+    fn rank_to_score(ctx: &SearchContext<'_>, rank: Rank) -> ScoreDetails {
+        let attribute_weights =
+            ctx.attribute_weights.as_ref().map(|weights| weights.boosts.clone());
+        ScoreDetails::Fid(score_details::Fid { rank, attribute_weights })
     }
 }
@@ -113,7 +113,7 @@ pub trait RankingRuleGraphTrait: Sized + 'static {
     ) -> Result<Vec<(u32, Interned<Self::Condition>)>>;
 
     /// Convert the rank of a path to its corresponding score for the ranking rule
-    fn rank_to_score(rank: Rank) -> ScoreDetails;
+    fn rank_to_score(ctx: &SearchContext<'_>, rank: Rank) -> ScoreDetails;
 }
 
 /// The graph used by graph-based ranking rules.
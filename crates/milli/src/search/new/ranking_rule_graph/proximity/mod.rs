@@ -41,7 +41,7 @@ impl RankingRuleGraphTrait for ProximityGraph {
     }
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::proximity")]
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
+    fn rank_to_score(_ctx: &SearchContext<'_>, rank: Rank) -> ScoreDetails {
         ScoreDetails::Proximity(rank)
     }
 }
@@ -79,7 +79,7 @@ impl RankingRuleGraphTrait for TypoGraph {
     }
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::typo")]
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
+    fn rank_to_score(_ctx: &SearchContext<'_>, rank: Rank) -> ScoreDetails {
         ScoreDetails::Typo(score_details::Typo::from_rank(rank))
     }
 }
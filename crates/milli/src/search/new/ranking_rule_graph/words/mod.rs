@@ -47,7 +47,7 @@ impl RankingRuleGraphTrait for WordsGraph {
     }
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::words")]
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
+    fn rank_to_score(_ctx: &SearchContext<'_>, rank: Rank) -> ScoreDetails {
         ScoreDetails::Words(score_details::Words::from_rank(rank))
     }
 }
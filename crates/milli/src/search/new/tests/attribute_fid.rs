@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::index::tests::TempIndex;
 use crate::{db_snap, Criterion, SearchResult, TermsMatchingStrategy};
 
@@ -159,3 +161,27 @@ fn test_attribute_fid_ngrams() {
     let document_ids_scores: Vec<_> = documents_ids.iter().zip(document_scores).collect();
     insta::assert_snapshot!(format!("{document_ids_scores:#?}"));
 }
+
+#[test]
+fn test_attribute_fid_query_weights() {
+    let index = create_index();
+    let weights = BTreeMap::from([("plot".to_owned(), 2), ("title".to_owned(), 1)]);
+
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("the quick brown fox jumps over the lazy dog");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let position =
+        |documents_ids: &[u32], docid| documents_ids.iter().position(|&id| id == docid).unwrap();
+    // the title is the most important attribute of the settings
+    assert!(position(&documents_ids, 2) < position(&documents_ids, 0));
+
+    s.attribute_weights(&weights);
+    let SearchResult { documents_ids: weighted_documents_ids, .. } = s.execute().unwrap();
+    // the plot is now the most important attribute
+    assert!(position(&weighted_documents_ids, 0) < position(&weighted_documents_ids, 2));
+    assert_eq!(weighted_documents_ids.len(), documents_ids.len());
+}
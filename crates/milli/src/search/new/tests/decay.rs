@@ -0,0 +1,327 @@
+/*!
+This module tests the decay ranking rules:
+
+1. the documents are ranked by the score of the decay function, whether it is monotonic or not
+2. the documents without a numeric value for the field are returned last
+3. the log boost ranks the greatest values first
+4. the score of the decay rule is part of the score details
+5. the score of the decay rule is blended with the relevancy according to its weight
+6. a placeholder search only scores the documents around the origin of the rule
+7. the origin `now` can be frozen to keep the ranking stable between pages
+8. the search is only degraded when some documents could not be scored
+*/
+
+use big_s::S;
+use ordered_float::OrderedFloat;
+use time::OffsetDateTime;
+
+use crate::documents::mmap_from_objects;
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails, ScoreValue, ScoringStrategy};
+use crate::search::new::decay::MAX_SCORED_DOCUMENTS;
+use crate::{
+    Criterion, DecayFunction, DecayOrigin, DecayRule, SearchResult, TermsMatchingStrategy,
+};
+
+fn create_index(rule: DecayRule) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Decay(rule)]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "price": 25, "popularity": 0 },
+            { "id": 1, "price": 3, "popularity": 10 },
+            { "id": 2, "price": 10, "popularity": 1000 },
+            { "id": 3, "price": 14 },
+            { "id": 4, "popularity": 100 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_decay_gauss() {
+    let rule = DecayRule {
+        field: S("price"),
+        function: DecayFunction::Gauss {
+            origin: DecayOrigin::Value(OrderedFloat(10.0)),
+            scale: OrderedFloat(5.0),
+        },
+        weight: DecayRule::DEFAULT_WEIGHT,
+    };
+    let index = create_index(rule.clone());
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+    // the document without price comes last
+    assert_eq!(documents_ids, [2, 3, 1, 0, 4]);
+    assert_eq!(
+        document_scores[0],
+        [ScoreDetails::Decay(score_details::Decay { rule, redacted: false, score: 1.0 })]
+    );
+    let scores: Vec<_> =
+        document_scores.iter().map(|scores| ScoreDetails::global_score(scores.iter())).collect();
+    assert_eq!(scores[0], 1.0);
+    // the scores are not discretized, the document without price only scores the relevancy
+    assert!(scores[3] > scores[4]);
+    assert_eq!(scores[4], 0.5);
+}
+
+#[test]
+fn test_decay_log() {
+    let rule = DecayRule {
+        field: S("popularity"),
+        function: DecayFunction::Log,
+        weight: DecayRule::DEFAULT_WEIGHT,
+    };
+    let index = create_index(rule);
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+    assert_eq!(documents_ids, [2, 4, 1, 0, 3]);
+    let scores: Vec<_> =
+        document_scores.iter().map(|scores| ScoreDetails::global_score(scores.iter())).collect();
+    assert!(scores.windows(2).all(|window| window[0] >= window[1]));
+    assert_eq!(scores[0], 1.0);
+    // both the document with a popularity of 0 and the one without popularity score 0
+    assert_eq!(document_scores[3], document_scores[4]);
+}
+
+fn create_blended_index(weight: f64) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Decay(DecayRule {
+                    field: S("popularity"),
+                    function: DecayFunction::Log,
+                    weight: OrderedFloat(weight),
+                }),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "quick brown fox", "popularity": 0 },
+            { "id": 1, "title": "quick brown", "popularity": 1000 },
+            { "id": 2, "title": "quick brown fox", "popularity": 10 },
+            { "id": 3, "title": "quick", "popularity": 100 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_decay_blended_with_relevancy() {
+    // with a heavy weight, the popularity outweighs a missing word
+    let index = create_blended_index(1.0);
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.query("quick brown fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+    assert_eq!(documents_ids, [1, 2, 3, 0]);
+    let scores: Vec<_> =
+        document_scores.iter().map(|scores| ScoreDetails::global_score(scores.iter())).collect();
+    assert!(scores.windows(2).all(|window| window[0] >= window[1]));
+
+    // with a light weight, the relevancy comes first
+    let index = create_blended_index(0.1);
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.query("quick brown fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    assert_eq!(documents_ids, [2, 0, 1, 3]);
+}
+
+#[test]
+fn test_decay_blended_pagination() {
+    let index = create_blended_index(1.0);
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut pages = Vec::new();
+    for offset in 0..4 {
+        let mut s = index.search(&txn, &fields_ids_map);
+        s.query("quick brown fox");
+        s.terms_matching_strategy(TermsMatchingStrategy::Last);
+        s.offset(offset);
+        s.limit(1);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        pages.extend(documents_ids);
+    }
+
+    assert_eq!(pages, [1, 2, 3, 0]);
+}
+
+#[test]
+fn test_decay_placeholder_pages() {
+    let rule = DecayRule {
+        field: S("price"),
+        function: DecayFunction::Gauss {
+            origin: DecayOrigin::Value(OrderedFloat(10.0)),
+            scale: OrderedFloat(5.0),
+        },
+        weight: DecayRule::DEFAULT_WEIGHT,
+    };
+    let index = create_index(rule);
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut pages = Vec::new();
+    for offset in 0..5 {
+        let mut s = index.search(&txn, &fields_ids_map);
+        s.offset(offset);
+        s.limit(1);
+        let SearchResult { documents_ids, candidates, degraded, .. } = s.execute().unwrap();
+        assert_eq!(candidates.len(), 5);
+        assert!(!degraded);
+        pages.extend(documents_ids);
+    }
+    assert_eq!(pages, [2, 3, 1, 0, 4]);
+
+    // the documents far from the origin are below the threshold
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.ranking_score_threshold(0.6);
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, [2, 3, 1]);
+    assert_eq!(candidates.len(), 3);
+}
+
+#[test]
+fn test_decay_not_in_score_values() {
+    let index = create_blended_index(1.0);
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.query("quick brown fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { document_scores, .. } = s.execute().unwrap();
+
+    // the decay rules are only blended with the global score
+    for scores in &document_scores {
+        let without_decay: Vec<_> = scores
+            .iter()
+            .filter(|details| !matches!(details, ScoreDetails::Decay(_)))
+            .cloned()
+            .collect();
+        let local_scores = |details: &[ScoreDetails]| -> Vec<Option<f64>> {
+            ScoreDetails::score_values(details.iter())
+                .map(|value| match value {
+                    ScoreValue::Score(score) => Some(score),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(local_scores(scores), local_scores(&without_decay));
+    }
+}
+
+#[test]
+fn test_decay_frozen_now() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Decay(DecayRule {
+                field: S("published"),
+                function: DecayFunction::Gauss {
+                    origin: DecayOrigin::Now,
+                    scale: OrderedFloat(1000.0),
+                },
+                weight: DecayRule::DEFAULT_WEIGHT,
+            })]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "published": 1000 },
+            { "id": 1, "published": 3000 },
+            { "id": 2, "published": 5000 },
+        ]))
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    for (now, expected) in [(1000, [0, 1, 2]), (5000, [2, 1, 0])] {
+        let mut s = index.search(&txn, &fields_ids_map);
+        s.decay_now(OffsetDateTime::from_unix_timestamp(now).unwrap());
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        assert_eq!(documents_ids, expected, "now: {now}");
+    }
+}
+
+#[test]
+fn test_decay_degraded_past_max_scored() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Decay(DecayRule {
+                    field: S("price"),
+                    function: DecayFunction::Gauss {
+                        origin: DecayOrigin::Value(OrderedFloat(0.0)),
+                        scale: OrderedFloat(100.0),
+                    },
+                    weight: DecayRule::DEFAULT_WEIGHT,
+                }),
+            ]);
+        })
+        .unwrap();
+
+    let document = |id: usize| {
+        serde_json::json!({ "id": id, "title": "fox", "price": id }).as_object().unwrap().clone()
+    };
+    index.add_documents(mmap_from_objects((0..MAX_SCORED_DOCUMENTS).map(document))).unwrap();
+
+    let search = |index: &TempIndex| {
+        let txn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+        let mut s = index.search(&txn, &fields_ids_map);
+        s.query("fox");
+        s.limit(3);
+        let SearchResult { documents_ids, degraded, .. } = s.execute().unwrap();
+        (documents_ids, degraded)
+    };
+
+    // all the documents are scored, none is left out
+    let (documents_ids, degraded) = search(&index);
+    assert_eq!(documents_ids, [0, 1, 2]);
+    assert!(!degraded);
+
+    index.add_documents(mmap_from_objects([document(MAX_SCORED_DOCUMENTS)])).unwrap();
+    let (_, degraded) = search(&index);
+    assert!(degraded);
+}
@@ -20,7 +20,7 @@ use maplit::hashset;
 use super::collect_field_values;
 use crate::index::tests::TempIndex;
 use crate::{
-    AscDesc, Criterion, FilterableAttributesRule, Index, Member, SearchResult,
+    distinct_group, AscDesc, Criterion, FilterableAttributesRule, Index, Member, SearchResult,
     TermsMatchingStrategy,
 };
 
@@ -642,3 +642,41 @@ fn test_distinct_typo() {
     ]
     "###);
 }
+
+#[test]
+fn test_distinct_groups() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Sort]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let letter = fields_ids_map.id("letter").unwrap();
+
+    // document 9 is part of the `E` group
+    let group = distinct_group(&index, &txn, letter, 9).unwrap();
+    let docids: Vec<_> = group.docids.iter().collect();
+    insta::assert_snapshot!(format!("{}: {docids:?}", group.value), @r###""E": [9, 10, 11, 12, 13, 14]"###);
+
+    // a document without distinct value is alone in its group
+    let group_without_value = distinct_group(&index, &txn, letter, 24).unwrap();
+    let docids: Vec<_> = group_without_value.docids.iter().collect();
+    insta::assert_snapshot!(format!("{}: {docids:?}", group_without_value.value), @"null: [24]");
+
+    // the distinct attribute of the index collapses the group
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank1")))]);
+    s.candidates(&group.docids);
+    s.limit(3);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[14]");
+
+    // unless it is ignored to rank the documents of the group
+    s.ignore_distinct(true);
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[14, 13, 12]");
+    insta::assert_snapshot!(candidates.len(), @"6");
+}
@@ -0,0 +1,84 @@
+/*!
+This module tests the explanation of the ranking:
+
+1. each ranking rule reports its iterations, the candidates it sorted and the sizes of its buckets
+2. each returned document is described by the rank of its bucket for each ranking rule
+3. the derivations of the query terms used by the search are reported
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "quick fox" },
+            { "id": 1, "text": "quack fox" },
+            { "id": 2, "text": "quick brown" },
+            { "id": 3, "text": "nothing here" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_explain_ranking_rules() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.query("quick fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.explain(true);
+    let SearchResult { documents_ids, explanation, .. } = s.execute().unwrap();
+    let explanation = explanation.unwrap();
+
+    assert_eq!(documents_ids, [0, 1, 2]);
+    assert_eq!(explanation.initial_candidates, 3);
+
+    let names: Vec<_> = explanation.ranking_rules.iter().map(|rr| rr.name.as_str()).collect();
+    assert_eq!(names, ["words", "typo"]);
+
+    let words = &explanation.ranking_rules[0];
+    assert_eq!((words.iterations, words.candidates), (1, 3));
+    assert_eq!(words.bucket_sizes, [2, 1]);
+
+    // the typo rule only sorts the first bucket of the words rule, the second one is a single document
+    let typo = &explanation.ranking_rules[1];
+    assert_eq!((typo.iterations, typo.candidates), (1, 2));
+    assert_eq!(typo.bucket_sizes, [1, 1]);
+
+    assert_eq!(explanation.documents[&0], [Some(0), Some(0)]);
+    assert_eq!(explanation.documents[&1], [Some(0), Some(1)]);
+    assert_eq!(explanation.documents[&2], [Some(1)]);
+
+    let quick = &explanation.query_terms[0];
+    assert_eq!(quick.original, "quick");
+    assert_eq!(quick.exact.as_deref(), Some("quick"));
+    assert!(quick.one_typo.contains(&"quack".to_owned()));
+    assert!(explanation.query_terms[1].is_prefix);
+}
+
+#[test]
+fn test_explain_disabled() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.query("quick fox");
+    let SearchResult { explanation, .. } = s.execute().unwrap();
+
+    assert!(explanation.is_none());
+}
@@ -1,7 +1,9 @@
 pub mod attribute_fid;
 pub mod cutoff;
+pub mod decay;
 pub mod distinct;
 pub mod exactness;
+pub mod explain;
 pub mod geo_sort;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
@@ -10,6 +12,7 @@ pub mod language;
 pub mod ngram_split_words;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod query_syntax;
 pub mod sort;
 pub mod stop_words;
 pub mod typo;
@@ -0,0 +1,108 @@
+/*!
+This module tests the advanced query syntax:
+
+1. `OR` matches the documents containing any of the alternatives
+2. `field:term` only matches the terms within the given searchable field
+3. `+` makes a term mandatory whatever the terms matching strategy
+4. `-` excludes the documents matching a field-scoped term or a group
+5. an unknown field returns an error
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Error, QuerySyntax, SearchResult, TermsMatchingStrategy, UserError};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "overview".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the dark knight", "overview": "batman fights the joker" },
+            { "id": 1, "title": "batman begins", "overview": "bruce wayne becomes batman" },
+            { "id": 2, "title": "superman", "overview": "a hero from krypton" },
+            { "id": 3, "title": "the joker", "overview": "a villain story in gotham" },
+            { "id": 4, "title": "wonder woman", "overview": "an amazon hero" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(
+    index: &TempIndex,
+    query: &str,
+    terms_matching_strategy: TermsMatchingStrategy,
+) -> crate::Result<Vec<u32>> {
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.query(query);
+    s.query_syntax(QuerySyntax::Advanced);
+    s.terms_matching_strategy(terms_matching_strategy);
+    let SearchResult { mut documents_ids, .. } = s.execute()?;
+    documents_ids.sort_unstable();
+    Ok(documents_ids)
+}
+
+#[test]
+fn test_advanced_syntax_or() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "batman OR joker", TermsMatchingStrategy::All).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3]");
+
+    let documents_ids =
+        search(&index, "title:(dark OR begins)", TermsMatchingStrategy::All).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+}
+
+#[test]
+fn test_advanced_syntax_field_scope() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "title:batman", TermsMatchingStrategy::Last).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let documents_ids = search(&index, "overview:joker", TermsMatchingStrategy::Last).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
+
+#[test]
+fn test_advanced_syntax_required_terms() {
+    let index = create_index();
+
+    // the `last` strategy keeps the documents containing `hero`
+    let documents_ids = search(&index, "hero woman", TermsMatchingStrategy::Last).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 4]");
+
+    // unless `woman` is mandatory
+    let documents_ids = search(&index, "hero +woman", TermsMatchingStrategy::Last).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4]");
+}
+
+#[test]
+fn test_advanced_syntax_exclusions() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "batman -title:dark", TermsMatchingStrategy::Last).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let documents_ids =
+        search(&index, "hero OR joker -(wonder OR gotham)", TermsMatchingStrategy::Last).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2]");
+}
+
+#[test]
+fn test_advanced_syntax_unknown_field() {
+    let index = create_index();
+
+    let error = search(&index, "director:nolan", TermsMatchingStrategy::Last).unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidSearchQueryAttribute { .. })));
+    insta::assert_snapshot!(error, @"Attribute `director` used in the query is not searchable. Available searchable attributes are: `overview, title`.");
+}
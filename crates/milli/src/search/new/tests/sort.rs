@@ -9,6 +9,7 @@ This module tests the `sort` ranking rule:
 6. documents with either: (1) no value, (2) null, or (3) an object for the field-to-sort appear at the end of the bucket
 7. boolean values are translated to strings
 8. if a field contains an array, it is sorted by the best value in the array according to the sort rule
+9. the documents after a search cursor are the same as the ones of the full search, even when degraded
 */
 
 use big_s::S;
@@ -17,8 +18,12 @@ use meili_snap::insta;
 
 use crate::criterion::AttributeState;
 use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::tests::collect_field_values;
-use crate::{score_details, AscDesc, Criterion, Member, SearchResult, TermsMatchingStrategy};
+use crate::{
+    score_details, AscDesc, Criterion, Deadline, Error, Member, SearchCursor, SearchResult,
+    TermsMatchingStrategy, UserError,
+};
 
 fn create_index() -> TempIndex {
     let index = TempIndex::new();
@@ -357,3 +362,130 @@ fn test_redacted() {
     insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 4, 5, 22, 23, 13, 1, 3, 12, 21, 11, 20, 6, 7, 8, 9, 10, 14, 15]");
     insta::assert_json_snapshot!(document_scores_json);
 }
+
+#[test]
+fn test_search_after() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.sort_criteria(vec![
+        AscDesc::Desc(Member::Field(S("letter"))),
+        AscDesc::Asc(Member::Field(S("rank"))),
+    ]);
+    s.limit(24);
+    let SearchResult { documents_ids: expected, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{expected:?}"), @"[21, 22, 23, 20, 18, 19, 15, 16, 17, 9, 10, 11, 12, 13, 14, 8, 5, 6, 7, 2, 3, 4, 0, 1]");
+
+    // paginate through the same results with a cursor made from the last hit of each page
+    let mut documents_ids = Vec::new();
+    let mut cursor: Option<SearchCursor> = None;
+    loop {
+        s.limit(5);
+        if let Some(cursor) = cursor.take() {
+            // the cursor must survive a serialization roundtrip
+            let json = serde_json::to_string(&cursor).unwrap();
+            s.search_after(serde_json::from_str(&json).unwrap());
+        }
+        let SearchResult { documents_ids: page, document_scores, .. } = s.execute().unwrap();
+        let (Some(&docid), Some(scores)) = (page.last(), document_scores.last()) else {
+            break;
+        };
+        documents_ids.extend_from_slice(&page);
+        cursor = Some(SearchCursor { scores: scores.clone(), docid });
+    }
+
+    assert_eq!(documents_ids, expected);
+}
+
+#[test]
+fn test_search_after_every_page_size() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.sort_criteria(vec![
+        AscDesc::Desc(Member::Field(S("letter"))),
+        AscDesc::Asc(Member::Field(S("rank"))),
+        AscDesc::Desc(Member::Field(S("vague"))),
+    ]);
+    s.limit(24);
+    let SearchResult { documents_ids: expected, .. } = s.execute().unwrap();
+
+    // the cursor ends up at every depth of the sort criteria
+    for page_size in 1..=7 {
+        let mut documents_ids = Vec::new();
+        let mut cursor: Option<SearchCursor> = None;
+        loop {
+            s.limit(page_size);
+            if let Some(cursor) = cursor.take() {
+                s.search_after(cursor);
+            }
+            let SearchResult { documents_ids: page, document_scores, .. } = s.execute().unwrap();
+            let (Some(&docid), Some(scores)) = (page.last(), document_scores.last()) else {
+                break;
+            };
+            documents_ids.extend_from_slice(&page);
+            cursor = Some(SearchCursor { scores: scores.clone(), docid });
+        }
+        assert_eq!(documents_ids, expected, "page size: {page_size}");
+    }
+}
+
+#[test]
+fn test_search_after_degraded() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.sort_criteria(vec![
+        AscDesc::Desc(Member::Field(S("letter"))),
+        AscDesc::Asc(Member::Field(S("rank"))),
+    ]);
+    s.limit(24);
+    let SearchResult { documents_ids: expected, document_scores, .. } = s.execute().unwrap();
+
+    for position in [0, 4, 10] {
+        let docid = expected[position];
+        let cursor = SearchCursor { scores: document_scores[position].clone(), docid };
+        for stop_after in 1..=4 {
+            s.search_after(cursor.clone());
+            s.deadline(Deadline::never().with_stop_after(stop_after));
+            let SearchResult { documents_ids: page, degraded, .. } = s.execute().unwrap();
+            assert!(degraded);
+            // the documents that could not be ranked against the cursor are the ones with a greater id
+            for page_docid in page {
+                let page_position = expected.iter().position(|&id| id == page_docid).unwrap();
+                assert!(
+                    page_position > position || page_docid > docid,
+                    "document {page_docid} is before the cursor {docid} (stop after: {stop_after})"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_search_after_other_ranking_rules() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("letter")))]);
+    s.search_after(SearchCursor {
+        scores: vec![ScoreDetails::Words(score_details::Words {
+            matching_words: 1,
+            max_matching_words: 1,
+        })],
+        docid: 0,
+    });
+    let result = s.execute();
+    assert!(matches!(result, Err(Error::UserError(UserError::InvalidSearchCursor))));
+}
@@ -108,9 +108,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -161,9 +164,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -214,9 +220,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -266,9 +275,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -326,9 +338,12 @@ fn test_stop_words_in_phrase() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -367,9 +382,12 @@ fn test_stop_words_in_phrase() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -419,9 +437,12 @@ fn test_stop_words_in_phrase() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -16,13 +16,15 @@ This module tests the following properties:
 12. Prefix tolerance costs nothing according to the typo ranking rule
 13. Split words cost 1 typo according to the typo ranking rule
 14. Synonyms cost nothing according to the typo ranking rule
+15. The typo tolerance settings can be overridden for a single search
+16. The synonyms can be ignored for a single search
 */
 
 use std::collections::BTreeMap;
 
 use crate::index::tests::TempIndex;
 use crate::search::new::tests::collect_field_values;
-use crate::{Criterion, SearchResult, TermsMatchingStrategy};
+use crate::{Criterion, SearchResult, TermsMatchingStrategy, TypoToleranceOverride};
 
 fn create_index() -> TempIndex {
     let index = TempIndex::new();
@@ -645,3 +647,80 @@ fn test_typo_synonyms() {
     ]
     "###);
 }
+
+#[test]
+fn test_typo_tolerance_override() {
+    let index = create_index();
+    {
+        let txn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+        // typos disabled for this search only
+        let mut s = index.search(&txn, &fields_ids_map);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query("the quack brown fox jumps over the lazy dog");
+        s.typo_tolerance(TypoToleranceOverride { enabled: Some(false), ..Default::default() });
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+        // `quack` is too short to have a typo
+        let mut s = index.search(&txn, &fields_ids_map);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query("the quack brown fox jumps over the lazy dog");
+        s.typo_tolerance(TypoToleranceOverride {
+            min_word_len_one_typo: Some(6),
+            ..Default::default()
+        });
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+        // the other searches keep the settings of the index
+        let mut s = index.search(&txn, &fields_ids_map);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query("the quack brown fox jumps over the lazy dog");
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+    }
+
+    index
+        .update_settings(|s| {
+            s.set_authorize_typos(false);
+        })
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+    // typos enabled for this search only
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("the quack brown fox jumps over the lazy dog");
+    s.typo_tolerance(TypoToleranceOverride { enabled: Some(true), ..Default::default() });
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
+
+#[test]
+fn test_ignore_synonyms() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            let mut synonyms = BTreeMap::new();
+            synonyms.insert("lackadaisical".to_owned(), vec!["lazy".to_owned()]);
+            s.set_synonyms(synonyms);
+        })
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+
+    let mut s = index.search(&txn, &fields_ids_map);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("the quick brown fox jumps over the lackadaisical dog");
+    s.ignore_synonyms(true);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"the quick brown fox jumps over the lackadaisical dog\"",
+    ]
+    "###);
+}
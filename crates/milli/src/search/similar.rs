@@ -1,12 +1,29 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
+use charabia::TokenizerBuilder;
 use roaring::RoaringBitmap;
+use serde_json::Value;
 
 use crate::progress::Progress;
 use crate::score_details::{self, ScoreDetails};
 use crate::search::facet::IndexFilter;
 use crate::vector::{Embedder, VectorStore};
-use crate::{filtered_universe, DocumentId, FieldsIdsMap, Index, Result, SearchResult};
+use crate::{
+    all_obkv_to_json, filtered_universe, DocumentId, FieldsIdsMap, Index, Result, SearchResult,
+    MAX_WORD_LENGTH,
+};
+
+/// The maximum number of words of the source document used by the keyword mode.
+const MAX_KEYWORDS: usize = 25;
+
+/// How the documents similar to the source document are found.
+enum SimilarKind {
+    /// Nearest neighbors of the embedding of the source document.
+    Semantic { embedder_name: String, embedder: Arc<Embedder>, quantized: bool },
+    /// Documents sharing the most discriminative words of the source document.
+    Keyword,
+}
 
 pub struct Similar<'a> {
     id: DocumentId,
@@ -17,10 +34,8 @@ pub struct Similar<'a> {
     rtxn: &'a heed::RoTxn<'a>,
     fields_ids_map: &'a FieldsIdsMap,
     index: &'a Index,
-    embedder_name: String,
-    embedder: Arc<Embedder>,
+    kind: SimilarKind,
     ranking_score_threshold: Option<f64>,
-    quantized: bool,
     progress: &'a Progress,
 }
 
@@ -46,10 +61,33 @@ impl<'a> Similar<'a> {
             index,
             rtxn,
             fields_ids_map,
-            embedder_name,
-            embedder,
+            kind: SimilarKind::Semantic { embedder_name, embedder, quantized },
+            ranking_score_threshold: None,
+            progress,
+        }
+    }
+
+    /// Finds the similar documents without embedder, using the words of the source document
+    /// that are the most discriminative in the index as a weighted OR query.
+    pub fn keyword(
+        id: DocumentId,
+        offset: usize,
+        limit: usize,
+        index: &'a Index,
+        rtxn: &'a heed::RoTxn<'a>,
+        fields_ids_map: &'a FieldsIdsMap,
+        progress: &'a Progress,
+    ) -> Self {
+        Self {
+            id,
+            filter: None,
+            offset,
+            limit,
+            index,
+            rtxn,
+            fields_ids_map,
+            kind: SimilarKind::Keyword,
             ranking_score_threshold: None,
-            quantized,
             progress,
         }
     }
@@ -79,18 +117,21 @@ impl<'a> Similar<'a> {
 
         let universe = universe;
 
-        let embedder_index = self
-            .index
-            .embedding_configs()
-            .embedder_id(self.rtxn, &self.embedder_name)?
-            .ok_or_else(|| {
-                crate::UserError::InvalidSimilarEmbedder(self.embedder_name.to_owned())
-            })?;
+        let (embedder_name, embedder, quantized) = match &self.kind {
+            SimilarKind::Semantic { embedder_name, embedder, quantized } => {
+                (embedder_name, embedder, *quantized)
+            }
+            SimilarKind::Keyword => return self.execute_keyword(universe),
+        };
+
+        let embedder_index =
+            self.index.embedding_configs().embedder_id(self.rtxn, embedder_name)?.ok_or_else(
+                || crate::UserError::InvalidSimilarEmbedder(embedder_name.to_owned()),
+            )?;
 
         let backend = self.index.get_vector_store(self.rtxn)?.unwrap_or_default();
 
-        let reader =
-            VectorStore::new(backend, self.index.vector_store, embedder_index, self.quantized);
+        let reader = VectorStore::new(backend, self.index.vector_store, embedder_index, quantized);
         let results = reader.nns_by_item(
             self.rtxn,
             self.id,
@@ -116,8 +157,7 @@ impl<'a> Similar<'a> {
             .take(self.limit)
         {
             let score = 1.0 - distance;
-            let score = self
-                .embedder
+            let score = embedder
                 .distribution()
                 .map(|distribution| distribution.shift(score))
                 .unwrap_or(score);
@@ -149,6 +189,158 @@ impl<'a> Similar<'a> {
             degraded: false,
             used_negative_operator: false,
             query_vector: None,
+            explanation: None,
+        })
+    }
+
+    /// Ranks the documents containing any of the keywords by the sum of the weights of the
+    /// keywords they contain, so that a document sharing several keywords is ranked before a
+    /// document only sharing the most discriminative one.
+    fn execute_keyword(&self, universe: RoaringBitmap) -> Result<SearchResult> {
+        let keywords = self.keywords()?;
+        let max_weight: f64 = keywords.iter().map(|(_, weight, _)| weight).sum();
+
+        // weighted OR query: a document scores the sum of the weights of the keywords it contains
+        let mut candidates = RoaringBitmap::new();
+        let mut scores = HashMap::<DocumentId, (f64, u32)>::new();
+        for (_, weight, docids) in &keywords {
+            let docids = docids & &universe;
+            for docid in &docids {
+                let (score, matching_keywords) = scores.entry(docid).or_default();
+                *score += weight;
+                *matching_keywords += 1;
+            }
+            candidates |= docids;
+        }
+
+        let mut scores: Vec<_> = scores.into_iter().collect();
+        scores.sort_unstable_by(|(left_docid, (left, _)), (right_docid, (right, _))| {
+            right.total_cmp(left).then(left_docid.cmp(right_docid))
+        });
+
+        let mut documents_ids = Vec::with_capacity(self.limit);
+        let mut document_scores = Vec::with_capacity(self.limit);
+        for (index, &(docid, (score, matching_keywords))) in
+            scores.iter().enumerate().skip(self.offset).take(self.limit)
+        {
+            let score_details = vec![ScoreDetails::Keywords(score_details::Keywords {
+                matching_keywords,
+                max_matching_keywords: keywords.len() as u32,
+                similarity: score / max_weight,
+            })];
+
+            if let Some(ranking_score_threshold) = &self.ranking_score_threshold {
+                if ScoreDetails::global_score(score_details.iter()) < *ranking_score_threshold {
+                    // this document and the following ones are no longer candidates
+                    candidates = scores[..index].iter().map(|(docid, _)| *docid).collect();
+                    break;
+                }
+            }
+
+            documents_ids.push(docid);
+            document_scores.push(score_details);
+        }
+
+        Ok(SearchResult {
+            matching_words: Default::default(),
+            candidates,
+            documents_ids,
+            document_scores,
+            degraded: false,
+            used_negative_operator: false,
+            query_vector: None,
+            explanation: None,
         })
     }
+
+    /// Returns the most discriminative words of the source document along with their weights
+    /// and the documents containing them.
+    ///
+    /// The weight of a word is its number of occurrences in the searchable attributes of the
+    /// source document times its inverse document frequency in the index.
+    fn keywords(&self) -> Result<Vec<(String, f64, RoaringBitmap)>> {
+        let rtxn = self.rtxn;
+        let searchable: HashSet<_> = self
+            .index
+            .searchable_fields(rtxn, self.fields_ids_map)?
+            .into_iter()
+            .map(|field| field.into_owned())
+            .collect();
+
+        let mut tokbuilder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(rtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            tokbuilder.stop_words(stop_words);
+        }
+        let separators = self.index.allowed_separators(rtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            tokbuilder.separators(separators);
+        }
+        let dictionary = self.index.dictionary(rtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            tokbuilder.words_dict(dictionary);
+        }
+        let tokenizer = tokbuilder.build();
+
+        let document = self.index.document(rtxn, self.id)?;
+        let mut texts = Vec::new();
+        for (name, value) in all_obkv_to_json(document, self.fields_ids_map)? {
+            searchable_texts(&name, &value, &searchable, &mut texts);
+        }
+
+        let mut frequencies = BTreeMap::<String, u32>::new();
+        for text in texts {
+            for token in tokenizer.tokenize(&text).filter(|token| token.is_word()) {
+                let word = token.lemma().trim();
+                if !word.is_empty() && word.len() <= MAX_WORD_LENGTH {
+                    *frequencies.entry(word.to_string()).or_default() += 1;
+                }
+            }
+        }
+
+        let number_of_documents = self.index.number_of_documents(rtxn)? as f64;
+        let mut keywords = Vec::new();
+        for (word, frequency) in frequencies {
+            let Some(docids) = self.index.word_docids.get(rtxn, word.as_str())? else { continue };
+            let document_frequency = docids.len() as f64;
+            // the word only appears in the source document
+            if document_frequency <= 1.0 {
+                continue;
+            }
+            let idf = (1.0
+                + (number_of_documents - document_frequency + 0.5) / (document_frequency + 0.5))
+                .ln();
+            keywords.push((word, frequency as f64 * idf, docids));
+        }
+
+        keywords.sort_by(|(_, left, _), (_, right, _)| right.total_cmp(left));
+        keywords.truncate(MAX_KEYWORDS);
+        Ok(keywords)
+    }
+}
+
+/// Collects the strings and numbers of the searchable fields of the value.
+fn searchable_texts(
+    path: &str,
+    value: &Value,
+    searchable: &HashSet<String>,
+    texts: &mut Vec<String>,
+) {
+    match value {
+        Value::String(string) if searchable.contains(path) => texts.push(string.clone()),
+        Value::Number(number) if searchable.contains(path) => texts.push(number.to_string()),
+        Value::Array(values) => {
+            values.iter().for_each(|value| searchable_texts(path, value, searchable, texts))
+        }
+        Value::Object(object) => {
+            for (key, value) in object {
+                searchable_texts(&format!("{path}.{key}"), value, searchable, texts);
+            }
+        }
+        _ => (),
+    }
 }
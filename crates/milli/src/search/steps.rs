@@ -12,6 +12,7 @@ make_enum_progress! {
         SemanticRanking,
         Format,
         FacetDistribution,
+        GroupHits,
         Personalization,
     }
 }
@@ -6,11 +6,13 @@ use thiserror::Error;
 
 /// A rayon ThreadPool wrapper that can catch panics in the pool
 /// and modifies the install function accordingly.
-#[derive(Debug)]
+///
+/// Its clones share the same pool.
+#[derive(Debug, Clone)]
 pub struct ThreadPoolNoAbort {
-    thread_pool: ThreadPool,
+    thread_pool: Arc<ThreadPool>,
     /// The number of active operations.
-    active_operations: AtomicUsize,
+    active_operations: Arc<AtomicUsize>,
     /// Set to true if the thread pool caught a panic.
     pool_caught_panic: Arc<AtomicBool>,
 }
@@ -94,8 +96,8 @@ impl ThreadPoolNoAbortBuilder {
             move |_result| caught_panic.store(true, Ordering::SeqCst)
         });
         Ok(ThreadPoolNoAbort {
-            thread_pool: self.0.build()?,
-            active_operations: AtomicUsize::new(0),
+            thread_pool: Arc::new(self.0.build()?),
+            active_operations: Arc::new(AtomicUsize::new(0)),
             pool_caught_panic,
         })
     }
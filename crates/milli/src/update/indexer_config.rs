@@ -8,7 +8,7 @@ use super::GrenadParameters;
 use crate::thread_pool_no_abort::ThreadPoolNoAbort;
 use crate::ThreadPoolNoAbortBuilder;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IndexerConfig {
     pub log_every_n: Option<usize>,
     pub max_nb_chunks: Option<usize>,
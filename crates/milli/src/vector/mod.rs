@@ -27,3 +27,56 @@ pub const REQUEST_PARALLELISM: usize = 40;
 pub const fn is_cuda_enabled() -> bool {
     cfg!(feature = "cuda")
 }
+
+/// Combines weighted embeddings of the same dimensions into a single embedding.
+///
+/// The embeddings are normalized before being summed so that their weights alone decide of
+/// their importance, a negative weight moves the combination away from its embedding.
+///
+/// Returns `None` when there is no embedding or when the embeddings cancel each other out.
+pub fn combine_weighted_embeddings(embeddings: &[(Embedding, f32)]) -> Option<Embedding> {
+    let dimensions = embeddings.first()?.0.len();
+    let mut combined = vec![0.0; dimensions];
+
+    for (embedding, weight) in embeddings {
+        let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        for (combined, x) in combined.iter_mut().zip(embedding) {
+            *combined += weight * x / norm;
+        }
+    }
+
+    let norm = combined.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm <= f32::EPSILON {
+        return None;
+    }
+    combined.iter_mut().for_each(|x| *x /= norm);
+    Some(combined)
+}
+
+#[cfg(test)]
+mod test {
+    use super::combine_weighted_embeddings;
+
+    #[test]
+    fn combine_embeddings() {
+        // the length of the embeddings doesn't matter, only their direction and weight
+        let combined =
+            combine_weighted_embeddings(&[(vec![2.0, 0.0], 1.0), (vec![0.0, 0.5], 1.0)]).unwrap();
+        assert!((combined[0] - combined[1]).abs() < 1e-6);
+        assert!((combined[0] - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+
+        // a negative weight moves away from the embedding
+        let combined =
+            combine_weighted_embeddings(&[(vec![1.0, 1.0], 1.0), (vec![0.0, 1.0], -1.0)]).unwrap();
+        assert!(combined[0] > 0.0 && combined[1] < combined[0]);
+
+        assert_eq!(combine_weighted_embeddings(&[]), None);
+        assert_eq!(
+            combine_weighted_embeddings(&[(vec![1.0, 0.0], 1.0), (vec![1.0, 0.0], -1.0)]),
+            None
+        );
+    }
+}
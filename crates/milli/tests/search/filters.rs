@@ -33,6 +33,9 @@ fn condition_to_index_condition(filter: FilterCondition) -> IndexFilterCondition
             IndexFilterCondition::GeoBoundingBox { top_right_point, bottom_left_point }
         }
         FilterCondition::GeoPolygon { points } => IndexFilterCondition::GeoPolygon { points },
+        FilterCondition::GeoShape { relation, shape } => {
+            IndexFilterCondition::GeoShape { relation, shape }
+        }
         FilterCondition::Foreign { .. } => {
             unreachable!("Foreign filters are not supported in index conditions")
         }